@@ -0,0 +1,159 @@
+import axios from 'axios';
+import * as crypto from 'crypto';
+import { TrackerError } from './error';
+import type { InternalPipelineState, Storage } from './pipelineTracker';
+import { reviveState } from './storage';
+
+// Reads and writes cache entries; `ActionsCacheClient` talks to the GitHub Actions cache
+export interface CacheClient {
+  // Content of the most recently created entry whose key starts with `prefix`, if any
+  restore(prefix: string): Promise<string | undefined>;
+  save(key: string, content: string): Promise<void>;
+}
+
+// Options for the Actions cache storage backend
+export interface ActionsCacheStorageOptions {
+  // Workflow run sharing the state, defaults to GITHUB_RUN_ID
+  runId?: string;
+  client?: CacheClient;
+}
+
+// Cache entries of a run start with this prefix, followed by the run ID
+export const CACHE_KEY_PREFIX = 'discord-tracker-state';
+
+// Written by `clearPipelineState`, since cache entries cannot be deleted
+const CLEARED_CONTENT = 'null';
+
+// Cache service endpoint, relative to ACTIONS_RESULTS_URL
+const CACHE_SERVICE_PATH = 'twirp/github.actions.results.api.v1.CacheService';
+
+// Entries only match lookups with the same version; these are raw JSON, not tar archives
+const CACHE_VERSION = crypto.createHash('sha256').update(CACHE_KEY_PREFIX).digest('hex');
+
+/// Wraps anything thrown by the cache client or `JSON.parse` as an Error
+function toError(error: unknown): Error {
+  return error instanceof Error ? error : new Error(String(error));
+}
+
+/// Client of the cache service used by `actions/cache`, authenticated with the runtime token
+/// the runner gives to every action
+export class ActionsCacheClient implements CacheClient {
+  private baseUrl: string;
+  private token: string;
+
+  constructor(
+    baseUrl: string | undefined = process.env.ACTIONS_RESULTS_URL,
+    token: string | undefined = process.env.ACTIONS_RUNTIME_TOKEN
+  ) {
+    if (!baseUrl || !token) {
+      throw new TrackerError(
+        'The Actions cache needs the ACTIONS_RESULTS_URL and ACTIONS_RUNTIME_TOKEN of a job',
+        'CACHE_UNAVAILABLE'
+      );
+    }
+    this.baseUrl = baseUrl.replace(/\/?$/, '/');
+    this.token = token;
+  }
+
+  private async call(method: string, body: Record<string, unknown>): Promise<any> {
+    const response = await axios.post(`${this.baseUrl}${CACHE_SERVICE_PATH}/${method}`, body, {
+      headers: { Authorization: `Bearer ${this.token}`, 'Content-Type': 'application/json' },
+    });
+    return response.data;
+  }
+
+  async restore(prefix: string): Promise<string | undefined> {
+    const entry = await this.call('GetCacheEntryDownloadURL', {
+      key: prefix,
+      restore_keys: [prefix],
+      version: CACHE_VERSION,
+    });
+    const url = entry.signed_download_url ?? entry.signedDownloadUrl;
+    if (!entry.ok || !url) {
+      return undefined;
+    }
+    const response = await axios.get(url, { responseType: 'text' });
+    return response.data;
+  }
+
+  async save(key: string, content: string): Promise<void> {
+    const created = await this.call('CreateCacheEntry', { key, version: CACHE_VERSION });
+    const url = created.signed_upload_url ?? created.signedUploadUrl;
+    if (!created.ok || !url) {
+      throw new Error(`the cache service refused to create entry ${key}`);
+    }
+    await axios.put(url, content, {
+      headers: { 'x-ms-blob-type': 'BlockBlob', 'Content-Type': 'application/octet-stream' },
+    });
+    const finalized = await this.call('FinalizeCacheEntryUpload', {
+      key,
+      version: CACHE_VERSION,
+      size_bytes: String(Buffer.byteLength(content)),
+    });
+    if (!finalized.ok) {
+      throw new Error(`the cache service did not finalize entry ${key}`);
+    }
+  }
+}
+
+/// Storage backend that keeps the state in the GitHub Actions cache, keyed by the workflow
+/// run, so jobs on other runners of the same run share the pipeline message. Cache entries
+/// are immutable: every save adds an entry and a load reads the newest one.
+export class ActionsCacheStorage implements Storage {
+  private prefix: string;
+  private client: CacheClient | undefined;
+
+  constructor(options: ActionsCacheStorageOptions = {}) {
+    const runId = options.runId || process.env.GITHUB_RUN_ID || 'local';
+    this.prefix = `${CACHE_KEY_PREFIX}-${runId}-`;
+    this.client = options.client;
+  }
+
+  /// Created on first use, so a missing runtime token only fails when the cache is needed
+  private getClient(): CacheClient {
+    if (!this.client) {
+      this.client = new ActionsCacheClient();
+    }
+    return this.client;
+  }
+
+  private async write(content: string): Promise<void> {
+    try {
+      // Keys cannot be reused, and jobs of the run may save in the same millisecond
+      const suffix = `${Date.now()}-${crypto.randomBytes(4).toString('hex')}`;
+      await this.getClient().save(`${this.prefix}${suffix}`, content);
+    } catch (error: unknown) {
+      throw TrackerError.stateSaveError(toError(error));
+    }
+  }
+
+  async savePipelineState(state: InternalPipelineState): Promise<void> {
+    await this.write(JSON.stringify(state));
+  }
+
+  async loadPipelineState(): Promise<InternalPipelineState | null> {
+    let content: string | undefined;
+    try {
+      content = await this.getClient().restore(this.prefix);
+    } catch (error: unknown) {
+      throw TrackerError.stateLoadError(toError(error));
+    }
+    if (!content || content.trim() === '' || content.trim() === CLEARED_CONTENT) {
+      return null;
+    }
+
+    try {
+      return reviveState(JSON.parse(content));
+    } catch (error: unknown) {
+      throw TrackerError.jsonError(toError(error));
+    }
+  }
+
+  async clearPipelineState(): Promise<void> {
+    await this.write(CLEARED_CONTENT);
+  }
+
+  getKeyPrefix(): string {
+    return this.prefix;
+  }
+}
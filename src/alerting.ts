@@ -0,0 +1,32 @@
+import axios from 'axios';
+import { proxyRequestOptions } from './proxy';
+
+/// Sends a minimal "tracker is broken" notice to an out-of-band webhook.
+/// Failures are swallowed so alerting can never make a tracker failure worse.
+export async function sendTrackerAlert(
+  webhookUrl: string | undefined,
+  operation: string,
+  error: unknown,
+  proxyUrl?: string
+): Promise<void> {
+  if (!webhookUrl) {
+    return;
+  }
+
+  const errorMessage = error instanceof Error ? error.message : String(error);
+  const text = `tracker is broken: ${operation} failed - ${errorMessage}`;
+
+  try {
+    // `content` is understood by Discord webhooks, `text` by Slack-compatible ones
+    await axios.post(
+      webhookUrl,
+      { content: text, text },
+      { timeout: 10000, ...proxyRequestOptions(webhookUrl, proxyUrl) }
+    );
+  } catch (alertError) {
+    console.warn(
+      '⚠️  Failed to deliver tracker alert:',
+      alertError instanceof Error ? alertError.message : String(alertError)
+    );
+  }
+}
@@ -0,0 +1,28 @@
+import * as fs from 'fs/promises';
+import * as path from 'path';
+import { TrackerError } from './error';
+import type { FileUpload } from './models';
+
+// Stay below Discord's upload limit for bots without boosted servers
+export const MAX_ATTACHMENT_BYTES = 8 * 1024 * 1024;
+
+/// Reads a log file as an upload. Logs over `maxBytes` keep only their tail, where the
+/// failure usually is, prefixed with a truncation marker.
+export async function readLogAttachment(
+  filePath: string,
+  maxBytes: number = MAX_ATTACHMENT_BYTES
+): Promise<FileUpload> {
+  let content: Buffer;
+  try {
+    content = await fs.readFile(filePath);
+  } catch (error: any) {
+    throw TrackerError.fileSystemError(error);
+  }
+
+  if (content.length > maxBytes) {
+    const marker = Buffer.from(`[… truncated ${content.length - maxBytes} bytes]\n`);
+    content = Buffer.concat([marker, content.subarray(content.length - maxBytes)]);
+  }
+
+  return { filename: path.basename(filePath), content, contentType: 'text/plain' };
+}
@@ -0,0 +1,71 @@
+import * as fs from 'fs/promises';
+import * as path from 'path';
+import { TrackerError } from './error';
+
+// Entries not updated for this long are dropped from the board
+const BOARD_RETENTION_MS = 24 * 60 * 60 * 1000;
+
+// Latest known status of one pipeline shown on the shared board
+export interface BoardEntry {
+  repository: string;
+  prNumber: number;
+  prTitle: string;
+  statusLine: string;
+  finished: boolean;
+  updatedAt: string;
+}
+
+/// Identifies the pipeline an entry belongs to
+export function boardEntryKey(entry: Pick<BoardEntry, 'repository' | 'prNumber'>): string {
+  return `${entry.repository}#${entry.prNumber}`;
+}
+
+/// File-based board of the latest status of many pipelines. Pipelines only record their line
+/// locally; the `board` action renders every line into a single message edit.
+export class BoardStore {
+  private filePath: string;
+
+  constructor(filePath?: string) {
+    this.filePath = filePath || path.join(process.cwd(), '.discord-pipeline-board');
+  }
+
+  /// Loads the board entries, most recently updated first
+  async load(): Promise<BoardEntry[]> {
+    try {
+      const content = await fs.readFile(this.filePath, 'utf-8');
+      if (content.trim() === '') {
+        return [];
+      }
+      const entries = JSON.parse(content);
+      return Array.isArray(entries)
+        ? entries.sort((a: BoardEntry, b: BoardEntry) => b.updatedAt.localeCompare(a.updatedAt))
+        : [];
+    } catch (error: any) {
+      if (error.code === 'ENOENT') {
+        return [];
+      }
+      throw TrackerError.jsonError(error);
+    }
+  }
+
+  /// Replaces the entry of the same pipeline and drops entries past the retention window
+  async upsert(entry: BoardEntry, now: Date = new Date()): Promise<void> {
+    const key = boardEntryKey(entry);
+    const cutoff = now.getTime() - BOARD_RETENTION_MS;
+    const entries = (await this.load()).filter(
+      (existing) =>
+        boardEntryKey(existing) !== key && new Date(existing.updatedAt).getTime() >= cutoff
+    );
+    entries.push(entry);
+
+    try {
+      await fs.writeFile(this.filePath, JSON.stringify(entries, null, 2), 'utf-8');
+    } catch (error: any) {
+      throw TrackerError.fileSystemError(error);
+    }
+  }
+
+  getFilePath(): string {
+    return this.filePath;
+  }
+}
@@ -0,0 +1,78 @@
+import { isWebhookUrl } from './discordApi';
+import { TrackerError } from './error';
+import { PipelineTracker } from './pipelineTracker';
+import { FileStorage } from './storage';
+import { validateBotTokenFormat } from './validation';
+
+// Command line arguments split into `--flag value` pairs and the command after `--`
+export interface ParsedArgs {
+  flags: Record<string, string>;
+  positional: string[];
+  command: string[];
+}
+
+/// Parses `--name value` / `--name=value` flags, positional arguments and everything after `--`.
+/// Flags without a value (followed by another flag or nothing) are set to "true".
+export function parseArgs(args: string[]): ParsedArgs {
+  const flags: Record<string, string> = {};
+  const positional: string[] = [];
+  let command: string[] = [];
+
+  for (let i = 0; i < args.length; i++) {
+    const arg = args[i];
+    if (arg === '--') {
+      command = args.slice(i + 1);
+      break;
+    }
+    if (arg.startsWith('--')) {
+      const separator = arg.indexOf('=');
+      if (separator > 0) {
+        flags[arg.slice(2, separator)] = arg.slice(separator + 1);
+      } else if (i + 1 < args.length && !args[i + 1].startsWith('--')) {
+        flags[arg.slice(2)] = args[++i];
+      } else {
+        flags[arg.slice(2)] = 'true';
+      }
+    } else {
+      positional.push(arg);
+    }
+  }
+
+  return { flags, positional, command };
+}
+
+/// Returns a flag value, falling back to an environment variable
+export function getFlag(
+  flags: Record<string, string>,
+  name: string,
+  envName?: string
+): string | undefined {
+  return flags[name] || (envName ? process.env[envName] : undefined) || undefined;
+}
+
+/// Returns a required flag value or throws a missing input error
+export function requireFlag(flags: Record<string, string>, name: string, envName?: string): string {
+  const value = getFlag(flags, name, envName);
+  if (!value) {
+    throw TrackerError.missingRequiredInput(name);
+  }
+  return value;
+}
+
+/// Returns the bot token from `--bot-token` or DISCORD_BOT_TOKEN, rejecting malformed tokens
+/// unless `--skip-token-validation` is set
+export function requireBotToken(flags: Record<string, string>): string {
+  const botToken = requireFlag(flags, 'bot-token', 'DISCORD_BOT_TOKEN');
+  if (flags['skip-token-validation'] !== 'true' && !isWebhookUrl(botToken)) {
+    validateBotTokenFormat(botToken);
+  }
+  return botToken;
+}
+
+/// Creates a file-backed tracker from `--bot-token`/`--channel-id` or the
+/// DISCORD_BOT_TOKEN/DISCORD_CHANNEL_ID environment variables
+export function createTrackerFromFlags(flags: Record<string, string>): PipelineTracker {
+  const botToken = requireBotToken(flags);
+  const channelId = requireFlag(flags, 'channel-id', 'DISCORD_CHANNEL_ID');
+  return new PipelineTracker(botToken, channelId, new FileStorage());
+}
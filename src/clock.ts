@@ -0,0 +1,38 @@
+// Source of the current time for rendering and step timing. A frozen clock makes the
+// rendered embeds byte-identical across runs, for screenshots, golden tests and the demo.
+export interface Clock {
+  now(): Date;
+  // Whether the clock always returns the same time
+  frozen: boolean;
+}
+
+export const systemClock: Clock = {
+  now: () => new Date(),
+  frozen: false,
+};
+
+/// Clock stopped at `at`
+export function frozenClock(at: Date): Clock {
+  return {
+    now: () => new Date(at.getTime()),
+    frozen: true,
+  };
+}
+
+/// Clock frozen at a render seed given in Unix seconds
+export function seededClock(seed: number): Clock {
+  return frozenClock(new Date(seed * 1000));
+}
+
+/// Deterministic source of randomness in [0, 1) (mulberry32), replacing `Math.random` when a
+/// seed is given
+export function seededRandom(seed: number): () => number {
+  let state = seed >>> 0;
+  return () => {
+    state = (state + 0x6d2b79f5) >>> 0;
+    let t = state;
+    t = Math.imul(t ^ (t >>> 15), t | 1);
+    t ^= t + Math.imul(t ^ (t >>> 7), t | 61);
+    return ((t ^ (t >>> 14)) >>> 0) / 4294967296;
+  };
+}
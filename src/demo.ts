@@ -0,0 +1,89 @@
+import type { PipelineTracker } from './pipelineTracker';
+
+// Steps of the simulated pipeline, in order
+export const DEMO_STEPS = ['Install dependencies', 'Lint', 'Unit tests', 'Build', 'Deploy'];
+
+// Pull request the simulated pipeline reports on
+const DEMO_PR = {
+  number: '1',
+  title: 'Demo: simulated pipeline',
+  author: 'discord-tracker',
+  repository: 'demo/discord-tracker',
+  branch: 'main',
+};
+
+// Simulated outcome of one step
+export interface DemoStep {
+  name: string;
+  durationMs: number;
+  status: 'success' | 'warning' | 'failed' | 'skipped';
+  additionalInfo: Array<[string, string]>;
+}
+
+// Settings of a demo run
+export interface DemoOptions {
+  // Average simulated step duration; each step takes between half and one and a half of it
+  stepDelayMs: number;
+  // Chance of each step failing; the steps after a failure are skipped
+  failureRate: number;
+  // Source of randomness in [0, 1), replaceable for reproducible runs
+  random?: () => number;
+  sleep?: (ms: number) => Promise<void>;
+  // Called after every update of the pipeline message, e.g. to print it in dry-run mode
+  onUpdate?: () => void | Promise<void>;
+}
+
+/// Draws the duration and outcome of every demo step
+export function planDemoSteps(
+  options: Pick<DemoOptions, 'stepDelayMs' | 'failureRate' | 'random'>
+): DemoStep[] {
+  const random = options.random || Math.random;
+  let failed = false;
+
+  return DEMO_STEPS.map((name): DemoStep => {
+    const durationMs = Math.round(options.stepDelayMs * (0.5 + random()));
+    const roll = random();
+    if (failed) {
+      return { name, durationMs: 0, status: 'skipped', additionalInfo: [] };
+    }
+    if (roll < options.failureRate) {
+      failed = true;
+      const additionalInfo: Array<[string, string]> = [['error', 'Simulated failure']];
+      return { name, durationMs, status: 'failed', additionalInfo };
+    }
+    const status = roll < options.failureRate + 0.1 ? 'warning' : 'success';
+    return { name, durationMs, status, additionalInfo: [['simulated', `${durationMs} ms`]] };
+  });
+}
+
+/// Runs a simulated pipeline through the tracker so the embeds can be watched as they evolve:
+/// each step is reported as running, waits for its simulated duration, then reports its outcome
+export async function runDemo(tracker: PipelineTracker, options: DemoOptions): Promise<DemoStep[]> {
+  const sleep = options.sleep || ((ms) => new Promise((resolve) => setTimeout(resolve, ms)));
+  const steps = planDemoSteps(options);
+
+  await tracker.initPipeline(
+    DEMO_PR.number,
+    DEMO_PR.title,
+    DEMO_PR.author,
+    DEMO_PR.repository,
+    DEMO_PR.branch
+  );
+  await options.onUpdate?.();
+
+  for (const [index, step] of steps.entries()) {
+    const number = index + 1;
+    if (step.status !== 'skipped') {
+      console.log(`▶️  Demo step ${number}/${steps.length}: ${step.name}`);
+      await tracker.updateStep(number, steps.length, step.name, 'running', []);
+      await options.onUpdate?.();
+      await sleep(step.durationMs);
+    }
+    await tracker.updateStep(number, steps.length, step.name, step.status, step.additionalInfo);
+    await options.onUpdate?.();
+  }
+
+  await tracker.completePipeline();
+  await options.onUpdate?.();
+  return steps;
+}
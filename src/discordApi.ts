@@ -1,7 +1,22 @@
-import axios, { type AxiosError, type AxiosInstance } from 'axios';
+import axios, {
+  type AxiosError,
+  type AxiosInstance,
+  type InternalAxiosRequestConfig,
+} from 'axios';
 import { TrackerError } from './error';
-import type { DiscordMessage } from './models';
-import { validateBotToken, validateChannelId } from './validation';
+import type {
+  DiscordChannel,
+  DiscordGuildMember,
+  DiscordMessage,
+  DiscordMessageData,
+  DiscordReaction,
+  DiscordRole,
+  DiscordUser,
+  FileUpload,
+} from './models';
+import { fitMessageToLimits } from './messageBuilder';
+import { proxyRequestOptions } from './proxy';
+import { validateBotToken, validateChannelId, validateMessageEmbeds } from './validation';
 
 interface DiscordErrorResponse {
   code?: number;
@@ -9,41 +24,328 @@ interface DiscordErrorResponse {
   retry_after?: number;
 }
 
-interface RetryConfig {
+// Discord JSON error codes distinguishing failures that share an HTTP status
+const DISCORD_UNKNOWN_CHANNEL = 10003;
+const DISCORD_UNKNOWN_MESSAGE = 10008;
+const DISCORD_UNAUTHORIZED = 40001;
+const DISCORD_MISSING_ACCESS = 50001;
+const DISCORD_MISSING_PERMISSIONS = 50013;
+const DISCORD_NON_TEXT_CHANNEL = 50008;
+
+/// Maps a failed Discord response to the matching TrackerError variant, preferring the
+/// Discord JSON error code over the HTTP status when both are known
+export function classifyDiscordError(
+  status: number,
+  discordCode: number | undefined,
+  message: string
+): TrackerError {
+  if (discordCode === DISCORD_UNAUTHORIZED || status === 401) {
+    return TrackerError.unauthorized(message);
+  }
+  if (
+    discordCode === DISCORD_MISSING_ACCESS ||
+    discordCode === DISCORD_MISSING_PERMISSIONS ||
+    status === 403
+  ) {
+    return TrackerError.forbidden(message);
+  }
+  if (discordCode === DISCORD_NON_TEXT_CHANNEL) {
+    return TrackerError.unsupportedChannelType(`${message}. ${CHANNEL_TYPE_HINT}`);
+  }
+  if (discordCode === DISCORD_UNKNOWN_CHANNEL) {
+    return TrackerError.invalidChannelId(message);
+  }
+  if (discordCode === DISCORD_UNKNOWN_MESSAGE || status === 404) {
+    return TrackerError.messageNotFound(message);
+  }
+  if (status === 429) {
+    return TrackerError.rateLimited(message);
+  }
+  return TrackerError.unknownDiscordError(message);
+}
+
+export interface RetryConfig {
   maxRetries: number;
   baseDelay: number;
   maxDelay: number;
+  // Total time an operation may spend waiting on 429 responses; these waits do not use up retries
+  maxRateLimitWait: number;
+}
+
+// Per-invocation counters of the Discord API traffic
+export interface ApiTelemetry {
+  httpCalls: number;
+  retries: number;
+  rateLimitHits: number;
+  rateLimitWaitMs: number;
+  totalLatencyMs: number;
+  // Request bodies sent, in bytes (JSON payloads and attachments)
+  payloadBytes: number;
+  // Requests per latency bucket, keyed by the bucket's upper bound in ms ("+Inf" for the rest)
+  latencyHistogram: Record<string, number>;
+  // Responses per HTTP status; "0" counts requests that got no response
+  statusCodes: Record<string, number>;
+}
+
+// Upper bounds of the latency histogram buckets, in milliseconds
+export const LATENCY_BUCKETS_MS = [100, 250, 500, 1000, 2500, 5000];
+
+/// Returns the histogram bucket a request latency falls into
+export function latencyBucket(latencyMs: number): string {
+  const bound = LATENCY_BUCKETS_MS.find((le) => latencyMs <= le);
+  return bound === undefined ? '+Inf' : String(bound);
+}
+
+/// Measures the size of a request body: a JSON string, an object serialized as JSON, or
+/// multipart form data (text fields and file sizes)
+export function payloadBytes(data: unknown): number {
+  if (data === undefined || data === null) {
+    return 0;
+  }
+  if (typeof data === 'string') {
+    return Buffer.byteLength(data, 'utf-8');
+  }
+  if (data instanceof FormData) {
+    let bytes = 0;
+    for (const [, value] of data.entries()) {
+      bytes += typeof value === 'string' ? Buffer.byteLength(value, 'utf-8') : value.size;
+    }
+    return bytes;
+  }
+  return Buffer.byteLength(JSON.stringify(data), 'utf-8');
+}
+
+/// Renders the latency histogram as one bar per bucket, for the debug log
+export function formatLatencyHistogram(histogram: Record<string, number>): string {
+  const buckets = [...LATENCY_BUCKETS_MS.map(String), '+Inf'];
+  const max = Math.max(1, ...Object.values(histogram));
+  return buckets
+    .map((bucket) => {
+      const count = histogram[bucket] || 0;
+      const label = bucket === '+Inf' ? `> ${LATENCY_BUCKETS_MS.at(-1)}ms` : `≤ ${bucket}ms`;
+      return `${label.padStart(9)} | ${'█'.repeat(Math.round((count / max) * 20))} ${count}`;
+    })
+    .join('\n');
 }
 
 const DEFAULT_RETRY_CONFIG: RetryConfig = {
   maxRetries: 3,
   baseDelay: 1000, // 1 second
   maxDelay: 30000, // 30 seconds
+  maxRateLimitWait: 60000, // 1 minute
 };
 
-/// Discord API client for sending messages
-export class DiscordApi {
+// Channel types that cannot hold the pipeline message, named for the errors rejecting them
+export const UNSUPPORTED_CHANNEL_TYPES: Record<number, string> = {
+  1: 'DM channel',
+  2: 'voice channel',
+  3: 'group DM channel',
+  4: 'category',
+  13: 'stage channel',
+  14: 'server directory',
+};
+
+// Suggested fix of a channel that cannot hold the pipeline message
+const CHANNEL_TYPE_HINT = 'Use a text, announcement or forum channel, or a thread';
+
+/// Explains why a channel cannot hold the pipeline message, naming its type when known
+export function unsupportedChannelError(channel: DiscordChannel): TrackerError {
+  const { id, type } = channel;
+  const name = UNSUPPORTED_CHANNEL_TYPES[type];
+  const what = name ? `: it is a ${name}` : '';
+  const hint = type === 4 ? 'Use one of the text channels in the category' : CHANNEL_TYPE_HINT;
+  return TrackerError.unsupportedChannelType(
+    `Channel ${id} (type ${type}) cannot hold the pipeline message${what}. ${hint}`
+  );
+}
+
+// Matches channel webhook URLs such as https://discord.com/api/webhooks/<id>/<token>
+const WEBHOOK_URL_PATTERN =
+  /^https:\/\/(?:(?:ptb|canary)\.)?discord(?:app)?\.com\/api\/(?:v\d+\/)?webhooks\/\d+\/[\w-]+\/?$/;
+
+/// Determines whether a credential is a channel webhook URL rather than a bot token
+export function isWebhookUrl(value: string): boolean {
+  return WEBHOOK_URL_PATTERN.test(value.trim());
+}
+
+/// Builds the multipart body used to upload files with a message: the message as
+/// `payload_json` plus one `files[n]` part per file
+export function buildMultipartBody(message: DiscordMessage, files: FileUpload[]): FormData {
+  const form = new FormData();
+  form.append(
+    'payload_json',
+    JSON.stringify({
+      ...message,
+      attachments: files.map((file, id) => ({ id, filename: file.filename })),
+    })
+  );
+  files.forEach((file, id) => {
+    const blob = new Blob([file.content], { type: file.contentType || 'text/plain' });
+    form.append(`files[${id}]`, blob, file.filename);
+  });
+  return form;
+}
+
+/// Builds a `https://discord.com/channels/...` deep link; DMs use `@me` instead of a guild id
+export function buildMessageUrl(
+  guildId: string | undefined,
+  channelId: string,
+  messageId: string
+): string {
+  return `https://discord.com/channels/${guildId || '@me'}/${channelId}/${messageId}`;
+}
+
+// Discord limits thread and forum post names to 100 characters
+function threadName(name: string): string {
+  return name.length > 100 ? `${name.slice(0, 99)}…` : name;
+}
+
+/// Discord operations the pipeline tracker relies on. `DiscordApi` talks to Discord;
+/// `InMemoryDiscordApi` is a fake for testing tracker logic without the network.
+export interface DiscordApiClient {
+  isWebhookMode(): boolean;
+  getTelemetry(): ApiTelemetry;
+  sendMessage(message: DiscordMessage, threadId?: string): Promise<string>;
+  updateMessage(messageId: string, message: DiscordMessage, files?: FileUpload[]): Promise<void>;
+  deleteMessage(messageId: string): Promise<void>;
+  getMessage(messageId: string): Promise<DiscordMessageData>;
+  listMessages(before?: string, limit?: number): Promise<DiscordMessageData[]>;
+  getMessageUrl(messageId: string): Promise<string>;
+  createThread(messageId: string, name: string): Promise<string>;
+  createForumPost(name: string, message: DiscordMessage): Promise<string>;
+  forChannel(channelId: string): DiscordApiClient;
+  sendDirectMessage(userId: string, message: DiscordMessage): Promise<string>;
+  crosspostMessage(messageId: string): Promise<void>;
+  pinMessage(messageId: string): Promise<void>;
+  unpinMessage(messageId: string): Promise<void>;
+  addReaction(messageId: string, emoji: string): Promise<void>;
+  removeReaction(messageId: string, emoji: string): Promise<void>;
+  getReactions(messageId: string): Promise<DiscordReaction[]>;
+  getChannel(channelId?: string): Promise<DiscordChannel>;
+  getCurrentUser(): Promise<DiscordUser>;
+  getGuildRoles(guildId: string): Promise<DiscordRole[]>;
+  getGuildMember(guildId: string, userId: string): Promise<DiscordGuildMember>;
+}
+
+/// Discord API client for sending messages. When a channel webhook URL is supplied instead
+/// of a bot token, messages are sent and edited through the webhook endpoints.
+export class DiscordApi implements DiscordApiClient {
   private client: AxiosInstance;
   private botToken: string;
   private channelId: string;
   private retryConfig: RetryConfig;
+  private proxyUrl: string | undefined;
+  private webhookMode: boolean;
+  private telemetry: ApiTelemetry = {
+    httpCalls: 0,
+    retries: 0,
+    rateLimitHits: 0,
+    rateLimitWaitMs: 0,
+    totalLatencyMs: 0,
+    payloadBytes: 0,
+    latencyHistogram: {},
+    statusCodes: {},
+  };
+  // Start time of each in-flight request, for the per-request latency
+  private requestStarts = new WeakMap<InternalAxiosRequestConfig, number>();
 
-  constructor(botToken: string, channelId: string, retryConfig: Partial<RetryConfig> = {}) {
+  /// `proxyUrl` overrides HTTPS_PROXY/HTTP_PROXY; NO_PROXY is honoured either way
+  constructor(
+    botToken: string,
+    channelId: string,
+    retryConfig: Partial<RetryConfig> = {},
+    proxyUrl?: string
+  ) {
     validateBotToken(botToken);
-    validateChannelId(channelId);
+    this.webhookMode = isWebhookUrl(botToken);
+    if (!this.webhookMode) {
+      validateChannelId(channelId);
+    }
 
     this.botToken = botToken;
     this.channelId = channelId;
     this.retryConfig = { ...DEFAULT_RETRY_CONFIG, ...retryConfig };
+    this.proxyUrl = proxyUrl;
 
-    this.client = axios.create({
-      baseURL: 'https://discord.com/api/v10',
-      timeout: 30000, // 30 seconds
-      headers: {
-        Authorization: `Bot ${this.botToken}`,
-        'Content-Type': 'application/json',
-      },
+    const baseURL = this.webhookMode
+      ? botToken.trim().replace(/\/$/, '')
+      : 'https://discord.com/api/v10';
+    this.client = this.webhookMode
+      ? axios.create({
+          baseURL,
+          timeout: 30000, // 30 seconds
+          headers: { 'Content-Type': 'application/json' },
+          ...proxyRequestOptions(baseURL, proxyUrl),
+        })
+      : axios.create({
+          baseURL,
+          timeout: 30000, // 30 seconds
+          ...proxyRequestOptions(baseURL, proxyUrl),
+          headers: {
+            Authorization: `Bot ${this.botToken}`,
+            'Content-Type': 'application/json',
+          },
+        });
+
+    this.client.interceptors.request.use((config) => {
+      this.requestStarts.set(config, Date.now());
+      return config;
     });
+    this.client.interceptors.response.use(
+      (response) => {
+        this.recordRequest(response.config, response.status);
+        return response;
+      },
+      (error: AxiosError) => {
+        if (error.config) {
+          this.recordRequest(error.config, error.response?.status ?? 0);
+        }
+        return Promise.reject(error);
+      }
+    );
+  }
+
+  /// Returns a snapshot of the API traffic counters of this client
+  getTelemetry(): ApiTelemetry {
+    return {
+      ...this.telemetry,
+      latencyHistogram: { ...this.telemetry.latencyHistogram },
+      statusCodes: { ...this.telemetry.statusCodes },
+    };
+  }
+
+  /// Records the size, latency and status of one completed HTTP request
+  private recordRequest(config: InternalAxiosRequestConfig, status: number): void {
+    const startedAt = this.requestStarts.get(config);
+    const { latencyHistogram, statusCodes } = this.telemetry;
+    this.telemetry.payloadBytes += payloadBytes(config.data);
+    if (startedAt !== undefined) {
+      const bucket = latencyBucket(Date.now() - startedAt);
+      latencyHistogram[bucket] = (latencyHistogram[bucket] || 0) + 1;
+    }
+    statusCodes[String(status)] = (statusCodes[String(status)] || 0) + 1;
+  }
+
+  /// Runs a single HTTP attempt, recording it in the telemetry
+  private async timed<T>(operation: () => Promise<T>): Promise<T> {
+    const startedAt = Date.now();
+    this.telemetry.httpCalls++;
+    try {
+      return await operation();
+    } finally {
+      this.telemetry.totalLatencyMs += Date.now() - startedAt;
+    }
+  }
+
+  /// Returns whether messages go through a channel webhook instead of the bot API
+  isWebhookMode(): boolean {
+    return this.webhookMode;
+  }
+
+  /// Path of the channel messages collection, or of a single message
+  private messagePath(messageId?: string): string {
+    const base = this.webhookMode ? '' : `/channels/${this.channelId}`;
+    return messageId ? `${base}/messages/${messageId}` : `${base}/messages`;
   }
 
   /// Determines if an error is retryable
@@ -69,13 +371,20 @@ export class DiscordApi {
     return false;
   }
 
-  /// Calculates delay for exponential backoff
-  private calculateDelay(attempt: number, retryAfter?: number): number {
-    if (retryAfter) {
-      // Discord provided retry_after, use it (convert from seconds to milliseconds)
-      return Math.min(retryAfter * 1000, this.retryConfig.maxDelay);
+  /// Reads how long Discord asked us to wait from the 429 body (`retry_after`, in seconds)
+  /// or the Retry-After header, falling back to the base delay
+  private getRateLimitDelay(error: AxiosError): number {
+    const retryAfter =
+      (error.response?.data as DiscordErrorResponse)?.retry_after ??
+      Number(error.response?.headers?.['retry-after']);
+    if (!retryAfter || Number.isNaN(retryAfter) || retryAfter < 0) {
+      return this.retryConfig.baseDelay;
     }
-    
+    return Math.ceil(retryAfter * 1000);
+  }
+
+  /// Calculates delay for exponential backoff
+  private calculateDelay(attempt: number): number {
     // Exponential backoff: baseDelay * 2^attempt with jitter
     const exponentialDelay = this.retryConfig.baseDelay * (2 ** attempt);
     const jitter = Math.random() * 0.1 * exponentialDelay; // Add up to 10% jitter
@@ -95,21 +404,36 @@ export class DiscordApi {
     operationName: string
   ): Promise<T> {
     let lastError: AxiosError | null = null;
-    
+    let rateLimitWaited = 0;
+
     for (let attempt = 0; attempt <= this.retryConfig.maxRetries; attempt++) {
       try {
-        return await operation();
+        return await this.timed(operation);
       } catch (error) {
         lastError = error as AxiosError;
-        
+
+        // Rate limits are waited out transparently, within the rate limit wait budget
+        if (lastError.response?.status === 429) {
+          this.telemetry.rateLimitHits++;
+          const wait = this.getRateLimitDelay(lastError);
+          if (rateLimitWaited + wait > this.retryConfig.maxRateLimitWait) {
+            break;
+          }
+          rateLimitWaited += wait;
+          this.telemetry.rateLimitWaitMs += wait;
+          console.warn(`Discord API ${operationName} rate limited, retrying in ${wait}ms`);
+          await this.sleep(wait);
+          attempt--;
+          continue;
+        }
+
         // If this is the last attempt or error is not retryable, break to throw
         if (attempt === this.retryConfig.maxRetries || !this.isRetryableError(lastError)) {
           break;
         }
         
         // Calculate delay for next attempt
-        const retryAfter = (lastError.response?.data as DiscordErrorResponse)?.retry_after;
-        const delay = this.calculateDelay(attempt, retryAfter);
+        const delay = this.calculateDelay(attempt);
         
         console.warn(
           `Discord API ${operationName} failed (attempt ${attempt + 1}/${this.retryConfig.maxRetries + 1}), ` +
@@ -117,6 +441,7 @@ export class DiscordApi {
           (lastError.response?.status ? ` (HTTP ${lastError.response.status})` : '')
         );
         
+        this.telemetry.retries++;
         await this.sleep(delay);
       }
     }
@@ -143,7 +468,8 @@ export class DiscordApi {
           actionableMessage += 'Channel or message not found. Please verify the channel ID is correct and the bot has access to it.';
           break;
         case 429:
-          actionableMessage += 'Rate limit exceeded. The bot is being rate limited by Discord. Consider reducing message frequency.';
+          actionableMessage = `Discord API ${operationName} was rate limited for longer than the ${this.retryConfig.maxRateLimitWait}ms rate limit wait budget. `;
+          actionableMessage += 'The bot is being rate limited by Discord. Consider reducing message frequency.';
           break;
         case 500:
         case 502:
@@ -152,10 +478,10 @@ export class DiscordApi {
           actionableMessage += 'Discord server error. This is a temporary issue with Discord\'s servers. The operation will be retried automatically.';
           break;
         default:
-          actionableMessage += `HTTP ${status}: ${errorResponse.message || 'Unknown error'}`;
+          actionableMessage += `HTTP ${status}: ${errorResponse?.message || 'Unknown error'}`;
       }
       
-      throw TrackerError.discordApiError(actionableMessage, status);
+      throw classifyDiscordError(status, errorResponse?.code, actionableMessage);
     } else if (lastError.request) {
       throw TrackerError.discordApiError(
         `Discord API ${operationName} failed: No response received after ${this.retryConfig.maxRetries + 1} attempts. ` +
@@ -170,32 +496,257 @@ export class DiscordApi {
     }
   }
 
-  /// Sends a message to Discord
-  async sendMessage(message: DiscordMessage): Promise<string> {
+  /// Sends a message to Discord, to the target channel or to a thread of it
+  async sendMessage(message: DiscordMessage, threadId?: string): Promise<string> {
+    validateMessageEmbeds(message);
+    message = fitMessageToLimits(message);
     return this.executeWithRetry(async () => {
-      const response = await this.client.post(`/channels/${this.channelId}/messages`, message);
+      if (this.webhookMode) {
+        // wait=true makes Discord return the created message so it can be edited later;
+        // with_components lets non application-owned webhooks send link buttons
+        const response = await this.client.post('', message, {
+          params: {
+            wait: true,
+            ...(threadId ? { thread_id: threadId } : {}),
+            ...(message.components?.length ? { with_components: true } : {}),
+          },
+        });
+        this.channelId = this.channelId || response.data.channel_id;
+        return response.data.id;
+      }
+      const path = threadId ? `/channels/${threadId}/messages` : this.messagePath();
+      const response = await this.client.post(path, message);
       return response.data.id;
     }, 'sendMessage');
   }
 
-  /// Updates an existing message
-  async updateMessage(messageId: string, message: DiscordMessage): Promise<void> {
+  /// Starts a thread off an existing message and returns the thread (channel) ID
+  async createThread(messageId: string, name: string): Promise<string> {
+    if (this.webhookMode) {
+      throw TrackerError.discordApiError('Threads cannot be created through a webhook');
+    }
+    return this.executeWithRetry(async () => {
+      const response = await this.client.post(`${this.messagePath(messageId)}/threads`, {
+        name: threadName(name),
+        auto_archive_duration: 1440,
+      });
+      return response.data.id;
+    }, 'createThread');
+  }
+
+  /// Opens a post in the target forum or media channel with the message as its starter
+  /// message, and returns the post's thread ID, which Discord also gives to the starter message
+  async createForumPost(name: string, message: DiscordMessage): Promise<string> {
+    if (this.webhookMode) {
+      throw TrackerError.discordApiError('Forum posts cannot be created through a webhook');
+    }
+    validateMessageEmbeds(message);
+    return this.executeWithRetry(async () => {
+      const response = await this.client.post(`/channels/${this.channelId}/threads`, {
+        name: threadName(name),
+        auto_archive_duration: 1440,
+        message,
+      });
+      return response.data.id;
+    }, 'createForumPost');
+  }
+
+  /// Client for another channel of the bot, e.g. a forum post's thread, with the same retry
+  /// and proxy settings; its requests count towards this client's telemetry
+  forChannel(channelId: string): DiscordApiClient {
+    if (this.webhookMode) {
+      // A webhook only ever posts to its own channel
+      throw TrackerError.discordApiError('A webhook cannot target another channel');
+    }
+    const api = new DiscordApi(this.botToken, channelId, this.retryConfig, this.proxyUrl);
+    api.telemetry = this.telemetry;
+    return api;
+  }
+
+  /// Updates an existing message, uploading `files` as attachments when given
+  async updateMessage(
+    messageId: string,
+    message: DiscordMessage,
+    files: FileUpload[] = []
+  ): Promise<void> {
+    validateMessageEmbeds(message);
+    message = fitMessageToLimits(message);
     return this.executeWithRetry(async () => {
-      await this.client.patch(`/channels/${this.channelId}/messages/${messageId}`, message);
+      if (files.length > 0) {
+        // The JSON default would make axios serialize the form as JSON
+        await this.client.patch(this.messagePath(messageId), buildMultipartBody(message, files), {
+          headers: { 'Content-Type': 'multipart/form-data' },
+        });
+        return;
+      }
+      await this.client.patch(this.messagePath(messageId), message);
     }, 'updateMessage');
   }
 
+  /// Fetches a single message
+  async getMessage(messageId: string): Promise<DiscordMessageData> {
+    return this.executeWithRetry(async () => {
+      const response = await this.client.get(this.messagePath(messageId));
+      return response.data;
+    }, 'getMessage');
+  }
+
+  /// Opens (or reuses) the DM channel between the bot and a user and returns its ID
+  async createDmChannel(userId: string): Promise<string> {
+    if (this.webhookMode) {
+      throw TrackerError.discordApiError('Direct messages cannot be sent through a webhook');
+    }
+    return this.executeWithRetry(async () => {
+      const response = await this.client.post('/users/@me/channels', { recipient_id: userId });
+      return response.data.id;
+    }, 'createDmChannel');
+  }
+
+  /// Sends a direct message to a user, returning the message ID
+  async sendDirectMessage(userId: string, message: DiscordMessage): Promise<string> {
+    validateMessageEmbeds(message);
+    message = fitMessageToLimits(message);
+    const dmChannelId = await this.createDmChannel(userId);
+    return this.executeWithRetry(async () => {
+      const response = await this.client.post(`/channels/${dmChannelId}/messages`, message);
+      return response.data.id;
+    }, 'sendDirectMessage');
+  }
+
+  /// Publishes a message of an announcement channel to the channels following it
+  async crosspostMessage(messageId: string): Promise<void> {
+    if (this.webhookMode) {
+      throw TrackerError.discordApiError('Messages cannot be crossposted through a webhook');
+    }
+    return this.executeWithRetry(async () => {
+      await this.client.post(`${this.messagePath(messageId)}/crosspost`);
+    }, 'crosspostMessage');
+  }
+
+  /// Pins a message in the target channel
+  async pinMessage(messageId: string): Promise<void> {
+    if (this.webhookMode) {
+      throw TrackerError.discordApiError('Messages cannot be pinned through a webhook');
+    }
+    return this.executeWithRetry(async () => {
+      await this.client.put(`/channels/${this.channelId}/pins/${messageId}`);
+    }, 'pinMessage');
+  }
+
+  /// Unpins a message from the target channel
+  async unpinMessage(messageId: string): Promise<void> {
+    if (this.webhookMode) {
+      throw TrackerError.discordApiError('Messages cannot be unpinned through a webhook');
+    }
+    return this.executeWithRetry(async () => {
+      await this.client.delete(`/channels/${this.channelId}/pins/${messageId}`);
+    }, 'unpinMessage');
+  }
+
+  /// Adds a reaction from the bot to a message
+  async addReaction(messageId: string, emoji: string): Promise<void> {
+    if (this.webhookMode) {
+      throw TrackerError.discordApiError('Reactions cannot be added through a webhook');
+    }
+    const path = `${this.messagePath(messageId)}/reactions/${encodeURIComponent(emoji)}/@me`;
+    return this.executeWithRetry(async () => {
+      await this.client.put(path);
+    }, 'addReaction');
+  }
+
+  /// Removes the bot's own reaction from a message
+  async removeReaction(messageId: string, emoji: string): Promise<void> {
+    if (this.webhookMode) {
+      throw TrackerError.discordApiError('Reactions cannot be removed through a webhook');
+    }
+    const path = `${this.messagePath(messageId)}/reactions/${encodeURIComponent(emoji)}/@me`;
+    return this.executeWithRetry(async () => {
+      await this.client.delete(path);
+    }, 'removeReaction');
+  }
+
+  /// Lists up to 100 messages of the target channel, newest first, older than `before` if given
+  async listMessages(before?: string, limit = 100): Promise<DiscordMessageData[]> {
+    if (this.webhookMode) {
+      throw TrackerError.discordApiError('Channel messages cannot be listed through a webhook');
+    }
+    return this.executeWithRetry(async () => {
+      const response = await this.client.get(this.messagePath(), {
+        params: before ? { limit, before } : { limit },
+      });
+      return response.data;
+    }, 'listMessages');
+  }
+
+  /// Fetches the user the bot token belongs to
+  async getCurrentUser(): Promise<DiscordUser> {
+    if (this.webhookMode) {
+      throw TrackerError.discordApiError('The current user is not available through a webhook');
+    }
+    return this.executeWithRetry(async () => {
+      const response = await this.client.get('/users/@me');
+      return response.data;
+    }, 'getCurrentUser');
+  }
+
+  /// Fetches the roles of a guild, used to compute the bot's channel permissions
+  async getGuildRoles(guildId: string): Promise<DiscordRole[]> {
+    if (this.webhookMode) {
+      throw TrackerError.discordApiError('Guild roles are not available through a webhook');
+    }
+    return this.executeWithRetry(async () => {
+      const response = await this.client.get(`/guilds/${guildId}/roles`);
+      return response.data;
+    }, 'getGuildRoles');
+  }
+
+  /// Fetches a member of a guild
+  async getGuildMember(guildId: string, userId: string): Promise<DiscordGuildMember> {
+    if (this.webhookMode) {
+      throw TrackerError.discordApiError('Guild members are not available through a webhook');
+    }
+    return this.executeWithRetry(async () => {
+      const response = await this.client.get(`/guilds/${guildId}/members/${userId}`);
+      return response.data;
+    }, 'getGuildMember');
+  }
+
+  /// Gets the reactions currently on a message
+  async getReactions(messageId: string): Promise<DiscordReaction[]> {
+    const message = await this.getMessage(messageId);
+    return message.reactions || [];
+  }
+
+  /// Fetches the target channel, or another channel by ID. In webhook mode only the channel
+  /// and guild IDs of the webhook's own channel are known, read from the webhook itself.
+  async getChannel(channelId: string = this.channelId): Promise<DiscordChannel> {
+    return this.executeWithRetry(async () => {
+      if (this.webhookMode) {
+        const response = await this.client.get('');
+        return { id: response.data.channel_id, type: 0, guild_id: response.data.guild_id };
+      }
+      const response = await this.client.get(`/channels/${channelId}`);
+      return response.data;
+    }, 'getChannel');
+  }
+
+  /// Computes the canonical URL of a message in the target channel
+  async getMessageUrl(messageId: string): Promise<string> {
+    const channel = await this.getChannel();
+    return buildMessageUrl(channel.guild_id, channel.id || this.channelId, messageId);
+  }
+
   /// Deletes a message
   async deleteMessage(messageId: string): Promise<void> {
     return this.executeWithRetry(async () => {
-      await this.client.delete(`/channels/${this.channelId}/messages/${messageId}`);
+      await this.client.delete(this.messagePath(messageId));
     }, 'deleteMessage');
   }
 
   /// Checks if Discord API is available by attempting to get channel info
   async checkApiHealth(): Promise<{ available: boolean; error?: string }> {
     try {
-      await this.client.get(`/channels/${this.channelId}`);
+      await this.client.get(this.webhookMode ? '' : `/channels/${this.channelId}`);
       return { available: true };
     } catch (error) {
       const axiosError = error as AxiosError;
@@ -25,6 +25,30 @@ export class TrackerError extends Error {
     return new TrackerError(`Discord API Error: ${message}`, code);
   }
 
+  static unsupportedChannelType(message: string): TrackerError {
+    return new TrackerError(message, 'UNSUPPORTED_CHANNEL_TYPE');
+  }
+
+  static unauthorized(message: string): TrackerError {
+    return new TrackerError(`Discord API Error: ${message}`, 'UNAUTHORIZED');
+  }
+
+  static forbidden(message: string): TrackerError {
+    return new TrackerError(`Discord API Error: ${message}`, 'FORBIDDEN');
+  }
+
+  static messageNotFound(message: string): TrackerError {
+    return new TrackerError(`Discord API Error: ${message}`, 'MESSAGE_NOT_FOUND');
+  }
+
+  static rateLimited(message: string): TrackerError {
+    return new TrackerError(`Discord API Error: ${message}`, 'RATE_LIMITED');
+  }
+
+  static unknownDiscordError(message: string): TrackerError {
+    return new TrackerError(`Discord API Error: ${message}`, 'UNKNOWN_DISCORD_ERROR');
+  }
+
   static missingRequiredInput(inputName: string): TrackerError {
     return new TrackerError(`Missing required input: ${inputName}`, 'MISSING_INPUT');
   }
@@ -41,12 +65,14 @@ export class TrackerError extends Error {
     return new TrackerError(`JSON parsing error: ${error.message}`, 'JSON_ERROR');
   }
 
-  static invalidBotToken(): TrackerError {
-    return new TrackerError('Bot token is invalid', 'INVALID_BOT_TOKEN');
+  static invalidBotToken(detail?: string): TrackerError {
+    const message = detail ? `Bot token is invalid: ${detail}` : 'Bot token is invalid';
+    return new TrackerError(message, 'INVALID_BOT_TOKEN');
   }
 
-  static invalidChannelId(): TrackerError {
-    return new TrackerError('Channel ID is invalid', 'INVALID_CHANNEL_ID');
+  static invalidChannelId(detail?: string): TrackerError {
+    const message = detail ? `Channel ID is invalid: ${detail}` : 'Channel ID is invalid';
+    return new TrackerError(message, 'INVALID_CHANNEL_ID');
   }
 
   static stateLoadError(error: Error): TrackerError {
@@ -64,3 +90,8 @@ export class TrackerError extends Error {
     );
   }
 }
+
+/// Checks whether `error` is a TrackerError of the given variant, e.g. 'MESSAGE_NOT_FOUND'
+export function isTrackerError(error: unknown, code: string): boolean {
+  return error instanceof TrackerError && error.code === code;
+}
@@ -0,0 +1,126 @@
+import * as fs from 'fs/promises';
+import * as path from 'path';
+import { TrackerError } from './error';
+
+// Entries beyond this count are dropped from the feed, oldest first
+const DEFAULT_MAX_FEED_ENTRIES = 50;
+
+// One completed pipeline published in the feed
+export interface FeedEntry {
+  repository: string;
+  prNumber: number;
+  prTitle: string;
+  author: string;
+  branch: string;
+  conclusion: 'success' | 'failed';
+  completedAt: string;
+  // One line per step, e.g. "✅ Build"
+  summary: string[];
+  // Discord message of the pipeline, linked from the entry
+  url?: string;
+}
+
+// Feed-level metadata
+export interface FeedOptions {
+  title?: string;
+  // Site the feed is published on (e.g. the gh-pages URL), linked from the feed
+  link?: string;
+  maxEntries?: number;
+}
+
+/// Escapes text for use in XML content and attribute values
+export function escapeXml(text: string): string {
+  return text
+    .replace(/&/g, '&amp;')
+    .replace(/</g, '&lt;')
+    .replace(/>/g, '&gt;')
+    .replace(/"/g, '&quot;')
+    .replace(/'/g, '&apos;');
+}
+
+/// Builds the stable Atom ID of an entry (a tag URI)
+export function feedEntryId(entry: FeedEntry): string {
+  return `tag:discord-tracker,2025:${entry.repository}/pull/${entry.prNumber}/${entry.completedAt}`;
+}
+
+/// Renders one `<entry>` element
+export function buildFeedEntry(entry: FeedEntry): string {
+  const status = entry.conclusion === 'success' ? '✅ Succeeded' : '❌ Failed';
+  const title = `${status}: ${entry.repository} PR #${entry.prNumber} - ${entry.prTitle}`;
+  const content = [`Branch: ${entry.branch}`, ...entry.summary].join('\n');
+  const lines = [
+    '  <entry>',
+    `    <id>${escapeXml(feedEntryId(entry))}</id>`,
+    `    <title>${escapeXml(title)}</title>`,
+    `    <updated>${escapeXml(entry.completedAt)}</updated>`,
+    `    <author><name>${escapeXml(entry.author)}</name></author>`,
+    `    <category term="${escapeXml(entry.conclusion)}"/>`,
+  ];
+  if (entry.url) {
+    lines.push(`    <link rel="alternate" href="${escapeXml(entry.url)}"/>`);
+  }
+  lines.push(`    <content type="text">${escapeXml(content)}</content>`, '  </entry>');
+  return lines.join('\n');
+}
+
+/// Renders the whole Atom document around already rendered entries, newest first
+export function buildFeed(entries: string[], updated: string, options: FeedOptions = {}): string {
+  const title = options.title || 'Pipeline results';
+  const lines = [
+    '<?xml version="1.0" encoding="utf-8"?>',
+    '<feed xmlns="http://www.w3.org/2005/Atom">',
+    `  <id>tag:discord-tracker,2025:feed/${escapeXml(encodeURIComponent(title))}</id>`,
+    `  <title>${escapeXml(title)}</title>`,
+    `  <updated>${escapeXml(updated)}</updated>`,
+    '  <generator>discord-pipeline-tracker</generator>',
+  ];
+  if (options.link) {
+    lines.push(`  <link rel="alternate" href="${escapeXml(options.link)}"/>`);
+  }
+  return [...lines, ...entries, '</feed>', ''].join('\n');
+}
+
+/// Extracts the `<entry>` elements of a feed written by `buildFeed`
+export function parseFeedEntries(xml: string): string[] {
+  return xml.match(/ {2}<entry>[\s\S]*?<\/entry>/g) || [];
+}
+
+/// Atom feed file mirroring pipeline outcomes for stakeholders outside Discord. Every
+/// completion prepends an entry and rewrites the file, keeping the newest `maxEntries`.
+export class FeedStore {
+  private filePath: string;
+  private options: FeedOptions;
+
+  constructor(filePath?: string, options: FeedOptions = {}) {
+    this.filePath = filePath || path.join(process.cwd(), 'pipelines.atom');
+    this.options = options;
+  }
+
+  /// Loads the rendered entries of the feed, newest first
+  async loadEntries(): Promise<string[]> {
+    try {
+      return parseFeedEntries(await fs.readFile(this.filePath, 'utf-8'));
+    } catch (error: any) {
+      if (error.code === 'ENOENT') {
+        return [];
+      }
+      throw TrackerError.fileSystemError(error);
+    }
+  }
+
+  /// Prepends an entry and rewrites the feed
+  async append(entry: FeedEntry): Promise<void> {
+    const maxEntries = this.options.maxEntries || DEFAULT_MAX_FEED_ENTRIES;
+    const entries = [buildFeedEntry(entry), ...(await this.loadEntries())].slice(0, maxEntries);
+    const xml = buildFeed(entries, entry.completedAt, this.options);
+    try {
+      await fs.writeFile(this.filePath, xml, 'utf-8');
+    } catch (error: any) {
+      throw TrackerError.fileSystemError(error);
+    }
+  }
+
+  getFilePath(): string {
+    return this.filePath;
+  }
+}
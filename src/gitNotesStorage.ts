@@ -0,0 +1,168 @@
+import { spawn } from 'child_process';
+import { TrackerError } from './error';
+import type { InternalPipelineState, Storage } from './pipelineTracker';
+import { reviveState } from './storage';
+
+// Notes ref holding the tracker state, one note per commit
+export const DEFAULT_NOTES_REF = 'refs/notes/discord-tracker';
+
+// Runs `git <args>`, writing `input` to stdin, and resolves with stdout
+export type GitRunner = (args: string[], input?: string) => Promise<string>;
+
+// Options for the git notes storage backend
+export interface GitNotesStorageOptions {
+  // Notes ref, defaults to refs/notes/discord-tracker
+  ref?: string;
+  // Commit the note is attached to, defaults to GITHUB_SHA or HEAD
+  commit?: string;
+  // Remote the notes are fetched from and pushed to; empty keeps them local
+  remote?: string;
+  git?: GitRunner;
+}
+
+// Pushes rejected because another job updated the notes ref are retried this many times
+const MAX_PUSH_ATTEMPTS = 3;
+
+// Identity used for the commits behind note changes when the runner has no git user configured
+const GIT_IDENTITY = [
+  '-c',
+  'user.name=github-actions[bot]',
+  '-c',
+  'user.email=41898282+github-actions[bot]@users.noreply.github.com',
+];
+
+/// Runs git in the working directory, rejecting with stderr when it exits non-zero
+export function runGit(args: string[], input?: string): Promise<string> {
+  return new Promise((resolve, reject) => {
+    const child = spawn('git', args, { stdio: ['pipe', 'pipe', 'pipe'] });
+    let stdout = '';
+    let stderr = '';
+
+    child.stdout.on('data', (chunk) => {
+      stdout += chunk;
+    });
+    child.stderr.on('data', (chunk) => {
+      stderr += chunk;
+    });
+    child.on('error', reject);
+    child.on('close', (code) => {
+      if (code === 0) {
+        resolve(stdout);
+      } else {
+        reject(new Error(`git ${args[0]} exited with code ${code}: ${stderr.trim()}`));
+      }
+    });
+
+    child.stdin.end(input ?? '');
+  });
+}
+
+/// Storage backend that keeps the state in a git note attached to the workflow commit,
+/// so later jobs on other runners can read it back without extra services
+export class GitNotesStorage implements Storage {
+  private ref: string;
+  private commit: string;
+  private remote: string;
+  private git: GitRunner;
+
+  constructor(options: GitNotesStorageOptions = {}) {
+    this.ref = options.ref || DEFAULT_NOTES_REF;
+    this.commit = options.commit || process.env.GITHUB_SHA || 'HEAD';
+    this.remote = options.remote ?? 'origin';
+    this.git = options.git || runGit;
+  }
+
+  /// Fetches the notes ref from the remote, replacing the local copy.
+  /// A missing remote ref (nothing saved yet) is not an error.
+  private async fetchNotes(): Promise<void> {
+    if (!this.remote) {
+      return;
+    }
+    try {
+      await this.git(['fetch', '--quiet', this.remote, `+${this.ref}:${this.ref}`]);
+    } catch {
+      // The ref does not exist on the remote until the first save
+    }
+  }
+
+  /// Attaches `content` as the note of the tracked commit, or removes the note when undefined
+  private async writeNote(content: string | undefined): Promise<void> {
+    if (content === undefined) {
+      await this.git([
+        ...GIT_IDENTITY,
+        'notes',
+        '--ref',
+        this.ref,
+        'remove',
+        '--ignore-missing',
+        this.commit,
+      ]);
+    } else {
+      await this.git(
+        [...GIT_IDENTITY, 'notes', '--ref', this.ref, 'add', '--force', '--file', '-', this.commit],
+        content
+      );
+    }
+  }
+
+  /// Writes the note and pushes it, re-applying it on top of the remote notes when the
+  /// push is rejected because another job pushed in between
+  private async publish(content: string | undefined): Promise<void> {
+    try {
+      for (let attempt = 1; ; attempt++) {
+        await this.fetchNotes();
+        await this.writeNote(content);
+        if (!this.remote) {
+          return;
+        }
+        try {
+          await this.git(['push', '--quiet', this.remote, `${this.ref}:${this.ref}`]);
+          return;
+        } catch (error) {
+          if (attempt >= MAX_PUSH_ATTEMPTS) {
+            throw error;
+          }
+        }
+      }
+    } catch (error: any) {
+      throw TrackerError.stateSaveError(error);
+    }
+  }
+
+  async savePipelineState(state: InternalPipelineState): Promise<void> {
+    await this.publish(JSON.stringify(state));
+  }
+
+  async loadPipelineState(): Promise<InternalPipelineState | null> {
+    await this.fetchNotes();
+
+    let content: string;
+    try {
+      content = await this.git(['notes', '--ref', this.ref, 'show', this.commit]);
+    } catch {
+      // No note attached to the commit yet
+      return null;
+    }
+    if (content.trim() === '') {
+      return null;
+    }
+
+    try {
+      return reviveState(JSON.parse(content));
+    } catch (error: any) {
+      throw TrackerError.jsonError(error);
+    }
+  }
+
+  async clearPipelineState(): Promise<void> {
+    await this.publish(undefined);
+  }
+
+  getRef(): string {
+    return this.ref;
+  }
+
+  getCommit(): string {
+    return this.commit;
+  }
+}
@@ -0,0 +1,343 @@
+import * as github from '@actions/github';
+import { TrackerError } from './error';
+import type { PipelineLinks } from './messageBuilder';
+import type { ChangelogCommit } from './models';
+
+// Hidden marker identifying the tracker's sticky PR comment
+export const STICKY_COMMENT_MARKER = '<!-- discord-pipeline-tracker -->';
+
+/// Splits an `owner/repo` string into its parts
+export function splitRepository(repository: string): { owner: string; repo: string } {
+  const [owner, repo] = repository.split('/');
+  if (!owner || !repo) {
+    throw new TrackerError('Invalid repository format. Expected: owner/repo', 'INVALID_REPOSITORY');
+  }
+  return { owner, repo };
+}
+
+/// Checks a branch against patterns matching exactly, or by prefix with a trailing `*`
+/// (e.g. `release/*`)
+export function matchesBranch(branch: string, patterns: string[]): boolean {
+  return patterns.some((pattern) =>
+    pattern.endsWith('*') ? branch.startsWith(pattern.slice(0, -1)) : branch === pattern
+  );
+}
+
+/// Builds the body of the sticky PR comment linking to the Discord message
+export function buildPrCommentBody(messageUrl: string, statusLine: string): string {
+  return [
+    STICKY_COMMENT_MARKER,
+    `**Discord pipeline tracker:** ${statusLine}`,
+    '',
+    `[View the pipeline discussion in Discord](${messageUrl})`,
+  ].join('\n');
+}
+
+// Locations GitHub reads the CODEOWNERS file from, in order of precedence
+const CODEOWNERS_PATHS = ['.github/CODEOWNERS', 'CODEOWNERS', 'docs/CODEOWNERS'];
+
+// One CODEOWNERS line: a path pattern and its owners (`@user`, `@org/team` or an email)
+export interface CodeownersRule {
+  pattern: string;
+  owners: string[];
+}
+
+/// Parses a CODEOWNERS file, skipping blank lines and comments
+export function parseCodeowners(content: string): CodeownersRule[] {
+  return content
+    .split('\n')
+    .map((line) => line.trim())
+    .filter((line) => line.length > 0 && !line.startsWith('#'))
+    .map((line) => {
+      const [pattern, ...rest] = line.split(/\s+/);
+      const comment = rest.findIndex((token) => token.startsWith('#'));
+      return { pattern, owners: comment === -1 ? rest : rest.slice(0, comment) };
+    });
+}
+
+/// Converts a CODEOWNERS pattern (gitignore syntax) to a regular expression matching file
+/// paths relative to the repository root. Patterns without a leading or inner `/` match at
+/// any depth, and a pattern naming a directory matches everything below it, except after a
+/// trailing `*` (`docs/*` only covers the files directly in `docs`).
+export function codeownersPatternToRegExp(pattern: string): RegExp {
+  const anchored = pattern.startsWith('/') || pattern.slice(0, -1).includes('/');
+  const path = pattern.replace(/^\//, '').replace(/\/$/, '');
+
+  let source = '';
+  for (let i = 0; i < path.length; i++) {
+    const char = path[i];
+    if (char === '*' && path[i + 1] === '*') {
+      const crossesDirectory = path[i + 2] === '/';
+      source += crossesDirectory ? '(?:.*/)?' : '.*';
+      i += crossesDirectory ? 2 : 1;
+    } else if (char === '*') {
+      source += '[^/]*';
+    } else if (char === '?') {
+      source += '[^/]';
+    } else {
+      source += char.replace(/[.+^${}()|[\]\\]/g, '\\$&');
+    }
+  }
+
+  const directorySuffix = /(^|[^*])\*$/.test(path) ? '' : '(?:/.*)?';
+  return new RegExp(`^${anchored ? '' : '(?:.*/)?'}${source}${directorySuffix}$`);
+}
+
+/// Owners of a file path; the last matching rule wins, as on GitHub
+export function findCodeowners(rules: CodeownersRule[], filePath: string): string[] {
+  const normalized = filePath.trim().replace(/^\.?\//, '');
+  const match = [...rules]
+    .reverse()
+    .find((rule) => codeownersPatternToRegExp(rule.pattern).test(normalized));
+  return match ? match.owners : [];
+}
+
+/// Resolves the code owners of failing paths. The CODEOWNERS file is read once per
+/// invocation and the owners of each path are cached.
+export class CodeownersResolver {
+  private rules: Promise<CodeownersRule[]> | undefined;
+  private cache = new Map<string, string[]>();
+
+  /// `fetchContent` returns the CODEOWNERS file, or undefined when the repository has none
+  constructor(private fetchContent: () => Promise<string | undefined>) {}
+
+  /// Owners of any of the paths, each listed once, in the order they were first found
+  async ownersOf(paths: string[]): Promise<string[]> {
+    if (paths.length === 0) {
+      return [];
+    }
+    if (!this.rules) {
+      this.rules = this.fetchContent().then((content) => parseCodeowners(content || ''));
+    }
+    const rules = await this.rules;
+
+    const owners = new Set<string>();
+    for (const filePath of paths) {
+      let pathOwners = this.cache.get(filePath);
+      if (!pathOwners) {
+        pathOwners = findCodeowners(rules, filePath);
+        this.cache.set(filePath, pathOwners);
+      }
+      for (const owner of pathOwners) {
+        owners.add(owner);
+      }
+    }
+    return [...owners];
+  }
+}
+
+/// Creates a resolver reading CODEOWNERS from the repository at `ref` through the GitHub API
+export function createCodeownersResolver(
+  token: string,
+  repository: string,
+  ref?: string
+): CodeownersResolver {
+  return new CodeownersResolver(async () => {
+    const octokit = github.getOctokit(token);
+    const { owner, repo } = splitRepository(repository);
+    for (const path of CODEOWNERS_PATHS) {
+      try {
+        const { data } = await octokit.rest.repos.getContent({ owner, repo, path, ref });
+        if (!Array.isArray(data) && data.type === 'file') {
+          return Buffer.from(data.content, 'base64').toString('utf-8');
+        }
+      } catch (error: any) {
+        if (error.status !== 404) {
+          throw error;
+        }
+      }
+    }
+    return undefined;
+  });
+}
+
+// Where the time of a workflow run went besides running the steps
+export interface RunTiming {
+  // From the run being created to its first job starting on a runner
+  queueMs: number;
+  // Runner time billed across all runner types; 0 for public repositories and self-hosted runners
+  billableMs: number;
+}
+
+/// Time from the run being created to the earliest job start; jobs not started yet are ignored
+export function computeQueueMs(runCreatedAt: string, jobStartedAts: Array<string | null>): number {
+  const starts = jobStartedAts
+    .filter((startedAt): startedAt is string => Boolean(startedAt))
+    .map((startedAt) => new Date(startedAt).getTime());
+  if (starts.length === 0) {
+    return 0;
+  }
+  return Math.max(0, Math.min(...starts) - new Date(runCreatedAt).getTime());
+}
+
+/// Fetches the queue time and billable runner time of a workflow run. The billable time
+/// covers the jobs finished so far, so the job reporting completion is not included.
+export async function fetchRunTiming(
+  token: string,
+  repository: string,
+  runId: number
+): Promise<RunTiming> {
+  const octokit = github.getOctokit(token);
+  const { owner, repo } = splitRepository(repository);
+
+  const [{ data: run }, jobs, { data: usage }] = await Promise.all([
+    octokit.rest.actions.getWorkflowRun({ owner, repo, run_id: runId }),
+    octokit.paginate(octokit.rest.actions.listJobsForWorkflowRun, {
+      owner,
+      repo,
+      run_id: runId,
+      per_page: 100,
+    }),
+    octokit.rest.actions.getWorkflowRunUsage({ owner, repo, run_id: runId }),
+  ]);
+
+  const billable = Object.values(usage.billable || {}) as Array<{ total_ms?: number }>;
+  return {
+    queueMs: computeQueueMs(run.created_at, jobs.map((job) => job.started_at)),
+    billableMs: billable.reduce((total, runner) => total + (runner?.total_ms || 0), 0),
+  };
+}
+
+/// Derives the workflow run URL from the variables GitHub Actions sets, and the PR URL from
+/// the triggering pull request, when the action inputs do not provide them
+export function derivePipelineLinks(
+  env: NodeJS.ProcessEnv = process.env,
+  pullRequestUrl: string | undefined = github.context.payload.pull_request?.html_url
+): PipelineLinks {
+  const { GITHUB_SERVER_URL: server, GITHUB_REPOSITORY: repository, GITHUB_RUN_ID: runId } = env;
+  return {
+    runUrl:
+      server && repository && runId ? `${server}/${repository}/actions/runs/${runId}` : undefined,
+    prUrl: pullRequestUrl,
+  };
+}
+
+// How notification delivery is published back to GitHub
+export type StatusPublishMode = 'none' | 'status' | 'check';
+
+// Context/check name used for published statuses
+export const STATUS_CONTEXT = 'discord-tracker';
+
+/// Resolves the commit to attach statuses to: the PR head when available, otherwise the workflow SHA
+export function resolveHeadSha(): string {
+  return github.context.payload.pull_request?.head?.sha || github.context.sha;
+}
+
+/// Returns the message of the pushed commit; only push events carry it
+export function resolveHeadCommitMessage(): string | undefined {
+  return github.context.payload.head_commit?.message;
+}
+
+// Run whose cancellation is propagated to its stored pipeline by the `cancel` action
+export interface CancellationEvent {
+  repository: string;
+  // Commit the cancelled run was building, which the git-notes state is attached to
+  headSha: string;
+}
+
+/// Reads the cancelled run out of a completed `workflow_run` or `check_suite` event payload;
+/// undefined for other events and conclusions
+export function parseCancellationEvent(
+  eventName: string,
+  payload: Record<string, any>
+): CancellationEvent | undefined {
+  if (eventName !== 'workflow_run' && eventName !== 'check_suite') {
+    return undefined;
+  }
+  const run = payload[eventName];
+  if (payload.action !== 'completed' || run?.conclusion !== 'cancelled' || !run.head_sha) {
+    return undefined;
+  }
+  return { repository: payload.repository?.full_name || '', headSha: run.head_sha };
+}
+
+/// Reads the cancelled run out of the event that triggered the workflow
+export function resolveCancellationEvent(): CancellationEvent | undefined {
+  return parseCancellationEvent(github.context.eventName, github.context.payload);
+}
+
+/// Reads the pushed commits of a push event payload, oldest first; empty for other events
+export function parseEventCommits(payload: Record<string, any>): ChangelogCommit[] {
+  return (payload.commits || [])
+    .filter((commit: any) => typeof commit?.id === 'string')
+    .map((commit: any) => ({ sha: commit.id, message: String(commit.message || '') }));
+}
+
+/// Reads the pushed commits of the event that triggered the workflow
+export function resolveEventCommits(): ChangelogCommit[] {
+  return parseEventCommits(github.context.payload);
+}
+
+/// Builds the short description published with the notification status
+export function buildDeliveryDescription(delivered: boolean, error?: string): string {
+  const description = delivered ? 'notified ✅' : `notification failed ❌ ${error || ''}`.trim();
+  // Commit status descriptions are limited to 140 characters
+  return description.length > 140 ? `${description.slice(0, 139)}…` : description;
+}
+
+/// Publishes whether Discord notifications were delivered as a commit status or check run
+export async function publishDeliveryStatus(
+  token: string,
+  repository: string,
+  sha: string,
+  mode: StatusPublishMode,
+  delivered: boolean,
+  error?: string
+): Promise<void> {
+  if (mode === 'none') {
+    return;
+  }
+
+  const octokit = github.getOctokit(token);
+  const { owner, repo } = splitRepository(repository);
+  const description = buildDeliveryDescription(delivered, error);
+
+  if (mode === 'status') {
+    await octokit.rest.repos.createCommitStatus({
+      owner,
+      repo,
+      sha,
+      state: delivered ? 'success' : 'failure',
+      context: STATUS_CONTEXT,
+      description,
+    });
+  } else {
+    await octokit.rest.checks.create({
+      owner,
+      repo,
+      name: STATUS_CONTEXT,
+      head_sha: sha,
+      status: 'completed',
+      conclusion: delivered ? 'success' : 'failure',
+      output: {
+        title: `${STATUS_CONTEXT}: ${description}`,
+        summary: error || 'All Discord notifications were delivered.',
+      },
+    });
+  }
+}
+
+/// Creates the sticky PR comment, or updates it in place if it already exists
+export async function upsertPrComment(
+  token: string,
+  repository: string,
+  prNumber: number,
+  body: string
+): Promise<void> {
+  const octokit = github.getOctokit(token);
+  const { owner, repo } = splitRepository(repository);
+
+  const comments = await octokit.paginate(octokit.rest.issues.listComments, {
+    owner,
+    repo,
+    issue_number: prNumber,
+    per_page: 100,
+  });
+  const existing = comments.find((comment) => comment.body?.includes(STICKY_COMMENT_MARKER));
+
+  if (existing) {
+    await octokit.rest.issues.updateComment({ owner, repo, comment_id: existing.id, body });
+  } else {
+    await octokit.rest.issues.createComment({ owner, repo, issue_number: prNumber, body });
+  }
+}
@@ -0,0 +1,315 @@
+import * as fs from 'fs/promises';
+import * as path from 'path';
+import { TrackerError } from './error';
+import { type BenchmarkResult, type StepInfo, StepStatus } from './models';
+
+// Outcome of a finished pipeline run
+export type RunConclusion = 'success' | 'failed';
+
+// Per-step entry recorded in the history store
+export interface StepRunRecord {
+  name: string;
+  status: StepStatus;
+  startedAt?: string;
+  completedAt?: string;
+  benchmarks?: BenchmarkResult[];
+  budgetMs?: number;
+  // How long the step ran past its budget, when it exceeded it
+  overBudgetMs?: number;
+  // Attempts after the first one
+  retries?: number;
+}
+
+// 👍/👎 reaction counts collected from a completion message, excluding the bot's own
+export interface ReleaseFeedback {
+  up: number;
+  down: number;
+  collectedAt: string;
+}
+
+// Rollback of a deployed run, recorded by the `rollback` action
+export interface RollbackRecord {
+  at: string;
+  // Who rolled back, e.g. the GitHub actor
+  by: string;
+  reason?: string;
+  // Reply posted under the run's pipeline message
+  messageId: string;
+}
+
+// A single completed pipeline run
+export interface PipelineRunRecord {
+  repository: string;
+  prNumber: number;
+  prTitle: string;
+  author: string;
+  branch: string;
+  conclusion: RunConclusion;
+  startedAt: string;
+  completedAt: string;
+  durationMs: number;
+  estimatedCost?: number;
+  tags?: Array<[string, string]>;
+  steps: StepRunRecord[];
+  // Discord message of the run, used to attach feedback collected later
+  messageId?: string;
+  feedback?: ReleaseFeedback;
+  // GitHub Actions run the record was imported from by `history import`
+  runId?: number;
+  // Deploy identifier given at `init`, used by `rollback` to find the run
+  correlationId?: string;
+  rollback?: RollbackRecord;
+}
+
+// Aggregated view of the runs completed on one calendar day (UTC)
+export interface DailySummary {
+  date: string;
+  runs: number;
+  failures: number;
+  totalDurationMs: number;
+  totalCost: number;
+}
+
+/// Determines the conclusion of a run from its steps
+export function conclusionFromSteps(steps: StepInfo[]): RunConclusion {
+  return steps.some((step) => step.status === StepStatus.Failed) ? 'failed' : 'success';
+}
+
+/// Keeps the records matching every filter pair. The `conclusion` key matches the run
+/// outcome, any other key matches the run's tags.
+export function filterRecords(
+  records: PipelineRunRecord[],
+  filter: Array<[string, string]>
+): PipelineRunRecord[] {
+  return records.filter((record) =>
+    filter.every(([key, value]) => {
+      if (key === 'conclusion') {
+        return record.conclusion === value;
+      }
+      return (record.tags || []).some(([tagKey, tagValue]) => tagKey === key && tagValue === value);
+    })
+  );
+}
+
+// Success rate of a repository's runs compared against its SLO target
+export interface SloStatus {
+  repository: string;
+  runs: number;
+  successes: number;
+  successRate: number;
+  targetPercent: number;
+  breached: boolean;
+}
+
+/// Computes the success rate of each repository's runs against the SLO target (in percent)
+export function evaluateSlo(records: PipelineRunRecord[], targetPercent: number): SloStatus[] {
+  const byRepository = new Map<string, { runs: number; successes: number }>();
+  for (const record of records) {
+    const counts = byRepository.get(record.repository) || { runs: 0, successes: 0 };
+    counts.runs++;
+    if (record.conclusion === 'success') {
+      counts.successes++;
+    }
+    byRepository.set(record.repository, counts);
+  }
+
+  return [...byRepository.entries()]
+    .map(([repository, { runs, successes }]) => {
+      const successRate = (successes / runs) * 100;
+      return {
+        repository,
+        runs,
+        successes,
+        successRate,
+        targetPercent,
+        breached: successRate < targetPercent,
+      };
+    })
+    .sort((a, b) => a.repository.localeCompare(b.repository));
+}
+
+// Recent runs per step whose durations feed the estimates; older runs are ignored
+const ESTIMATE_SAMPLE_SIZE = 10;
+
+/// Median duration of each step of a repository over its most recent runs, keyed by step
+/// name. Steps recorded without both a start and a completion time are ignored.
+export function estimateStepDurations(
+  records: PipelineRunRecord[],
+  repository: string
+): Map<string, number> {
+  const samples = new Map<string, number[]>();
+  for (const record of [...records].reverse()) {
+    if (record.repository !== repository) {
+      continue;
+    }
+    for (const step of record.steps) {
+      const durations = samples.get(step.name) || [];
+      if (step.startedAt && step.completedAt && durations.length < ESTIMATE_SAMPLE_SIZE) {
+        durations.push(new Date(step.completedAt).getTime() - new Date(step.startedAt).getTime());
+        samples.set(step.name, durations);
+      }
+    }
+  }
+
+  const medians = new Map<string, number>();
+  for (const [name, durations] of samples) {
+    const sorted = [...durations].sort((a, b) => a - b);
+    const middle = Math.floor(sorted.length / 2);
+    medians.set(
+      name,
+      sorted.length % 2 === 1 ? sorted[middle] : (sorted[middle - 1] + sorted[middle]) / 2
+    );
+  }
+  return medians;
+}
+
+/// Estimates the time left in a running pipeline: the historical durations of the steps of
+/// the repository's most recent run that this run has not finished yet, minus the time the
+/// running ones have already taken. Undefined when the repository has no usable history.
+export function estimateRemainingMs(
+  records: PipelineRunRecord[],
+  repository: string,
+  steps: StepInfo[],
+  now: Date
+): number | undefined {
+  const durations = estimateStepDurations(records, repository);
+  const previous = [...records].reverse().find((record) => record.repository === repository);
+  if (!previous || durations.size === 0) {
+    return undefined;
+  }
+
+  const current = new Map(steps.map((step) => [step.name, step]));
+  let remaining = 0;
+  for (const { name } of previous.steps) {
+    const step = current.get(name);
+    const expected = durations.get(name);
+    if (expected === undefined || step?.completedAt) {
+      continue;
+    }
+    const elapsed = step?.startedAt ? now.getTime() - new Date(step.startedAt).getTime() : 0;
+    remaining += Math.max(0, expected - elapsed);
+  }
+  return remaining;
+}
+
+/// Aggregates run records per UTC day, most recent day first
+export function summarizeByDay(records: PipelineRunRecord[]): DailySummary[] {
+  const byDay = new Map<string, DailySummary>();
+
+  for (const record of records) {
+    const date = record.completedAt.slice(0, 10);
+    const summary = byDay.get(date) || {
+      date,
+      runs: 0,
+      failures: 0,
+      totalDurationMs: 0,
+      totalCost: 0,
+    };
+    summary.runs++;
+    if (record.conclusion === 'failed') {
+      summary.failures++;
+    }
+    summary.totalDurationMs += record.durationMs;
+    summary.totalCost += record.estimatedCost || 0;
+    byDay.set(date, summary);
+  }
+
+  return [...byDay.values()].sort((a, b) => b.date.localeCompare(a.date));
+}
+
+/// File-based store of completed pipeline runs, used by the digest and run statistics
+export class HistoryStore {
+  private filePath: string;
+
+  constructor(filePath?: string) {
+    this.filePath = filePath || path.join(process.cwd(), '.discord-pipeline-history');
+  }
+
+  /// Loads all recorded runs, oldest first
+  async load(): Promise<PipelineRunRecord[]> {
+    try {
+      const content = await fs.readFile(this.filePath, 'utf-8');
+      if (content.trim() === '') {
+        return [];
+      }
+      const records = JSON.parse(content);
+      return Array.isArray(records) ? records : [];
+    } catch (error: any) {
+      if (error.code === 'ENOENT') {
+        return [];
+      }
+      throw TrackerError.jsonError(error);
+    }
+  }
+
+  private async write(records: PipelineRunRecord[]): Promise<void> {
+    try {
+      await fs.writeFile(this.filePath, JSON.stringify(records, null, 2), 'utf-8');
+    } catch (error: any) {
+      throw TrackerError.fileSystemError(error);
+    }
+  }
+
+  /// Appends a completed run to the history file
+  async append(record: PipelineRunRecord): Promise<void> {
+    const records = await this.load();
+    records.push(record);
+    await this.write(records);
+  }
+
+  /// Adds several runs at once, keeping the file ordered by completion time
+  async merge(records: PipelineRunRecord[]): Promise<void> {
+    if (records.length === 0) {
+      return;
+    }
+    const merged = [...(await this.load()), ...records].sort((a, b) =>
+      a.completedAt.localeCompare(b.completedAt)
+    );
+    await this.write(merged);
+  }
+
+  /// Attaches feedback to the most recent run posted as `messageId`, replacing any feedback
+  /// collected before. Returns false when no recorded run has that message.
+  async recordFeedback(messageId: string, feedback: ReleaseFeedback): Promise<boolean> {
+    const records = await this.load();
+    const record = [...records].reverse().find((candidate) => candidate.messageId === messageId);
+    if (!record) {
+      return false;
+    }
+    record.feedback = feedback;
+    await this.write(records);
+    return true;
+  }
+
+  /// Finds the most recent run recorded with a correlation ID
+  async findByCorrelationId(correlationId: string): Promise<PipelineRunRecord | undefined> {
+    const records = await this.load();
+    return [...records].reverse().find((record) => record.correlationId === correlationId);
+  }
+
+  /// Records the rollback of the most recent run with a correlation ID. Returns false when no
+  /// recorded run has that ID.
+  async recordRollback(correlationId: string, rollback: RollbackRecord): Promise<boolean> {
+    const records = await this.load();
+    const record = [...records]
+      .reverse()
+      .find((candidate) => candidate.correlationId === correlationId);
+    if (!record) {
+      return false;
+    }
+    record.rollback = rollback;
+    await this.write(records);
+    return true;
+  }
+
+  /// Loads the runs completed within the last `days` days
+  async loadSince(days: number, now: Date = new Date()): Promise<PipelineRunRecord[]> {
+    const cutoff = now.getTime() - days * 24 * 60 * 60 * 1000;
+    const records = await this.load();
+    return records.filter((record) => new Date(record.completedAt).getTime() >= cutoff);
+  }
+
+  getFilePath(): string {
+    return this.filePath;
+  }
+}
@@ -0,0 +1,190 @@
+import * as github from '@actions/github';
+import { getFlag, requireFlag } from './cli';
+import { splitRepository } from './github';
+import { HistoryStore, type PipelineRunRecord, type StepRunRecord } from './history';
+import { StepStatus } from './models';
+
+// Fields of a GitHub Actions workflow run used by the import
+export interface WorkflowRun {
+  id: number;
+  name?: string | null;
+  display_title?: string;
+  head_branch: string | null;
+  status: string | null;
+  conclusion: string | null;
+  run_started_at?: string;
+  created_at: string;
+  updated_at: string;
+  actor?: { login: string } | null;
+  pull_requests?: Array<{ number: number }> | null;
+}
+
+// Fields of a workflow job, imported as one step of the run
+export interface WorkflowJob {
+  name: string;
+  conclusion: string | null;
+  started_at: string;
+  completed_at: string | null;
+}
+
+// Where past runs are read from; the GitHub API in practice, a fake in tests
+export interface WorkflowRunSource {
+  // Completed runs, most recent first
+  listRuns(limit: number): Promise<WorkflowRun[]>;
+  listJobs(runId: number): Promise<WorkflowJob[]>;
+}
+
+// Outcome of an import
+export interface HistoryImportResult {
+  imported: number;
+  // Runs already in the history, or that did not finish with a usable conclusion
+  skipped: number;
+}
+
+// Run conclusions imported as a run outcome; cancelled and skipped runs are left out
+const RUN_CONCLUSIONS: Record<string, PipelineRunRecord['conclusion']> = {
+  success: 'success',
+  failure: 'failed',
+  timed_out: 'failed',
+  startup_failure: 'failed',
+};
+
+// Job conclusions mapped to step statuses
+const JOB_STATUSES: Record<string, StepStatus> = {
+  success: StepStatus.Success,
+  failure: StepStatus.Failed,
+  timed_out: StepStatus.Failed,
+  cancelled: StepStatus.Cancelled,
+  skipped: StepStatus.Skipped,
+  neutral: StepStatus.Warning,
+  action_required: StepStatus.Warning,
+};
+
+/// Reads the completed runs of a repository, or of one of its workflows (file name or ID),
+/// from the GitHub Actions API
+export function createWorkflowRunSource(
+  token: string,
+  repository: string,
+  workflow?: string
+): WorkflowRunSource {
+  const octokit = github.getOctokit(token);
+  const { owner, repo } = splitRepository(repository);
+
+  return {
+    async listRuns(limit: number): Promise<WorkflowRun[]> {
+      const runs: WorkflowRun[] = [];
+      const perPage = Math.min(limit, 100);
+      for (let page = 1; runs.length < limit; page++) {
+        const params = { owner, repo, status: 'completed' as const, per_page: perPage, page };
+        const { data } = workflow
+          ? await octokit.rest.actions.listWorkflowRuns({ ...params, workflow_id: workflow })
+          : await octokit.rest.actions.listWorkflowRunsForRepo(params);
+        runs.push(...data.workflow_runs);
+        if (data.workflow_runs.length < perPage) {
+          break;
+        }
+      }
+      return runs.slice(0, limit);
+    },
+    async listJobs(runId: number): Promise<WorkflowJob[]> {
+      return octokit.paginate(octokit.rest.actions.listJobsForWorkflowRun, {
+        owner,
+        repo,
+        run_id: runId,
+        per_page: 100,
+      });
+    },
+  };
+}
+
+/// Converts a finished workflow run and its jobs into a history record, one step per job.
+/// Returns undefined for runs that were cancelled, skipped or have not finished.
+export function toRunRecord(
+  repository: string,
+  run: WorkflowRun,
+  jobs: WorkflowJob[]
+): PipelineRunRecord | undefined {
+  const conclusion = run.conclusion ? RUN_CONCLUSIONS[run.conclusion] : undefined;
+  if (run.status !== 'completed' || !conclusion) {
+    return undefined;
+  }
+
+  const startedAt = run.run_started_at || run.created_at;
+  const steps: StepRunRecord[] = jobs.map((job) => ({
+    name: job.name,
+    status: (job.conclusion && JOB_STATUSES[job.conclusion]) || StepStatus.Skipped,
+    startedAt: job.started_at,
+    completedAt: job.completed_at || undefined,
+  }));
+
+  return {
+    repository,
+    prNumber: run.pull_requests?.[0]?.number || 0,
+    prTitle: run.display_title || run.name || '',
+    author: run.actor?.login || '',
+    branch: run.head_branch || '',
+    conclusion,
+    startedAt,
+    completedAt: run.updated_at,
+    durationMs: Math.max(0, new Date(run.updated_at).getTime() - new Date(startedAt).getTime()),
+    steps,
+    runId: run.id,
+  };
+}
+
+/// Backfills the history store with up to `limit` past runs, skipping runs imported before
+export async function importWorkflowHistory(
+  source: WorkflowRunSource,
+  history: HistoryStore,
+  repository: string,
+  limit: number
+): Promise<HistoryImportResult> {
+  const known = new Set((await history.load()).map((record) => record.runId));
+  const records: PipelineRunRecord[] = [];
+  let skipped = 0;
+
+  for (const run of await source.listRuns(limit)) {
+    if (known.has(run.id)) {
+      skipped++;
+      continue;
+    }
+    const record = toRunRecord(repository, run, await source.listJobs(run.id));
+    if (record) {
+      records.push(record);
+    } else {
+      skipped++;
+    }
+  }
+
+  await history.merge(records);
+  return { imported: records.length, skipped };
+}
+
+/// Runs `history import`, reading the token and repository from flags or the GITHUB_TOKEN and
+/// GITHUB_REPOSITORY environment variables, and returns the process exit code
+export async function runHistoryCommand(
+  positional: string[],
+  flags: Record<string, string>
+): Promise<number> {
+  const [operation] = positional;
+  if (operation !== 'import') {
+    console.error(
+      'Error: Usage: history import [--history-file FILE] [--github-token TOKEN] ' +
+        '[--repository OWNER/REPO] [--workflow FILE] [--limit N]'
+    );
+    return 1;
+  }
+
+  const token = requireFlag(flags, 'github-token', 'GITHUB_TOKEN');
+  const repository = requireFlag(flags, 'repository', 'GITHUB_REPOSITORY');
+  const history = new HistoryStore(getFlag(flags, 'history-file'));
+  const limit = parseInt(flags.limit || '100', 10) || 100;
+
+  const source = createWorkflowRunSource(token, repository, getFlag(flags, 'workflow'));
+  const result = await importWorkflowHistory(source, history, repository, limit);
+  console.log(
+    `✅ Imported ${result.imported} runs into ${history.getFilePath()} ` +
+      `(${result.skipped} skipped)`
+  );
+  return 0;
+}
@@ -0,0 +1,117 @@
+import { spawn } from 'child_process';
+import type { PipelineEvent, PipelineEventType } from './events';
+
+// Lifecycle points a hook command can be attached to
+export type HookName = 'on_init' | 'on_step' | 'on_complete' | 'on_fail';
+
+// Shell command of each configured hook
+export type HookCommands = Partial<Record<HookName, string>>;
+
+// Runs a shell command with `input` on stdin; rejects when it fails or runs out of time
+export type HookExecutor = (command: string, input: string, timeoutMs: number) => Promise<void>;
+
+// Hook that failed, and why; the other hooks and the tracker carry on
+export interface HookFailure {
+  hook: HookName;
+  error: string;
+}
+
+// A hook still running after this many milliseconds is killed
+export const DEFAULT_HOOK_TIMEOUT_MS = 30000;
+
+// Hook run for each event; on_fail is added by the tracker when a step turns failed
+export const HOOK_BY_EVENT: Record<PipelineEventType, HookName> = {
+  'pipeline.started': 'on_init',
+  'step.updated': 'on_step',
+  'pipeline.completed': 'on_complete',
+};
+
+/// Runs a command through the shell, writing the input to its stdin. Its stdout is passed
+/// through to the action log; stderr is kept for the error of a failed run.
+export function runHookCommand(command: string, input: string, timeoutMs: number): Promise<void> {
+  return new Promise((resolve, reject) => {
+    // In its own process group, so a timeout also kills what the command started
+    const child = spawn(command, {
+      shell: true,
+      detached: true,
+      stdio: ['pipe', 'inherit', 'pipe'],
+    });
+    let stderr = '';
+    let timedOut = false;
+
+    const timer = setTimeout(() => {
+      timedOut = true;
+      try {
+        process.kill(-(child.pid as number), 'SIGKILL');
+      } catch {
+        child.kill('SIGKILL');
+      }
+    }, timeoutMs);
+
+    child.stderr.on('data', (chunk) => {
+      stderr += chunk;
+    });
+    // A command that exits without reading its stdin is not a failure
+    child.stdin.on('error', () => {});
+    child.on('error', (error) => {
+      clearTimeout(timer);
+      reject(error);
+    });
+    child.on('close', (code) => {
+      clearTimeout(timer);
+      if (timedOut) {
+        reject(new Error(`timed out after ${timeoutMs}ms`));
+      } else if (code === 0) {
+        resolve();
+      } else {
+        const detail = stderr.trim() ? `: ${stderr.trim()}` : '';
+        reject(new Error(`exited with code ${code}${detail}`));
+      }
+    });
+
+    child.stdin.end(input);
+  });
+}
+
+/// Runs the configured hook commands with the pipeline event as JSON on stdin, so teams can
+/// plug in their own behavior (status pages, ticket creation) without changing the tracker
+export class HookRunner {
+  private commands: HookCommands;
+  private timeoutMs: number;
+  private execute: HookExecutor;
+
+  constructor(
+    commands: HookCommands,
+    timeoutMs: number = DEFAULT_HOOK_TIMEOUT_MS,
+    execute: HookExecutor = runHookCommand
+  ) {
+    this.commands = commands;
+    this.timeoutMs = timeoutMs;
+    this.execute = execute;
+  }
+
+  /// Whether at least one hook command is configured
+  hasHooks(): boolean {
+    return Object.values(this.commands).some((command) => command && command.trim());
+  }
+
+  /// Runs the given hooks one after another and returns those that failed. A failing hook
+  /// never stops the next one.
+  async run(hooks: HookName[], event: PipelineEvent): Promise<HookFailure[]> {
+    const failures: HookFailure[] = [];
+    const input = JSON.stringify(event);
+    for (const hook of hooks) {
+      const command = this.commands[hook]?.trim();
+      if (!command) {
+        continue;
+      }
+      try {
+        await this.execute(command, input, this.timeoutMs);
+        console.log(`✅ ${hook} hook ran`);
+      } catch (error) {
+        failures.push({ hook, error: error instanceof Error ? error.message : String(error) });
+      }
+    }
+    return failures;
+  }
+}
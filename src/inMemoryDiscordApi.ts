@@ -0,0 +1,227 @@
+import { type ApiTelemetry, buildMessageUrl, type DiscordApiClient } from './discordApi';
+import { TrackerError } from './error';
+import type {
+  DiscordChannel,
+  DiscordGuildMember,
+  DiscordMessage,
+  DiscordMessageData,
+  DiscordReaction,
+  DiscordRole,
+  DiscordUser,
+  FileUpload,
+} from './models';
+import { PERMISSIONS } from './permissions';
+
+// A message held by the fake, with what the tracker did to it
+export interface StoredMessage {
+  id: string;
+  message: DiscordMessage;
+  // Thread or DM the message was posted in; undefined for the channel itself
+  threadId?: string;
+  dmUserId?: string;
+  files: FileUpload[];
+  edits: number;
+  pinned: boolean;
+  crossposted: boolean;
+  reactions: Set<string>;
+  timestamp: string;
+}
+
+/// Discord client keeping every message in memory, for testing tracker logic without the
+/// network. Snowflake-like IDs increase with each message, so `listMessages` pages like Discord.
+export class InMemoryDiscordApi implements DiscordApiClient {
+  readonly messages = new Map<string, StoredMessage>();
+  readonly threads = new Map<string, { parentId: string; name: string }>();
+  private nextId = 1000000000000000000n;
+  private channel: DiscordChannel;
+  private user: DiscordUser = { id: '900000000000000000', username: 'tracker', bot: true };
+
+  constructor(channel: Partial<DiscordChannel> = {}) {
+    this.channel = { id: '100000000000000000', type: 0, ...channel };
+  }
+
+  /// ID of the channel the fake posts to
+  get channelId(): string {
+    return this.channel.id;
+  }
+
+  isWebhookMode(): boolean {
+    return false;
+  }
+
+  getTelemetry(): ApiTelemetry {
+    return {
+      httpCalls: 0,
+      retries: 0,
+      rateLimitHits: 0,
+      rateLimitWaitMs: 0,
+      totalLatencyMs: 0,
+      payloadBytes: 0,
+      latencyHistogram: {},
+      statusCodes: {},
+    };
+  }
+
+  /// Messages posted in the channel itself, oldest first
+  channelMessages(): StoredMessage[] {
+    return [...this.messages.values()].filter((stored) => !stored.threadId && !stored.dmUserId);
+  }
+
+  async sendMessage(message: DiscordMessage, threadId?: string): Promise<string> {
+    return this.store(message, { threadId });
+  }
+
+  async updateMessage(
+    messageId: string,
+    message: DiscordMessage,
+    files: FileUpload[] = []
+  ): Promise<void> {
+    const stored = this.find(messageId);
+    stored.message = message;
+    stored.files = files;
+    stored.edits++;
+  }
+
+  async deleteMessage(messageId: string): Promise<void> {
+    this.find(messageId);
+    this.messages.delete(messageId);
+  }
+
+  async getMessage(messageId: string): Promise<DiscordMessageData> {
+    return this.toMessageData(this.find(messageId));
+  }
+
+  async listMessages(before?: string, limit = 100): Promise<DiscordMessageData[]> {
+    return this.channelMessages()
+      .filter((stored) => before === undefined || BigInt(stored.id) < BigInt(before))
+      .reverse()
+      .slice(0, limit)
+      .map((stored) => this.toMessageData(stored));
+  }
+
+  async getMessageUrl(messageId: string): Promise<string> {
+    return buildMessageUrl(this.channel.guild_id, this.channel.id, messageId);
+  }
+
+  async createThread(messageId: string, name: string): Promise<string> {
+    this.find(messageId);
+    const threadId = this.generateId();
+    this.threads.set(threadId, { parentId: messageId, name });
+    return threadId;
+  }
+
+  async createForumPost(name: string, message: DiscordMessage): Promise<string> {
+    const threadId = this.generateId();
+    this.threads.set(threadId, { parentId: this.channel.id, name });
+    // Like on Discord, the starter message shares the ID of its post
+    return this.store(message, { threadId }, threadId);
+  }
+
+  /// Messages are keyed by ID whatever their channel, so the fake serves its threads itself
+  forChannel(_channelId: string): DiscordApiClient {
+    return this;
+  }
+
+  async sendDirectMessage(userId: string, message: DiscordMessage): Promise<string> {
+    return this.store(message, { dmUserId: userId });
+  }
+
+  async crosspostMessage(messageId: string): Promise<void> {
+    this.find(messageId).crossposted = true;
+  }
+
+  async pinMessage(messageId: string): Promise<void> {
+    this.find(messageId).pinned = true;
+  }
+
+  async unpinMessage(messageId: string): Promise<void> {
+    this.find(messageId).pinned = false;
+  }
+
+  async addReaction(messageId: string, emoji: string): Promise<void> {
+    this.find(messageId).reactions.add(emoji);
+  }
+
+  async removeReaction(messageId: string, emoji: string): Promise<void> {
+    this.find(messageId).reactions.delete(emoji);
+  }
+
+  async getReactions(messageId: string): Promise<DiscordReaction[]> {
+    return this.reactionsOf(this.find(messageId));
+  }
+
+  async getChannel(channelId: string = this.channel.id): Promise<DiscordChannel> {
+    if (channelId !== this.channel.id) {
+      throw TrackerError.messageNotFound(`Unknown channel ${channelId}`);
+    }
+    return this.channel;
+  }
+
+  async getCurrentUser(): Promise<DiscordUser> {
+    return this.user;
+  }
+
+  /// Only the @everyone role, with every permission the tracker needs
+  async getGuildRoles(guildId: string): Promise<DiscordRole[]> {
+    return [{ id: guildId, permissions: String(PERMISSIONS.ADMINISTRATOR) }];
+  }
+
+  async getGuildMember(): Promise<DiscordGuildMember> {
+    return { roles: [] };
+  }
+
+  private store(
+    message: DiscordMessage,
+    target: { threadId?: string; dmUserId?: string },
+    id: string = this.generateId()
+  ): string {
+    if (target.threadId && !this.threads.has(target.threadId)) {
+      throw TrackerError.messageNotFound(`Unknown thread ${target.threadId}`);
+    }
+    this.messages.set(id, {
+      id,
+      message,
+      ...target,
+      files: [],
+      edits: 0,
+      pinned: false,
+      crossposted: false,
+      reactions: new Set(),
+      timestamp: new Date().toISOString(),
+    });
+    return id;
+  }
+
+  private find(messageId: string): StoredMessage {
+    const stored = this.messages.get(messageId);
+    if (!stored) {
+      throw TrackerError.messageNotFound(messageId);
+    }
+    return stored;
+  }
+
+  private toMessageData(stored: StoredMessage): DiscordMessageData {
+    return {
+      id: stored.id,
+      content: stored.message.content,
+      timestamp: stored.timestamp,
+      embeds: stored.message.embeds,
+      reactions: stored.reactions.size > 0 ? this.reactionsOf(stored) : undefined,
+      author: this.user,
+      message_reference: stored.message.message_reference,
+    };
+  }
+
+  private reactionsOf(stored: StoredMessage): DiscordReaction[] {
+    return [...stored.reactions].map((emoji) => ({
+      count: 1,
+      me: true,
+      emoji: { id: null, name: emoji },
+    }));
+  }
+
+  private generateId(): string {
+    this.nextId++;
+    return this.nextId.toString();
+  }
+}
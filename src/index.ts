@@ -1,9 +1,106 @@
 import * as core from '@actions/core';
+import * as fs from 'fs';
+import { sendTrackerAlert } from './alerting';
+import { readLogAttachment } from './attachments';
+import { formatLatencyHistogram, isWebhookUrl } from './discordApi';
 import { PipelineTracker } from './pipelineTracker';
 import { TrackerError } from './error';
-import { FileStorage } from './storage';
+import {
+  buildPrCommentBody,
+  createCodeownersResolver,
+  derivePipelineLinks,
+  fetchRunTiming,
+  publishDeliveryStatus,
+  resolveCancellationEvent,
+  resolveEventCommits,
+  resolveHeadCommitMessage,
+  resolveHeadSha,
+  upsertPrComment,
+} from './github';
+import { BoardStore } from './board';
+import { seededClock, systemClock } from './clock';
+import { EventPublisher } from './events';
+import { FeedStore } from './feeds';
+import { HistoryStore } from './history';
+import { HookRunner } from './hooks';
+import { buildStatusLine } from './messageBuilder';
+import type { StepDetails } from './models';
+import { MuteStore } from './mute';
+import { PermissionCache } from './permissionCache';
+import {
+  buildBenchmarkTable,
+  compareBenchmarks,
+  findBaseline,
+  parseBenchmarkResults,
+} from './reports/bench';
+import { createStorage } from './storage';
+import {
+  parseChangelog,
+  parseChannelIdList,
+  parseColorScheme,
+  parseDiscordIdList,
+  parseKeyValueList,
+  parseOwnerMap,
+  parseStepBudgets,
+  parseStatusEmoji,
+  parseStepListHide,
+  splitAdditionalInfo,
+  validateBenchThreshold,
+  validateBotTokenFormat,
+  validateCostPerMinute,
+  validateDedupeSearchLimit,
+  validateEmbedLayout,
+  validateExitCode,
+  validateHookTimeout,
+  validateLinkUrl,
+  validateLocale,
+  validateMuteMinutes,
+  validatePercentDecimals,
+  validatePreflightTtl,
+  validateProgressDisplay,
+  validateRateLimitMaxWait,
+  validateRenderSeed,
+  validateRolloutPercent,
+  validateRetryAttempts,
+  validateSloTarget,
+  validateStatusPublishMode,
+  validateStepListStyle,
+  validateStorageBackend,
+  validateTheme,
+} from './validation';
+
+/// Exposes the Discord API traffic counters as outputs and debug logs
+function reportTelemetry(tracker: PipelineTracker): void {
+  const telemetry = tracker.getTelemetry();
+  core.debug(`Discord API telemetry: ${JSON.stringify(telemetry)}`);
+  core.setOutput('api_calls', String(telemetry.httpCalls));
+  core.setOutput('api_retries', String(telemetry.retries));
+  core.setOutput('api_rate_limit_hits', String(telemetry.rateLimitHits));
+  core.setOutput('api_rate_limit_wait_ms', String(telemetry.rateLimitWaitMs));
+  core.setOutput('api_latency_ms', String(telemetry.totalLatencyMs));
+  core.setOutput('api_payload_bytes', String(telemetry.payloadBytes));
+  core.setOutput('api_latency_histogram', JSON.stringify(telemetry.latencyHistogram));
+  if (telemetry.httpCalls > 0) {
+    const histogram = formatLatencyHistogram(telemetry.latencyHistogram);
+    core.debug(`Discord API latency histogram:\n${histogram}`);
+  }
+}
+
+/// Exposes the non-fatal warnings of the run as a JSON output and workflow warnings
+function reportWarnings(tracker: PipelineTracker): void {
+  const warnings = tracker.getWarnings();
+  for (const warning of warnings) {
+    core.warning(warning.message);
+  }
+  core.setOutput('warnings', JSON.stringify(warnings));
+}
 
 async function run(): Promise<void> {
+  const alertWebhookUrl = core.getInput('alert_webhook_url') || undefined;
+  // Also used for the failure alert, so it is read before any input is validated
+  const proxyUrl = core.getInput('proxy_url') || undefined;
+  let telemetrySource: PipelineTracker | undefined;
+
   try {
     // Get inputs
     const action = core.getInput('action', { required: true });
@@ -15,16 +112,198 @@ async function run(): Promise<void> {
     const stepNumber = core.getInput('step_number');
     const totalSteps = core.getInput('total_steps');
     const stepName = core.getInput('step_name');
+    const phase = core.getInput('phase') || undefined;
+    const stepOwner = core.getInput('step_owner') || undefined;
     const status = core.getInput('status');
     const additionalInfo = core.getInput('additional_info');
     const errorMessage = core.getInput('error_message');
     const botToken = core.getInput('discord_bot_token', { required: true });
-    const channelId = core.getInput('discord_channel_id', { required: true });
+    // Escape hatch for token formats the check does not know about yet
+    if (core.getInput('skip_token_validation') !== 'true' && !isWebhookUrl(botToken)) {
+      validateBotTokenFormat(botToken);
+    }
+    // Not needed when the bot token input holds a channel webhook URL. Channels after the
+    // first one receive a mirrored copy of the pipeline message.
+    const channels = parseChannelIdList(core.getInput('discord_channel_id'));
+    const channelId = channels.primary;
+    const progressDisplay = core.getInput('progress_display');
+    const percentDecimals = core.getInput('percent_decimals');
+    const costPerMinute = core.getInput('runner_cost_per_minute');
+    const historyFile = core.getInput('history_file');
+    const digestDays = core.getInput('digest_days');
+    const tags = core.getInput('tags');
+    const digestFilter = core.getInput('digest_filter');
+    const messageId = core.getInput('message_id');
+    const remindAfterMinutes = core.getInput('remind_after_minutes');
+    const escalationTarget = core.getInput('escalation_target');
+    const note = core.getInput('note');
+    const githubToken = core.getInput('github_token');
+    const publishStatus = validateStatusPublishMode(core.getInput('publish_status'));
+    const boardFile = core.getInput('board_file');
+    const feedFile = core.getInput('feed_file');
+    const logFile = core.getInput('log_file');
+    const benchFile = core.getInput('bench_file');
+    const benchThreshold = validateBenchThreshold(core.getInput('bench_threshold_percent'));
+    const benchWarnOnRegression = core.getInput('bench_warn_on_regression') === 'true';
+    // Explicit commits win over the ones pushed in the triggering event
+    const changelog = parseChangelog(core.getInput('changelog'));
+    // Event export stays off unless explicitly enabled, even when a URL is configured
+    const eventExport = core.getInput('event_export') === 'true';
+    const preflight = core.getInput('preflight') === 'true';
+    // Kill-switch shared by every invocation that sees the same mute file
+    const muteStore = new MuteStore(core.getInput('mute_file') || undefined);
+    const mute = ['mute', 'unmute'].includes(action) ? undefined : await muteStore.active();
+    const hooks = new HookRunner(
+      {
+        on_init: core.getInput('on_init'),
+        on_step: core.getInput('on_step'),
+        on_complete: core.getInput('on_complete'),
+        on_fail: core.getInput('on_fail'),
+      },
+      validateHookTimeout(core.getInput('hook_timeout'))
+    );
+
+    // Initialize tracker with the configured storage backend
+    const storageBackend = validateStorageBackend(core.getInput('storage'));
+    // With `from_event`, `cancel` follows the run named by a workflow_run/check_suite
+    // cancellation, whose state is attached to that run's commit
+    const fromEvent = action === 'cancel' && core.getInput('from_event') === 'true';
+    const cancellation = fromEvent ? resolveCancellationEvent() : undefined;
+    const storage = createStorage(storageBackend, cancellation?.headSha);
+    const history = historyFile ? new HistoryStore(historyFile) : undefined;
+    const sloTarget = validateSloTarget(core.getInput('slo_target'));
+    const sloWindowDays = parseInt(core.getInput('slo_window_days'), 10) || 7;
+    const renderSeed = validateRenderSeed(core.getInput('render_seed'));
+    const dedupeSearchLimit = validateDedupeSearchLimit(core.getInput('dedupe_search_limit'));
+    const derivedLinks = derivePipelineLinks();
+    const tracker = new PipelineTracker(botToken, channelId, storage, {
+      render: {
+        progressDisplay: validateProgressDisplay(progressDisplay),
+        percentDecimals: validatePercentDecimals(percentDecimals),
+        costPerMinute: validateCostPerMinute(costPerMinute),
+        stepListStyle: validateStepListStyle(core.getInput('step_list_style')),
+        embedLayout: validateEmbedLayout(core.getInput('embed_layout')),
+        contentFallback: core.getInput('content_fallback') === 'true',
+        locale: validateLocale(core.getInput('locale')),
+        colors: parseColorScheme(core.getInput('color_scheme')),
+        theme: validateTheme(core.getInput('theme')),
+        // The env variable lets a whole workflow share the overrides
+        statusEmoji: parseStatusEmoji(
+          core.getInput('status_emoji') || process.env.DISCORD_TRACKER_STATUS_EMOJI || ''
+        ),
+        clock: renderSeed === undefined ? systemClock : seededClock(renderSeed),
+        ...parseStepListHide(core.getInput('step_list_hide')),
+      },
+      alertWebhookUrl,
+      history,
+      board: boardFile ? new BoardStore(boardFile) : undefined,
+      feed: feedFile
+        ? new FeedStore(feedFile, {
+            title: core.getInput('feed_title') || undefined,
+            link: validateLinkUrl(core.getInput('feed_link'), 'feed_link'),
+          })
+        : undefined,
+      events: eventExport
+        ? new EventPublisher(
+            core.getInput('event_export_url', { required: true }),
+            core.getInput('event_export_subject') || 'discord-tracker.pipeline'
+          )
+        : undefined,
+      hooks: hooks.hasHooks() ? hooks : undefined,
+      threads: core.getInput('create_thread') === 'true',
+      reactions: core.getInput('status_reactions') === 'true',
+      mirrorChannelIds: channels.mirrors,
+      feedbackPoll: core.getInput('feedback_poll') === 'true',
+      pin: core.getInput('pin_message') === 'true',
+      crosspostBranches: core
+        .getInput('crosspost_branches')
+        .split(',')
+        .map((branch) => branch.trim())
+        .filter((branch) => branch.length > 0),
+      slo:
+        sloTarget !== undefined
+          ? { targetPercent: sloTarget, windowDays: sloWindowDays }
+          : undefined,
+      eta: core.getInput('show_eta') === 'true',
+      stepGroups: core.getInput('step_groups') === 'true',
+      runId: process.env.GITHUB_RUN_ID,
+      githubServerUrl: process.env.GITHUB_SERVER_URL,
+      dedupeSearchLimit,
+      runTiming:
+        core.getInput('show_run_timing') === 'true' && githubToken && process.env.GITHUB_RUN_ID
+          ? () =>
+              fetchRunTiming(
+                githubToken,
+                repository || (process.env.GITHUB_REPOSITORY as string),
+                Number(process.env.GITHUB_RUN_ID)
+              )
+          : undefined,
+      failureMentions: {
+        users: parseDiscordIdList(core.getInput('mention_users'), 'mention_users'),
+        roles: parseDiscordIdList(core.getInput('mention_roles'), 'mention_roles'),
+      },
+      ownerMap: parseOwnerMap(core.getInput('owner_map')),
+      stepBudgets: parseStepBudgets(core.getInput('step_budgets')),
+      changelog:
+        changelog.length > 0
+          ? changelog
+          : core.getInput('changelog_from_event') === 'true'
+            ? resolveEventCommits()
+            : undefined,
+      // CODEOWNERS is read at the commit being built, only when a step reports failing paths
+      codeowners:
+        githubToken && (repository || process.env.GITHUB_REPOSITORY)
+          ? createCodeownersResolver(
+              githubToken,
+              repository || (process.env.GITHUB_REPOSITORY as string),
+              resolveHeadSha()
+            )
+          : undefined,
+      dmAuthor: core.getInput('dm_author_on_failure') === 'true',
+      links: {
+        runUrl: validateLinkUrl(core.getInput('run_url'), 'run_url') || derivedLinks.runUrl,
+        prUrl: validateLinkUrl(core.getInput('pr_url'), 'pr_url') || derivedLinks.prUrl,
+      },
+      decorations: {
+        url: validateLinkUrl(core.getInput('embed_url'), 'embed_url'),
+        author: {
+          name: core.getInput('embed_author_name').trim(),
+          url: validateLinkUrl(core.getInput('embed_author_url'), 'embed_author_url'),
+          icon_url: validateLinkUrl(
+            core.getInput('embed_author_icon_url'),
+            'embed_author_icon_url'
+          ),
+        },
+        thumbnailUrl: validateLinkUrl(core.getInput('embed_thumbnail_url'), 'embed_thumbnail_url'),
+        imageUrl: validateLinkUrl(core.getInput('embed_image_url'), 'embed_image_url'),
+      },
+      authorAvatar: core.getInput('show_author_avatar') === 'true',
+      authorAvatarUrl: validateLinkUrl(core.getInput('author_avatar_url'), 'author_avatar_url'),
+      retry: {
+        maxRetries: validateRetryAttempts(core.getInput('retry_attempts')),
+        maxRateLimitWait: validateRateLimitMaxWait(core.getInput('rate_limit_max_wait_seconds')),
+      },
+      proxyUrl: validateLinkUrl(proxyUrl || '', 'proxy_url'),
+      permissionCache: preflight
+        ? new PermissionCache(
+            undefined,
+            validatePreflightTtl(core.getInput('preflight_cache_ttl_seconds'))
+          )
+        : undefined,
+      muted: mute !== undefined,
+    });
+    core.info(`Pipeline tracker initialized with ${storageBackend} storage`);
+    if (mute) {
+      const reason = mute.reason ? `: ${mute.reason}` : '';
+      core.info(`Notifications muted by ${mute.by} since ${mute.mutedAt}${reason}`);
+    }
+    core.setOutput('muted', String(mute !== undefined));
+    telemetrySource = tracker;
 
-    // Initialize tracker with file storage
-    const storage = new FileStorage();
-    const tracker = new PipelineTracker(botToken, channelId, storage);
-    core.info('Pipeline tracker initialized with file storage');
+    // `verify` always checks the channel itself, bypassing the preflight cache
+    if (preflight && action !== 'verify') {
+      await tracker.preflight();
+    }
 
     let result: void;
 
@@ -34,7 +313,14 @@ async function run(): Promise<void> {
           throw new Error('Missing required parameters for init action');
         }
         core.info(`Initializing pipeline tracker for PR #${prNumber}`);
-        result = await tracker.initPipeline(prNumber, prTitle, author, repository, branch);
+        result = await tracker.initPipeline(prNumber, prTitle, author, repository, branch, {
+          tags: parseKeyValueList(tags, 'tags'),
+          metadata: parseKeyValueList(core.getInput('metadata'), 'metadata'),
+          correlationId: core.getInput('correlation_id') || undefined,
+          // Push events carry the head commit message; other events need `commit_message`
+          commitSha: core.getInput('commit_sha') || resolveHeadSha() || undefined,
+          commitMessage: core.getInput('commit_message') || resolveHeadCommitMessage(),
+        });
         break;
 
       case 'step': {
@@ -45,14 +331,25 @@ async function run(): Promise<void> {
         const total = parseInt(totalSteps, 10) || 1;
 
         let additionalInfoPairs: Array<[string, string]> = [];
+        const details: StepDetails = {
+          tables: [],
+          phase,
+          owner: stepOwner,
+          rolloutPercent: validateRolloutPercent(core.getInput('rollout_percent')),
+          logsUrl: validateLinkUrl(core.getInput('logs_url'), 'logs_url'),
+          failingPaths: core
+            .getInput('failing_paths')
+            .split(/[,\n]/)
+            .map((failingPath) => failingPath.trim())
+            .filter((failingPath) => failingPath.length > 0),
+        };
         if (additionalInfo) {
           try {
             const parsedInfo = JSON.parse(additionalInfo);
             if (typeof parsedInfo === 'object' && parsedInfo !== null) {
-              additionalInfoPairs = Object.entries(parsedInfo).map(([key, value]) => [
-                key,
-                String(value),
-              ]);
+              const split = splitAdditionalInfo(parsedInfo);
+              additionalInfoPairs = split.pairs;
+              details.tables = split.tables;
             }
           } catch (e) {
             core.warning(
@@ -61,8 +358,34 @@ async function run(): Promise<void> {
           }
         }
 
+        let stepStatus = status;
+        if (benchFile) {
+          const benchmarks = parseBenchmarkResults(fs.readFileSync(benchFile, 'utf-8'));
+          const baseline = history ? findBaseline(await history.load(), stepName) : [];
+          const comparisons = compareBenchmarks(benchmarks, baseline, benchThreshold);
+          details.tables?.push(buildBenchmarkTable(comparisons));
+          details.benchmarks = benchmarks;
+
+          const regressions = comparisons.filter((comparison) => comparison.regression);
+          if (regressions.length > 0) {
+            core.warning(
+              `${regressions.length} benchmark(s) regressed by more than ${benchThreshold}%`
+            );
+            if (benchWarnOnRegression && status === 'success') {
+              stepStatus = 'warning';
+            }
+          }
+        }
+
         core.info(`Updating step ${stepNum}: ${stepName}`);
-        result = await tracker.updateStep(stepNum, total, stepName, status, additionalInfoPairs);
+        result = await tracker.updateStep(
+          stepNum,
+          total,
+          stepName,
+          stepStatus,
+          additionalInfoPairs,
+          details
+        );
         break;
       }
 
@@ -76,21 +399,205 @@ async function run(): Promise<void> {
           throw new Error('Missing required parameters for fail action');
         }
         core.error(`Pipeline failed at step: ${stepName}`);
-        result = await tracker.updateStep(1, 1, stepName, 'failed', [['error', errorMessage]]);
+        result = await tracker.updateStep(1, 1, stepName, 'failed', [], {
+          phase,
+          owner: stepOwner,
+          logsUrl: validateLinkUrl(core.getInput('logs_url'), 'logs_url'),
+          failure: {
+            error: errorMessage,
+            command: core.getInput('failed_command') || undefined,
+            exitCode: validateExitCode(core.getInput('exit_code')),
+          },
+          files: logFile ? [await readLogAttachment(logFile)] : undefined,
+        });
+        break;
+
+      case 'digest': {
+        const days = parseInt(digestDays, 10) || 7;
+        core.info(`Posting pipeline digest for the last ${days} days`);
+        result = await tracker.postDigest(days, parseKeyValueList(digestFilter, 'digest_filter'));
+        break;
+      }
+
+      case 'board': {
+        core.info('Updating the pipeline board');
+        const boardMessageId = await tracker.flushBoard(messageId || undefined);
+        core.setOutput('message_id', boardMessageId);
+        break;
+      }
+
+      case 'annotate': {
+        if (!stepNumber || !note) {
+          throw new Error('Missing required parameters for annotate action');
+        }
+        const stepNum = parseInt(stepNumber, 10) || 1;
+        core.info(`Annotating step ${stepNum}`);
+        result = await tracker.annotateStep(stepNum, note);
+        break;
+      }
+
+      case 'cleanup': {
+        const maxAgeDays = parseInt(core.getInput('cleanup_max_age_days'), 10) || 30;
+        core.info(`Deleting pipeline messages older than ${maxAgeDays} days`);
+        const deleted = await tracker.cleanupMessages(maxAgeDays);
+        core.setOutput('deleted_messages', String(deleted));
+        break;
+      }
+
+      case 'collect-feedback': {
+        if (!messageId) {
+          throw TrackerError.missingRequiredInput('message_id');
+        }
+        core.info(`Collecting feedback reactions of message ${messageId}`);
+        const feedback = await tracker.collectFeedback(messageId);
+        core.setOutput('feedback_up', String(feedback.up));
+        core.setOutput('feedback_down', String(feedback.down));
+        break;
+      }
+
+      case 'rollback': {
+        const correlationId = core.getInput('correlation_id');
+        if (!correlationId) {
+          throw TrackerError.missingRequiredInput('correlation_id');
+        }
+        const actor = core.getInput('rollback_actor') || process.env.GITHUB_ACTOR || 'unknown';
+        core.info(`Rolling back deploy ${correlationId}`);
+        const rollback = await tracker.rollbackDeploy(
+          correlationId,
+          actor,
+          core.getInput('rollback_reason') || undefined
+        );
+        core.setOutput('message_id', rollback.messageId);
+        break;
+      }
+
+      case 'cancel': {
+        if (fromEvent && !cancellation) {
+          core.info('Triggering event is not a cancelled workflow run - nothing to cancel');
+          core.setOutput('cancelled', 'false');
+          break;
+        }
+        core.info('Cancelling pipeline');
+        const cancelled = await tracker.cancelPipeline(cancellation?.repository);
+        core.setOutput('cancelled', String(cancelled));
+        break;
+      }
+
+      case 'verify': {
+        const verification = await tracker.verifyChannel();
+        const name = verification.name ? ` #${verification.name}` : '';
+        core.info(`Channel${name} (${verification.channelType}) is ready for pipeline messages`);
+        break;
+      }
+
+      case 'remind': {
+        const windowMinutes = parseInt(remindAfterMinutes, 10) || 60;
+        core.info(`Checking for failures unacknowledged for ${windowMinutes} minutes`);
+        const reminded = await tracker.remindUnacknowledged(
+          messageId || undefined,
+          windowMinutes,
+          escalationTarget
+        );
+        core.setOutput('reminded', String(reminded));
+        break;
+      }
+
+      case 'mute': {
+        const muteMinutes = validateMuteMinutes(core.getInput('mute_minutes'));
+        const now = new Date();
+        await muteStore.mute({
+          mutedAt: now.toISOString(),
+          by: process.env.GITHUB_ACTOR || 'unknown',
+          reason: core.getInput('mute_reason') || undefined,
+          until:
+            muteMinutes !== undefined
+              ? new Date(now.getTime() + muteMinutes * 60000).toISOString()
+              : undefined,
+        });
+        const duration = muteMinutes !== undefined ? ` for ${muteMinutes} minutes` : '';
+        core.info(`🔇 Notifications muted${duration}`);
+        core.setOutput('muted', 'true');
+        break;
+      }
+
+      case 'unmute':
+        await muteStore.unmute();
+        core.info('🔊 Notifications unmuted');
+        core.setOutput('muted', 'false');
         break;
 
       default:
         throw new Error(`Invalid action: ${action}`);
     }
 
+    if (['init', 'step', 'complete', 'fail', 'annotate', 'cancel'].includes(action)) {
+      const messageUrl = await tracker.getMessageUrl();
+      if (messageUrl) {
+        core.setOutput('message_id', tracker.getMessageId() || '');
+        core.setOutput('message_url', messageUrl);
+        if (action === 'init') {
+          await core.summary
+            .addHeading('Discord Pipeline Tracker', 3)
+            .addLink('View pipeline message in Discord', messageUrl)
+            .write();
+        }
+
+        const prInfo = tracker.getPrInfo();
+        if (githubToken && prInfo && ['init', 'complete', 'fail', 'cancel'].includes(action)) {
+          const finished = action === 'complete' || action === 'cancel';
+          const statusLine = buildStatusLine(tracker.getSteps(), finished);
+          try {
+            await upsertPrComment(
+              githubToken,
+              prInfo.repository,
+              parseInt(prInfo.number, 10),
+              buildPrCommentBody(messageUrl, statusLine)
+            );
+            core.info('PR comment with Discord message link updated');
+          } catch (e) {
+            core.warning(
+              `Failed to update PR comment: ${e instanceof Error ? e.message : String(e)}`
+            );
+          }
+        }
+      }
+    }
+
+    const targetRepository =
+      tracker.getPrInfo()?.repository || repository || process.env.GITHUB_REPOSITORY;
+    if (publishStatus !== 'none' && githubToken && targetRepository) {
+      const delivery = tracker.getDeliveryStatus();
+      try {
+        await publishDeliveryStatus(
+          githubToken,
+          targetRepository,
+          cancellation?.headSha || resolveHeadSha(),
+          publishStatus,
+          delivery.delivered,
+          delivery.error
+        );
+        core.info(`Notification delivery published as a commit ${publishStatus}`);
+      } catch (e) {
+        core.warning(
+          `Failed to publish delivery status: ${e instanceof Error ? e.message : String(e)}`
+        );
+      }
+    }
+
     core.info('Action completed successfully');
     core.setOutput('success', 'true');
   } catch (error) {
     const errorMessage = error instanceof Error ? error.message : 'Unknown error occurred';
     core.error(`Action failed: ${errorMessage}`);
+    await sendTrackerAlert(alertWebhookUrl, 'action', error, proxyUrl);
     core.setOutput('error', errorMessage);
     core.setOutput('success', 'false');
     core.setFailed(errorMessage);
+  } finally {
+    if (telemetrySource) {
+      reportTelemetry(telemetrySource);
+      reportWarnings(telemetrySource);
+    }
   }
 }
 
@@ -0,0 +1,57 @@
+// Locale-aware number formatting shared by the message builders. An empty locale keeps the
+// locale-independent output (`12.5`, `3m 5s`), which is the default.
+
+// Keeps a number and its unit on the same line without the full width of a space
+const NARROW_NO_BREAK_SPACE = '\u202f';
+
+/// Formats a number with exactly `decimals` decimal places, e.g. `1,5` for `de-DE`
+export function formatDecimal(value: number, decimals: number, locale = ''): string {
+  if (!locale) {
+    return value.toFixed(decimals);
+  }
+  return new Intl.NumberFormat(locale, {
+    minimumFractionDigits: decimals,
+    maximumFractionDigits: decimals,
+  }).format(value);
+}
+
+/// Formats a percentage given out of 100, e.g. `50.0%` or `50,0 %` for `fr-FR`
+export function formatPercent(value: number, decimals: number, locale = ''): string {
+  if (!locale) {
+    return `${value.toFixed(decimals)}%`;
+  }
+  return new Intl.NumberFormat(locale, {
+    style: 'percent',
+    minimumFractionDigits: decimals,
+    maximumFractionDigits: decimals,
+  }).format(value / 100);
+}
+
+/// Formats whole minutes and seconds with the locale's narrow unit names, e.g. `3m 5s` for
+/// `en-US` or `3min 5s` for `fr-FR`
+export function formatMinutesSeconds(minutes: number, seconds: number, locale = ''): string {
+  if (!locale) {
+    return `${minutes}m ${seconds}s`;
+  }
+  const unit = (name: 'minute' | 'second', value: number) =>
+    new Intl.NumberFormat(locale, { style: 'unit', unit: name, unitDisplay: 'narrow' }).format(
+      value
+    );
+  return `${unit('minute', minutes)} ${unit('second', seconds)}`;
+}
+
+/// Joins a formatted number and a unit symbol, separated by a narrow no-break space when
+/// formatting for a locale so the pair never wraps
+export function withUnit(formatted: string, unit: string, locale = ''): string {
+  return `${formatted}${locale ? NARROW_NO_BREAK_SPACE : ' '}${unit}`;
+}
+
+/// Checks whether the runtime can format numbers for a BCP 47 locale tag
+export function isSupportedLocale(locale: string): boolean {
+  try {
+    return Intl.NumberFormat.supportedLocalesOf([locale]).length > 0;
+  } catch {
+    // Malformed tags throw a RangeError
+    return false;
+  }
+}
@@ -1,13 +1,129 @@
-import { PipelineTracker } from './pipelineTracker';
+import { InMemoryStorage, PipelineTracker } from './pipelineTracker';
 import { TrackerError } from './error';
 import { FileStorage } from './storage';
+import { formatOutput, writeOutputs } from './outputs';
+import { createTrackerFromFlags, getFlag, parseArgs, requireBotToken, requireFlag } from './cli';
+import { runWrappedStep } from './wrap';
+import { watchPipeline } from './watchdog';
+import { runStateCommand } from './stateCommand';
+import { runSchemaCommand } from './schema';
+import { runDemo } from './demo';
+import { runHistoryCommand } from './historyImport';
+import { runThemeCommand } from './themeDiff';
+import { annotateStepFailure } from './workflowCommands';
+import { seededClock, seededRandom, systemClock } from './clock';
+import { validateRenderSeed } from './validation';
+import { InMemoryDiscordApi } from './inMemoryDiscordApi';
 import * as fs from 'fs';
 import * as path from 'path';
 
 // Placeholder for PipelineTracker and its methods. These will need to be implemented in TypeScript.
 // For now, we'll assume they exist and have similar signatures.
 
+// Subcommands handled before the positional action arguments
+const SUBCOMMANDS = ['wrap', 'watchdog', 'state', 'schema', 'demo', 'history', 'theme'];
+
+/// Runs a subcommand and returns the process exit code
+async function runSubcommand(name: string, args: string[]): Promise<number> {
+  const { flags, positional, command } = parseArgs(args);
+
+  switch (name) {
+    case 'wrap': {
+      if (command.length === 0) {
+        console.error(
+          'Error: Missing command. ' +
+            'Usage: wrap --step-number N --total-steps N --step-name NAME [--phase NAME] ' +
+            '-- <command>'
+        );
+        return 1;
+      }
+      const tracker = createTrackerFromFlags(flags);
+      const stepName = requireFlag(flags, 'step-name');
+      const exitCode = await runWrappedStep(
+        tracker,
+        {
+          number: parseInt(requireFlag(flags, 'step-number'), 10) || 1,
+          total: parseInt(requireFlag(flags, 'total-steps'), 10) || 1,
+          name: stepName,
+          phase: getFlag(flags, 'phase'),
+        },
+        command,
+        { sampleResources: flags['resource-stats'] === 'true' }
+      );
+      if (exitCode !== 0) {
+        annotateStepFailure(stepName, `exit code ${exitCode}`);
+      }
+      return exitCode;
+    }
+    case 'watchdog': {
+      const tracker = createTrackerFromFlags(flags);
+      const outcome = await watchPipeline(tracker, new FileStorage(), {
+        maxSilenceMinutes: parseInt(requireFlag(flags, 'max-silence-minutes'), 10) || 30,
+        pollIntervalSeconds: parseInt(flags['poll-interval-seconds'] || '60', 10) || 60,
+        escalationTarget: flags['escalation-target'] || '',
+      });
+      // A stalled pipeline is reported as a failure so the watchdog job fails too
+      return outcome === 'stalled' ? 1 : 0;
+    }
+    case 'state':
+      return runStateCommand(new FileStorage(), positional, flags);
+    case 'schema':
+      return runSchemaCommand(positional);
+    case 'demo': {
+      // A seed makes the run reproducible: same steps, same outcomes, same timestamps
+      const seed = validateRenderSeed(flags.seed || '');
+      const demoOptions = {
+        stepDelayMs: parseInt(flags['step-delay-ms'] || '3000', 10) || 0,
+        failureRate: parseFloat(flags['failure-rate'] || '0.15') || 0,
+        random: seed === undefined ? undefined : seededRandom(seed),
+      };
+      const render = { clock: seed === undefined ? systemClock : seededClock(seed) };
+      // Demo state is kept in memory so a real pipeline's state file is never overwritten
+      if (flags['dry-run'] === 'true') {
+        const api = new InMemoryDiscordApi();
+        const storage = new InMemoryStorage();
+        const tracker = new PipelineTracker('dry-run', api.channelId, storage, { api, render });
+        await runDemo(tracker, {
+          ...demoOptions,
+          onUpdate: () => {
+            const [stored] = api.channelMessages();
+            if (stored) {
+              console.log(JSON.stringify(stored.message, null, 2));
+            }
+          },
+        });
+        return 0;
+      }
+      const tracker = new PipelineTracker(
+        requireBotToken(flags),
+        requireFlag(flags, 'channel-id', 'DISCORD_CHANNEL_ID'),
+        new InMemoryStorage(),
+        { render }
+      );
+      await runDemo(tracker, demoOptions);
+      return 0;
+    }
+    case 'history':
+      return runHistoryCommand(positional, flags);
+    case 'theme':
+      return runThemeCommand(positional, flags);
+    default:
+      console.error(`Error: Unknown subcommand: ${name}`);
+      return 1;
+  }
+}
+
 async function main() {
+  const cliArgs = process.argv.slice(2);
+  if (SUBCOMMANDS.includes(cliArgs[0])) {
+    try {
+      process.exit(await runSubcommand(cliArgs[0], cliArgs.slice(1)));
+    } catch (e: any) {
+      console.error(`Error: ${e.message}`);
+      process.exit(1);
+    }
+  }
+
   console.info('Starting Discord Tracker GitHub Action');
 
   // Get GitHub output path
@@ -19,7 +135,10 @@ async function main() {
     // For now, we'll simulate writing to a file if possible, or just exit.
     try {
       // Use path.join to ensure correct path separators
-      fs.writeFileSync(path.join(process.cwd(), 'error.log'), `error=${errorMsg}\nsuccess=false`);
+      fs.writeFileSync(
+        path.join(process.cwd(), 'error.log'),
+        formatOutput('error', errorMsg) + formatOutput('success', 'false')
+      );
     } catch (e) {
       console.error('Could not write to error.log:', e);
     }
@@ -33,7 +152,7 @@ async function main() {
   if (args.length < 14) {
     const errorMsg = 'Insufficient arguments provided';
     console.error(`Error: ${errorMsg}`);
-    fs.writeFileSync(githubOutputPath, `error=${errorMsg}\nsuccess=false`);
+    writeOutputs(githubOutputPath, { error: errorMsg, success: 'false' });
     process.exit(1);
   }
 
@@ -63,7 +182,7 @@ async function main() {
   } catch (e: any) {
     const errorMsg = `Failed to create pipeline tracker: ${e.message}`;
     console.error(`Error: ${errorMsg}`);
-    fs.writeFileSync(githubOutputPath, `error=${errorMsg}\nsuccess=false`);
+    writeOutputs(githubOutputPath, { error: errorMsg, success: 'false' });
     process.exit(1);
   }
 
@@ -75,7 +194,7 @@ async function main() {
         if (!prNumber || !prTitle || !author || !repository || !branch) {
           const errorMsg = 'Missing required parameters for init action';
           console.error(`Error: ${errorMsg}`);
-          fs.writeFileSync(githubOutputPath, `error=${errorMsg}\nsuccess=false`);
+          writeOutputs(githubOutputPath, { error: errorMsg, success: 'false' });
           process.exit(1);
         }
         console.info(`Initializing pipeline tracker for PR #${prNumber}`);
@@ -86,7 +205,7 @@ async function main() {
           if (!stepNumber || !totalSteps || !stepName || !status) {
             const errorMsg = 'Missing required parameters for step action';
             console.error(`Error: ${errorMsg}`);
-            fs.writeFileSync(githubOutputPath, `error=${errorMsg}\nsuccess=false`);
+            writeOutputs(githubOutputPath, { error: errorMsg, success: 'false' });
             process.exit(1);
           }
           const stepNum = parseInt(stepNumber, 10) || 1;
@@ -110,6 +229,10 @@ async function main() {
 
           console.info(`Updating step ${stepNum}: ${stepName}`);
           result = await tracker.updateStep(stepNum, total, stepName, status, additionalInfoPairs);
+          if (status === 'failed') {
+            const error = additionalInfoPairs.find(([key]) => key === 'error')?.[1];
+            annotateStepFailure(stepName, error);
+          }
         }
         break;
       case 'complete':
@@ -120,28 +243,31 @@ async function main() {
         if (!stepName || !errorMessage) {
           const errorMsg = 'Missing required parameters for fail action';
           console.error(`Error: ${errorMsg}`);
-          fs.writeFileSync(githubOutputPath, `error=${errorMsg}\nsuccess=false`);
+          writeOutputs(githubOutputPath, { error: errorMsg, success: 'false' });
           process.exit(1);
         }
         console.error(`Pipeline failed at step: ${stepName}`);
         // Assuming updateStep can handle a "failed" status and an error message
-        result = await tracker.updateStep(1, 1, stepName, 'failed', [['error', errorMessage]]);
+        result = await tracker.updateStep(1, 1, stepName, 'failed', [], {
+          failure: { error: errorMessage },
+        });
+        annotateStepFailure(stepName, errorMessage);
         break;
       default: {
         const errorMsg = `Invalid action: ${action}`;
         console.error(`Error: ${errorMsg}`);
-        fs.writeFileSync(githubOutputPath, `error=${errorMsg}\nsuccess=false`);
+        writeOutputs(githubOutputPath, { error: errorMsg, success: 'false' });
         process.exit(1);
       }
     }
 
     console.info('Action completed successfully');
-    fs.writeFileSync(githubOutputPath, 'success=true');
+    writeOutputs(githubOutputPath, { success: 'true' });
     process.exit(0); // Exit successfully
   } catch (e: any) {
     const errorMsg = `Action failed: ${e.message}`;
     console.error(`Error: ${errorMsg}`);
-    fs.writeFileSync(githubOutputPath, `error=${errorMsg}\nsuccess=false`);
+    writeOutputs(githubOutputPath, { error: errorMsg, success: 'false' });
     process.exit(1);
   }
 }
@@ -1,30 +1,238 @@
-import { DiscordEmbed, DiscordField, DiscordFooter, StepInfo, StepStatusHelper } from './models';
+import type { BoardEntry } from './board';
+import { type Clock, systemClock } from './clock';
+import type { RunTiming } from './github';
+import type { DailySummary, PipelineRunRecord, SloStatus } from './history';
+import { formatDecimal, formatMinutesSeconds, formatPercent } from './locale';
+import {
+  ButtonStyle,
+  ChangelogCommit,
+  ComponentType,
+  DiscordActionRow,
+  DiscordAllowedMentions,
+  DiscordEmbed,
+  DiscordEmbedAuthor,
+  DiscordField,
+  DiscordFooter,
+  DiscordMessage,
+  DiscordMessageData,
+  DiscordReaction,
+  PrInfo,
+  RolloutIncrement,
+  type StatusEmojiMap,
+  type StepFailure,
+  StepInfo,
+  StepInfoManager,
+  StepStatus,
+  StepStatusHelper,
+  StepTable,
+} from './models';
+
+/// Controls how the Progress/Completion fields are displayed; `bar` draws a progress bar
+/// followed by the percentage, e.g. `▰▰▰▱▱▱▱ 43%`
+export type ProgressDisplay = 'steps' | 'percent' | 'both' | 'bar';
+
+/// Controls how the step list of the step-update embed is laid out: one field per step,
+/// a single comma-separated line, or a `- [x]` checklist
+export type StepListStyle = 'fields' | 'inline' | 'checkboxes';
+
+/// Whether the step fields are part of the update embed (`single`) or posted as separate
+/// detail embeds, one per phase, below a summary embed (`multi`)
+export type EmbedLayout = 'single' | 'multi';
+
+/// How much the pipeline embeds show: `standard`, `compact` (a one-line embed without fields
+/// for noisy channels) or `detailed` (every step as a field with its timings and details)
+export type Theme = 'standard' | 'compact' | 'detailed';
+
+// Embed colors of the pipeline messages, as 0xRRGGBB
+export interface ColorScheme {
+  init: number;
+  inProgress: number;
+  success: number;
+  failure: number;
+  // Completed with skipped steps
+  skipped: number;
+  // Warnings, rollbacks and stalled pipelines
+  warning: number;
+  // Cancelled pipelines
+  cancelled: number;
+}
+
+export const DEFAULT_COLOR_SCHEME: ColorScheme = {
+  init: 0x0099ff, // Blue
+  inProgress: 0x0099ff, // Blue
+  success: 0x00ff00, // Green
+  failure: 0xff0000, // Red
+  skipped: 0xffff00, // Yellow
+  warning: 0xff8800, // Orange
+  cancelled: 0x555555, // Dark gray
+};
+
+/// Rendering options shared by the embed builders
+export interface RenderConfig {
+  percentDecimals: number;
+  progressDisplay: ProgressDisplay;
+  // Runner cost per billed minute; 0 disables cost estimates
+  costPerMinute: number;
+  stepListStyle: StepListStyle;
+  showStepNumbers: boolean;
+  showStepDurations: boolean;
+  showAdditionalInfo: boolean;
+  embedLayout: EmbedLayout;
+  // Mirror a one-line text summary into `content` for clients that strip embeds
+  contentFallback: boolean;
+  // BCP 47 locale of percentages, costs and durations (e.g. `de-DE`); empty keeps `12.5`
+  locale: string;
+  // Time shown in footers and timestamps; frozen for reproducible output
+  clock: Clock;
+  // Emoji replacing the default of some step statuses
+  statusEmoji: StatusEmojiMap;
+  colors: ColorScheme;
+  theme: Theme;
+}
+
+export const DEFAULT_RENDER_CONFIG: RenderConfig = {
+  percentDecimals: 0,
+  progressDisplay: 'both',
+  costPerMinute: 0,
+  stepListStyle: 'fields',
+  showStepNumbers: true,
+  showStepDurations: true,
+  showAdditionalInfo: true,
+  embedLayout: 'single',
+  contentFallback: false,
+  locale: '',
+  clock: systemClock,
+  statusEmoji: {},
+  colors: DEFAULT_COLOR_SCHEME,
+  theme: 'standard',
+};
+
+/// Merges the render options over the defaults; the detailed theme forces every step detail on
+function resolveRenderConfig(config: Partial<RenderConfig>): RenderConfig {
+  const renderConfig = { ...DEFAULT_RENDER_CONFIG, ...config };
+  if (renderConfig.theme !== 'detailed') {
+    return renderConfig;
+  }
+  return {
+    ...renderConfig,
+    stepListStyle: 'fields',
+    showStepNumbers: true,
+    showStepDurations: true,
+    showAdditionalInfo: true,
+  };
+}
+
+// URLs are left as is by the escaping, so links keep working
+const URL_PATTERN = /(https?:\/\/[^\s<>]+)/;
+
+/// Escapes the Discord markdown in text from workflow inputs (PR titles, branches, step
+/// names, additional info), so `*`, `_` or backticks show as typed instead of formatting
+export function escapeMarkdown(text: string): string {
+  return text
+    .split(URL_PATTERN)
+    .map((part, i) =>
+      // Odd parts are the URLs captured by the split
+      i % 2 === 1
+        ? part
+        : part.replace(/[\\*_~`|[\]]/g, '\\$&').replace(/^(\s*)([>#-])/gm, '$1\\$2')
+    )
+    .join('');
+}
+
+/// Breaks @everyone, @here and user/role mentions with a zero-width space, so they show as
+/// text and can never ping, even in the plain-text content
+export function neutralizeMentions(text: string): string {
+  return text.replace(/@(everyone|here)/g, '@\u200b$1').replace(/<@/g, '<@\u200b');
+}
+
+/// Makes text from workflow inputs safe to render: markdown escaped, mentions neutralized
+export function escapeUserText(text: string): string {
+  return neutralizeMentions(escapeMarkdown(text));
+}
+
+/// Makes text from workflow inputs safe inside an inline code span, where backslash escapes
+/// would show as typed: backticks are replaced by a look-alike so the span cannot be closed
+export function escapeCodeSpan(text: string): string {
+  return neutralizeMentions(text.replace(/`/g, 'ˋ'));
+}
+
+/// Builds the one-line embed of the compact theme: the PR title and status, then the steps
+/// as emoji on a single line
+function buildCompactEmbed(
+  title: string,
+  prTitle: string,
+  status: string,
+  steps: StepInfo[],
+  color: number,
+  footerText: string,
+  now: Date,
+  renderConfig: RenderConfig
+): DiscordEmbed {
+  const stepLine = steps
+    .map((step) => {
+      const emoji = StepStatusHelper.getEmoji(step.status, renderConfig.statusEmoji);
+      return `${emoji} ${escapeUserText(step.name)}`;
+    })
+    .join(' · ');
+  return {
+    title,
+    description: `**${escapeUserText(prTitle)}** · ${status}${stepLine ? `\n${stepLine}` : ''}`,
+    color,
+    footer: { text: footerText },
+    timestamp: now.toISOString(),
+  };
+}
+
+/// Formats a footer time in the runner's locale and time zone, or in UTC when the clock is
+/// frozen so the output does not depend on the machine rendering it
+function formatFooterTime(date: Date, renderConfig: RenderConfig): string {
+  if (!renderConfig.clock.frozen) {
+    return date.toLocaleString();
+  }
+  return date.toLocaleString(renderConfig.locale || 'en-US', { timeZone: 'UTC' });
+}
 
 export function buildInitEmbed(
   prNumber: string,
   prTitle: string,
   author: string,
   repository: string,
-  branch: string
+  branch: string,
+  config: Partial<RenderConfig> = {}
 ): DiscordEmbed {
+  const renderConfig = resolveRenderConfig(config);
+  const now = renderConfig.clock.now();
+  const footerText = `Pipeline started at ${formatFooterTime(now, renderConfig)}`;
+  if (renderConfig.theme === 'compact') {
+    return buildCompactEmbed(
+      `🚀 Pipeline Started - PR #${prNumber}`,
+      prTitle,
+      `⏳ Initializing · ${escapeUserText(author)} on ${escapeUserText(`${repository}@${branch}`)}`,
+      [],
+      renderConfig.colors.init,
+      footerText,
+      now,
+      renderConfig
+    );
+  }
   return {
     title: `🚀 Pipeline Started - PR #${prNumber}`,
-    description: `**${prTitle}**`,
-    color: 0x0099ff, // Blue
+    description: `**${escapeUserText(prTitle)}**`,
+    color: renderConfig.colors.init,
     fields: [
       {
         name: '👤 Author',
-        value: author,
+        value: escapeUserText(author),
         inline: true,
       },
       {
         name: '📦 Repository',
-        value: repository,
+        value: escapeUserText(repository),
         inline: true,
       },
       {
         name: '🌿 Branch',
-        value: branch,
+        value: escapeUserText(branch),
         inline: true,
       },
       {
@@ -34,65 +242,85 @@ export function buildInitEmbed(
       },
     ],
     footer: {
-      text: `Pipeline started at ${new Date().toLocaleString()}`,
+      text: footerText,
     },
-    timestamp: new Date().toISOString(),
+    timestamp: now.toISOString(),
   };
 }
 
+// Name of the step-update field showing the time since the pipeline started. The compact
+// theme has no fields and leaves it out.
+const ELAPSED_FIELD_NAME = '⏱️ Elapsed';
+
 export function buildStepUpdateEmbed(
   prNumber: string,
   prTitle: string,
   steps: StepInfo[],
   currentStep: number,
-  totalSteps: number
+  totalSteps: number,
+  config: Partial<RenderConfig> = {},
+  startTime?: Date
 ): DiscordEmbed {
+  const renderConfig = resolveRenderConfig(config);
+  const now = renderConfig.clock.now();
   const progress = getProgress(steps);
   const currentStepInfo = steps.find((step) => step.number === currentStep);
 
   // Build step list
-  const stepFields: DiscordField[] = steps.map((step) => {
-    const emoji = StepStatusHelper.getEmoji(step.status);
-    const statusText = step.status.charAt(0).toUpperCase() + step.status.slice(1);
-    let value = `${emoji} **${step.name}** - ${statusText}`;
-
-    // Add additional info if available
-    if (step.additionalInfo && step.additionalInfo.length > 0) {
-      const infoText = step.additionalInfo.map(([key, val]) => `**${key}:** ${val}`).join(', ');
-      value += `\n└ ${infoText}`;
-    }
-
-    return {
-      name: `Step ${step.number}`,
-      value,
-      inline: false,
-    };
-  });
+  const groups = groupStepsByPhase(steps);
+  const hasPhases = groups.some((group) => group.phase);
+  const stepFields: DiscordField[] = hasPhases
+    ? groups.flatMap((group) => [
+        { name: formatPhaseName(group), value: formatPhaseRollup(group.steps), inline: false },
+        ...group.steps.map((step) => buildStepField(step, renderConfig)),
+      ])
+    : steps.map((step) => buildStepField(step, renderConfig));
 
   // Determine overall status and color
   let overallStatus = '🔄 Running';
-  let color = 0x0099ff; // Blue
+  let color = renderConfig.colors.inProgress;
 
   if (progress.completed === progress.total && progress.total > 0) {
     const hasFailures = steps.some((step) => step.status === 'failed');
     if (hasFailures) {
       overallStatus = '❌ Failed';
-      color = 0xff0000; // Red
+      color = renderConfig.colors.failure;
+    } else if (steps.some((step) => step.status === 'cancelled')) {
+      overallStatus = '🚫 Cancelled';
+      color = renderConfig.colors.cancelled;
     } else {
       overallStatus = '✅ Completed';
-      color = 0x00ff00; // Green
+      color = renderConfig.colors.success;
     }
   }
+  // A rollout going backwards is shown as a rollback unless the pipeline already failed
+  if (overallStatus !== '❌ Failed' && steps.some(StepInfoManager.isRolledBack)) {
+    overallStatus = '⏪ Rolled back';
+    color = renderConfig.colors.warning;
+  }
+
+  if (renderConfig.theme === 'compact') {
+    return buildCompactEmbed(
+      `🔄 Pipeline Update - PR #${prNumber}`,
+      prTitle,
+      `${overallStatus} ${progress.completed}/${progress.total}`,
+      steps,
+      color,
+      `Last updated at ${formatFooterTime(now, renderConfig)}`,
+      now,
+      renderConfig
+    );
+  }
 
   const fields: DiscordField[] = [
     {
       name: '📊 Progress',
-      value: `${progress.completed}/${progress.total} steps completed (${progress.percentage}%)`,
+      value: formatProgress(progress, renderConfig, 'steps completed'),
       inline: true,
     },
     {
       name: '🎯 Current Step',
-      value: currentStepInfo ? currentStepInfo.name : `Step ${currentStep}`,
+      value: currentStepInfo ? escapeUserText(currentStepInfo.name) : `Step ${currentStep}`,
       inline: true,
     },
     {
@@ -100,26 +328,55 @@ export function buildStepUpdateEmbed(
       value: overallStatus,
       inline: true,
     },
+    // Wall-clock time since the pipeline started, refreshed on every update
+    ...(startTime
+      ? [
+          {
+            name: ELAPSED_FIELD_NAME,
+            value: formatDuration(now.getTime() - startTime.getTime(), renderConfig.locale),
+            inline: true,
+          },
+        ]
+      : []),
+    ...buildRolloutFields(steps),
+    ...buildFailureFields(steps),
   ];
 
+  // Step fields move to the detail embeds in the multi-embed layout
+  const separateDetails =
+    renderConfig.embedLayout === 'multi' && renderConfig.stepListStyle === 'fields';
+
   // Add step details if there are any
   if (stepFields.length > 0) {
     fields.push({
       name: '📝 Steps',
-      value: stepFields.length > 0 ? 'See fields below' : 'No steps recorded',
+      value:
+        renderConfig.stepListStyle === 'fields'
+          ? separateDetails
+            ? 'See the step details below'
+            : 'See fields below'
+          : fitChecklist(
+              groups.flatMap((group) => [
+                ...(hasPhases ? [phaseHeaderItem(group)] : []),
+                ...formatCompactStepItems(group.steps, renderConfig),
+              ])
+            ),
       inline: false,
     });
   }
 
   return {
     title: `🔄 Pipeline Update - PR #${prNumber}`,
-    description: `**${prTitle}**`,
+    description: `**${escapeUserText(prTitle)}**`,
     color,
-    fields: [...fields, ...stepFields],
+    fields:
+      renderConfig.stepListStyle === 'fields' && !separateDetails
+        ? [...fields, ...stepFields]
+        : fields,
     footer: {
-      text: `Last updated at ${new Date().toLocaleString()}`,
+      text: `Last updated at ${formatFooterTime(now, renderConfig)}`,
     },
-    timestamp: new Date().toISOString(),
+    timestamp: now.toISOString(),
   };
 }
 
@@ -128,28 +385,54 @@ export function buildCompletionEmbed(
   prTitle: string,
   steps: StepInfo[],
   totalSteps: number,
-  startTime: Date
+  startTime: Date,
+  config: Partial<RenderConfig> = {}
 ): DiscordEmbed {
+  const renderConfig = resolveRenderConfig(config);
   const progress = getProgress(steps);
-  const duration = Date.now() - startTime.getTime();
-  const durationMinutes = Math.floor(duration / 60000);
-  const durationSeconds = Math.floor((duration % 60000) / 1000);
+  const now = renderConfig.clock.now();
+  const duration = now.getTime() - startTime.getTime();
 
   const hasFailures = steps.some((step) => step.status === 'failed');
+  const hasCancelled = steps.some((step) => step.status === 'cancelled');
   const hasSkipped = steps.some((step) => step.status === 'skipped');
 
   let status = '✅ Success';
-  let color = 0x00ff00; // Green
+  let color = renderConfig.colors.success;
   let emoji = '🎉';
 
   if (hasFailures) {
     status = '❌ Failed';
-    color = 0xff0000; // Red
+    color = renderConfig.colors.failure;
     emoji = '💥';
+  } else if (hasCancelled) {
+    status = '🚫 Cancelled';
+    color = renderConfig.colors.cancelled;
+    emoji = '🚫';
   } else if (hasSkipped) {
     status = '⚠️ Completed with skipped steps';
-    color = 0xffff00; // Yellow
+    color = renderConfig.colors.skipped;
     emoji = '⚠️';
+  } else if (steps.some(StepInfoManager.isRolledBack)) {
+    status = '⏪ Rolled back';
+    color = renderConfig.colors.warning;
+    emoji = '⏪';
+  }
+
+  const outcome = hasFailures ? 'Failed' : hasCancelled ? 'Cancelled' : 'Completed';
+  const title = `${emoji} Pipeline ${outcome} - PR #${prNumber}`;
+  const footerText = `Pipeline completed at ${formatFooterTime(now, renderConfig)}`;
+  if (renderConfig.theme === 'compact') {
+    return buildCompactEmbed(
+      title,
+      prTitle,
+      `${status} in ${formatDuration(duration, renderConfig.locale)}`,
+      steps,
+      color,
+      footerText,
+      now,
+      renderConfig
+    );
   }
 
   const fields: DiscordField[] = [
@@ -160,24 +443,43 @@ export function buildCompletionEmbed(
     },
     {
       name: '⏱️ Duration',
-      value: `${durationMinutes}m ${durationSeconds}s`,
+      value: formatDuration(duration, renderConfig.locale),
       inline: true,
     },
     {
       name: '📈 Completion',
-      value: `${progress.completed}/${progress.total} steps (${progress.percentage}%)`,
+      value: formatProgress(progress, renderConfig, 'steps'),
       inline: true,
     },
+    ...buildRolloutFields(steps),
   ];
 
-  // Add step summary
+  if (renderConfig.costPerMinute > 0) {
+    fields.push({
+      name: '💰 Estimated Cost',
+      value: formatCost(
+        estimateRunCost(duration, renderConfig.costPerMinute),
+        renderConfig.locale
+      ),
+      inline: true,
+    });
+  }
+
+  // Add step summary, with a rollup header per phase when steps are grouped in phases
   if (steps.length > 0) {
-    const stepSummary = steps
-      .map((step) => {
-        const stepEmoji = StepStatusHelper.getEmoji(step.status);
-        return `${stepEmoji} ${step.name}`;
-      })
-      .join('\n');
+    const groups = groupStepsByPhase(steps);
+    const hasPhases = groups.some((group) => group.phase);
+    const stepSummary = fitChecklist(
+      groups.flatMap((group) => [
+        ...(hasPhases ? [phaseHeaderItem(group)] : []),
+        ...group.steps.map((step) => summaryItem(step, renderConfig)),
+      ])
+    );
+
+    const durationTable = formatStepDurationTable(steps, renderConfig);
+    if (durationTable) {
+      fields.push({ name: '⏱️ Step Durations', value: durationTable, inline: false });
+    }
 
     fields.push({
       name: '📝 Steps Summary',
@@ -185,30 +487,1503 @@ export function buildCompletionEmbed(
       inline: false,
     });
   }
+  fields.push(...buildFailureFields(steps));
 
   return {
-    title: `${emoji} Pipeline ${hasFailures ? 'Failed' : 'Completed'} - PR #${prNumber}`,
-    description: `**${prTitle}**`,
+    title,
+    description: `**${escapeUserText(prTitle)}**`,
     color,
     fields,
     footer: {
-      text: `Pipeline completed at ${new Date().toLocaleString()}`,
+      text: footerText,
     },
-    timestamp: new Date().toISOString(),
+    timestamp: now.toISOString(),
+  };
+}
+
+/// Builds the embed shown when the watchdog sees no state change for `silentMinutes`
+export function buildStalledEmbed(
+  prNumber: string,
+  prTitle: string,
+  steps: StepInfo[],
+  silentMinutes: number,
+  config: Partial<RenderConfig> = {}
+): DiscordEmbed {
+  const renderConfig = resolveRenderConfig(config);
+  const now = renderConfig.clock.now();
+  const stalledSince = new Date(now.getTime() - silentMinutes * 60000);
+  const fields: DiscordField[] = [
+    {
+      name: '📊 Status',
+      value: `⚠️ stalled — no updates for ${silentMinutes} minutes`,
+      inline: false,
+    },
+  ];
+
+  if (steps.length > 0) {
+    fields.push({
+      name: '📝 Steps Summary',
+      value: fitChecklist(steps.map((step) => summaryItem(step, renderConfig))),
+      inline: false,
+    });
+  }
+
+  return {
+    title: `⚠️ Pipeline Stalled - PR #${prNumber}`,
+    description: `**${escapeUserText(prTitle)}**`,
+    color: renderConfig.colors.warning,
+    fields,
+    footer: {
+      text: `Stalled since ${formatFooterTime(stalledSince, renderConfig)}`,
+    },
+    timestamp: now.toISOString(),
+  };
+}
+
+// Shown instead of empty field names/values, which Discord rejects
+export const EMPTY_FIELD_PLACEHOLDER = '—';
+
+// Discord rejects messages whose embeds total more than this many characters
+export const MESSAGE_EMBED_CHAR_LIMIT = 6000;
+
+// Discord limits embeds to 25 fields
+const MAX_EMBED_FIELDS = 25;
+
+// Per-part embed limits; Discord answers 400 to anything longer
+const EMBED_TITLE_LIMIT = 256;
+const EMBED_DESCRIPTION_LIMIT = 4096;
+const FIELD_NAME_LIMIT = 256;
+const AUTHOR_NAME_LIMIT = 256;
+const FOOTER_TEXT_LIMIT = 2048;
+
+// Discord limits messages to 10 embeds
+export const MAX_MESSAGE_EMBEDS = 10;
+
+/// Builds the detail embeds of the multi-embed layout: one per phase (a single one when steps
+/// have no phase), each listing the step fields. Phases beyond the embed limit, leaving room
+/// for the summary embed, are merged into the last detail embed.
+export function buildStepDetailEmbeds(
+  steps: StepInfo[],
+  config: Partial<RenderConfig> = {}
+): DiscordEmbed[] {
+  const renderConfig = resolveRenderConfig(config);
+  if (
+    renderConfig.theme === 'compact' ||
+    renderConfig.embedLayout !== 'multi' ||
+    renderConfig.stepListStyle !== 'fields'
+  ) {
+    return [];
+  }
+
+  const embeds: DiscordEmbed[] = [];
+  for (const group of groupStepsByPhase(steps)) {
+    const fields = group.steps.map((step) => buildStepField(step, renderConfig));
+    const last = embeds[embeds.length - 1];
+    if (embeds.length === MAX_MESSAGE_EMBEDS - 1) {
+      last.fields = [
+        ...(last.fields || []),
+        { name: formatPhaseName(group), value: formatPhaseRollup(group.steps), inline: false },
+        ...fields,
+      ];
+      continue;
+    }
+    embeds.push({
+      title: group.phase ? formatPhaseName(group) : '📝 Steps',
+      description: formatPhaseRollup(group.steps),
+      color: getPhaseColor(group.steps, renderConfig.colors),
+      fields,
+    });
+  }
+  return embeds;
+}
+
+/// Splits a message's embeds so they fit Discord's limits: embeds are kept in order while the
+/// total stays within the character and embed count limits, the rest go to the overflow
+/// reply. A single oversized embed is split by `splitEmbedOverflow`.
+export function splitMessageEmbeds(
+  embeds: DiscordEmbed[],
+  limit: number = MESSAGE_EMBED_CHAR_LIMIT
+): { main: DiscordEmbed[]; overflow: DiscordEmbed[] } {
+  const [first, ...rest] = embeds;
+  if (!first) {
+    return { main: [], overflow: [] };
+  }
+
+  const split = splitEmbedOverflow(first, limit);
+  const main = [split.main];
+  const overflow = split.overflow ? [split.overflow] : [];
+  let mainLength = embedLength(split.main);
+  let overflowLength = overflow.reduce((sum, embed) => sum + embedLength(embed), 0);
+
+  for (const embed of rest) {
+    const length = embedLength(embed);
+    if (overflow.length === 0 && main.length < MAX_MESSAGE_EMBEDS && mainLength + length <= limit) {
+      main.push(embed);
+      mainLength += length;
+    } else if (overflow.length < MAX_MESSAGE_EMBEDS && overflowLength + length <= limit) {
+      overflow.push(embed);
+      overflowLength += length;
+    } else {
+      console.warn(`⚠️  Dropped embed "${embed.title}" - it fits in neither message`);
+    }
+  }
+
+  const noticeLength = OVERFLOW_NOTICE.name.length + OVERFLOW_NOTICE.value.length;
+  if (overflow.length > 0 && !split.overflow && mainLength + noticeLength <= limit) {
+    main[0] = { ...main[0], fields: [...(main[0].fields || []), OVERFLOW_NOTICE] };
+  }
+
+  return { main, overflow };
+}
+
+// Field appended to the main embed when part of it moved to the overflow reply
+const OVERFLOW_NOTICE: DiscordField = {
+  name: '📎 More details',
+  value: 'Continued in the reply below',
+  inline: false,
+};
+
+/// Counts the embed characters Discord includes in the per-message limit
+export function embedLength(embed: DiscordEmbed): number {
+  const fields = (embed.fields || []).reduce(
+    (sum, field) => sum + field.name.length + field.value.length,
+    0
+  );
+  return (
+    (embed.title?.length || 0) +
+    (embed.description?.length || 0) +
+    (embed.footer?.text.length || 0) +
+    (embed.author?.name.length || 0) +
+    fields
+  );
+}
+
+/// Cuts text to at most `limit` characters, ending it with an ellipsis when cut
+export function truncateText(text: string, limit: number): string {
+  if (text.length <= limit) {
+    return text;
+  }
+  return limit <= 0 ? '' : `${text.slice(0, limit - 1)}…`;
+}
+
+/// Last-resort validation pass before a message is sent: truncates the title, description,
+/// field names and values, footer and author name of every embed to Discord's limits, keeps at most 25
+/// fields, and shares the message's total character budget between the embeds in order.
+/// Parts left without budget are dropped, so Discord never rejects the message as too long.
+export function enforceEmbedLimits(
+  embeds: DiscordEmbed[],
+  limit: number = MESSAGE_EMBED_CHAR_LIMIT
+): DiscordEmbed[] {
+  let budget = limit;
+  const take = (text: string, partLimit: number): string => {
+    const fitted = truncateText(text, Math.min(partLimit, budget));
+    budget -= fitted.length;
+    return fitted;
+  };
+
+  return embeds.map((embed) => {
+    const { title, description, fields, footer, author, ...rest } = embed;
+    const fitted: DiscordEmbed = { ...rest };
+    const fittedTitle = title && take(title, EMBED_TITLE_LIMIT);
+    if (fittedTitle) {
+      fitted.title = fittedTitle;
+    }
+    const fittedDescription = description && take(description, EMBED_DESCRIPTION_LIMIT);
+    if (fittedDescription) {
+      fitted.description = fittedDescription;
+    }
+    if (fields !== undefined) {
+      fitted.fields = [];
+      for (const field of fields.slice(0, MAX_EMBED_FIELDS)) {
+        // A field needs a name and a value; stop once both no longer fit
+        if (budget < 2) {
+          break;
+        }
+        // Leaves at least one character of the budget for the value
+        const name = take(field.name, Math.min(FIELD_NAME_LIMIT, budget - 1));
+        fitted.fields.push({ ...field, name, value: take(field.value, FIELD_VALUE_LIMIT) });
+      }
+    }
+    if (footer !== undefined) {
+      const text = take(footer.text, FOOTER_TEXT_LIMIT);
+      if (text) {
+        fitted.footer = { ...footer, text };
+      }
+    }
+    if (author !== undefined) {
+      const name = take(author.name, AUTHOR_NAME_LIMIT);
+      if (name) {
+        fitted.author = { ...author, name };
+      }
+    }
+    return fitted;
+  });
+}
+
+/// Applies `enforceEmbedLimits` to a message, returning it unchanged when it has no embeds
+export function fitMessageToLimits<T extends DiscordMessage>(message: T): T {
+  return message.embeds ? { ...message, embeds: enforceEmbedLimits(message.embeds) } : message;
+}
+
+/// Splits an embed exceeding the message limit. The trailing fields (the per-step details,
+/// least important) move to an overflow embed posted as a reply, so the summary fields stay
+/// on the main message; fields that fit in neither are counted in the overflow description.
+export function splitEmbedOverflow(
+  embed: DiscordEmbed,
+  limit: number = MESSAGE_EMBED_CHAR_LIMIT
+): { main: DiscordEmbed; overflow?: DiscordEmbed } {
+  if (embedLength(embed) <= limit) {
+    return { main: embed };
+  }
+
+  const kept = [...(embed.fields || [])];
+  const moved: DiscordField[] = [];
+  while (kept.length > 0 && embedLength({ ...embed, fields: [...kept, OVERFLOW_NOTICE] }) > limit) {
+    moved.unshift(kept.pop() as DiscordField);
+  }
+
+  const overflow: DiscordEmbed = {
+    title: `${embed.title || 'Pipeline'} (continued)`,
+    color: embed.color,
+    fields: [],
   };
+  // Leaves room for the omitted-fields description
+  const overflowLimit = limit - 50;
+  let omitted = 0;
+  for (const field of moved) {
+    const fields = [...(overflow.fields || []), field];
+    if (
+      omitted === 0 &&
+      fields.length <= MAX_EMBED_FIELDS &&
+      embedLength({ ...overflow, fields }) <= overflowLimit
+    ) {
+      overflow.fields = fields;
+    } else {
+      omitted++;
+    }
+  }
+  if (omitted > 0) {
+    overflow.description = `… ${omitted} more field(s) omitted`;
+  }
+
+  return { main: { ...embed, fields: [...kept, OVERFLOW_NOTICE] }, overflow };
+}
+
+/// Drops the parts of an embed that change on every render (footer, timestamp and elapsed
+/// time), keeping what is worth comparing between renders
+export function stripVolatileFields(embed: DiscordEmbed): DiscordEmbed {
+  const { footer: _footer, timestamp: _timestamp, ...rest } = embed;
+  if (!rest.fields?.some((field) => field.name === ELAPSED_FIELD_NAME)) {
+    return rest;
+  }
+  return { ...rest, fields: rest.fields.filter((field) => field.name !== ELAPSED_FIELD_NAME) };
+}
+
+/// Lists what differs between two renders of a message, ignoring footers, timestamps and the
+/// elapsed time, e.g. `['title', 'field "Step 2"', 'embed 2 added']`. Empty when nothing else
+/// changed.
+export function diffEmbeds(previous: DiscordEmbed[], next: DiscordEmbed[]): string[] {
+  const changes: string[] = [];
+  const multiple = previous.length > 1 || next.length > 1;
+
+  for (let i = 0; i < Math.max(previous.length, next.length); i++) {
+    if (!previous[i] || !next[i]) {
+      changes.push(`embed ${i + 1} ${previous[i] ? 'removed' : 'added'}`);
+      continue;
+    }
+    const before = stripVolatileFields(previous[i]);
+    const after = stripVolatileFields(next[i]);
+    const prefix = multiple ? `embed ${i + 1} ` : '';
+
+    for (const key of new Set([...Object.keys(before), ...Object.keys(after)])) {
+      const beforeValue = (before as Record<string, unknown>)[key];
+      const afterValue = (after as Record<string, unknown>)[key];
+      if (key !== 'fields' && JSON.stringify(beforeValue) !== JSON.stringify(afterValue)) {
+        changes.push(`${prefix}${key}`);
+      }
+    }
+
+    const beforeFields = before.fields || [];
+    const afterFields = after.fields || [];
+    for (let f = 0; f < Math.max(beforeFields.length, afterFields.length); f++) {
+      if (JSON.stringify(beforeFields[f]) !== JSON.stringify(afterFields[f])) {
+        changes.push(`${prefix}field "${(afterFields[f] || beforeFields[f]).name}"`);
+      }
+    }
+  }
+
+  return changes;
+}
+
+/// Replaces empty field names/values with a placeholder and suffixes duplicate field names
+/// so a single bad value cannot make Discord reject the whole update
+export function sanitizeEmbed(embed: DiscordEmbed): DiscordEmbed {
+  if (!embed.fields) {
+    return embed;
+  }
+
+  const seen = new Map<string, number>();
+  const fields = embed.fields.map((field) => {
+    let name = field.name.trim() === '' ? EMPTY_FIELD_PLACEHOLDER : field.name;
+    const value = field.value.trim() === '' ? EMPTY_FIELD_PLACEHOLDER : field.value;
+
+    const count = (seen.get(name) || 0) + 1;
+    seen.set(name, count);
+    if (count > 1) {
+      console.warn(`⚠️  Duplicate embed field name "${name}" - renamed to "${name} (${count})"`);
+      name = `${name} (${count})`;
+    }
+
+    return { ...field, name, value };
+  });
+
+  return { ...embed, fields };
+}
+
+// Table rendering limits, keeping a step field well under Discord's 1024 character limit
+const TABLE_MAX_COLUMN_WIDTH = 20;
+const TABLE_MAX_ROWS = 10;
+
+/// Formats a finished step's duration as ` (Xm Ys)`, or nothing when hidden or unknown
+function formatStepDuration(step: StepInfo, config: RenderConfig): string {
+  const duration = StepInfoManager.duration(step);
+  // Steps first reported as finished have no meaningful duration
+  if (!config.showStepDurations || duration === undefined || duration < 1000) {
+    return '';
+  }
+  return ` (${formatDuration(duration, config.locale)})`;
+}
+
+/// Attempt number of a retried step, e.g. ` (attempt 3)`; empty for a first run
+function formatAttempt(step: StepInfo): string {
+  return step.retries ? ` (attempt ${step.retries + 1})` : '';
+}
+
+/// Flags a finished step that ran past its budget, e.g. ` · ⏰ over budget by 0m 42s`. Shown
+/// even when durations are hidden, since it calls for attention.
+function formatBudgetOverrun(step: StepInfo, config: RenderConfig): string {
+  const overrun = StepInfoManager.overBudgetMs(step);
+  if (overrun === undefined || overrun < 1000) {
+    return '';
+  }
+  return ` · ⏰ over budget by ${formatDuration(overrun, config.locale)}`;
+}
+
+/// Tabulates the duration of each finished step, slowest first, so the slow steps stand out.
+/// Empty when durations are hidden or no step has a meaningful duration.
+export function formatStepDurationTable(steps: StepInfo[], config: RenderConfig): string {
+  if (!config.showStepDurations) {
+    return '';
+  }
+  const timed = steps
+    .map((step) => ({ step, duration: StepInfoManager.duration(step) }))
+    .filter((entry): entry is { step: StepInfo; duration: number } =>
+      // Steps first reported as finished have no meaningful duration
+      entry.duration !== undefined && entry.duration >= 1000
+    )
+    .sort((a, b) => b.duration - a.duration);
+  if (timed.length === 0) {
+    return '';
+  }
+
+  const total = timed.reduce((sum, entry) => sum + entry.duration, 0);
+  return formatTable([
+    ['step', 'duration', 'share'],
+    ...timed.map(({ step, duration }) => [
+      step.name,
+      formatDuration(duration, config.locale),
+      // Share of the summed step time, since steps of parallel jobs overlap
+      formatPercent((duration / total) * 100, 0, config.locale),
+    ]),
+  ]);
+}
+
+// Steps of one phase, in the order they were first reported
+export interface PhaseGroup {
+  phase?: string;
+  steps: StepInfo[];
+}
+
+/// Groups steps by phase, keeping phases in the order they first appeared. Steps without a
+/// phase form their own group.
+export function groupStepsByPhase(steps: StepInfo[]): PhaseGroup[] {
+  const groups: PhaseGroup[] = [];
+  for (const step of steps) {
+    const group = groups.find((candidate) => candidate.phase === step.phase);
+    if (group) {
+      group.steps.push(step);
+    } else {
+      groups.push({ phase: step.phase, steps: [step] });
+    }
+  }
+  return groups;
+}
+
+/// Rolls the steps of a phase up into one status with its progress, e.g. "✅ Success (3/3)"
+export function formatPhaseRollup(steps: StepInfo[]): string {
+  const progress = getProgress(steps);
+  const counts = `(${progress.completed}/${progress.total})`;
+
+  if (steps.some((step) => step.status === 'failed')) {
+    return `❌ Failed ${counts}`;
+  }
+  if (steps.some((step) => step.status === 'cancelled')) {
+    return `🚫 Cancelled ${counts}`;
+  }
+  if (progress.completed < progress.total) {
+    return `🔄 Running ${counts}`;
+  }
+  if (steps.some((step) => step.status === 'warning')) {
+    return `⚠️ Completed with warnings ${counts}`;
+  }
+  return `✅ Success ${counts}`;
+}
+
+/// Picks the embed color matching a phase rollup
+function getPhaseColor(steps: StepInfo[], colors: ColorScheme): number {
+  const progress = getProgress(steps);
+  if (steps.some((step) => step.status === 'failed')) {
+    return colors.failure;
+  }
+  if (steps.some((step) => step.status === 'cancelled')) {
+    return colors.cancelled;
+  }
+  if (progress.completed < progress.total) {
+    return colors.inProgress;
+  }
+  return steps.some((step) => step.status === 'warning') ? colors.warning : colors.success;
+}
+
+function formatPhaseName(group: PhaseGroup): string {
+  return `🧩 ${group.phase || 'Other steps'}`;
+}
+
+/// Builds the field describing one step: status, duration, additional info, tables and notes
+function buildStepField(step: StepInfo, renderConfig: RenderConfig): DiscordField {
+  const emoji = StepStatusHelper.getEmoji(step.status, renderConfig.statusEmoji);
+  const statusText = step.status.charAt(0).toUpperCase() + step.status.slice(1);
+  const duration = formatStepDuration(step, renderConfig) + formatBudgetOverrun(step, renderConfig);
+  const owner = step.owner ? ` · 👤 @${step.owner}` : '';
+  const logs = step.logsUrl ? ` · [📜 Logs](${step.logsUrl})` : '';
+  const status = `${statusText}${formatAttempt(step)}`;
+  let value = `${emoji} **${escapeUserText(step.name)}** - ${status}${duration}${owner}${logs}`;
+
+  // The detailed theme adds when the step ran, shown in the reader's time zone by Discord
+  if (renderConfig.theme === 'detailed' && step.startedAt) {
+    const started = `<t:${Math.floor(new Date(step.startedAt).getTime() / 1000)}:T>`;
+    const completed = step.completedAt
+      ? ` → <t:${Math.floor(new Date(step.completedAt).getTime() / 1000)}:T>`
+      : '';
+    value += `\n🕐 ${started}${completed}`;
+  }
+
+  // Add additional info if available
+  if (renderConfig.showAdditionalInfo && step.additionalInfo && step.additionalInfo.length > 0) {
+    const { pairs, scanReport } = splitScanReport(step.additionalInfo);
+    const infoText = pairs
+      .map(([key, val]) => `**${escapeUserText(key)}:** ${formatInfoValue(val)}`)
+      .join(', ');
+    if (infoText) {
+      value += `\n└ ${infoText}`;
+    }
+    if (scanReport) {
+      value += `\n└ ${scanReport}`;
+    }
+  }
+
+  for (const table of step.tables || []) {
+    value += `\n**${escapeUserText(table.title)}**\n${formatTable(table.rows)}`;
+  }
+
+  // Add post-hoc notes in italics
+  for (const note of step.notes || []) {
+    value += `\n└ _${escapeUserText(note)}_`;
+  }
+
+  return {
+    name: renderConfig.showStepNumbers ? `Step ${step.number}` : escapeUserText(step.name),
+    value,
+    inline: false,
+  };
+}
+
+/// Renders an additional info value, escaped, or a placeholder when it is blank
+function formatInfoValue(value: string): string {
+  return escapeUserText(value.trim()) || EMPTY_FIELD_PLACEHOLDER;
+}
+
+// Additional info keys rendered as a scan report link with a pass/fail badge
+const SCAN_REPORT_KEY = 'scan_report';
+const SCAN_RESULT_KEY = 'scan_result';
+const SCAN_PASSED = ['pass', 'passed', 'success', 'ok', 'clean'];
+const SCAN_FAILED = ['fail', 'failed', 'failure', 'error', 'vulnerable'];
+
+/// Formats the badge of a scan result: passed, failed, or the raw result (e.g. `3 high`)
+export function formatScanBadge(result: string): string {
+  const normalized = result.trim().toLowerCase();
+  if (SCAN_PASSED.includes(normalized)) {
+    return '✅ Passed';
+  }
+  if (SCAN_FAILED.includes(normalized)) {
+    return '❌ Failed';
+  }
+  return `⚠️ ${escapeUserText(result.trim())}`;
+}
+
+// Additional info key taken as the step's logs link by the tracker
+export const LOGS_URL_KEY = 'logs_url';
+
+/// Returns the http(s) URL of a `logs_url` additional info pair; other values stay plain pairs
+export function findLogsUrl(additionalInfo: Array<[string, string]>): string | undefined {
+  const url = additionalInfo.find(([key]) => key === LOGS_URL_KEY)?.[1].trim();
+  return url && /^https?:\/\/\S+$/.test(url) ? url : undefined;
+}
+
+/// Takes the `scan_report` URL and `scan_result` out of the additional info and renders them
+/// as a `🔍 Scan report` link with its badge. A report value that is not an http(s) URL stays
+/// a plain pair.
+export function splitScanReport(additionalInfo: Array<[string, string]>): {
+  pairs: Array<[string, string]>;
+  scanReport?: string;
+} {
+  const report = additionalInfo.find(([key]) => key === SCAN_REPORT_KEY)?.[1].trim();
+  if (!report || !/^https?:\/\/\S+$/.test(report)) {
+    return { pairs: additionalInfo };
+  }
+
+  const result = additionalInfo.find(([key]) => key === SCAN_RESULT_KEY)?.[1];
+  const badge = result?.trim() ? ` ${formatScanBadge(result)}` : '';
+  return {
+    pairs: additionalInfo.filter(([key]) => key !== SCAN_REPORT_KEY && key !== SCAN_RESULT_KEY),
+    scanReport: `🔍 [Scan report](${report})${badge}`,
+  };
+}
+
+/// Renders the steps as comma-separated entries or as `- [x]` checklist lines
+function formatCompactStepItems(steps: StepInfo[], config: RenderConfig): ChecklistItem[] {
+  const checkboxes = config.stepListStyle === 'checkboxes';
+  return steps.map((step, i) => {
+    const number = config.showStepNumbers ? `${step.number}. ` : '';
+    const duration = formatStepDuration(step, config) + formatBudgetOverrun(step, config);
+    const label = `${number}${escapeUserText(step.name)}${formatAttempt(step)}${duration}`;
+    const failed = step.status === 'failed' ? ' ❌' : '';
+    return {
+      text: checkboxes
+        ? `- [${step.completedAt ? 'x' : ' '}] ${label}${failed}`
+        : `${StepStatusHelper.getEmoji(step.status, config.statusEmoji)} ${label}`,
+      // Each phase starts on its own line
+      separator: i === 0 || checkboxes ? '\n' : ', ',
+      step,
+    };
+  });
+}
+
+/// Summary line of a step, one per line
+function summaryItem(step: StepInfo, config: RenderConfig): ChecklistItem {
+  const emoji = StepStatusHelper.getEmoji(step.status, config.statusEmoji);
+  const suffix =
+    formatAttempt(step) +
+    (config.theme === 'detailed' ? formatStepDuration(step, config) : '') +
+    formatBudgetOverrun(step, config);
+  return { text: `${emoji} ${escapeUserText(step.name)}${suffix}`, separator: '\n', step };
+}
+
+/// Header line of a phase, which is never collapsed
+function phaseHeaderItem(group: PhaseGroup): ChecklistItem {
+  return {
+    text: `**${formatPhaseName(group)}** - ${formatPhaseRollup(group.steps)}`,
+    separator: '\n',
+  };
+}
+
+// Discord rejects field values longer than this
+export const FIELD_VALUE_LIMIT = 1024;
+
+// Entry of a step checklist; entries with a `step` may be collapsed into a summary
+export interface ChecklistItem {
+  text: string;
+  // Placed before the entry unless it comes first
+  separator: string;
+  step?: StepInfo;
+}
+
+/// Joins checklist entries into a field value of at most `limit` characters. When the list is
+/// too long, runs of consecutive successful steps are collapsed into "… N steps passed …",
+/// longest run first, so failed and running steps stay visible. Whatever still does not fit
+/// is cut off.
+export function fitChecklist(items: ChecklistItem[], limit: number = FIELD_VALUE_LIMIT): string {
+  const render = (list: ChecklistItem[]) =>
+    list.map((item, i) => (i > 0 ? item.separator : '') + item.text).join('');
+
+  let current = items;
+  let run = longestPassedRun(current);
+  while (render(current).length > limit && run) {
+    const count = run.length;
+    current = [
+      ...current.slice(0, run.start),
+      {
+        text: `… ${count} step${count === 1 ? '' : 's'} passed …`,
+        separator: current[run.start].separator,
+      },
+      ...current.slice(run.start + count),
+    ];
+    run = longestPassedRun(current);
+  }
+
+  const value = render(current);
+  return value.length > limit ? `${value.slice(0, limit - 1)}…` : value;
+}
+
+/// Finds the longest run of consecutive successful steps among the entries
+function longestPassedRun(items: ChecklistItem[]): { start: number; length: number } | undefined {
+  let best: { start: number; length: number } | undefined;
+  let start = -1;
+  for (let i = 0; i <= items.length; i++) {
+    const passed = i < items.length && items[i].step?.status === StepStatus.Success;
+    if (passed && start === -1) {
+      start = i;
+    } else if (!passed && start !== -1) {
+      if (!best || i - start > best.length) {
+        best = { start, length: i - start };
+      }
+      start = -1;
+    }
+  }
+  return best;
+}
+
+/// Renders rows as an aligned code-block table. The first row is the header; cells longer than
+/// the column limit are truncated and rows beyond the limit are summarized.
+export function formatTable(rows: StepTable['rows']): string {
+  if (rows.length === 0) {
+    return '';
+  }
+
+  const truncate = (cell: string) =>
+    cell.length > TABLE_MAX_COLUMN_WIDTH ? `${cell.slice(0, TABLE_MAX_COLUMN_WIDTH - 1)}…` : cell;
+  const shown = rows.slice(0, TABLE_MAX_ROWS + 1).map((row) => row.map(truncate));
+  const columns = Math.max(...shown.map((row) => row.length));
+  const widths = Array.from({ length: columns }, (_, col) =>
+    Math.max(...shown.map((row) => (row[col] || '').length))
+  );
+
+  const formatRow = (row: string[]) =>
+    widths
+      .map((width, col) => (row[col] || '').padEnd(width))
+      .join(' | ')
+      .trimEnd();
+  const lines = [
+    formatRow(shown[0]),
+    widths.map((width) => '-'.repeat(width)).join('-|-'),
+    ...shown.slice(1).map(formatRow),
+  ];
+
+  const hidden = rows.length - shown.length;
+  if (hidden > 0) {
+    lines.push(`… ${hidden} more rows`);
+  }
+
+  return `\`\`\`\n${lines.join('\n')}\n\`\`\``;
+}
+
+/// Builds the compact pipeline tags field, or undefined when there are no tags
+export function buildTagsField(tags: Array<[string, string]>): DiscordField | undefined {
+  if (tags.length === 0) {
+    return undefined;
+  }
+  return {
+    name: '🏷️ Tags',
+    value: formatTags(tags),
+    inline: false,
+  };
+}
+
+// Metadata fields shown per embed; Discord allows 25 fields in total
+const METADATA_FIELD_LIMIT = 6;
+
+/// Builds one inline field per pipeline metadata pair, e.g. `Environment: staging`
+export function buildMetadataFields(metadata: Array<[string, string]>): DiscordField[] {
+  return metadata.slice(0, METADATA_FIELD_LIMIT).map(([key, value]) => ({
+    name: escapeUserText(key),
+    value: formatInfoValue(value),
+    inline: true,
+  }));
+}
+
+/// Formats `key=value` pairs as inline code spans
+export function formatTags(tags: Array<[string, string]>): string {
+  return tags.map(([key, value]) => `\`${escapeCodeSpan(`${key}=${value}`)}\``).join(' ');
+}
+
+export function buildDigestEmbed(
+  summaries: DailySummary[],
+  days: number,
+  filter: Array<[string, string]> = [],
+  config: Partial<RenderConfig> = {}
+): DiscordEmbed {
+  const renderConfig = resolveRenderConfig(config);
+  const now = renderConfig.clock.now();
+  const totalRuns = summaries.reduce((sum, day) => sum + day.runs, 0);
+  const totalFailures = summaries.reduce((sum, day) => sum + day.failures, 0);
+  const totalCost = summaries.reduce((sum, day) => sum + day.totalCost, 0);
+
+  const fields: DiscordField[] = summaries.map((day) => {
+    let value = `${day.runs} runs, ${day.failures} failed, ${formatDuration(day.totalDurationMs)} total`;
+    if (day.totalCost > 0) {
+      value += `, ${formatCost(day.totalCost)}`;
+    }
+    return { name: `📅 ${day.date}`, value, inline: false };
+  });
+
+  let description = `${totalRuns} runs, ${totalFailures} failed`;
+  if (totalCost > 0) {
+    description += ` - estimated cost ${formatCost(totalCost)}`;
+  }
+  if (filter.length > 0) {
+    description += `\nFiltered by ${formatTags(filter)}`;
+  }
+
+  return {
+    title: `📰 Pipeline Digest - last ${days} days`,
+    description: totalRuns > 0 ? description : 'No pipeline runs recorded',
+    color: totalFailures > 0 ? renderConfig.colors.warning : renderConfig.colors.success,
+    fields,
+    footer: {
+      text: `Digest generated at ${formatFooterTime(now, renderConfig)}`,
+    },
+    timestamp: now.toISOString(),
+  };
+}
+
+/// Builds the shared board listing one line per pipeline, most recently updated first
+export function buildBoardEmbed(
+  entries: BoardEntry[],
+  config: Partial<RenderConfig> = {}
+): DiscordEmbed {
+  const renderConfig = resolveRenderConfig(config);
+  const now = renderConfig.clock.now();
+  const lines: string[] = [];
+  let length = 0;
+
+  for (const [index, entry] of entries.entries()) {
+    const title = escapeUserText(entry.prTitle);
+    const repository = escapeUserText(entry.repository);
+    const line = `**${repository}#${entry.prNumber}** ${title} - ${entry.statusLine}`;
+    const overflow = `… and ${entries.length - index} more`;
+    if (length + line.length + overflow.length + 2 > EMBED_DESCRIPTION_LIMIT) {
+      lines.push(overflow);
+      break;
+    }
+    lines.push(line);
+    length += line.length + 1;
+  }
+
+  const running = entries.filter((entry) => !entry.finished).length;
+  const finished = entries.length - running;
+  const failing = entries.some((entry) => entry.statusLine.startsWith('❌'));
+  const updatedAt = formatFooterTime(now, renderConfig);
+  return {
+    title: '📋 Pipeline Board',
+    description: lines.length > 0 ? lines.join('\n') : 'No pipelines recorded',
+    color: failing ? renderConfig.colors.failure : renderConfig.colors.inProgress,
+    footer: {
+      text: `${running} running, ${finished} finished - updated at ${updatedAt}`,
+    },
+    timestamp: now.toISOString(),
+  };
+}
+
+/// Optional links and images shown on the pipeline embeds
+export interface EmbedDecorations {
+  // Link opened by clicking the embed title
+  url?: string;
+  author?: DiscordEmbedAuthor;
+  thumbnailUrl?: string;
+  imageUrl?: string;
+}
+
+// GitHub login: alphanumerics and single inner hyphens, at most 39 characters
+const GITHUB_LOGIN_PATTERN = /^[A-Za-z0-9](?:[A-Za-z0-9]|-(?=[A-Za-z0-9])){0,38}$/;
+
+/// Links the GitHub avatar of a login (`https://github.com/{login}.png`). Returns undefined
+/// when the author is not a GitHub login, e.g. a display name with spaces.
+export function buildAvatarUrl(
+  login: string,
+  serverUrl = 'https://github.com'
+): string | undefined {
+  const handle = login.trim().replace(/^@/, '');
+  if (!GITHUB_LOGIN_PATTERN.test(handle)) {
+    return undefined;
+  }
+  return `${serverUrl.replace(/\/+$/, '')}/${handle}.png`;
+}
+
+/// Applies the configured title link, author, thumbnail and image to an embed, keeping the
+/// embed's own values where the builder already set them
+export function withDecorations(embed: DiscordEmbed, decorations: EmbedDecorations): DiscordEmbed {
+  const decorated = { ...embed };
+  if (decorations.url && !decorated.url) {
+    decorated.url = decorations.url;
+  }
+  if (decorations.author?.name && !decorated.author) {
+    decorated.author = decorations.author;
+  }
+  if (decorations.thumbnailUrl && !decorated.thumbnail) {
+    decorated.thumbnail = { url: decorations.thumbnailUrl };
+  }
+  if (decorations.imageUrl && !decorated.image) {
+    decorated.image = { url: decorations.imageUrl };
+  }
+  return decorated;
+}
+
+/// Appends an SLO breach banner to the embed description for every breached status
+export function withSloBanner(
+  embed: DiscordEmbed,
+  statuses: SloStatus[],
+  windowDays: number
+): DiscordEmbed {
+  const lines = statuses
+    .filter((status) => status.breached)
+    .map(
+      (status) =>
+        `🚨 **SLO breach** - ${escapeUserText(status.repository)}: ` +
+        `${status.successRate.toFixed(1)}% success over ${windowDays} days ` +
+        `(${status.runs} runs), target ${status.targetPercent}%`
+    );
+  if (lines.length === 0) {
+    return embed;
+  }
+  const description = embed.description ? `${embed.description}\n\n` : '';
+  return { ...embed, description: `${description}${lines.join('\n')}` };
+}
+
+/// Adds the estimated time remaining next to the status field of a step-update embed
+export function withEtaField(embed: DiscordEmbed, remainingMs: number, locale = ''): DiscordEmbed {
+  const field: DiscordField = {
+    name: '⏳ ETA',
+    value:
+      remainingMs >= 1000 ? `~${formatDuration(remainingMs, locale)} remaining` : 'Almost done',
+    inline: true,
+  };
+  const fields = [...(embed.fields || [])];
+  const statusIndex = fields.findIndex((existing) => existing.name === '📋 Status');
+  fields.splice(statusIndex === -1 ? fields.length : statusIndex + 1, 0, field);
+  return { ...embed, fields };
+}
+
+/// Adds the queue and billable runner time next to the duration of a completion embed, so a
+/// slow runner queue can be told apart from slow steps
+export function withRunTimingFields(
+  embed: DiscordEmbed,
+  timing: RunTiming,
+  locale = ''
+): DiscordEmbed {
+  const timingFields: DiscordField[] = [
+    { name: '🕒 Queue Time', value: formatDuration(timing.queueMs, locale), inline: true },
+    { name: '💳 Billable Time', value: formatDuration(timing.billableMs, locale), inline: true },
+  ];
+  const fields = [...(embed.fields || [])];
+  const durationIndex = fields.findIndex((existing) => existing.name === '⏱️ Duration');
+  fields.splice(durationIndex === -1 ? fields.length : durationIndex + 1, 0, ...timingFields);
+  return { ...embed, fields };
+}
+
+/// Builds a one-line pipeline status used outside Discord (e.g. the sticky PR comment)
+export function buildStatusLine(steps: StepInfo[], finished: boolean): string {
+  const progress = getProgress(steps);
+  const stepsText = `${progress.completed}/${progress.total} steps completed`;
+  const hasFailures = steps.some((step) => step.status === 'failed');
+
+  if (hasFailures) {
+    return `❌ Failed - ${stepsText}`;
+  }
+  if (steps.some((step) => step.status === 'cancelled')) {
+    return `🚫 Cancelled - ${stepsText}`;
+  }
+  if (finished) {
+    return `✅ Completed - ${stepsText}`;
+  }
+  return `🔄 Running - ${stepsText}`;
+}
+
+/// Recognizes the embeds of messages posted by the tracker for a pipeline (including the
+/// overflow reply), as opposed to digests, boards or unrelated bot messages. Tagged messages
+/// are recognized by their machine tag, older ones by their title.
+export function isPipelineMessage(message: DiscordMessageData): boolean {
+  if (readMachineTag(message)) {
+    return true;
+  }
+  const title = message.embeds?.[0]?.title || '';
+  return /Pipeline \w+ - PR #\d+/.test(title);
+}
+
+// Identifies the pipeline message of one workflow run of a PR, written into the embed footers
+// so the tracker recognises its own messages without the state file
+export interface MachineTag {
+  repository: string;
+  prNumber: string;
+  runId: string;
+}
+
+// Zero-width characters carrying the machine tag: a frame around one character per bit
+const HIDDEN_TAG_FRAME = '\u2063';
+const HIDDEN_TAG_BITS = ['\u200b', '\u200c'];
+
+/// Formats a machine tag as `dtrk:v1:owner/repo:42:123456789`
+export function formatMachineTag(tag: MachineTag): string {
+  return `dtrk:v1:${tag.repository}:${tag.prNumber}:${tag.runId}`;
+}
+
+/// Parses a `dtrk:v1:...` machine tag; other versions and malformed tags give undefined
+export function parseMachineTag(text: string): MachineTag | undefined {
+  const match = text.match(/^dtrk:v1:([^:]+):(\d*):([^:]*)$/);
+  return match ? { repository: match[1], prNumber: match[2], runId: match[3] } : undefined;
+}
+
+/// Encodes text as invisible zero-width characters, eight per UTF-8 byte
+export function encodeHiddenText(text: string): string {
+  const bits = [...Buffer.from(text, 'utf-8')]
+    .map((byte) => byte.toString(2).padStart(8, '0'))
+    .join('');
+  const hidden = [...bits].map((bit) => HIDDEN_TAG_BITS[Number(bit)]).join('');
+  return `${HIDDEN_TAG_FRAME}${hidden}${HIDDEN_TAG_FRAME}`;
+}
+
+/// Decodes the first hidden text found in a string, if any
+export function decodeHiddenText(text: string): string | undefined {
+  const match = text.match(/\u2063([\u200b\u200c]+)\u2063/);
+  if (!match || match[1].length % 8 !== 0) {
+    return undefined;
+  }
+  const bytes: number[] = [];
+  for (let i = 0; i < match[1].length; i += 8) {
+    const bits = [...match[1].slice(i, i + 8)].map((char) => HIDDEN_TAG_BITS.indexOf(char));
+    bytes.push(parseInt(bits.join(''), 2));
+  }
+  return Buffer.from(bytes).toString('utf-8');
+}
+
+/// Appends the machine tag, hidden, to the footer of an embed
+export function withMachineTag(embed: DiscordEmbed, tag: MachineTag): DiscordEmbed {
+  const text = `${embed.footer?.text || ''}${encodeHiddenText(formatMachineTag(tag))}`;
+  return { ...embed, footer: { ...embed.footer, text } };
+}
+
+/// Reads the machine tag hidden in the footer of a message's first embed
+export function readMachineTag(message: DiscordMessageData): MachineTag | undefined {
+  const hidden = decodeHiddenText(message.embeds?.[0]?.footer?.text || '');
+  return hidden ? parseMachineTag(hidden) : undefined;
+}
+
+/// Picks the reaction mirroring the pipeline status: ❌ once a step failed, ✅ when finished,
+/// 🔄 while running
+export function statusReaction(steps: StepInfo[], finished: boolean): string {
+  if (steps.some((step) => step.status === 'failed')) {
+    return '❌';
+  }
+  if (steps.some((step) => step.status === 'cancelled')) {
+    return '🚫';
+  }
+  return finished ? '✅' : '🔄';
+}
+
+// Reactions added to a completion message so readers can rate the release
+export const FEEDBACK_REACTIONS = { up: '👍', down: '👎' } as const;
+
+/// Counts the 👍/👎 feedback reactions of a message, leaving out the bot's own
+export function countFeedback(reactions: DiscordReaction[]): { up: number; down: number } {
+  const count = (emoji: string) => {
+    const reaction = reactions.find((candidate) => candidate.emoji.name === emoji);
+    return reaction ? reaction.count - (reaction.me ? 1 : 0) : 0;
+  };
+  return { up: count(FEEDBACK_REACTIONS.up), down: count(FEEDBACK_REACTIONS.down) };
+}
+
+/// Builds the detailed step message posted into the pipeline thread
+export function buildThreadStepMessage(
+  step: StepInfo,
+  config: Partial<RenderConfig> = {}
+): DiscordMessage {
+  const emoji = StepStatusHelper.getEmoji(step.status, config.statusEmoji);
+  const statusText = step.status.charAt(0).toUpperCase() + step.status.slice(1);
+  const name = escapeUserText(step.name);
+  const lines = [`${emoji} **Step ${step.number}: ${name}** - ${statusText}${formatAttempt(step)}`];
+  if (step.logsUrl) {
+    lines.push(`└ [📜 Logs](${step.logsUrl})`);
+  }
+  const { pairs, scanReport } = splitScanReport(step.additionalInfo);
+  for (const [key, value] of pairs) {
+    lines.push(`└ **${escapeUserText(key)}:** ${formatInfoValue(value)}`);
+  }
+  if (scanReport) {
+    lines.push(`└ ${scanReport}`);
+  }
+  return { content: lines.join('\n') };
+}
+
+/// Builds the reply that escalates an unacknowledged failure
+/// Whether a pipeline message shows a failure: the title of a failed completion, or a failed
+/// step in an update embed (its `💥 Error` field or `- Failed` status)
+export function showsFailure(message: DiscordMessageData): boolean {
+  const embed = message.embeds?.[0];
+  if (!embed) {
+    return false;
+  }
+  return (
+    (embed.title || '').includes('Failed') ||
+    (embed.fields || []).some(
+      (field) => field.name.startsWith('💥 Error') || /\*\* - Failed\b/.test(field.value)
+    )
+  );
+}
+
+export function buildReminderMessage(
+  escalationTarget: string,
+  silentMinutes: number,
+  messageId: string
+): DiscordMessage {
+  const mention = escalationTarget ? `${escalationTarget} ` : '';
+  return {
+    content: `${mention}⏰ This pipeline failure has not been acknowledged for ${silentMinutes} minutes.`,
+    message_reference: { message_id: messageId },
+    allowed_mentions: buildAllowedMentions(escalationTarget),
+  };
+}
+
+/// Adds a rollback banner to the description of a deploy's pipeline embed, shown in the
+/// reader's time zone by Discord, and turns the embed orange
+export function withRollbackBanner(
+  embed: DiscordEmbed,
+  actor: string,
+  at: Date,
+  colors: ColorScheme = DEFAULT_COLOR_SCHEME
+): DiscordEmbed {
+  const seconds = Math.floor(at.getTime() / 1000);
+  const banner = `↩️ **Rolled back** at <t:${seconds}:f> by ${actor}`;
+  const description = embed.description ? `${embed.description}\n\n` : '';
+  return { ...embed, description: `${description}${banner}`, color: colors.warning };
+}
+
+/// Builds the rollback message posted as a reply to the deploy's pipeline message
+export function buildRollbackMessage(
+  run: PipelineRunRecord,
+  actor: string,
+  at: Date,
+  messageUrl: string,
+  reason?: string,
+  colors: ColorScheme = DEFAULT_COLOR_SCHEME
+): DiscordMessage {
+  const fields: DiscordField[] = [
+    { name: '🔖 Deploy', value: run.correlationId || `PR #${run.prNumber}`, inline: true },
+    { name: '👤 Rolled back by', value: actor, inline: true },
+    { name: '🔗 Original deploy', value: `[Pipeline message](${messageUrl})`, inline: true },
+  ];
+  if (reason) {
+    fields.push({ name: '📝 Reason', value: reason, inline: false });
+  }
+  return {
+    embeds: [
+      {
+        title: `↩️ Rollback - ${run.repository}`,
+        description: `**${escapeUserText(run.prTitle)}**`,
+        color: colors.warning,
+        fields,
+        timestamp: at.toISOString(),
+      },
+    ],
+    message_reference: { message_id: run.messageId as string },
+  };
+}
+
+/// Builds the reply that pings the escalation target about a stalled pipeline
+export function buildStalledPing(
+  escalationTarget: string,
+  silentMinutes: number,
+  messageId: string
+): DiscordMessage {
+  return {
+    content: `${escalationTarget} ⚠️ This pipeline has stalled - no updates for ${silentMinutes} minutes.`,
+    message_reference: { message_id: messageId },
+    allowed_mentions: buildAllowedMentions(escalationTarget),
+  };
+}
+
+// Links shown as buttons below the pipeline message
+export interface PipelineLinks {
+  runUrl?: string;
+  prUrl?: string;
+}
+
+/// Builds the action row with "View Run"/"View PR" link buttons, or no rows when no link is set
+export function buildLinkButtons(links: PipelineLinks): DiscordActionRow[] {
+  const buttons = [
+    { label: 'View Run', url: links.runUrl, emoji: '🏃' },
+    { label: 'View PR', url: links.prUrl, emoji: '🔀' },
+  ]
+    .filter((button): button is { label: string; url: string; emoji: string } => !!button.url)
+    .map((button) => ({
+      type: ComponentType.Button as const,
+      style: ButtonStyle.Link,
+      label: button.label,
+      url: button.url,
+      emoji: { name: button.emoji },
+    }));
+
+  return buttons.length > 0 ? [{ type: ComponentType.ActionRow, components: buttons }] : [];
+}
+
+/// Links the embed title to the workflow run and adds the run and PR links below the
+/// description, so the failing run is one click away. A title link set by the decorations wins.
+export function withPipelineLinks(embed: DiscordEmbed, links: PipelineLinks): DiscordEmbed {
+  const linkLine = [
+    links.runUrl ? `[🏃 Workflow run](${links.runUrl})` : undefined,
+    links.prUrl ? `[🔀 Pull request](${links.prUrl})` : undefined,
+  ]
+    .filter((link): link is string => !!link)
+    .join(' · ');
+  if (!linkLine) {
+    return embed;
+  }
+  const description = embed.description ? `${embed.description}\n${linkLine}` : linkLine;
+  return { ...embed, url: embed.url || links.runUrl || links.prUrl, description };
+}
+
+// Longest commit message subject shown on the embeds
+const COMMIT_SUBJECT_MAX_LENGTH = 100;
+
+/// Builds the "Changes" embed appended to the completion message: one line per commit with
+/// its short SHA, linked to the commit, and the first line of its message. Commits that do
+/// not fit in the description are counted in a last line.
+export function buildChangelogEmbed(
+  commits: ChangelogCommit[],
+  repository: string,
+  serverUrl?: string
+): DiscordEmbed {
+  const lines: string[] = [];
+  let length = 0;
+
+  for (const [index, commit] of commits.entries()) {
+    const url = buildCommitUrl(repository, commit.sha, serverUrl);
+    const subject = truncateText(commit.message.split('\n')[0].trim(), COMMIT_SUBJECT_MAX_LENGTH);
+    const line = `[\`${commit.sha.slice(0, 7)}\`](${url}) ${escapeUserText(subject)}`.trimEnd();
+    const overflow = `… and ${commits.length - index} more`;
+    if (length + line.length + overflow.length + 2 > EMBED_DESCRIPTION_LIMIT) {
+      lines.push(overflow);
+      break;
+    }
+    lines.push(line);
+    length += line.length + 1;
+  }
+
+  return {
+    title: `📦 Changes (${commits.length} commit${commits.length === 1 ? '' : 's'})`,
+    description: lines.join('\n'),
+    color: 0x0099ff,
+  };
+}
+
+/// Links a commit on GitHub, or on a GitHub Enterprise Server when `serverUrl` is set
+export function buildCommitUrl(
+  repository: string,
+  sha: string,
+  serverUrl = 'https://github.com'
+): string {
+  return `${serverUrl.replace(/\/+$/, '')}/${repository}/commit/${sha}`;
+}
+
+/// Adds the commit being built: its short SHA, linked to the commit when a URL is given, and
+/// the first line of its message. Compact embeds, which have no fields, get it as a line of
+/// the description instead.
+export function withCommitField(
+  embed: DiscordEmbed,
+  sha: string,
+  message?: string,
+  url?: string
+): DiscordEmbed {
+  const shortSha = `\`${sha.slice(0, 7)}\``;
+  const subject = message?.split('\n')[0].trim();
+  const value = [
+    url ? `[${shortSha}](${url})` : shortSha,
+    subject ? truncateText(subject, COMMIT_SUBJECT_MAX_LENGTH) : undefined,
+  ]
+    .filter((part): part is string => !!part)
+    .join(' ');
+
+  if (!embed.fields?.length) {
+    const line = `🔖 ${value}`;
+    return { ...embed, description: embed.description ? `${embed.description}\n${line}` : line };
+  }
+  const fields = [...embed.fields];
+  const branchIndex = fields.findIndex((existing) => existing.name === '🌿 Branch');
+  const index = branchIndex === -1 ? fields.length : branchIndex + 1;
+  fields.splice(index, 0, { name: '🔖 Commit', value, inline: false });
+  return { ...embed, fields };
+}
+
+// Users and roles pinged when a step fails
+export interface FailureMentions {
+  users: string[];
+  roles: string[];
+}
+
+/// Builds the reply pinging the configured users/roles about a failed step. allowed_mentions
+/// is limited to exactly those IDs so text in the step name can never trigger other pings.
+export function buildFailureMentionMessage(
+  mentions: FailureMentions,
+  stepName: string,
+  messageId: string,
+  codeOwners: string[] = []
+): DiscordMessage {
+  const targets = [
+    ...mentions.roles.map((id) => `<@&${id}>`),
+    ...mentions.users.map((id) => `<@${id}>`),
+  ];
+  const ownersLine =
+    codeOwners.length > 0 ? `\n📂 Code owners of the failing paths: ${codeOwners.join(', ')}` : '';
+  return {
+    content:
+      `${targets.join(' ')} ❌ Step **${escapeMarkdown(stepName)}** failed.${ownersLine}`.trim(),
+    message_reference: { message_id: messageId },
+    allowed_mentions: { parse: [], users: mentions.users, roles: mentions.roles },
+  };
+}
+
+/// Builds the direct message sent to the PR author when their pipeline fails
+export function buildFailureDirectMessage(
+  prNumber: string,
+  repository: string,
+  embed: DiscordEmbed,
+  messageUrl?: string
+): DiscordMessage {
+  const link = messageUrl ? `\n${messageUrl}` : '';
+  return {
+    content: `❌ The pipeline of your PR #${prNumber} in ${repository} failed.${link}`,
+    embeds: [embed],
+  };
+}
+
+/// Restricts pings to the user/role mentions explicitly present in `mentions`
+export function buildAllowedMentions(mentions: string): DiscordAllowedMentions {
+  const users = [...mentions.matchAll(/<@!?(\d+)>/g)].map((match) => match[1]);
+  const roles = [...mentions.matchAll(/<@&(\d+)>/g)].map((match) => match[1]);
+  return { parse: [], users, roles };
+}
+
+/// Estimates the runner cost of a run, billing started minutes like GitHub-hosted runners
+export function estimateRunCost(durationMs: number, costPerMinute: number): number {
+  const billedMinutes = Math.max(1, Math.ceil(durationMs / 60000));
+  return billedMinutes * costPerMinute;
+}
+
+/// Formats an estimated cost in dollars
+export function formatCost(cost: number, locale = ''): string {
+  return `$${formatDecimal(cost, cost < 1 ? 3 : 2, locale)}`;
+}
+
+/// Formats a duration in milliseconds as `Xm Ys`, with the locale's unit names when given
+export function formatDuration(durationMs: number, locale = ''): string {
+  const minutes = Math.floor(durationMs / 60000);
+  const seconds = Math.floor((durationMs % 60000) / 1000);
+  return formatMinutesSeconds(minutes, seconds, locale);
+}
+
+/// Rounds the completion percentage to the configured number of decimal places
+export function formatPercentage(completed: number, total: number, decimals: number): string {
+  if (total <= 0) {
+    return (0).toFixed(decimals);
+  }
+  const factor = 10 ** decimals;
+  const rounded = Math.round((completed / total) * 100 * factor) / factor;
+  return rounded.toFixed(decimals);
+}
+
+/// Formats a progress summary according to the configured display mode
+export function formatProgress(
+  progress: { completed: number; total: number },
+  config: RenderConfig,
+  stepsLabel: string
+): string {
+  const steps = `${progress.completed}/${progress.total} ${stepsLabel}`;
+  const percent = formatPercent(
+    Number(formatPercentage(progress.completed, progress.total, config.percentDecimals)),
+    config.percentDecimals,
+    config.locale
+  );
+
+  switch (config.progressDisplay) {
+    case 'steps':
+      return steps;
+    case 'percent':
+      return percent;
+    case 'bar':
+      return `${formatProgressBar(progress.completed, progress.total)} ${percent}`;
+    default:
+      return `${steps} (${percent})`;
+  }
+}
+
+// Segments of the progress bar; pipelines with fewer steps get one segment per step
+const PROGRESS_BAR_WIDTH = 10;
+
+/// Draws a progress bar such as `▰▰▰▱▱▱▱`, one segment per step up to ten segments
+export function formatProgressBar(completed: number, total: number): string {
+  const width = Math.min(Math.max(total, 1), PROGRESS_BAR_WIDTH);
+  const filled = total > 0 ? Math.min(width, Math.round((completed / total) * width)) : 0;
+  return '▰'.repeat(filled) + '▱'.repeat(width - filled);
+}
+
+// Rollout increments listed in the rollout field; older ones are summarised
+const ROLLOUT_INCREMENT_LIMIT = 10;
+
+/// Builds one field per deploy step with a rollout: a bar of the current percentage, marked
+/// as rolled back when it decreased, then each increment with its time (shown in the reader's
+/// time zone by Discord)
+export function buildRolloutFields(steps: StepInfo[]): DiscordField[] {
+  return steps
+    .filter((step) => step.rollout && step.rollout.length > 0)
+    .map((step) => {
+      const rollout = step.rollout as RolloutIncrement[];
+      const current = rollout[rollout.length - 1].percent;
+      const bar = `${formatProgressBar(current, 100)} ${current}%`;
+      const peak = Math.max(...rollout.map((increment) => increment.percent));
+      const headline = StepInfoManager.isRolledBack(step)
+        ? `⏪ ${bar} - rolled back from ${peak}%`
+        : bar;
+
+      const shown = rollout.slice(-ROLLOUT_INCREMENT_LIMIT);
+      const increments = shown.map((increment, index) => {
+        const previous = index > 0 ? shown[index - 1].percent : undefined;
+        const arrow = previous !== undefined && increment.percent < previous ? '↘️' : '↗️';
+        const seconds = Math.floor(new Date(increment.at).getTime() / 1000);
+        return `${arrow} ${increment.percent}% at <t:${seconds}:t>`;
+      });
+      const hidden = rollout.length - shown.length;
+      const lines = [headline, ...(hidden > 0 ? [`… ${hidden} earlier`] : []), ...increments];
+
+      return {
+        name: `🚦 Rollout - ${escapeUserText(step.name)}`,
+        value: lines.join('\n'),
+        inline: false,
+      };
+    });
+}
+
+/// Wraps text in a fenced code block of at most `limit` characters, newlines kept. Backtick
+/// runs inside the text are broken with zero-width spaces so they cannot close the block early.
+export function formatCodeBlock(
+  text: string,
+  limit: number = FIELD_VALUE_LIMIT,
+  language: string = ''
+): string {
+  const open = `\`\`\`${language}\n`;
+  const close = '\n```';
+  const body = text.replace(/\r\n/g, '\n').trimEnd().replace(/`(?=`)/g, '`\u200b');
+  const room = limit - open.length - close.length;
+  const fitted = body.length > room ? `${body.slice(0, room - 1)}…` : body;
+  return `${open}${fitted}${close}`;
+}
+
+/// Builds the fields of each failed step's error: the message in a code block, then the
+/// failing command and its exit code when they were reported
+export function buildFailureFields(steps: StepInfo[]): DiscordField[] {
+  return steps
+    .filter((step) => step.status === StepStatus.Failed && step.failure)
+    .flatMap((step) => {
+      const failure = step.failure as StepFailure;
+      const fields: DiscordField[] = [
+        {
+          name: `💥 Error - ${escapeUserText(step.name)}`,
+          value: formatCodeBlock(failure.error),
+          inline: false,
+        },
+      ];
+      if (failure.command) {
+        fields.push({
+          name: '⌨️ Command',
+          value: formatCodeBlock(failure.command, FIELD_VALUE_LIMIT, 'sh'),
+          inline: false,
+        });
+      }
+      if (failure.exitCode !== undefined) {
+        fields.push({ name: '🔢 Exit Code', value: `\`${failure.exitCode}\``, inline: true });
+      }
+      return fields;
+    });
+}
+
+/// Pipeline stage summarised by the plain-text content fallback
+export type ContentStage = 'started' | 'running' | 'completed' | 'stalled';
+
+// PR titles longer than this are cut in the content fallback
+const CONTENT_TITLE_LIMIT = 100;
+
+/// Builds the one-line text summary mirrored into `content` for clients and integrations that
+/// strip embeds: status, progress and the PR link (in <> so Discord does not preview it)
+export function buildContentFallback(
+  prInfo: PrInfo,
+  steps: StepInfo[],
+  totalSteps: number,
+  stage: ContentStage,
+  config: Partial<RenderConfig> = {},
+  prUrl?: string
+): string {
+  const renderConfig = { ...DEFAULT_RENDER_CONFIG, ...config };
+  const failed = steps.find((step) => step.status === StepStatus.Failed);
+  const current =
+    [...steps].reverse().find((step) => step.status === StepStatus.Running) ||
+    steps[steps.length - 1];
+
+  let status: string;
+  switch (stage) {
+    case 'started':
+      status = '🚀 Pipeline started';
+      break;
+    case 'stalled':
+      status = '⚠️ Pipeline stalled';
+      break;
+    case 'completed':
+      status = failed ? '❌ Pipeline failed' : '✅ Pipeline succeeded';
+      break;
+    default:
+      if (failed) {
+        status = `❌ ${escapeUserText(failed.name)} failed`;
+      } else if (current) {
+        const emoji = StepStatusHelper.getEmoji(current.status, renderConfig.statusEmoji);
+        status = `${emoji} ${escapeUserText(current.name)}`;
+      } else {
+        status = '🔄 Pipeline running';
+      }
+  }
+
+  const title =
+    prInfo.title.length > CONTENT_TITLE_LIMIT
+      ? `${prInfo.title.slice(0, CONTENT_TITLE_LIMIT - 1)}…`
+      : prInfo.title;
+  const total = Math.max(totalSteps, steps.length);
+  const parts = [status, `PR #${prInfo.number} ${escapeUserText(title)}`];
+  if (total > 0) {
+    const { completed } = getProgress(steps);
+    parts.push(formatProgress({ completed, total }, renderConfig, 'steps'));
+  }
+  parts.push(`<${prUrl || `https://github.com/${prInfo.repository}/pull/${prInfo.number}`}>`);
+  return parts.join(' · ');
 }
 
 // Helper function to get progress information
 function getProgress(steps: StepInfo[]): {
   completed: number;
   total: number;
-  percentage: number;
 } {
   const total = steps.length;
   const completed = steps.filter(
-    (step) => step.status === 'success' || step.status === 'failed' || step.status === 'skipped'
+    (step) =>
+      step.status === 'success' ||
+      step.status === 'failed' ||
+      step.status === 'skipped' ||
+      step.status === 'warning' ||
+      step.status === 'cancelled'
   ).length;
-  const percentage = total > 0 ? Math.round((completed / total) * 100) : 0;
 
-  return { completed, total, percentage };
+  return { completed, total };
 }
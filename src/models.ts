@@ -2,6 +2,122 @@
 export interface DiscordMessage {
   content: string;
   embeds?: DiscordEmbed[];
+  allowed_mentions?: DiscordAllowedMentions;
+  message_reference?: DiscordMessageReference;
+  attachments?: DiscordAttachment[];
+  components?: DiscordActionRow[];
+}
+
+// Message component types and button styles used by the tracker
+export enum ComponentType {
+  ActionRow = 1,
+  Button = 2,
+}
+
+export enum ButtonStyle {
+  Link = 5,
+}
+
+// Row of up to five buttons shown below the message embeds
+export interface DiscordActionRow {
+  type: ComponentType.ActionRow;
+  components: DiscordButton[];
+}
+
+// Link buttons open `url` and never send an interaction back to the bot
+export interface DiscordButton {
+  type: ComponentType.Button;
+  style: ButtonStyle;
+  label: string;
+  url: string;
+  emoji?: { name: string };
+}
+
+// Attachment metadata; `id` refers to the index of the uploaded `files[n]` part
+export interface DiscordAttachment {
+  id: number;
+  filename: string;
+  description?: string;
+}
+
+// File uploaded alongside a message as a multipart part
+export interface FileUpload {
+  filename: string;
+  content: Uint8Array;
+  contentType?: string;
+}
+
+export interface DiscordAllowedMentions {
+  parse?: Array<'roles' | 'users' | 'everyone'>;
+  roles?: string[];
+  users?: string[];
+  replied_user?: boolean;
+}
+
+export interface DiscordMessageReference {
+  message_id: string;
+}
+
+// Message as returned by GET /channels/{channel.id}/messages/{message.id}
+export interface DiscordMessageData {
+  id: string;
+  content: string;
+  timestamp: string;
+  edited_timestamp?: string | null;
+  embeds?: DiscordEmbed[];
+  reactions?: DiscordReaction[];
+  author?: DiscordUser;
+  // Set on replies, pointing at the message replied to
+  message_reference?: DiscordMessageReference;
+}
+
+// User as returned by GET /users/@me and in message authors
+export interface DiscordUser {
+  id: string;
+  username?: string;
+  bot?: boolean;
+}
+
+// Channel as returned by GET /channels/{channel.id}
+export interface DiscordChannel {
+  id: string;
+  type: number;
+  guild_id?: string;
+  name?: string;
+  // Set on threads, which inherit the permission overwrites of their parent channel
+  parent_id?: string;
+  permission_overwrites?: DiscordPermissionOverwrite[];
+}
+
+// Channel-level permission change for a role (type 0) or member (type 1); bitsets as strings
+export interface DiscordPermissionOverwrite {
+  id: string;
+  type: number;
+  allow: string;
+  deny: string;
+}
+
+// Guild role; the @everyone role has the guild's ID
+export interface DiscordRole {
+  id: string;
+  permissions: string;
+}
+
+// Guild member as returned by GET /guilds/{guild.id}/members/{user.id}
+export interface DiscordGuildMember {
+  roles: string[];
+}
+
+// Channel types without messages of their own, where the pipeline message opens a post
+export const FORUM_CHANNEL_TYPES = [15, 16];
+
+export interface DiscordReaction {
+  count: number;
+  me: boolean;
+  emoji: {
+    id: string | null;
+    name: string | null;
+  };
 }
 
 export interface DiscordEmbed {
@@ -11,7 +127,13 @@ export interface DiscordEmbed {
   fields?: DiscordField[];
   footer?: DiscordFooter;
   timestamp?: string;
+  // Makes the title a link
   url?: string;
+  author?: DiscordEmbedAuthor;
+  // Small image in the top right corner
+  thumbnail?: DiscordEmbedMedia;
+  // Large image below the fields
+  image?: DiscordEmbedMedia;
 }
 
 export interface DiscordField {
@@ -25,6 +147,16 @@ export interface DiscordFooter {
   icon_url?: string;
 }
 
+export interface DiscordEmbedAuthor {
+  name: string;
+  url?: string;
+  icon_url?: string;
+}
+
+export interface DiscordEmbedMedia {
+  url: string;
+}
+
 // Pipeline Types
 export enum StepStatus {
   Pending = 'pending',
@@ -32,6 +164,8 @@ export enum StepStatus {
   Success = 'success',
   Failed = 'failed',
   Skipped = 'skipped',
+  Warning = 'warning',
+  Cancelled = 'cancelled',
 }
 
 export interface StepInfo {
@@ -39,7 +173,74 @@ export interface StepInfo {
   name: string;
   status: StepStatus;
   additionalInfo: Array<[string, string]>;
+  startedAt?: Date;
   completedAt?: Date;
+  notes?: string[];
+  tables?: StepTable[];
+  benchmarks?: BenchmarkResult[];
+  // Phase (e.g. build, release, deploy) the step belongs to; steps are numbered per phase
+  phase?: string;
+  // GitHub handle of the step owner, pinged when this step fails
+  owner?: string;
+  // Rollout percentages reported by a canary/percentage deploy step, oldest first
+  rollout?: RolloutIncrement[];
+  // Expected duration of the step; a longer run is flagged as over budget
+  budgetMs?: number;
+  // Error reported by the `fail` action, rendered in code blocks
+  failure?: StepFailure;
+  // Times the step was reported as `retrying`; shown as its attempt number
+  retries?: number;
+  // Job logs of the step, linked next to it
+  logsUrl?: string;
+}
+
+// Why a step failed; the error keeps its newlines
+export interface StepFailure {
+  error: string;
+  command?: string;
+  exitCode?: number;
+}
+
+// One rollout percentage of a deploy step and when it was reported
+export interface RolloutIncrement {
+  percent: number;
+  at: string;
+}
+
+// Extra structured results attached to a step update
+export interface StepDetails {
+  phase?: string;
+  owner?: string;
+  tables?: StepTable[];
+  benchmarks?: BenchmarkResult[];
+  // Current rollout percentage (0-100) of a deploy step, appended to its rollout history
+  rolloutPercent?: number;
+  // Files involved in a failure, whose code owners are pinged; never persisted in the state
+  failingPaths?: string[];
+  failure?: StepFailure;
+  // Overrides a `logs_url` additional info pair
+  logsUrl?: string;
+  // Uploaded with the message update only; never persisted in the state
+  files?: FileUpload[];
+}
+
+// A single benchmark measurement; lower values are better
+export interface BenchmarkResult {
+  name: string;
+  value: number;
+  unit: string;
+}
+
+// Commit listed in the changelog embed of the completion message
+export interface ChangelogCommit {
+  sha: string;
+  message: string;
+}
+
+// Structured step result rendered as an aligned table; the first row is the header
+export interface StepTable {
+  title: string;
+  rows: string[][];
 }
 
 export interface PrInfo {
@@ -59,8 +260,20 @@ export interface PipelineState {
   branch: string;
   steps: StepInfo[];
   pipelineStartedAt: Date;
+  tags?: Array<[string, string]>;
+  metadata?: Array<[string, string]>;
+  correlationId?: string;
+  commitSha?: string;
+  commitMessage?: string;
+  threadId?: string;
+  forumThreadId?: string;
+  overflowMessageId?: string;
+  mirrorMessageIds?: Record<string, string>;
 }
 
+// Emoji replacing the default of some statuses, e.g. a custom server emoji `<:ci_pass:123>`
+export type StatusEmojiMap = Partial<Record<StepStatus, string>>;
+
 // Step Status Helper
 export class StepStatusHelper {
   static fromStr(status: string): StepStatus | string {
@@ -73,6 +286,8 @@ export class StepStatusHelper {
       case 'running':
       case 'in_progress':
       case 'in-progress':
+      case 'retrying':
+      case 'retry':
         return StepStatus.Running;
       case 'success':
       case 'passed':
@@ -84,11 +299,23 @@ export class StepStatusHelper {
       case 'skipped':
       case 'ignore':
         return StepStatus.Skipped;
+      case 'warning':
+      case 'warn':
+        return StepStatus.Warning;
+      case 'cancelled':
+      case 'canceled':
+      case 'aborted':
+        return StepStatus.Cancelled;
       default:
         return `Invalid status: ${status}`;
     }
   }
 
+  /// Whether the status reports a new attempt of the step, which runs again
+  static isRetry(status: string): boolean {
+    return ['retrying', 'retry'].includes(status.toLowerCase().trim());
+  }
+
   static isValid(status: string): boolean {
     const result = this.fromStr(status);
     return typeof result === 'string' && result.startsWith('Invalid status:') === false;
@@ -106,12 +333,20 @@ export class StepStatusHelper {
         return 0xff0000; // Red
       case StepStatus.Skipped:
         return 0xffff00; // Yellow
+      case StepStatus.Warning:
+        return 0xff8800; // Orange
+      case StepStatus.Cancelled:
+        return 0x555555; // Dark gray
       default:
         return 0x808080; // Gray
     }
   }
 
-  static getEmoji(status: StepStatus): string {
+  static getEmoji(status: StepStatus, overrides: StatusEmojiMap = {}): string {
+    const override = overrides[status];
+    if (override) {
+      return override;
+    }
     switch (status) {
       case StepStatus.Pending:
         return '⏳';
@@ -123,12 +358,86 @@ export class StepStatusHelper {
         return '❌';
       case StepStatus.Skipped:
         return '⏭️';
+      case StepStatus.Warning:
+        return '⚠️';
+      case StepStatus.Cancelled:
+        return '🚫';
       default:
         return '❓';
     }
   }
 }
 
+/// Builds a DiscordEmbed field by field, so library users are not broken when
+/// new optional embed fields are added
+export class EmbedBuilder {
+  private embed: DiscordEmbed = {};
+
+  title(title: string): this {
+    this.embed.title = title;
+    return this;
+  }
+
+  description(description: string): this {
+    this.embed.description = description;
+    return this;
+  }
+
+  color(color: number): this {
+    this.embed.color = color;
+    return this;
+  }
+
+  url(url: string): this {
+    this.embed.url = url;
+    return this;
+  }
+
+  field(name: string, value: string, inline = false): this {
+    this.embed.fields = [...(this.embed.fields || []), { name, value, inline }];
+    return this;
+  }
+
+  footer(text: string): this {
+    this.embed.footer = { text };
+    return this;
+  }
+
+  author(name: string, url?: string, iconUrl?: string): this {
+    this.embed.author = { name, url, icon_url: iconUrl };
+    return this;
+  }
+
+  thumbnail(url: string): this {
+    this.embed.thumbnail = { url };
+    return this;
+  }
+
+  image(url: string): this {
+    this.embed.image = { url };
+    return this;
+  }
+
+  timestamp(date: Date = new Date()): this {
+    this.embed.timestamp = date.toISOString();
+    return this;
+  }
+
+  build(): DiscordEmbed {
+    return { ...this.embed, fields: this.embed.fields ? [...this.embed.fields] : undefined };
+  }
+}
+
+// `group/step name` convention, e.g. "build/compile"; the group is a single word
+const STEP_GROUP_PATTERN = /^([\w.-]+)\/(.*\S.*)$/;
+
+/// Splits a `group/step name` step name into its group and the step's own name, e.g.
+/// "build/lint" into "build" and "lint". Other names are returned without a group.
+export function splitStepGroup(stepName: string): { group?: string; name: string } {
+  const match = stepName.match(STEP_GROUP_PATTERN);
+  return match ? { group: match[1], name: match[2].trim() } : { name: stepName };
+}
+
 // Step Info Manager
 export class StepInfoManager {
   static new(
@@ -145,15 +454,62 @@ export class StepInfoManager {
     };
   }
 
-  static markCompleted(step: StepInfo): void {
-    step.completedAt = new Date();
+  /// Duration between the first report of the step and its completion, if both are known
+  static duration(step: StepInfo): number | undefined {
+    if (!step.startedAt || !step.completedAt) {
+      return undefined;
+    }
+    return new Date(step.completedAt).getTime() - new Date(step.startedAt).getTime();
+  }
+
+  /// How long a finished step ran past its budget, if it has one and exceeded it
+  static overBudgetMs(step: StepInfo): number | undefined {
+    const duration = StepInfoManager.duration(step);
+    if (step.budgetMs === undefined || duration === undefined || duration <= step.budgetMs) {
+      return undefined;
+    }
+    return duration - step.budgetMs;
+  }
+
+  /// Appends a reported rollout percentage, clamped to 0-100. Repeating the current percentage
+  /// keeps the history unchanged.
+  static recordRollout(step: StepInfo, percent: number, now: Date = new Date()): void {
+    const clamped = Math.min(100, Math.max(0, percent));
+    const rollout = step.rollout || [];
+    if (rollout.length > 0 && rollout[rollout.length - 1].percent === clamped) {
+      return;
+    }
+    step.rollout = [...rollout, { percent: clamped, at: now.toISOString() }];
+  }
+
+  /// Whether the step's rollout went back below the highest percentage it reached
+  static isRolledBack(step: StepInfo): boolean {
+    const rollout = step.rollout || [];
+    if (rollout.length === 0) {
+      return false;
+    }
+    const peak = Math.max(...rollout.map((increment) => increment.percent));
+    return rollout[rollout.length - 1].percent < peak;
+  }
+
+  /// Counts a new attempt of the step. The step runs again, so it is no longer completed; its
+  /// duration keeps counting from the first attempt.
+  static recordRetry(step: StepInfo): void {
+    step.retries = (step.retries || 0) + 1;
+    step.completedAt = undefined;
+  }
+
+  static markCompleted(step: StepInfo, now: Date = new Date()): void {
+    step.completedAt = now;
   }
 
   static isCompleted(step: StepInfo): boolean {
     return (
       step.status === StepStatus.Success ||
       step.status === StepStatus.Failed ||
-      step.status === StepStatus.Skipped
+      step.status === StepStatus.Skipped ||
+      step.status === StepStatus.Warning ||
+      step.status === StepStatus.Cancelled
     );
   }
 
@@ -0,0 +1,166 @@
+import * as fs from 'fs/promises';
+import * as path from 'path';
+import type { ApiTelemetry, DiscordApiClient, FileUpload } from './discordApi';
+import { TrackerError } from './error';
+import type {
+  DiscordChannel,
+  DiscordGuildMember,
+  DiscordMessage,
+  DiscordMessageData,
+  DiscordReaction,
+  DiscordRole,
+  DiscordUser,
+} from './models';
+
+// Flag written by the `mute` action and checked by every later invocation
+export interface MuteState {
+  mutedAt: string;
+  by: string;
+  reason?: string;
+  // Notifications resume on their own after this time; unset mutes until `unmute`
+  until?: string;
+}
+
+/// File-based notification kill-switch, shared by every invocation that sees the same state
+/// dir (or mute file)
+export class MuteStore {
+  private filePath: string;
+
+  constructor(filePath?: string) {
+    this.filePath = filePath || path.join(process.cwd(), '.discord-pipeline-mute');
+  }
+
+  /// Returns the mute in effect at `now`, if any. An expired mute is ignored.
+  async active(now: Date = new Date()): Promise<MuteState | undefined> {
+    let content: string;
+    try {
+      content = await fs.readFile(this.filePath, 'utf-8');
+    } catch (error: any) {
+      if (error.code === 'ENOENT') {
+        return undefined;
+      }
+      throw TrackerError.fileSystemError(error);
+    }
+
+    let state: MuteState;
+    try {
+      state = JSON.parse(content);
+    } catch (error) {
+      throw TrackerError.jsonError(error as Error);
+    }
+    if (state.until && Date.parse(state.until) <= now.getTime()) {
+      return undefined;
+    }
+    return state;
+  }
+
+  async mute(state: MuteState): Promise<void> {
+    try {
+      await fs.writeFile(this.filePath, JSON.stringify(state, null, 2), 'utf-8');
+    } catch (error: any) {
+      throw TrackerError.fileSystemError(error);
+    }
+  }
+
+  /// Removes the flag; unmuting when not muted is not an error
+  async unmute(): Promise<void> {
+    try {
+      await fs.rm(this.filePath, { force: true });
+    } catch (error: any) {
+      throw TrackerError.fileSystemError(error);
+    }
+  }
+
+  getFilePath(): string {
+    return this.filePath;
+  }
+}
+
+/// Discord client used while notifications are muted: reads still reach Discord, but nothing
+/// is posted, edited, reacted to or deleted. Created messages get an empty ID, which the
+/// tracker treats as a pipeline without a message.
+export class MutedDiscordApi implements DiscordApiClient {
+  private api: DiscordApiClient;
+
+  constructor(api: DiscordApiClient) {
+    this.api = api;
+  }
+
+  isWebhookMode(): boolean {
+    return this.api.isWebhookMode();
+  }
+
+  getTelemetry(): ApiTelemetry {
+    return this.api.getTelemetry();
+  }
+
+  async sendMessage(_message: DiscordMessage, _threadId?: string): Promise<string> {
+    return '';
+  }
+
+  async updateMessage(
+    _messageId: string,
+    _message: DiscordMessage,
+    _files?: FileUpload[]
+  ): Promise<void> {}
+
+  async deleteMessage(_messageId: string): Promise<void> {}
+
+  getMessage(messageId: string): Promise<DiscordMessageData> {
+    return this.api.getMessage(messageId);
+  }
+
+  listMessages(before?: string, limit?: number): Promise<DiscordMessageData[]> {
+    return this.api.listMessages(before, limit);
+  }
+
+  getMessageUrl(messageId: string): Promise<string> {
+    return this.api.getMessageUrl(messageId);
+  }
+
+  async createThread(_messageId: string, _name: string): Promise<string> {
+    return '';
+  }
+
+  async createForumPost(_name: string, _message: DiscordMessage): Promise<string> {
+    return '';
+  }
+
+  forChannel(channelId: string): DiscordApiClient {
+    return new MutedDiscordApi(this.api.forChannel(channelId));
+  }
+
+  async sendDirectMessage(_userId: string, _message: DiscordMessage): Promise<string> {
+    return '';
+  }
+
+  async crosspostMessage(_messageId: string): Promise<void> {}
+
+  async pinMessage(_messageId: string): Promise<void> {}
+
+  async unpinMessage(_messageId: string): Promise<void> {}
+
+  async addReaction(_messageId: string, _emoji: string): Promise<void> {}
+
+  async removeReaction(_messageId: string, _emoji: string): Promise<void> {}
+
+  getReactions(messageId: string): Promise<DiscordReaction[]> {
+    return this.api.getReactions(messageId);
+  }
+
+  getChannel(channelId?: string): Promise<DiscordChannel> {
+    return this.api.getChannel(channelId);
+  }
+
+  getCurrentUser(): Promise<DiscordUser> {
+    return this.api.getCurrentUser();
+  }
+
+  getGuildRoles(guildId: string): Promise<DiscordRole[]> {
+    return this.api.getGuildRoles(guildId);
+  }
+
+  getGuildMember(guildId: string, userId: string): Promise<DiscordGuildMember> {
+    return this.api.getGuildMember(guildId, userId);
+  }
+}
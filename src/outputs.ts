@@ -0,0 +1,27 @@
+import * as crypto from 'crypto';
+import * as fs from 'fs';
+import * as os from 'os';
+import { TrackerError } from './error';
+
+/// Formats a single GITHUB_OUTPUT entry using the `name<<DELIMITER` heredoc syntax
+/// so values containing newlines cannot corrupt neighbouring outputs
+export function formatOutput(name: string, value: string): string {
+  const delimiter = `ghadelimiter_${crypto.randomUUID()}`;
+  // Either would end the entry early, as the runner reads the file line by line
+  if (/[\r\n]/.test(name) || name.includes('<<')) {
+    throw new TrackerError(`Invalid output name: ${JSON.stringify(name)}`, 'INVALID_OUTPUT');
+  }
+  if (value.includes(delimiter)) {
+    throw new TrackerError(`Output ${name} contains its delimiter`, 'INVALID_OUTPUT');
+  }
+  return `${name}<<${delimiter}${os.EOL}${value}${os.EOL}${delimiter}${os.EOL}`;
+}
+
+/// Appends outputs to the GITHUB_OUTPUT file without truncating entries written
+/// earlier in the same step by other tools
+export function writeOutputs(outputPath: string, outputs: Record<string, string>): void {
+  const content = Object.entries(outputs)
+    .map(([name, value]) => formatOutput(name, value))
+    .join('');
+  fs.appendFileSync(outputPath, content, 'utf-8');
+}
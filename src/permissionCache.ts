@@ -0,0 +1,77 @@
+import * as crypto from 'crypto';
+import * as fs from 'fs/promises';
+import * as path from 'path';
+import { TrackerError } from './error';
+
+// A successful preflight is trusted for this long before the channel is checked again
+export const DEFAULT_PREFLIGHT_TTL_SECONDS = 600;
+
+// Time of the last successful preflight, keyed by `preflightKey`
+type PreflightEntries = Record<string, string>;
+
+/// Derives the cache key of a token and channel. The token is hashed so it is never written
+/// to disk.
+export function preflightKey(botToken: string, channelId: string): string {
+  return crypto.createHash('sha256').update(`${botToken}\n${channelId}`).digest('hex');
+}
+
+/// File-backed cache of successful channel permission preflights, shared by every
+/// invocation of the action within a job (or across jobs when the state dir is cached)
+export class PermissionCache {
+  private filePath: string;
+  private ttlMs: number;
+
+  constructor(filePath?: string, ttlSeconds = DEFAULT_PREFLIGHT_TTL_SECONDS) {
+    this.filePath = filePath || path.join(process.cwd(), '.discord-permission-cache');
+    this.ttlMs = Math.max(ttlSeconds, 0) * 1000;
+  }
+
+  /// Loads the cached entries; a missing or corrupt cache file is treated as empty
+  private async load(): Promise<PreflightEntries> {
+    try {
+      const parsed = JSON.parse(await fs.readFile(this.filePath, 'utf-8'));
+      return typeof parsed === 'object' && parsed !== null ? parsed : {};
+    } catch {
+      return {};
+    }
+  }
+
+  private async write(entries: PreflightEntries): Promise<void> {
+    try {
+      await fs.writeFile(this.filePath, JSON.stringify(entries, null, 2), 'utf-8');
+    } catch (error: any) {
+      throw TrackerError.fileSystemError(error);
+    }
+  }
+
+  /// Checks whether a preflight for `key` succeeded within the TTL
+  async isFresh(key: string, now: Date = new Date()): Promise<boolean> {
+    const checkedAt = Date.parse((await this.load())[key] || '');
+    return !Number.isNaN(checkedAt) && now.getTime() - checkedAt < this.ttlMs;
+  }
+
+  /// Records a successful preflight for `key`, dropping expired entries of other channels
+  async record(key: string, now: Date = new Date()): Promise<void> {
+    const entries = await this.load();
+    const fresh = Object.fromEntries(
+      Object.entries(entries).filter(
+        ([, checkedAt]) => now.getTime() - Date.parse(checkedAt) < this.ttlMs
+      )
+    );
+    await this.write({ ...fresh, [key]: now.toISOString() });
+  }
+
+  /// Forgets the preflight for `key`, so the next invocation checks the channel again
+  async invalidate(key: string): Promise<void> {
+    const entries = await this.load();
+    if (!(key in entries)) {
+      return;
+    }
+    delete entries[key];
+    await this.write(entries);
+  }
+
+  getFilePath(): string {
+    return this.filePath;
+  }
+}
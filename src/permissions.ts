@@ -0,0 +1,86 @@
+import type { DiscordPermissionOverwrite, DiscordRole } from './models';
+
+// Permission bits, see https://discord.com/developers/docs/topics/permissions
+export const PERMISSIONS = {
+  ADMINISTRATOR: 1n << 3n,
+  VIEW_CHANNEL: 1n << 10n,
+  SEND_MESSAGES: 1n << 11n,
+  EMBED_LINKS: 1n << 14n,
+  READ_MESSAGE_HISTORY: 1n << 16n,
+  SEND_MESSAGES_IN_THREADS: 1n << 38n,
+} as const;
+
+// Human-readable names used in verification errors, matching the Discord client UI
+const PERMISSION_NAMES: Record<keyof typeof PERMISSIONS, string> = {
+  ADMINISTRATOR: 'Administrator',
+  VIEW_CHANNEL: 'View Channel',
+  SEND_MESSAGES: 'Send Messages',
+  EMBED_LINKS: 'Embed Links',
+  READ_MESSAGE_HISTORY: 'Read Message History',
+  SEND_MESSAGES_IN_THREADS: 'Send Messages in Threads',
+};
+
+// Channel types the pipeline message can be posted in
+export const TEXT_CHANNEL_TYPES: Record<number, string> = {
+  0: 'text channel',
+  5: 'announcement channel',
+  10: 'announcement thread',
+  11: 'public thread',
+  12: 'private thread',
+  15: 'forum channel',
+  16: 'media channel',
+};
+
+// Optional tracker features that only some channel types support
+export type ChannelFeature = 'crosspost' | 'thread';
+
+// Channel types supporting each feature: only announcement channels can crosspost, and threads
+// cannot be created inside a thread
+export const FEATURE_CHANNEL_TYPES: Record<ChannelFeature, number[]> = {
+  crosspost: [5],
+  thread: [0, 5],
+};
+
+/// Checks whether a channel type is a thread, which inherits the overwrites of its parent
+export function isThreadChannel(type: number): boolean {
+  return type === 10 || type === 11 || type === 12;
+}
+
+/// Computes a member's permissions in a channel: the @everyone role and the member's roles,
+/// then the channel overwrites for @everyone, for the member's roles and for the member
+export function computeChannelPermissions(
+  guildId: string,
+  userId: string,
+  memberRoles: string[],
+  roles: DiscordRole[],
+  overwrites: DiscordPermissionOverwrite[] = []
+): bigint {
+  const roleIds = new Set(memberRoles);
+  let permissions = roles
+    .filter((role) => role.id === guildId || roleIds.has(role.id))
+    .reduce((bits, role) => bits | BigInt(role.permissions), 0n);
+
+  if (permissions & PERMISSIONS.ADMINISTRATOR) {
+    return ~0n;
+  }
+
+  const apply = (matching: DiscordPermissionOverwrite[]) => {
+    const deny = matching.reduce((bits, overwrite) => bits | BigInt(overwrite.deny), 0n);
+    const allow = matching.reduce((bits, overwrite) => bits | BigInt(overwrite.allow), 0n);
+    permissions = (permissions & ~deny) | allow;
+  };
+  apply(overwrites.filter((overwrite) => overwrite.id === guildId));
+  apply(overwrites.filter((overwrite) => overwrite.type === 0 && roleIds.has(overwrite.id)));
+  apply(overwrites.filter((overwrite) => overwrite.type === 1 && overwrite.id === userId));
+  return permissions;
+}
+
+/// Lists the names of the required permissions missing from `permissions`
+export function missingPermissions(
+  permissions: bigint,
+  required: Array<keyof typeof PERMISSIONS>
+): string[] {
+  return required
+    .filter((name) => (permissions & PERMISSIONS[name]) === 0n)
+    .map((name) => PERMISSION_NAMES[name]);
+}
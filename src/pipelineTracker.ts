@@ -1,15 +1,109 @@
-import { DiscordApi } from './discordApi';
-import { TrackerError } from './error';
-import { buildCompletionEmbed, buildInitEmbed, buildStepUpdateEmbed } from './messageBuilder';
+import { sendTrackerAlert } from './alerting';
+import type { BoardStore } from './board';
+import { systemClock } from './clock';
 import {
+  type ApiTelemetry,
+  buildMessageUrl,
+  DiscordApi,
+  type DiscordApiClient,
+  type RetryConfig,
+  UNSUPPORTED_CHANNEL_TYPES,
+  unsupportedChannelError,
+} from './discordApi';
+import { isTrackerError, TrackerError } from './error';
+import type { EventPublisher, PipelineEvent, PipelineEventType } from './events';
+import { HOOK_BY_EVENT, type HookName, type HookRunner } from './hooks';
+import type { FeedStore } from './feeds';
+import { type CodeownersResolver, matchesBranch, type RunTiming } from './github';
+import {
+  conclusionFromSteps,
+  estimateRemainingMs,
+  evaluateSlo,
+  filterRecords,
+  type HistoryStore,
+  type PipelineRunRecord,
+  type ReleaseFeedback,
+  type SloStatus,
+  summarizeByDay,
+} from './history';
+import {
+  buildAvatarUrl,
+  buildBoardEmbed,
+  buildChangelogEmbed,
+  buildCommitUrl,
+  buildCompletionEmbed,
+  buildContentFallback,
+  buildDigestEmbed,
+  buildFailureDirectMessage,
+  buildFailureMentionMessage,
+  buildInitEmbed,
+  buildLinkButtons,
+  buildMetadataFields,
+  buildReminderMessage,
+  buildRollbackMessage,
+  buildStalledEmbed,
+  buildStalledPing,
+  buildStatusLine,
+  buildStepDetailEmbeds,
+  buildStepUpdateEmbed,
+  buildTagsField,
+  buildThreadStepMessage,
+  countFeedback,
+  diffEmbeds,
+  embedLength,
+  enforceEmbedLimits,
+  estimateRunCost,
+  FEEDBACK_REACTIONS,
+  findLogsUrl,
+  isPipelineMessage,
+  LOGS_URL_KEY,
+  type MachineTag,
+  type ContentStage,
+  type EmbedDecorations,
+  type FailureMentions,
+  type PipelineLinks,
+  type RenderConfig,
+  sanitizeEmbed,
+  showsFailure,
+  splitMessageEmbeds,
+  statusReaction,
+  stripVolatileFields,
+  readMachineTag,
+  withCommitField,
+  withDecorations,
+  withEtaField,
+  withMachineTag,
+  withPipelineLinks,
+  withRollbackBanner,
+  withRunTimingFields,
+  withSloBanner,
+} from './messageBuilder';
+import {
+  type ChangelogCommit,
+  type DiscordChannel,
+  type DiscordEmbed,
   type DiscordMessage,
+  type FileUpload,
+  FORUM_CHANNEL_TYPES,
   type PipelineState,
   type PrInfo,
+  type StepDetails,
   type StepInfo,
   StepInfoManager,
   StepStatus,
   StepStatusHelper,
+  splitStepGroup,
 } from './models';
+import { MutedDiscordApi } from './mute';
+import { type PermissionCache, preflightKey } from './permissionCache';
+import {
+  type ChannelFeature,
+  computeChannelPermissions,
+  FEATURE_CHANNEL_TYPES,
+  isThreadChannel,
+  missingPermissions,
+  TEXT_CHANNEL_TYPES,
+} from './permissions';
 
 // Internal pipeline state interface (matches file storage format)
 export interface InternalPipelineState {
@@ -21,9 +115,27 @@ export interface InternalPipelineState {
 	branch: string;
 	steps: StepInfo[];
 	pipelineStartedAt: Date;
+	tags?: Array<[string, string]>;
+	// Pipeline-level key/value details, e.g. environment or version, shown as inline fields
+	metadata?: Array<[string, string]>;
+	// Identifies the deploy for a later `rollback`, e.g. a release version
+	correlationId?: string;
+	// Commit being built, shown on the init and completion embeds
+	commitSha?: string;
+	commitMessage?: string;
+	threadId?: string;
+	// Post opened by the pipeline message in a forum channel; the message lives in its thread
+	forumThreadId?: string;
+	// Reply holding the fields that did not fit in the pipeline message
+	overflowMessageId?: string;
+	// Copy of the pipeline message in each mirror channel, keyed by channel ID
+	mirrorMessageIds?: Record<string, string>;
+	// Embeds of the pipeline message as last rendered, without footers and timestamps
+	renderedEmbeds?: DiscordEmbed[];
 }
 
-// Storage interface for dependency injection
+// Where the tracker keeps the pipeline state between invocations. A backend only needs the
+// save/load/clear methods; the optional ones add validation, backups and load warnings.
 export interface Storage {
 	savePipelineState(state: InternalPipelineState): Promise<void>;
 	clearPipelineState(): Promise<void>;
@@ -31,6 +143,8 @@ export interface Storage {
 	validateState?(state: InternalPipelineState): boolean;
 	createBackup?(): Promise<void>;
 	restoreFromBackup?(): Promise<InternalPipelineState | null>;
+	// Non-fatal problems met while loading (e.g. a legacy state file read), cleared once taken
+	takeWarnings?(): TrackerWarning[];
 }
 
 // In-memory storage implementation (for testing and simple use cases)
@@ -78,22 +192,267 @@ export class InMemoryStorage implements Storage {
   }
 }
 
+// Optional behaviour toggles for the tracker
+export interface TrackerOptions {
+  // Discord client used instead of the HTTP one, e.g. an `InMemoryDiscordApi` in tests
+  api?: DiscordApiClient;
+  render?: Partial<RenderConfig>;
+  alertWebhookUrl?: string;
+  history?: HistoryStore;
+  retry?: Partial<RetryConfig>;
+  // HTTP(S) proxy for Discord and alert requests, overriding HTTPS_PROXY/HTTP_PROXY
+  proxyUrl?: string;
+  // Successful channel preflights, reused by later invocations until they expire
+  permissionCache?: PermissionCache;
+  board?: BoardStore;
+  // Atom feed mirroring every completed pipeline
+  feed?: FeedStore;
+  // NATS subject or Redis channel receiving a JSON event at each lifecycle point
+  events?: EventPublisher;
+  // Shell commands run with the same event as JSON on stdin; failures only raise warnings
+  hooks?: HookRunner;
+  // Create a thread off the pipeline message and post every step update into it
+  threads?: boolean;
+  // Success rate target (percent) over a rolling window, evaluated from the history store
+  slo?: { targetPercent: number; windowDays: number };
+  // Show the time remaining, estimated from the step durations in the history store
+  eta?: boolean;
+  // Nest steps named `group/step name` under a `group` header, like phases. Off by default,
+  // since it changes the identity of steps whose names already contain a slash.
+  stepGroups?: boolean;
+  // Fetches the queue and billable runner time shown on the completion embed
+  runTiming?: () => Promise<RunTiming>;
+  // Base URL of commit links, for GitHub Enterprise Server; defaults to https://github.com
+  githubServerUrl?: string;
+  // Commits listed in a "Changes" embed appended to the completion message
+  changelog?: ChangelogCommit[];
+  // Workflow run written with the repository and PR into the machine tag of the embed footers
+  runId?: string;
+  // When no state is stored, look for the run's message among this many recent channel
+  // messages before posting a new one
+  dedupeSearchLimit?: number;
+  // "View Run"/"View PR" buttons added to the pipeline message; edits keep them in place
+  links?: PipelineLinks;
+  // Title link, author, thumbnail and image shown on the pipeline embeds
+  decorations?: EmbedDecorations;
+  // Show the GitHub avatar of the PR author as the thumbnail, unless decorations set one
+  authorAvatar?: boolean;
+  // Thumbnail shown instead of the GitHub avatar of the PR author
+  authorAvatarUrl?: string;
+  // Pinged once, in a reply to the pipeline message, when a step turns failed
+  failureMentions?: FailureMentions;
+  // Lowercase GitHub handle to Discord user ID; a failed step pings its mapped owner
+  ownerMap?: Record<string, string>;
+  // Expected duration in milliseconds of each step, by step name
+  stepBudgets?: Record<string, number>;
+  // Resolves the CODEOWNERS of a failed step's `failingPaths`, pinged through ownerMap
+  codeowners?: CodeownersResolver;
+  // DM the failure embed to the PR author (resolved through ownerMap) on the first failure
+  dmAuthor?: boolean;
+  // Mirror the pipeline status as a 🔄/❌/✅ reaction, visible in channel previews
+  reactions?: boolean;
+  // Add 👍/👎 reactions to the completion message, collected later by `collectFeedback`
+  feedbackPoll?: boolean;
+  // Channels receiving a copy of the pipeline message, kept in sync with every edit. Threads,
+  // overflow replies, reactions and pins stay in the primary channel.
+  mirrorChannelIds?: string[];
+  // Pin the pipeline message while it runs and unpin it on completion
+  pin?: boolean;
+  // Branches (a trailing `*` matches a prefix) whose completion message is crossposted
+  // from the announcement channel to the servers following it
+  crosspostBranches?: string[];
+  // Kill-switch set by the `mute` action: state and history are still recorded, but nothing
+  // is posted to or edited on Discord
+  muted?: boolean;
+}
+
+// Channel the pipeline is posted to, as confirmed by `verifyChannel`
+export interface ChannelVerification {
+  channelId: string;
+  // e.g. "text channel" or "public thread"
+  channelType: string;
+  name?: string;
+}
+
+// Non-fatal problem exposed through the `warnings` output, e.g. a feature skipped because the
+// channel does not support it
+export interface TrackerWarning {
+  code: string;
+  message: string;
+  channelId?: string;
+  channelType?: string;
+}
+
+// Optional pipeline-level details supplied at initialization
+export interface InitOptions {
+  tags?: Array<[string, string]>;
+  // Key/value details (environment, version, trigger) shown as inline fields on every embed
+  metadata?: Array<[string, string]>;
+  // Identifies the deploy so a later `rollback` can find its message in the history
+  correlationId?: string;
+  // Commit being built, shown as a short SHA linked to GitHub with its message subject
+  commitSha?: string;
+  commitMessage?: string;
+}
+
+// Rollback recorded by `rollbackDeploy`
+export interface RollbackResult {
+  // The deploy's original pipeline message, now carrying the rollback banner
+  originalMessageId: string;
+  // Reply posted under the original message
+  messageId: string;
+}
+
 /// Main pipeline tracker that orchestrates Discord notifications
 export class PipelineTracker {
-  private api: DiscordApi;
+  // Client of the pipeline message's channel: the forum post's thread in a forum channel
+  private api: DiscordApiClient;
+  // Configured channel, looked up by ID since `api` may target a forum post instead
+  private channelId: string;
+  private mirrorApis: Map<string, DiscordApiClient>;
   private storage: Storage;
+  private options: TrackerOptions;
   private messageId: string | undefined;
   private steps: StepInfo[];
   private prInfo: PrInfo | undefined;
   private pipelineStartedAt: Date | undefined;
+  private tags: Array<[string, string]>;
+  private metadata: Array<[string, string]>;
+  private correlationId: string | undefined;
+  private commit: { sha: string; message?: string } | undefined;
+  private threadId: string | undefined;
+  private forumThreadId: string | undefined;
+  private overflowMessageId: string | undefined;
+  private mirrorMessageIds: Record<string, string>;
+  private renderedEmbeds: DiscordEmbed[] | undefined;
+  private deliveryError: string | undefined;
+  private warnings: TrackerWarning[];
+  // Configured channel metadata, looked up once per invocation by `lookupChannel`
+  private channel: DiscordChannel | undefined;
+  private preflightKey: string;
 
-  constructor(botToken: string, channelId: string, storage?: Storage) {
-    this.api = new DiscordApi(botToken, channelId);
+  constructor(botToken: string, channelId: string, storage?: Storage, options: TrackerOptions = {}) {
+    this.api = options.api || new DiscordApi(botToken, channelId, options.retry, options.proxyUrl);
+    this.channelId = channelId;
+    if (options.muted) {
+      this.api = new MutedDiscordApi(this.api);
+      console.log('🔇 Notifications are muted - recording state without posting to Discord');
+    }
+    // Derived from the primary client, so they share its retries, proxy, muting and telemetry
+    this.mirrorApis = new Map();
+    if (options.mirrorChannelIds?.length && this.api.isWebhookMode()) {
+      console.warn('⚠️  Mirror channels require a bot token - a webhook posts to its own channel');
+    } else {
+      for (const mirrorChannelId of options.mirrorChannelIds || []) {
+        if (mirrorChannelId !== channelId) {
+          this.mirrorApis.set(mirrorChannelId, this.api.forChannel(mirrorChannelId));
+        }
+      }
+    }
+    this.mirrorMessageIds = {};
+    this.warnings = [];
+    this.preflightKey = preflightKey(botToken, channelId);
     this.storage = storage || new InMemoryStorage();
+    this.options = options;
     this.messageId = undefined;
     this.steps = [];
     this.prInfo = undefined;
     this.pipelineStartedAt = undefined;
+    this.tags = [];
+    this.metadata = [];
+  }
+
+  /// Checks that the bot can reach the channel before anything is posted. With a permission
+  /// cache, a recent successful check is reused instead of calling Discord again.
+  async preflight(now: Date = new Date()): Promise<void> {
+    const cache = this.options.permissionCache;
+    if (cache && (await cache.isFresh(this.preflightKey, now))) {
+      console.log('✅ Channel access verified recently, skipping preflight');
+      return;
+    }
+
+    const verification = await this.verifyChannel();
+    console.log(`✅ Channel access verified (${verification.channelType})`);
+
+    if (cache) {
+      try {
+        await cache.record(this.preflightKey, now);
+      } catch (error) {
+        console.warn('⚠️  Failed to cache the preflight result');
+        console.warn('   Error:', error instanceof Error ? error.message : String(error));
+      }
+    }
+  }
+
+  /// Checks that the channel exists, can hold the pipeline message and that the bot may post
+  /// embeds there, throwing an actionable error otherwise. A webhook can always post to its
+  /// own channel, so only its existence is checked.
+  async verifyChannel(): Promise<ChannelVerification> {
+    let channel: DiscordChannel;
+    try {
+      channel = await this.api.getChannel(this.channelId);
+    } catch (error) {
+      const notFound =
+        isTrackerError(error, 'MESSAGE_NOT_FOUND') || isTrackerError(error, 'INVALID_CHANNEL_ID');
+      if (notFound) {
+        throw TrackerError.invalidChannelId(
+          'channel not found. Check discord_channel_id and that the bot was added to the server'
+        );
+      }
+      if (isTrackerError(error, 'UNAUTHORIZED')) {
+        throw TrackerError.unauthorized('Invalid bot token. Check discord_bot_token');
+      }
+      if (isTrackerError(error, 'FORBIDDEN')) {
+        throw TrackerError.forbidden(
+          'The bot cannot see the channel. Grant it the View Channel permission'
+        );
+      }
+      throw error;
+    }
+
+    const channelType = TEXT_CHANNEL_TYPES[channel.type];
+    if (!channelType) {
+      throw unsupportedChannelError(channel);
+    }
+
+    if (channel.guild_id && !this.api.isWebhookMode()) {
+      const missing = await this.missingChannelPermissions(channel, channel.guild_id);
+      if (missing.length > 0) {
+        throw TrackerError.forbidden(
+          `The bot is missing permissions in channel ${channel.id}: ${missing.join(', ')}`
+        );
+      }
+    }
+
+    return { channelId: channel.id, channelType, name: channel.name };
+  }
+
+  /// Computes which of the permissions the tracker relies on the bot lacks in a channel
+  private async missingChannelPermissions(
+    channel: DiscordChannel,
+    guildId: string
+  ): Promise<string[]> {
+    const user = await this.api.getCurrentUser();
+    const [member, roles, overwriteSource] = await Promise.all([
+      this.api.getGuildMember(guildId, user.id),
+      this.api.getGuildRoles(guildId),
+      isThreadChannel(channel.type) && channel.parent_id
+        ? this.api.getChannel(channel.parent_id)
+        : Promise.resolve(channel),
+    ]);
+    const permissions = computeChannelPermissions(
+      guildId,
+      user.id,
+      member.roles,
+      roles,
+      overwriteSource.permission_overwrites
+    );
+    return missingPermissions(permissions, [
+      'VIEW_CHANNEL',
+      isThreadChannel(channel.type) ? 'SEND_MESSAGES_IN_THREADS' : 'SEND_MESSAGES',
+      'EMBED_LINKS',
+      'READ_MESSAGE_HISTORY',
+    ]);
   }
 
   /// Initializes the pipeline tracking
@@ -102,7 +461,8 @@ export class PipelineTracker {
     prTitle: string,
     author: string,
     repository: string,
-    branch: string
+    branch: string,
+    initOptions: InitOptions = {}
   ): Promise<void> {
     this.prInfo = {
       number: prNumber,
@@ -112,40 +472,66 @@ export class PipelineTracker {
       branch: branch,
     };
 
-    this.pipelineStartedAt = new Date();
+    this.pipelineStartedAt = this.now();
+    this.tags = initOptions.tags || [];
+    this.metadata = initOptions.metadata || [];
+    this.correlationId = initOptions.correlationId;
+    this.commit = initOptions.commitSha
+      ? { sha: initOptions.commitSha, message: initOptions.commitMessage }
+      : undefined;
 
-    const embed = buildInitEmbed(prNumber, prTitle, author, repository, branch);
+    const embed = this.withPipelineContext(
+      this.withCommit(
+        buildInitEmbed(prNumber, prTitle, author, repository, branch, this.options.render)
+      )
+    );
     const message: DiscordMessage = {
-      content: '',
+      content: this.messageContent('started'),
       embeds: [embed],
     };
+    const components = buildLinkButtons(this.options.links || {});
+    if (components.length > 0) {
+      message.components = components;
+    }
 
+    const existingId = await this.findExistingMessage();
     try {
-      const messageId = await this.api.sendMessage(message);
-      this.messageId = messageId;
-      console.log(`✅ Pipeline tracking initialized - Discord message created (ID: ${messageId})`);
+      if (existingId) {
+        // Adopted as is; the next update re-renders it from this state
+        this.messageId = existingId;
+        console.log(`♻️  Adopted the existing Discord message of this run (ID: ${existingId})`);
+      } else {
+        const messageId = await this.postInitialMessage(message, `PR #${prNumber}: ${prTitle}`);
+        this.messageId = messageId;
+        this.renderedEmbeds = [stripVolatileFields(embed)];
+        console.log(
+          `✅ Pipeline tracking initialized - Discord message created (ID: ${messageId})`
+        );
+
+        if (this.options.threads && (await this.channelSupports('thread'))) {
+          await this.createPipelineThread(messageId);
+        }
+        await this.syncStatusReaction(undefined, statusReaction(this.steps, false));
+        await this.setPinned(true);
+      }
     } catch (error) {
       console.error('❌ Failed to create initial Discord message');
       console.error('   Error:', error instanceof Error ? error.message : String(error));
       console.warn('⚠️  Pipeline will continue without Discord notifications');
       console.warn('   Check Discord API status, bot permissions, and channel accessibility');
       
+      await this.reportFailure('initPipeline', error);
+
       // Continue without Discord - set messageId to undefined so we know Discord is unavailable
       this.messageId = undefined;
     }
+    if (!existingId) {
+      await this.createMirrorMessages(message);
+    }
 
     // Save state - always attempt this even if Discord initialization failed
-    const state: InternalPipelineState = {
-    	messageId: this.messageId || '',
-    	prNumber: parseInt(prNumber, 10) || 0,
-    	prTitle: prTitle,
-    	author: author,
-    	repository: repository,
-    	branch: branch,
-    	steps: this.steps,
-    	pipelineStartedAt: this.pipelineStartedAt,
-    };
-    
+    const state = this.buildState(this.prInfo, this.pipelineStartedAt);
+
     try {
       await this.storage.savePipelineState(state);
       console.log('✅ Pipeline state saved successfully');
@@ -155,10 +541,15 @@ export class PipelineTracker {
       console.error('   Impact: Subsequent step updates may fail or be inconsistent');
       console.error('   Recommendation: Check file system permissions and storage configuration');
       
+      await this.reportFailure('savePipelineState', error);
+
       // This is critical - if we can't save state, subsequent operations will likely fail
       // But we don't throw here to allow the pipeline to attempt to continue
       console.warn('⚠️  Continuing with degraded functionality - state persistence disabled');
     }
+
+    await this.recordBoardEntry(false);
+    await this.publishEvent('pipeline.started');
   }
 
   /// Updates a step in the pipeline
@@ -167,7 +558,8 @@ export class PipelineTracker {
     totalSteps: number,
     stepName: string,
     status: string,
-    additionalInfo: [string, string][]
+    additionalInfo: [string, string][],
+    details: StepDetails = {}
   ): Promise<void> {
     // Load state from storage first (critical for GitHub Actions)
     try {
@@ -193,44 +585,102 @@ export class PipelineTracker {
       throw TrackerError.invalidStatus(status);
     }
 
-    // Find or create step
-    let step = this.steps.find((s) => s.number === stepNumber);
+    // A `logs_url` pair becomes the step's logs link instead of a line of additional info
+    const logsUrl = details.logsUrl || findLogsUrl(additionalInfo);
+    const info = logsUrl ? additionalInfo.filter(([key]) => key !== LOGS_URL_KEY) : additionalInfo;
+
+    const previousReaction = statusReaction(this.steps, false);
+    const hadFailures = this.steps.some((s) => s.status === StepStatus.Failed);
+
+    // With step groups, a `group/step name` nests the step under its group like a phase,
+    // unless a phase is set
+    const grouped =
+      this.options.stepGroups && !details.phase ? splitStepGroup(stepName) : { name: stepName };
+
+    // Find or create step; each phase numbers its own steps
+    const phase = details.phase || grouped.group;
+    let step = this.steps.find((s) => s.number === stepNumber && s.phase === phase);
+    const newlyFailed = stepStatus === StepStatus.Failed && step?.status !== StepStatus.Failed;
     if (step) {
       // Update existing step
-      step.name = stepName;
+      step.name = grouped.name;
       step.status = stepStatus;
-      step.additionalInfo = additionalInfo;
+      step.additionalInfo = info;
     } else {
       // Create new step
-      step = StepInfoManager.new(stepNumber, stepName, stepStatus, additionalInfo);
+      step = StepInfoManager.new(stepNumber, grouped.name, stepStatus, info);
+      step.startedAt = this.now();
+      if (phase) {
+        step.phase = phase;
+      }
       this.steps.push(step);
     }
+    if (details.owner) {
+      step.owner = details.owner.replace(/^@/, '');
+    }
+    // Kept in the state, so later invocations without the input still flag the step
+    const budgetMs = this.options.stepBudgets?.[step.name];
+    if (budgetMs !== undefined) {
+      step.budgetMs = budgetMs;
+    }
+    step.tables = details.tables?.length ? details.tables : undefined;
+    step.benchmarks = details.benchmarks?.length ? details.benchmarks : undefined;
+    step.failure = details.failure;
+    // Kept from earlier updates, so the link stays once the step finishes
+    if (logsUrl) {
+      step.logsUrl = logsUrl;
+    }
+    if (StepStatusHelper.isRetry(status)) {
+      StepInfoManager.recordRetry(step);
+    }
+    if (details.rolloutPercent !== undefined) {
+      StepInfoManager.recordRollout(step, details.rolloutPercent, this.now());
+    }
 
     // Mark step as completed if it's finished
     if (
       stepStatus === StepStatus.Success ||
       stepStatus === StepStatus.Failed ||
-      stepStatus === StepStatus.Skipped
+      stepStatus === StepStatus.Skipped ||
+      stepStatus === StepStatus.Warning ||
+      stepStatus === StepStatus.Cancelled
     ) {
-      StepInfoManager.markCompleted(step);
+      StepInfoManager.markCompleted(step, this.now());
+    }
+
+    // Rendered before saving so the state records what the message is about to show
+    const rendered = this.prInfo
+      ? await this.renderStepMessage(this.prInfo, stepNumber, totalSteps)
+      : undefined;
+    const changes =
+      rendered && this.renderedEmbeds ? diffEmbeds(this.renderedEmbeds, rendered.main) : undefined;
+    if (rendered) {
+      this.renderedEmbeds = rendered.main.map(stripVolatileFields);
     }
 
     // Save state BEFORE Discord API calls to ensure consistency (Requirements 4.1, 4.2)
     if (this.prInfo && this.pipelineStartedAt) {
-      const state: InternalPipelineState = {
-        messageId: this.messageId || '',
-        prNumber: parseInt(this.prInfo.number, 10) || 0,
-        prTitle: this.prInfo.title,
-        author: this.prInfo.author,
-        repository: this.prInfo.repository,
-        branch: this.prInfo.branch,
-        steps: this.steps,
-        pipelineStartedAt: this.pipelineStartedAt,
-      };
-      
+      const state = this.buildState(this.prInfo, this.pipelineStartedAt);
+
       try {
         await this.saveStateWithValidation(state);
         console.log(`✅ Pipeline state saved before Discord API call for step ${stepNumber}`);
+        await this.recordBoardEntry(false);
+        await this.publishEvent(
+          'step.updated',
+          {
+            step: {
+              number: step.number,
+              name: step.name,
+              status: step.status,
+              phase: step.phase,
+              overBudgetMs: StepInfoManager.overBudgetMs(step),
+              retries: step.retries,
+            },
+          },
+          // Runs once per failure, not on every later update of the failed step
+          newlyFailed ? ['on_fail'] : []
+        );
       } catch (error) {
         console.error('❌ Critical: Failed to save pipeline state before Discord API call');
         console.error('   Step details:', { stepNumber, stepName, status });
@@ -238,7 +688,8 @@ export class PipelineTracker {
         console.error('   Impact: Cannot proceed with Discord update due to state persistence failure');
         console.warn('⚠️  Aborting Discord update to maintain state consistency');
         console.warn('   Recommendation: Check file system permissions and available disk space');
-        
+        await this.reportFailure('savePipelineState', error);
+
         // Don't proceed with Discord API call if state saving failed
         // This ensures state consistency as per requirement 4.2
         return;
@@ -251,24 +702,37 @@ export class PipelineTracker {
 
     // Update Discord message with graceful degradation
     // State is already saved, so Discord failures won't affect consistency
-    if (this.prInfo && this.pipelineStartedAt) {
-      const embed = buildStepUpdateEmbed(
-        this.prInfo.number,
-        this.prInfo.title,
-        this.steps,
-        stepNumber,
-        totalSteps
-      );
+    if (rendered) {
+      const { main, overflow } = rendered;
 
       const message: DiscordMessage = {
-        content: '',
-        embeds: [embed],
+        content: this.messageContent('running', totalSteps),
+        embeds: main,
       };
 
       if (this.messageId) {
         try {
-          await this.api.updateMessage(this.messageId, message);
-          console.log(`✅ Discord message updated successfully for step ${stepNumber}: ${stepName}`);
+          if (changes?.length === 0 && !details.files?.length) {
+            // Only the footer/timestamp would change; not worth an edit (and a rate limit slot)
+            console.log(`⏭️  Discord edit skipped for step ${stepNumber} - nothing visible changed`);
+          } else {
+            console.debug(
+              `Embed changes for step ${stepNumber}: ${changes?.join(', ') || 'no previous render'}`
+            );
+            await this.api.updateMessage(this.messageId, message, details.files);
+            console.log(
+              `✅ Discord message updated successfully for step ${stepNumber}: ${stepName}`
+            );
+          }
+          await this.syncOverflowMessage(overflow);
+          await this.postToThread(buildThreadStepMessage(step, this.options.render));
+          if (newlyFailed) {
+            await this.pingFailureMentions(step, details.failingPaths);
+            if (!hadFailures) {
+              await this.dmAuthorOnFailure(main[0]);
+            }
+          }
+          await this.syncStatusReaction(previousReaction, statusReaction(this.steps, false));
         } catch (error) {
           console.error('❌ Discord API unavailable - step update failed but state remains consistent');
           console.error('   Step details:', { stepNumber, stepName, status });
@@ -276,20 +740,67 @@ export class PipelineTracker {
           console.warn('⚠️  Pipeline tracking continues locally with consistent state');
           console.warn('   Users will not see real-time updates until Discord API is restored');
           console.warn('   Consider checking Discord API status and bot permissions');
-          
+          await this.reportFailure('updateStep', error);
+          await this.forgetRenderedEmbeds();
+
           // State was already saved before the Discord call, so consistency is maintained
           // This satisfies requirement 4.2: "WHEN Discord API calls fail THEN the local state SHALL remain consistent"
         }
       } else {
         console.warn('⚠️  No Discord message ID available - cannot update Discord embed');
+        this.deliveryError = 'No Discord message available';
         console.warn('   This may indicate the initial message creation failed');
       }
+      if (changes?.length !== 0 || details.files?.length) {
+        await this.updateMirrorMessages(message, details.files);
+      }
     } else {
       console.warn('⚠️  Missing PR info or pipeline start time - cannot update Discord message');
       console.warn('   This may indicate incomplete pipeline initialization');
     }
   }
 
+  /// Appends a note to an existing step without changing its status or timings
+  async annotateStep(stepNumber: number, note: string): Promise<void> {
+    await this.loadState();
+
+    const step = this.steps.find((s) => s.number === stepNumber);
+    if (!step) {
+      throw TrackerError.invalidStepNumber(stepNumber);
+    }
+    if (!this.prInfo || !this.pipelineStartedAt) {
+      throw new TrackerError(
+        'Cannot annotate a step before the pipeline is initialized',
+        'PIPELINE_NOT_INITIALIZED'
+      );
+    }
+
+    step.notes = [...(step.notes || []), note];
+    const totalSteps = Math.max(...this.steps.map((s) => s.number));
+    const { main, overflow } = await this.renderStepMessage(this.prInfo, stepNumber, totalSteps);
+    this.renderedEmbeds = main.map(stripVolatileFields);
+    await this.saveStateWithValidation(this.buildState(this.prInfo, this.pipelineStartedAt));
+    console.log(`✅ Note added to step ${stepNumber}: ${step.name}`);
+
+    const content = this.messageContent('running', totalSteps);
+    await this.updateMirrorMessages({ content, embeds: main });
+    if (!this.messageId) {
+      console.warn('⚠️  No Discord message ID available - note saved to state only');
+      return;
+    }
+
+    try {
+      await this.api.updateMessage(this.messageId, { content, embeds: main });
+      console.log(`✅ Discord message updated with note for step ${stepNumber}`);
+      await this.syncOverflowMessage(overflow);
+    } catch (error) {
+      console.error('❌ Discord API unavailable - note saved to state but not displayed');
+      console.error('   Error:', error instanceof Error ? error.message : String(error));
+      await this.reportFailure('annotateStep', error);
+      await this.forgetRenderedEmbeds();
+    }
+  }
+
   /// Completes the pipeline
   async completePipeline(): Promise<void> {
     // Load state from storage first (critical for GitHub Actions)
@@ -304,36 +815,76 @@ export class PipelineTracker {
 
     if (this.prInfo && this.pipelineStartedAt) {
       const totalSteps = this.steps.length > 0 ? this.steps.length : 1;
-      const embed = buildCompletionEmbed(
-        this.prInfo.number,
-        this.prInfo.title,
-        this.steps,
-        totalSteps,
-        this.pipelineStartedAt
+      const embed = this.withPipelineContext(
+        await this.withSlo(
+          await this.withRunTiming(
+            this.withCommit(
+              buildCompletionEmbed(
+                this.prInfo.number,
+                this.prInfo.title,
+                this.steps,
+                totalSteps,
+                this.pipelineStartedAt,
+                this.options.render
+              )
+            )
+          )
+        )
       );
+      const changelog = this.options.changelog?.length
+        ? [
+            buildChangelogEmbed(
+              this.options.changelog,
+              this.prInfo.repository,
+              this.options.githubServerUrl
+            ),
+          ]
+        : [];
+      const { main, overflow } = splitMessageEmbeds([embed, ...changelog]);
 
       const message: DiscordMessage = {
-        content: '',
-        embeds: [embed],
+        content: this.messageContent('completed', totalSteps),
+        embeds: main,
       };
 
       if (this.messageId) {
         try {
           await this.api.updateMessage(this.messageId, message);
           console.log('✅ Pipeline completion message sent to Discord successfully');
+          await this.syncOverflowMessage(overflow);
+          await this.syncStatusReaction(
+            statusReaction(this.steps, false),
+            statusReaction(this.steps, true)
+          );
+          await this.crosspostCompletion();
+          await this.addFeedbackReactions();
+          await this.setPinned(false);
         } catch (error) {
           console.error('❌ Discord API unavailable - completion notification failed');
           console.error('   Error:', error instanceof Error ? error.message : String(error));
           console.warn('⚠️  Pipeline completed successfully but Discord notification failed');
           console.warn('   Users will not see the completion status in Discord');
+          await this.reportFailure('completePipeline', error);
         }
       } else {
         console.warn('⚠️  No Discord message ID available for completion update');
+        this.deliveryError = 'No Discord message available';
       }
+      await this.updateMirrorMessages(message);
     } else {
       console.warn('⚠️  Missing PR info or pipeline start time for completion');
     }
 
+    await this.recordHistory();
+    await this.recordFeedEntry();
+    await this.recordBoardEntry(true);
+    await this.publishEvent('pipeline.completed', {
+      conclusion: conclusionFromSteps(this.steps),
+      durationMs: this.pipelineStartedAt
+        ? this.now().getTime() - this.pipelineStartedAt.getTime()
+        : undefined,
+    });
+
     // Clear state - always attempt this even if Discord updates failed
     try {
       await this.storage.clearPipelineState();
@@ -345,9 +896,900 @@ export class PipelineTracker {
     }
   }
 
+  /// Marks the steps that had not finished as cancelled and completes the pipeline, for a run
+  /// killed before its `fail` or `complete` step ran. Returns false, leaving everything as is,
+  /// when no pipeline is stored or it belongs to another repository than `repository`.
+  async cancelPipeline(repository?: string): Promise<boolean> {
+    await this.loadState();
+    if (!this.prInfo || !this.pipelineStartedAt) {
+      console.log('ℹ️  No stored pipeline to cancel');
+      return false;
+    }
+    if (repository && this.prInfo.repository.toLowerCase() !== repository.toLowerCase()) {
+      console.log(`ℹ️  Stored pipeline belongs to ${this.prInfo.repository} - not cancelled`);
+      return false;
+    }
+
+    const now = this.now();
+    for (const step of this.steps) {
+      if (!StepInfoManager.isCompleted(step)) {
+        step.status = StepStatus.Cancelled;
+        StepInfoManager.markCompleted(step, now);
+      }
+    }
+    // completePipeline reads the state back, so the cancelled steps are saved first
+    await this.saveStateWithValidation(this.buildState(this.prInfo, this.pipelineStartedAt));
+    await this.completePipeline();
+    return true;
+  }
+
+  /// Posts a digest of the runs recorded in the history store over the last `days` days,
+  /// optionally restricted to runs matching every `filter` pair
+  async postDigest(days: number, filter: Array<[string, string]> = []): Promise<void> {
+    if (!this.options.history) {
+      throw new TrackerError(
+        'Digest requires a history file to be configured',
+        'HISTORY_NOT_CONFIGURED'
+      );
+    }
+
+    const records = filterRecords(await this.options.history.loadSince(days), filter);
+    let embed = buildDigestEmbed(summarizeByDay(records), days, filter, this.options.render);
+    if (this.options.slo) {
+      const sloRecords = filterRecords(
+        await this.options.history.loadSince(this.options.slo.windowDays),
+        filter
+      );
+      embed = withSloBanner(
+        embed,
+        evaluateSlo(sloRecords, this.options.slo.targetPercent),
+        this.options.slo.windowDays
+      );
+    }
+    embed = sanitizeEmbed(embed);
+    const messageId = await this.api.sendMessage({ content: '', embeds: [embed] });
+    console.log(`✅ Pipeline digest posted to Discord (ID: ${messageId})`);
+  }
+
+  /// Deletes the pipeline messages posted by the bot that are older than `maxAgeDays`, scanning
+  /// at most `maxPages` pages of 100 channel messages. Returns the number of deleted messages.
+  async cleanupMessages(
+    maxAgeDays: number,
+    maxPages = 10,
+    now: Date = new Date()
+  ): Promise<number> {
+    const cutoff = now.getTime() - maxAgeDays * 24 * 60 * 60 * 1000;
+    const bot = await this.api.getCurrentUser();
+    let deleted = 0;
+    let before: string | undefined;
+
+    for (let page = 0; page < maxPages; page++) {
+      const messages = await this.api.listMessages(before);
+      for (const message of messages) {
+        const stale = new Date(message.timestamp).getTime() < cutoff;
+        if (stale && message.author?.id === bot.id && isPipelineMessage(message)) {
+          await this.api.deleteMessage(message.id);
+          deleted++;
+        }
+      }
+      if (messages.length < 100) {
+        break;
+      }
+      before = messages[messages.length - 1].id;
+    }
+
+    console.log(`🧹 Deleted ${deleted} pipeline message(s) older than ${maxAgeDays} days`);
+    return deleted;
+  }
+
+  /// Renders every board entry into a single message, editing `messageId` when given and
+  /// posting a new board message otherwise. Returns the board message ID.
+  async flushBoard(messageId?: string): Promise<string> {
+    if (!this.options.board) {
+      throw new TrackerError(
+        'Board requires a board file to be configured',
+        'BOARD_NOT_CONFIGURED'
+      );
+    }
+
+    const entries = await this.options.board.load();
+    const message: DiscordMessage = {
+      content: '',
+      embeds: [buildBoardEmbed(entries, this.options.render)],
+    };
+    if (messageId) {
+      await this.api.updateMessage(messageId, message);
+    } else {
+      messageId = await this.api.sendMessage(message);
+    }
+    console.log(`✅ Pipeline board updated with ${entries.length} pipelines (ID: ${messageId})`);
+    return messageId;
+  }
+
+  /// Returns the canonical URL of the pipeline message, or undefined when no message exists
+  /// or the URL cannot be resolved
+  async getMessageUrl(): Promise<string | undefined> {
+    if (!this.messageId) {
+      await this.loadState();
+    }
+    if (!this.messageId) {
+      return undefined;
+    }
+
+    try {
+      // The channel metadata of this invocation gives the guild without another request
+      const channel = await this.lookupChannel();
+      if (channel) {
+        const channelId = this.forumThreadId || this.channelId;
+        return buildMessageUrl(channel.guild_id, channelId, this.messageId);
+      }
+      return await this.api.getMessageUrl(this.messageId);
+    } catch (error) {
+      console.warn('⚠️  Could not resolve the Discord message URL');
+      console.warn('   Error:', error instanceof Error ? error.message : String(error));
+      return undefined;
+    }
+  }
+
+  /// Returns the Discord API traffic counters of this invocation; the mirror and thread clients
+  /// are derived from the primary one and count into the same counters
+  getTelemetry(): ApiTelemetry {
+    return this.api.getTelemetry();
+  }
+
+  /// Returns the non-fatal warnings recorded during this invocation
+  getWarnings(): TrackerWarning[] {
+    return [...this.warnings];
+  }
+
+  /// Returns the ID of the pipeline message, if one was created
+  getMessageId(): string | undefined {
+    return this.messageId;
+  }
+
+  /// Reports whether every notification of this invocation reached Discord
+  getDeliveryStatus(): { delivered: boolean; error?: string } {
+    return this.deliveryError
+      ? { delivered: false, error: this.deliveryError }
+      : { delivered: true };
+  }
+
+  /// Returns the pull request details of the tracked pipeline
+  getPrInfo(): PrInfo | undefined {
+    return this.prInfo;
+  }
+
+  /// Returns the steps recorded so far
+  getSteps(): StepInfo[] {
+    return this.steps;
+  }
+
+  /// Reads the 👍/👎 reactions of a completion message and records them on the matching run in
+  /// the history store. The counts exclude the bot's own reactions.
+  async collectFeedback(messageId: string): Promise<ReleaseFeedback> {
+    if (!this.options.history) {
+      throw new TrackerError(
+        'Collecting feedback requires a history file to be configured',
+        'HISTORY_NOT_CONFIGURED'
+      );
+    }
+
+    const message = await this.api.getMessage(messageId);
+    const feedback: ReleaseFeedback = {
+      ...countFeedback(message.reactions || []),
+      collectedAt: new Date().toISOString(),
+    };
+    if (await this.options.history.recordFeedback(messageId, feedback)) {
+      console.log(`✅ Feedback recorded: ${feedback.up} 👍, ${feedback.down} 👎`);
+    } else {
+      console.warn(`⚠️  No recorded run has message ${messageId} - feedback not stored`);
+    }
+    return feedback;
+  }
+
+  /// Marks a previous deploy as rolled back: its pipeline message, found in the history by the
+  /// correlation ID given at `init`, gets a rollback banner and a linked reply is posted
+  /// under it, so the channel keeps an accurate audit trail
+  async rollbackDeploy(
+    correlationId: string,
+    actor: string,
+    reason?: string
+  ): Promise<RollbackResult> {
+    if (!this.options.history) {
+      throw new TrackerError(
+        'Rolling back requires a history file to be configured',
+        'HISTORY_NOT_CONFIGURED'
+      );
+    }
+    const record = await this.options.history.findByCorrelationId(correlationId);
+    if (!record?.messageId) {
+      throw new TrackerError(
+        `No recorded deploy with correlation ID ${correlationId}`,
+        'DEPLOY_NOT_FOUND'
+      );
+    }
+
+    const rolledBackAt = this.now();
+    const colors = this.options.render?.colors;
+    const original = await this.api.getMessage(record.messageId);
+    const [first, ...rest] = original.embeds || [];
+    if (first) {
+      await this.api.updateMessage(record.messageId, {
+        content: original.content,
+        embeds: [withRollbackBanner(first, actor, rolledBackAt, colors), ...rest],
+      });
+    }
+
+    const messageUrl = await this.api.getMessageUrl(record.messageId);
+    const messageId = await this.api.sendMessage(
+      buildRollbackMessage(record, actor, rolledBackAt, messageUrl, reason, colors)
+    );
+    await this.options.history.recordRollback(correlationId, {
+      at: rolledBackAt.toISOString(),
+      by: actor,
+      reason,
+      messageId,
+    });
+    console.log(`✅ Deploy ${correlationId} marked as rolled back (message ID: ${messageId})`);
+    return { originalMessageId: record.messageId, messageId };
+  }
+
+  /// Posts a reminder reply when a failed pipeline message has not received any reaction
+  /// within `windowMinutes`, once per message. Returns true when a reminder was sent.
+  async remindUnacknowledged(
+    messageId: string | undefined,
+    windowMinutes: number,
+    escalationTarget: string
+  ): Promise<boolean> {
+    await this.loadState();
+    messageId = messageId || this.messageId;
+    if (!messageId) {
+      throw TrackerError.missingRequiredInput('message_id');
+    }
+
+    // The stored steps also know failures reported by `fail` before any `complete`; a
+    // scheduled workflow without the state reads the failure off the embed instead
+    const message = await this.api.getMessage(messageId);
+    const failed =
+      messageId === this.messageId
+        ? this.steps.some((step) => step.status === StepStatus.Failed)
+        : showsFailure(message);
+    if (!failed) {
+      console.log('ℹ️  Pipeline message does not show a failure - no reminder needed');
+      return false;
+    }
+
+    const lastUpdate = new Date(message.edited_timestamp || message.timestamp);
+    const silentMinutes = Math.floor((this.now().getTime() - lastUpdate.getTime()) / 60000);
+    if (silentMinutes < windowMinutes) {
+      console.log(`ℹ️  Failure is ${silentMinutes} minutes old - reminder window not reached yet`);
+      return false;
+    }
+
+    // The bot's own reactions do not count as an acknowledgement
+    const reactions = await this.api.getReactions(messageId);
+    const acknowledgements = reactions.reduce(
+      (sum, reaction) => sum + reaction.count - (reaction.me ? 1 : 0),
+      0
+    );
+    if (acknowledgements > 0) {
+      console.log('ℹ️  Failure has been acknowledged with a reaction - no reminder needed');
+      return false;
+    }
+
+    if (await this.hasReminderReply(messageId)) {
+      console.log('ℹ️  A reminder was already posted for this failure - not reminding again');
+      return false;
+    }
+
+    await this.api.sendMessage(buildReminderMessage(escalationTarget, silentMinutes, messageId));
+    console.log(`✅ Reminder posted for unacknowledged failure (message ID: ${messageId})`);
+    return true;
+  }
+
+  /// Whether a bot reply among the channel's latest messages already reminds of the failure.
+  /// Webhooks cannot list messages, so there every scheduled run reminds again.
+  private async hasReminderReply(messageId: string): Promise<boolean> {
+    if (this.api.isWebhookMode()) {
+      return false;
+    }
+    const recent = await this.api.listMessages();
+    return recent.some(
+      (reply) => reply.message_reference?.message_id === messageId && reply.author?.bot
+    );
+  }
+
+  /// Marks the pipeline message as stalled and, when an escalation target is given,
+  /// replies to it with a ping
+  async markStalled(silentMinutes: number, escalationTarget: string): Promise<void> {
+    await this.loadState();
+    if (!this.prInfo || !this.messageId) {
+      throw new TrackerError(
+        'Cannot mark a pipeline as stalled before it is initialized',
+        'PIPELINE_NOT_INITIALIZED'
+      );
+    }
+
+    const embed = this.withPipelineContext(
+      buildStalledEmbed(
+        this.prInfo.number,
+        this.prInfo.title,
+        this.steps,
+        silentMinutes,
+        this.options.render
+      )
+    );
+    const content = this.messageContent('stalled');
+    await this.api.updateMessage(this.messageId, { content, embeds: [embed] });
+    await this.updateMirrorMessages({ content, embeds: [embed] });
+    console.log(`⚠️  Pipeline marked as stalled after ${silentMinutes} minutes without updates`);
+
+    // The next step update must replace the stalled embed even if the steps are unchanged
+    this.renderedEmbeds = [stripVolatileFields(embed)];
+    if (this.pipelineStartedAt) {
+      await this.saveStateWithValidation(this.buildState(this.prInfo, this.pipelineStartedAt));
+    }
+
+    if (escalationTarget) {
+      await this.api.sendMessage(buildStalledPing(escalationTarget, silentMinutes, this.messageId));
+    }
+  }
+
+  /// Drops the render saved before a Discord edit that failed: the message never showed it, so
+  /// the next update must not skip its edit as unchanged
+  private async forgetRenderedEmbeds(): Promise<void> {
+    if (!this.prInfo || !this.pipelineStartedAt) {
+      return;
+    }
+    this.renderedEmbeds = undefined;
+    try {
+      await this.saveStateWithValidation(this.buildState(this.prInfo, this.pipelineStartedAt));
+    } catch (error) {
+      console.warn('⚠️  Failed to clear the rendered embeds from the pipeline state');
+      console.warn('   Error:', error instanceof Error ? error.message : String(error));
+    }
+  }
+
+  /// Keeps the overflow reply in sync with the embeds split off the pipeline message: creates,
+  /// edits or deletes it, persisting its ID whenever it changes
+  private async syncOverflowMessage(overflow: DiscordEmbed[]): Promise<void> {
+    if (!this.messageId || !this.prInfo || !this.pipelineStartedAt) {
+      return;
+    }
+    try {
+      if (overflow.length > 0 && this.overflowMessageId) {
+        try {
+          await this.api.updateMessage(this.overflowMessageId, { content: '', embeds: overflow });
+          return;
+        } catch (error) {
+          // A reply deleted by hand is posted again below
+          if (!isTrackerError(error, 'MESSAGE_NOT_FOUND')) {
+            throw error;
+          }
+          this.overflowMessageId = undefined;
+        }
+      }
+      if (overflow.length > 0) {
+        this.overflowMessageId = await this.api.sendMessage({
+          content: '',
+          embeds: overflow,
+          message_reference: { message_id: this.messageId },
+        });
+      } else if (this.overflowMessageId) {
+        await this.api.deleteMessage(this.overflowMessageId).catch((error) => {
+          // Already deleted by hand
+          if (!isTrackerError(error, 'MESSAGE_NOT_FOUND')) {
+            throw error;
+          }
+        });
+        this.overflowMessageId = undefined;
+      } else {
+        return;
+      }
+      await this.saveStateWithValidation(this.buildState(this.prInfo, this.pipelineStartedAt));
+    } catch (error) {
+      console.warn('⚠️  Failed to update the overflow message - some step details may be missing');
+      console.warn('   Error:', error instanceof Error ? error.message : String(error));
+    }
+  }
+
+  /// Posts the initial message, opening a post named `postName` in forum channels. Channels
+  /// known not to hold messages are rejected with their type, before Discord's opaque 400.
+  private async postInitialMessage(message: DiscordMessage, postName: string): Promise<string> {
+    const channel = await this.lookupChannel();
+    if (channel && UNSUPPORTED_CHANNEL_TYPES[channel.type]) {
+      throw unsupportedChannelError(channel);
+    }
+    if (!channel || !FORUM_CHANNEL_TYPES.includes(channel.type)) {
+      return this.api.sendMessage(message);
+    }
+    const threadId = await this.api.createForumPost(postName, message);
+    this.useForumThread(threadId);
+    console.log(`🗂️  Forum post created (thread ID: ${threadId})`);
+    return threadId;
+  }
+
+  /// Sends message operations to the thread of the forum post holding the pipeline message.
+  /// The thread client is derived once per invocation, however often the state is loaded.
+  private useForumThread(threadId: string): void {
+    if (!threadId || threadId === this.forumThreadId) {
+      return;
+    }
+    this.forumThreadId = threadId;
+    this.api = this.api.forChannel(threadId);
+  }
+
+  /// Looks the configured channel up once per invocation; webhooks and failed lookups leave it
+  /// unset, and the message is then posted as usual
+  private async lookupChannel(): Promise<DiscordChannel | undefined> {
+    try {
+      if (!this.channel && !this.api.isWebhookMode()) {
+        this.channel = await this.api.getChannel(this.channelId);
+      }
+    } catch (error) {
+      console.debug(
+        `Channel lookup failed: ${error instanceof Error ? error.message : String(error)}`
+      );
+    }
+    return this.channel;
+  }
+
+  /// Creates the pipeline thread off the initial message; failures leave threads disabled
+  private async createPipelineThread(messageId: string): Promise<void> {
+    if (!this.prInfo) {
+      return;
+    }
+    try {
+      this.threadId = await this.api.createThread(
+        messageId,
+        `PR #${this.prInfo.number}: ${this.prInfo.title}`
+      );
+      console.log(`✅ Pipeline thread created (ID: ${this.threadId})`);
+    } catch (error) {
+      console.warn('⚠️  Failed to create pipeline thread - step details will not be posted');
+      console.warn('   Error:', error instanceof Error ? error.message : String(error));
+    }
+  }
+
+  /// Pins or unpins the pipeline message when pinning is enabled
+  private async setPinned(pinned: boolean): Promise<void> {
+    if (!this.options.pin || !this.messageId) {
+      return;
+    }
+    try {
+      if (pinned) {
+        await this.api.pinMessage(this.messageId);
+      } else {
+        await this.api.unpinMessage(this.messageId);
+      }
+    } catch (error) {
+      console.warn(`⚠️  Failed to ${pinned ? 'pin' : 'unpin'} the pipeline message`);
+      console.warn('   Error:', error instanceof Error ? error.message : String(error));
+    }
+  }
+
+  /// Crossposts the completion message when the pipeline ran on a crosspost branch
+  private async crosspostCompletion(): Promise<void> {
+    const branches = this.options.crosspostBranches || [];
+    if (!this.messageId || !this.prInfo || !matchesBranch(this.prInfo.branch, branches)) {
+      return;
+    }
+    if (!(await this.channelSupports('crosspost'))) {
+      return;
+    }
+    try {
+      await this.api.crosspostMessage(this.messageId);
+      console.log(`📣 Completion message crossposted for branch ${this.prInfo.branch}`);
+    } catch (error) {
+      console.warn('⚠️  Failed to crosspost the completion message');
+      console.warn('   Make sure the target channel is an announcement channel');
+      console.warn('   Error:', error instanceof Error ? error.message : String(error));
+    }
+  }
+
+  /// Checks from the channel metadata whether the primary channel supports a feature, recording
+  /// a warning when it does not. Without metadata (webhooks, failed lookups) the feature is
+  /// attempted as usual.
+  private async channelSupports(feature: ChannelFeature): Promise<boolean> {
+    const channel = await this.lookupChannel();
+    if (!channel || FEATURE_CHANNEL_TYPES[feature].includes(channel.type)) {
+      return true;
+    }
+
+    const { id, type } = channel;
+    const channelType = TEXT_CHANNEL_TYPES[type] || `type ${type} channel`;
+    const what = feature === 'crosspost' ? 'Crossposting' : 'Thread creation';
+    this.recordWarning({
+      code: 'UNSUPPORTED_CHANNEL_FEATURE',
+      message: `${what} skipped - not supported in a ${channelType} (channel ${id})`,
+      channelId: id,
+      channelType,
+    });
+    return false;
+  }
+
+  /// Logs a non-fatal warning and keeps it for the `warnings` output, once per message
+  private recordWarning(warning: TrackerWarning): void {
+    if (this.warnings.some((existing) => existing.message === warning.message)) {
+      return;
+    }
+    this.warnings.push(warning);
+    console.warn(`⚠️  ${warning.message}`);
+  }
+
+  /// Posts a copy of the pipeline message in every mirror channel. A mirror that fails is
+  /// skipped for the rest of the pipeline without affecting the primary channel.
+  private async createMirrorMessages(message: DiscordMessage): Promise<void> {
+    for (const [channelId, api] of this.mirrorApis) {
+      try {
+        this.mirrorMessageIds[channelId] = await api.sendMessage(message);
+        console.log(`✅ Pipeline message mirrored to channel ${channelId}`);
+      } catch (error) {
+        console.warn(`⚠️  Failed to mirror the pipeline message to channel ${channelId}`);
+        console.warn('   Error:', error instanceof Error ? error.message : String(error));
+      }
+    }
+  }
+
+  /// Applies an edit of the pipeline message to its copy in every mirror channel
+  private async updateMirrorMessages(
+    message: DiscordMessage,
+    files?: FileUpload[]
+  ): Promise<void> {
+    for (const [channelId, messageId] of Object.entries(this.mirrorMessageIds)) {
+      const api = this.mirrorApis.get(channelId);
+      if (!api) {
+        continue;
+      }
+      try {
+        await api.updateMessage(messageId, message, files);
+      } catch (error) {
+        console.warn(`⚠️  Failed to update the mirrored message in channel ${channelId}`);
+        console.warn('   Error:', error instanceof Error ? error.message : String(error));
+      }
+    }
+  }
+
+  /// Adds the 👍/👎 feedback poll to the completion message
+  private async addFeedbackReactions(): Promise<void> {
+    if (!this.options.feedbackPoll || !this.messageId) {
+      return;
+    }
+    try {
+      await this.api.addReaction(this.messageId, FEEDBACK_REACTIONS.up);
+      await this.api.addReaction(this.messageId, FEEDBACK_REACTIONS.down);
+      console.log('🗳️  Feedback reactions added to the completion message');
+    } catch (error) {
+      this.recordReactionWarning(`${FEEDBACK_REACTIONS.up}/${FEEDBACK_REACTIONS.down}`, error);
+    }
+  }
+
+  /// Records a reaction Discord refused, telling an emoji it does not know from other failures
+  private recordReactionWarning(emoji: string, error: unknown): void {
+    const detail = error instanceof Error ? error.message : String(error);
+    this.recordWarning({
+      code: /unknown emoji/i.test(detail) ? 'INVALID_EMOJI' : 'REACTION_FAILED',
+      message: `Reaction ${emoji} could not be added - ${detail}`,
+    });
+  }
+
+  /// Swaps the status reaction on the pipeline message when the pipeline status changed
+  private async syncStatusReaction(previous: string | undefined, next: string): Promise<void> {
+    if (!this.options.reactions || !this.messageId || previous === next) {
+      return;
+    }
+    try {
+      if (previous) {
+        await this.api.removeReaction(this.messageId, previous);
+      }
+      await this.api.addReaction(this.messageId, next);
+    } catch (error) {
+      this.recordReactionWarning(next, error);
+    }
+  }
+
+  /// Replies to the pipeline message pinging the configured failure mentions, the failed
+  /// step's owner and the code owners of the failing paths, if any
+  private async pingFailureMentions(step: StepInfo, failingPaths: string[] = []): Promise<void> {
+    const configured = this.options.failureMentions || { users: [], roles: [] };
+    const codeOwners = await this.resolveCodeowners(failingPaths);
+    const ownerIds: string[] = [];
+    for (const handle of [step.owner, ...codeOwners]) {
+      if (!handle) {
+        continue;
+      }
+      const id = this.options.ownerMap?.[handle.replace(/^@/, '').toLowerCase()];
+      if (id) {
+        ownerIds.push(id);
+      } else {
+        this.recordWarning({
+          code: 'MENTION_SKIPPED',
+          message: `No Discord user mapped to ${handle} in owner_map - not pinged`,
+        });
+      }
+    }
+    const mentions: FailureMentions = {
+      users: [...new Set([...configured.users, ...ownerIds])],
+      roles: configured.roles,
+    };
+    if (
+      !this.messageId ||
+      mentions.users.length + mentions.roles.length + codeOwners.length === 0
+    ) {
+      return;
+    }
+    try {
+      await this.api.sendMessage(
+        buildFailureMentionMessage(mentions, step.name, this.messageId, codeOwners)
+      );
+    } catch (error) {
+      console.warn('⚠️  Failed to ping the failure mentions');
+      console.warn('   Error:', error instanceof Error ? error.message : String(error));
+    }
+  }
+
+  /// Code owners of the failing paths, or none when CODEOWNERS cannot be read
+  private async resolveCodeowners(failingPaths: string[]): Promise<string[]> {
+    if (!this.options.codeowners || failingPaths.length === 0) {
+      return [];
+    }
+    try {
+      return await this.options.codeowners.ownersOf(failingPaths);
+    } catch (error) {
+      console.warn('⚠️  Could not resolve the code owners of the failing paths');
+      console.warn('   Error:', error instanceof Error ? error.message : String(error));
+      return [];
+    }
+  }
+
+  /// Sends the failure embed to the PR author in a DM, if enabled and the author is mapped
+  private async dmAuthorOnFailure(embed: DiscordEmbed): Promise<void> {
+    if (!this.options.dmAuthor || !this.prInfo) {
+      return;
+    }
+    const authorId = this.options.ownerMap?.[this.prInfo.author.replace(/^@/, '').toLowerCase()];
+    if (!authorId) {
+      this.recordWarning({
+        code: 'MENTION_SKIPPED',
+        message: `No Discord user mapped to ${this.prInfo.author} - failure DM skipped`,
+      });
+      return;
+    }
+    try {
+      const messageUrl = await this.getMessageUrl();
+      await this.api.sendDirectMessage(
+        authorId,
+        buildFailureDirectMessage(this.prInfo.number, this.prInfo.repository, embed, messageUrl)
+      );
+      console.log(`✅ Failure sent to ${this.prInfo.author} in a direct message`);
+    } catch (error) {
+      console.warn('⚠️  Failed to send the failure direct message');
+      console.warn('   Error:', error instanceof Error ? error.message : String(error));
+    }
+  }
+
+  /// Posts a message into the pipeline thread, if one exists
+  private async postToThread(message: DiscordMessage): Promise<void> {
+    if (!this.threadId) {
+      return;
+    }
+    try {
+      await this.api.sendMessage(message, this.threadId);
+    } catch (error) {
+      console.warn('⚠️  Failed to post step details to the pipeline thread');
+      console.warn('   Error:', error instanceof Error ? error.message : String(error));
+    }
+  }
+
+  /// Adds the SLO breach banner for this repository, counting the run being completed
+  private async withSlo(embed: DiscordEmbed): Promise<DiscordEmbed> {
+    const { slo, history } = this.options;
+    if (!slo || !history || !this.prInfo) {
+      return embed;
+    }
+
+    let statuses: SloStatus[];
+    try {
+      const repository = this.prInfo.repository;
+      const records = (await history.loadSince(slo.windowDays)).filter(
+        (record) => record.repository === repository
+      );
+      const current = { repository, conclusion: conclusionFromSteps(this.steps) };
+      statuses = evaluateSlo([...records, current as PipelineRunRecord], slo.targetPercent);
+    } catch (error) {
+      console.warn('⚠️  Could not evaluate the SLO from the history store');
+      console.warn('   Error:', error instanceof Error ? error.message : String(error));
+      return embed;
+    }
+    return withSloBanner(embed, statuses, slo.windowDays);
+  }
+
+  /// Adds the estimated time remaining, when enabled and the history knows the steps
+  private async withEta(embed: DiscordEmbed): Promise<DiscordEmbed> {
+    const { eta, history } = this.options;
+    if (!eta || !history || !this.prInfo) {
+      return embed;
+    }
+
+    let remainingMs: number | undefined;
+    try {
+      const records = await history.load();
+      remainingMs = estimateRemainingMs(records, this.prInfo.repository, this.steps, this.now());
+    } catch (error) {
+      console.warn('⚠️  Could not estimate the time remaining from the history store');
+      console.warn('   Error:', error instanceof Error ? error.message : String(error));
+      return embed;
+    }
+    if (remainingMs === undefined) {
+      return embed;
+    }
+    return withEtaField(embed, remainingMs, this.options.render?.locale);
+  }
+
+  /// Adds the commit being built, when known, linked to the repository on GitHub
+  private withCommit(embed: DiscordEmbed): DiscordEmbed {
+    if (!this.commit || !this.prInfo) {
+      return embed;
+    }
+    const { repository } = this.prInfo;
+    const url = buildCommitUrl(repository, this.commit.sha, this.options.githubServerUrl);
+    return withCommitField(embed, this.commit.sha, this.commit.message, url);
+  }
+
+  /// Adds the queue and billable runner time of the workflow run, when configured
+  private async withRunTiming(embed: DiscordEmbed): Promise<DiscordEmbed> {
+    if (!this.options.runTiming) {
+      return embed;
+    }
+
+    try {
+      const timing = await this.options.runTiming();
+      return withRunTimingFields(embed, timing, this.options.render?.locale);
+    } catch (error) {
+      console.warn('⚠️  Could not fetch the queue and billable time of the workflow run');
+      console.warn('   Error:', error instanceof Error ? error.message : String(error));
+      return embed;
+    }
+  }
+
+  /// Records this pipeline's status line on the board, if one is configured
+  private async recordBoardEntry(finished: boolean): Promise<void> {
+    if (!this.options.board || !this.prInfo) {
+      return;
+    }
+
+    try {
+      await this.options.board.upsert({
+        repository: this.prInfo.repository,
+        prNumber: parseInt(this.prInfo.number, 10) || 0,
+        prTitle: this.prInfo.title,
+        statusLine: buildStatusLine(this.steps, finished),
+        finished,
+        updatedAt: new Date().toISOString(),
+      });
+    } catch (error) {
+      console.error('❌ Failed to record pipeline status on the board');
+      console.error('   Error:', error instanceof Error ? error.message : String(error));
+    }
+  }
+
+  /// Appends the finished run to the history store, if one is configured
+  private async recordHistory(): Promise<void> {
+    if (!this.options.history || !this.prInfo || !this.pipelineStartedAt) {
+      return;
+    }
+
+    const completedAt = this.now();
+    const durationMs = completedAt.getTime() - this.pipelineStartedAt.getTime();
+    const costPerMinute = this.options.render?.costPerMinute || 0;
+
+    try {
+      await this.options.history.append({
+        repository: this.prInfo.repository,
+        prNumber: parseInt(this.prInfo.number, 10) || 0,
+        prTitle: this.prInfo.title,
+        author: this.prInfo.author,
+        branch: this.prInfo.branch,
+        conclusion: conclusionFromSteps(this.steps),
+        startedAt: this.pipelineStartedAt.toISOString(),
+        completedAt: completedAt.toISOString(),
+        durationMs,
+        estimatedCost: costPerMinute > 0 ? estimateRunCost(durationMs, costPerMinute) : undefined,
+        tags: this.tags,
+        messageId: this.messageId,
+        correlationId: this.correlationId,
+        steps: this.steps.map((step) => ({
+          name: step.name,
+          status: step.status,
+          startedAt: step.startedAt ? new Date(step.startedAt).toISOString() : undefined,
+          completedAt: step.completedAt ? new Date(step.completedAt).toISOString() : undefined,
+          benchmarks: step.benchmarks,
+          budgetMs: step.budgetMs,
+          overBudgetMs: StepInfoManager.overBudgetMs(step),
+          retries: step.retries,
+        })),
+      });
+      console.log('✅ Pipeline run recorded in history');
+    } catch (error) {
+      console.error('❌ Failed to record pipeline run in history');
+      console.error('   Error:', error instanceof Error ? error.message : String(error));
+    }
+  }
+
+  /// Appends the completed pipeline to the Atom feed, if one is configured
+  private async recordFeedEntry(): Promise<void> {
+    if (!this.options.feed || !this.prInfo) {
+      return;
+    }
+
+    try {
+      await this.options.feed.append({
+        repository: this.prInfo.repository,
+        prNumber: parseInt(this.prInfo.number, 10) || 0,
+        prTitle: this.prInfo.title,
+        author: this.prInfo.author,
+        branch: this.prInfo.branch,
+        conclusion: conclusionFromSteps(this.steps),
+        completedAt: new Date().toISOString(),
+        summary: this.steps.map(
+          (step) => `${StepStatusHelper.getEmoji(step.status)} ${step.name}`
+        ),
+        url: this.messageId ? await this.getMessageUrl() : undefined,
+      });
+      console.log('✅ Pipeline run added to the feed');
+    } catch (error) {
+      console.error('❌ Failed to add the pipeline run to the feed');
+      console.error('   Error:', error instanceof Error ? error.message : String(error));
+    }
+  }
+
+  /// Publishes a pipeline event to the configured broker, if any. Broker failures are
+  /// logged and never fail the pipeline.
+  private async publishEvent(
+    type: PipelineEventType,
+    extra: Partial<PipelineEvent> = {},
+    extraHooks: HookName[] = []
+  ): Promise<void> {
+    if ((!this.options.events && !this.options.hooks) || !this.prInfo) {
+      return;
+    }
+
+    const event: PipelineEvent = {
+      type,
+      repository: this.prInfo.repository,
+      prNumber: parseInt(this.prInfo.number, 10) || 0,
+      prTitle: this.prInfo.title,
+      author: this.prInfo.author,
+      branch: this.prInfo.branch,
+      timestamp: new Date().toISOString(),
+      tags: this.tags.length > 0 ? this.tags : undefined,
+      ...extra,
+    };
+
+    if (this.options.events) {
+      try {
+        await this.options.events.publish(event);
+      } catch (error) {
+        console.warn(`⚠️  Failed to publish the ${type} event`);
+        console.warn('   Error:', error instanceof Error ? error.message : String(error));
+      }
+    }
+
+    const hooks = [HOOK_BY_EVENT[type], ...extraHooks];
+    for (const failure of (await this.options.hooks?.run(hooks, event)) || []) {
+      this.recordWarning({
+        code: 'HOOK_FAILED',
+        message: `The ${failure.hook} hook failed: ${failure.error}`,
+      });
+    }
+  }
+
   /// Loads pipeline state from storage
   async loadState(): Promise<void> {
     const state = await this.storage.loadPipelineState();
+    for (const warning of this.storage.takeWarnings?.() || []) {
+      this.recordWarning(warning);
+    }
     if (state) {
       this.messageId = state.messageId;
       this.steps = state.steps;
@@ -360,6 +1802,189 @@ export class PipelineTracker {
       };
       // Convert string back to Date object when loading from JSON
       this.pipelineStartedAt = new Date(state.pipelineStartedAt);
+      this.tags = state.tags || [];
+      this.metadata = state.metadata || [];
+      this.correlationId = state.correlationId;
+      this.commit = state.commitSha
+        ? { sha: state.commitSha, message: state.commitMessage }
+        : undefined;
+      this.threadId = state.threadId;
+      if (state.forumThreadId) {
+        this.useForumThread(state.forumThreadId);
+      }
+      this.overflowMessageId = state.overflowMessageId;
+      this.mirrorMessageIds = state.mirrorMessageIds || {};
+      this.renderedEmbeds = state.renderedEmbeds;
+    }
+  }
+
+  /// Current time from the render clock, frozen when a render seed is set
+  private now(): Date {
+    return (this.options.render?.clock || systemClock).now();
+  }
+
+  /// Text summary mirrored into the pipeline message's `content` when the content fallback
+  /// is enabled; empty otherwise, leaving the embed as the only content
+  private messageContent(stage: ContentStage, totalSteps: number = this.steps.length): string {
+    if (!this.options.render?.contentFallback || !this.prInfo) {
+      return '';
+    }
+    return buildContentFallback(
+      this.prInfo,
+      this.steps,
+      totalSteps,
+      stage,
+      this.options.render,
+      this.options.links?.prUrl
+    );
+  }
+
+  /// Records a failed operation for the delivery status and notifies the alert webhook
+  private async reportFailure(operation: string, error: unknown): Promise<void> {
+    this.deliveryError = `${operation}: ${error instanceof Error ? error.message : String(error)}`;
+    if (isTrackerError(error, 'UNAUTHORIZED') || isTrackerError(error, 'FORBIDDEN')) {
+      await this.invalidatePreflight();
+    }
+    await sendTrackerAlert(this.options.alertWebhookUrl, operation, error, this.options.proxyUrl);
+  }
+
+  /// Drops the cached preflight after a permission error, so the next invocation re-checks
+  private async invalidatePreflight(): Promise<void> {
+    try {
+      await this.options.permissionCache?.invalidate(this.preflightKey);
+    } catch (error) {
+      console.warn('⚠️  Failed to invalidate the cached preflight');
+      console.warn('   Error:', error instanceof Error ? error.message : String(error));
+    }
+  }
+
+  /// Builds the persisted state from the current in-memory pipeline
+  private buildState(prInfo: PrInfo, pipelineStartedAt: Date): InternalPipelineState {
+    return {
+      messageId: this.messageId || '',
+      prNumber: parseInt(prInfo.number, 10) || 0,
+      prTitle: prInfo.title,
+      author: prInfo.author,
+      repository: prInfo.repository,
+      branch: prInfo.branch,
+      steps: this.steps,
+      pipelineStartedAt,
+      tags: this.tags,
+      metadata: this.metadata.length > 0 ? this.metadata : undefined,
+      correlationId: this.correlationId,
+      commitSha: this.commit?.sha,
+      commitMessage: this.commit?.message,
+      threadId: this.threadId,
+      forumThreadId: this.forumThreadId,
+      overflowMessageId: this.overflowMessageId,
+      mirrorMessageIds: this.mirrorMessageIds,
+      renderedEmbeds: this.renderedEmbeds,
+    };
+  }
+
+  /// Renders the pipeline message for a step update, split into the embeds that fit on the
+  /// message and those moved to the overflow reply
+  private async renderStepMessage(
+    prInfo: PrInfo,
+    stepNumber: number,
+    totalSteps: number
+  ): Promise<{ main: DiscordEmbed[]; overflow: DiscordEmbed[] }> {
+    const embed = this.withPipelineContext(
+      await this.withEta(
+        buildStepUpdateEmbed(
+          prInfo.number,
+          prInfo.title,
+          this.steps,
+          stepNumber,
+          totalSteps,
+          this.options.render,
+          this.pipelineStartedAt
+        )
+      )
+    );
+    return splitMessageEmbeds([embed, ...buildStepDetailEmbeds(this.steps, this.options.render)]);
+  }
+
+  /// Adds pipeline-level fields, links and images shared by every embed
+  private withPipelineContext(embed: DiscordEmbed): DiscordEmbed {
+    embed.fields = [...(embed.fields || []), ...buildMetadataFields(this.metadata)];
+    const tagsField = buildTagsField(this.tags);
+    if (tagsField) {
+      embed.fields = [...(embed.fields || []), tagsField];
+    }
+    const tag = this.machineTag();
+    const decorated = withPipelineLinks(
+      withDecorations(withDecorations(embed, this.options.decorations || {}), {
+        thumbnailUrl: this.authorAvatarUrl(),
+      }),
+      this.options.links || {}
+    );
+    const result = sanitizeEmbed(tag ? withMachineTag(decorated, tag) : decorated);
+    this.checkEmbedLimits(result);
+    return result;
+  }
+
+  /// Warns when the embed exceeds Discord's per-part limits, so the text cut by the API
+  /// layer before sending does not go unnoticed
+  private checkEmbedLimits(embed: DiscordEmbed): void {
+    const [fitted] = enforceEmbedLimits([embed], Number.MAX_SAFE_INTEGER);
+    if (embedLength(fitted) < embedLength(embed)) {
+      this.recordWarning({
+        code: 'EMBED_TRUNCATED',
+        message: "Embed text exceeded Discord's limits and was truncated",
+      });
+    }
+  }
+
+  /// Avatar of the PR author shown as the thumbnail, when enabled
+  private authorAvatarUrl(): string | undefined {
+    if (this.options.authorAvatarUrl) {
+      return this.options.authorAvatarUrl;
+    }
+    if (!this.options.authorAvatar || !this.prInfo) {
+      return undefined;
+    }
+    return buildAvatarUrl(this.prInfo.author, this.options.githubServerUrl);
+  }
+
+  /// Tag identifying this run's pipeline message
+  private machineTag(): MachineTag | undefined {
+    if (!this.prInfo) {
+      return undefined;
+    }
+    const { repository, number } = this.prInfo;
+    return { repository, prNumber: number, runId: this.options.runId || '' };
+  }
+
+  /// Looks for this run's message among the recent channel messages when no state is stored,
+  /// so a lost state file or a concurrent init adopts it instead of posting a duplicate
+  private async findExistingMessage(): Promise<string | undefined> {
+    const tag = this.machineTag();
+    const limit = this.options.dedupeSearchLimit || 0;
+    if (!tag || limit === 0 || this.api.isWebhookMode()) {
+      return undefined;
+    }
+
+    try {
+      if (await this.storage.loadPipelineState()) {
+        return undefined;
+      }
+      const bot = await this.api.getCurrentUser();
+      const messages = await this.api.listMessages(undefined, limit);
+      const existing = messages.find((message) => {
+        const found = readMachineTag(message);
+        return (
+          message.author?.id === bot.id &&
+          found?.repository === tag.repository &&
+          found.prNumber === tag.prNumber &&
+          found.runId === tag.runId
+        );
+      });
+      return existing?.id;
+    } catch (error) {
+      console.warn('⚠️  Could not search the channel for an existing pipeline message');
+      console.warn('   Error:', error instanceof Error ? error.message : String(error));
+      return undefined;
     }
   }
 
@@ -0,0 +1,45 @@
+// Stable public API for library users. Everything exported here follows semver: new optional
+// fields may be added to the exported interfaces in minor releases, so construct models with
+// the provided builders/factories (EmbedBuilder, StepInfoManager.new, TrackerError factories)
+// rather than relying on their exact shape.
+
+// Version of the prelude surface, bumped only on breaking changes
+export const PRELUDE_VERSION = 1;
+
+export { DiscordApi, type DiscordApiClient, type RetryConfig } from './discordApi';
+export { InMemoryDiscordApi } from './inMemoryDiscordApi';
+export { isTrackerError, TrackerError } from './error';
+export { HistoryStore, type PipelineRunRecord } from './history';
+export {
+  buildCompletionEmbed,
+  buildInitEmbed,
+  buildStepUpdateEmbed,
+  DEFAULT_RENDER_CONFIG,
+  type MachineTag,
+  readMachineTag,
+  type RenderConfig,
+} from './messageBuilder';
+export {
+  type DiscordEmbed,
+  type DiscordField,
+  type DiscordMessage,
+  EmbedBuilder,
+  type StepDetails,
+  type StepInfo,
+  StepInfoManager,
+  StepStatus,
+  StepStatusHelper,
+  type StepTable,
+} from './models';
+export {
+  InMemoryStorage,
+  type InitOptions,
+  type InternalPipelineState,
+  PipelineTracker,
+  type Storage,
+  type TrackerOptions,
+} from './pipelineTracker';
+export { FileStorage } from './storage';
+export { GitNotesStorage } from './gitNotesStorage';
+export { ActionsCacheStorage, type CacheClient } from './actionsCacheStorage';
+export { EventPublisher, type PipelineEvent } from './events';
@@ -0,0 +1,108 @@
+import * as http from 'http';
+import * as https from 'https';
+import type { Duplex } from 'stream';
+import * as tls from 'tls';
+
+/// Checks whether a host is excluded from proxying by a NO_PROXY list: comma or space
+/// separated hosts matching themselves and their subdomains (`.example.com` and
+/// `*.example.com` are equivalent), optionally with a port, or `*` for every host
+export function isNoProxyHost(hostname: string, noProxy: string | undefined): boolean {
+  const host = hostname.toLowerCase();
+  return (noProxy || '')
+    .split(/[\s,]+/)
+    .map((entry) => entry.trim().toLowerCase())
+    .filter((entry) => entry.length > 0)
+    .some((entry) => {
+      if (entry === '*') {
+        return true;
+      }
+      const domain = entry.replace(/:\d+$/, '').replace(/^\*?\./, '');
+      return host === domain || host.endsWith(`.${domain}`);
+    });
+}
+
+/// Resolves the proxy a request to `targetUrl` should go through: the explicit proxy if one
+/// is configured, else HTTPS_PROXY/HTTP_PROXY (either case) unless NO_PROXY excludes the host
+export function resolveProxy(
+  targetUrl: string,
+  explicitProxy?: string,
+  env: NodeJS.ProcessEnv = process.env
+): string | undefined {
+  const target = new URL(targetUrl);
+  if (isNoProxyHost(target.hostname, env.NO_PROXY ?? env.no_proxy)) {
+    return undefined;
+  }
+  if (explicitProxy) {
+    return explicitProxy;
+  }
+  const fromEnv =
+    target.protocol === 'https:'
+      ? env.HTTPS_PROXY || env.https_proxy || env.HTTP_PROXY || env.http_proxy
+      : env.HTTP_PROXY || env.http_proxy;
+  return fromEnv || undefined;
+}
+
+/// Builds the Proxy-Authorization header from the credentials embedded in a proxy URL
+function proxyAuthorization(proxy: URL): Record<string, string> {
+  if (!proxy.username) {
+    return {};
+  }
+  const username = decodeURIComponent(proxy.username);
+  const password = decodeURIComponent(proxy.password);
+  const credentials = Buffer.from(`${username}:${password}`).toString('base64');
+  return { 'Proxy-Authorization': `Basic ${credentials}` };
+}
+
+/// HTTPS agent tunnelling every connection through an HTTP(S) proxy with CONNECT, so TLS
+/// is still negotiated end to end with the target host
+export class HttpsProxyAgent extends https.Agent {
+  private proxy: URL;
+
+  constructor(proxyUrl: string, options: https.AgentOptions = {}) {
+    super({ keepAlive: true, ...options });
+    this.proxy = new URL(proxyUrl);
+  }
+
+  // Node's agent waits for the callback when no socket is returned synchronously
+  createConnection(
+    options: http.RequestOptions & tls.ConnectionOptions,
+    callback: (error: Error | null, socket?: Duplex) => void
+  ): any {
+    const target = `${options.host}:${options.port || 443}`;
+    const secureProxy = this.proxy.protocol === 'https:';
+    const request = (secureProxy ? https : http).request({
+      host: this.proxy.hostname,
+      port: this.proxy.port || (secureProxy ? 443 : 80),
+      method: 'CONNECT',
+      path: target,
+      headers: { Host: target, ...proxyAuthorization(this.proxy) },
+    });
+
+    request.once('connect', (response, socket) => {
+      if (response.statusCode !== 200) {
+        socket.destroy();
+        callback(new Error(`Proxy refused the tunnel to ${target} (HTTP ${response.statusCode})`));
+        return;
+      }
+      const servername = options.servername || options.host;
+      callback(null, tls.connect({ ...options, socket, servername }));
+    });
+    request.once('error', (error) => callback(error));
+    request.end();
+    return undefined;
+  }
+
+  getProxy(): string {
+    return this.proxy.origin;
+  }
+}
+
+/// Axios options routing requests to `baseURL` through the resolved proxy. Axios' own proxy
+/// support is disabled either way: it does not tunnel HTTPS through HTTP proxies.
+export function proxyRequestOptions(
+  baseURL: string,
+  explicitProxy?: string
+): { proxy: false; httpsAgent?: https.Agent } {
+  const proxy = resolveProxy(baseURL, explicitProxy);
+  return proxy ? { proxy: false, httpsAgent: new HttpsProxyAgent(proxy) } : { proxy: false };
+}
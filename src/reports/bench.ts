@@ -0,0 +1,138 @@
+import { TrackerError } from '../error';
+import type { PipelineRunRecord } from '../history';
+import type { BenchmarkResult, StepTable } from '../models';
+
+// Benchmark compared against its baseline
+export interface BenchmarkComparison {
+  name: string;
+  unit: string;
+  current: number;
+  baseline?: number;
+  changePercent?: number;
+  regression: boolean;
+}
+
+/// Parses benchmark results in one of the supported formats:
+/// - criterion JSON lines (`cargo criterion --message-format=json`)
+/// - an array of `{ "name", "value", "unit" }` entries
+/// - an object mapping benchmark names to numbers
+export function parseBenchmarkResults(content: string): BenchmarkResult[] {
+  const trimmed = content.trim();
+  if (trimmed === '') {
+    return [];
+  }
+
+  let parsed: unknown;
+  try {
+    parsed = JSON.parse(trimmed);
+  } catch {
+    return parseCriterionLines(trimmed);
+  }
+
+  if (Array.isArray(parsed)) {
+    return parsed.map((entry) => {
+      if (typeof entry?.name !== 'string' || typeof entry?.value !== 'number') {
+        throw invalidResults('array entries must have a string "name" and numeric "value"');
+      }
+      return { name: entry.name, value: entry.value, unit: String(entry.unit || '') };
+    });
+  }
+  if (parsed && typeof parsed === 'object') {
+    // A single criterion message is valid JSON on its own
+    if ((parsed as any).reason === 'benchmark-complete') {
+      return parseCriterionLines(trimmed);
+    }
+    return Object.entries(parsed).map(([name, value]) => {
+      if (typeof value !== 'number') {
+        throw invalidResults(`value of "${name}" must be a number`);
+      }
+      return { name, value, unit: '' };
+    });
+  }
+  throw invalidResults('expected an array, an object or criterion JSON lines');
+}
+
+/// Extracts the typical estimate of every `benchmark-complete` criterion message
+function parseCriterionLines(content: string): BenchmarkResult[] {
+  const results: BenchmarkResult[] = [];
+  for (const line of content.split('\n')) {
+    if (line.trim() === '') {
+      continue;
+    }
+    let message: any;
+    try {
+      message = JSON.parse(line);
+    } catch {
+      throw invalidResults('could not parse criterion output line');
+    }
+    if (message.reason === 'benchmark-complete' && message.typical) {
+      results.push({
+        name: message.id,
+        value: message.typical.estimate,
+        unit: message.typical.unit || 'ns',
+      });
+    }
+  }
+  return results;
+}
+
+function invalidResults(reason: string): TrackerError {
+  return new TrackerError(`Invalid benchmark results: ${reason}`, 'INVALID_BENCHMARK_RESULTS');
+}
+
+/// Returns the benchmarks of the most recent recorded run of the step with the same name
+export function findBaseline(records: PipelineRunRecord[], stepName: string): BenchmarkResult[] {
+  for (let i = records.length - 1; i >= 0; i--) {
+    const step = records[i].steps.find((s) => s.name === stepName && s.benchmarks?.length);
+    if (step?.benchmarks) {
+      return step.benchmarks;
+    }
+  }
+  return [];
+}
+
+/// Compares results against the baseline; a benchmark regresses when it is more than
+/// `thresholdPercent` slower than its baseline
+export function compareBenchmarks(
+  current: BenchmarkResult[],
+  baseline: BenchmarkResult[],
+  thresholdPercent: number
+): BenchmarkComparison[] {
+  return current.map((result) => {
+    const previous = baseline.find((b) => b.name === result.name && b.unit === result.unit);
+    if (!previous || previous.value <= 0) {
+      return { name: result.name, unit: result.unit, current: result.value, regression: false };
+    }
+    const changePercent = ((result.value - previous.value) / previous.value) * 100;
+    return {
+      name: result.name,
+      unit: result.unit,
+      current: result.value,
+      baseline: previous.value,
+      changePercent,
+      regression: changePercent > thresholdPercent,
+    };
+  });
+}
+
+/// Builds the step table listing every benchmark, with regressions marked ⚠️
+export function buildBenchmarkTable(comparisons: BenchmarkComparison[]): StepTable {
+  const rows = comparisons.map(({ name, unit, current, baseline, changePercent, regression }) => [
+    `${regression ? '⚠️ ' : ''}${name}`,
+    baseline !== undefined ? formatBenchValue(baseline, unit) : '-',
+    formatBenchValue(current, unit),
+    changePercent !== undefined ? formatChange(changePercent) : 'new',
+  ]);
+  return {
+    title: '📈 Benchmarks',
+    rows: [['benchmark', 'baseline', 'current', 'change'], ...rows],
+  };
+}
+
+function formatBenchValue(value: number, unit: string): string {
+  return `${Number(value.toFixed(2))}${unit ? ` ${unit}` : ''}`;
+}
+
+function formatChange(changePercent: number): string {
+  return `${changePercent > 0 ? '+' : ''}${changePercent.toFixed(1)}%`;
+}
@@ -0,0 +1,150 @@
+import * as fs from 'fs';
+import { formatDecimal, withUnit } from './locale';
+
+// Linux reports CPU times in clock ticks (USER_HZ) and resident memory in pages
+const CLOCK_TICKS_PER_SECOND = 100;
+const PAGE_SIZE_BYTES = 4096;
+
+// Fields of /proc/<pid>/stat used to measure a process tree
+export interface ProcStat {
+  pid: number;
+  ppid: number;
+  // utime + stime of the process plus cutime + cstime of its waited-for children
+  cpuTicks: number;
+  rssPages: number;
+}
+
+// Resource usage of a wrapped command and all of its descendants
+export interface ResourceStats {
+  peakRssBytes: number;
+  cpuTimeMs: number;
+}
+
+/// Parses the content of /proc/<pid>/stat. The command name may contain spaces and
+/// parentheses, so fields are counted from the last closing parenthesis.
+export function parseProcStat(content: string): ProcStat | undefined {
+  const commEnd = content.lastIndexOf(')');
+  if (commEnd === -1) {
+    return undefined;
+  }
+  const pid = parseInt(content, 10);
+  // fields[0] is the state (field 3 in proc(5)), so field N is at index N - 3
+  const fields = content.slice(commEnd + 2).split(' ');
+  if (Number.isNaN(pid) || fields.length < 22) {
+    return undefined;
+  }
+  const field = (n: number) => parseInt(fields[n - 3], 10) || 0;
+  return {
+    pid,
+    ppid: field(4),
+    cpuTicks: field(14) + field(15) + field(16) + field(17),
+    rssPages: field(24),
+  };
+}
+
+/// Reads the stat of every process visible in /proc; empty where /proc does not exist
+export function readProcStats(): ProcStat[] {
+  let entries: string[];
+  try {
+    entries = fs.readdirSync('/proc').filter((entry) => /^\d+$/.test(entry));
+  } catch {
+    return [];
+  }
+
+  const stats: ProcStat[] = [];
+  for (const entry of entries) {
+    try {
+      const stat = parseProcStat(fs.readFileSync(`/proc/${entry}/stat`, 'utf-8'));
+      if (stat) {
+        stats.push(stat);
+      }
+    } catch {
+      // The process exited between listing and reading
+    }
+  }
+  return stats;
+}
+
+/// Sums memory and CPU time over `rootPid` and all of its descendants
+export function measureProcessTree(
+  stats: ProcStat[],
+  rootPid: number
+): { rssBytes: number; cpuTimeMs: number } | undefined {
+  const tree = new Set([rootPid]);
+  // Parents are not guaranteed to be listed before their children
+  let grew = true;
+  while (grew) {
+    grew = false;
+    for (const stat of stats) {
+      if (!tree.has(stat.pid) && tree.has(stat.ppid)) {
+        tree.add(stat.pid);
+        grew = true;
+      }
+    }
+  }
+
+  const members = stats.filter((stat) => tree.has(stat.pid));
+  if (members.length === 0) {
+    return undefined;
+  }
+  const rssPages = members.reduce((sum, stat) => sum + stat.rssPages, 0);
+  const cpuTicks = members.reduce((sum, stat) => sum + stat.cpuTicks, 0);
+  return {
+    rssBytes: rssPages * PAGE_SIZE_BYTES,
+    cpuTimeMs: (cpuTicks * 1000) / CLOCK_TICKS_PER_SECOND,
+  };
+}
+
+/// Periodically samples the process tree of a running command, keeping the peak memory
+/// and the latest CPU time. Only Linux exposes /proc; elsewhere no stats are reported.
+export class ResourceSampler {
+  private pid: number;
+  private readStats: () => ProcStat[];
+  private timer: ReturnType<typeof setInterval> | undefined;
+  private stats: ResourceStats | undefined;
+
+  constructor(pid: number, readStats: () => ProcStat[] = readProcStats) {
+    this.pid = pid;
+    this.readStats = readStats;
+  }
+
+  /// Takes one sample, folding it into the collected stats
+  sample(): void {
+    const measured = measureProcessTree(this.readStats(), this.pid);
+    if (!measured) {
+      return;
+    }
+    this.stats = {
+      peakRssBytes: Math.max(this.stats?.peakRssBytes || 0, measured.rssBytes),
+      cpuTimeMs: Math.max(this.stats?.cpuTimeMs || 0, measured.cpuTimeMs),
+    };
+  }
+
+  start(intervalMs = 500): void {
+    this.sample();
+    this.timer = setInterval(() => this.sample(), intervalMs);
+  }
+
+  /// Stops sampling and returns the collected stats, if any sample succeeded
+  stop(): ResourceStats | undefined {
+    if (this.timer) {
+      clearInterval(this.timer);
+      this.timer = undefined;
+    }
+    return this.stats;
+  }
+}
+
+/// Formats a byte count with a binary unit, e.g. "512.0 MiB", or "512,0 MiB" for `de-DE`
+export function formatBytes(bytes: number, locale = ''): string {
+  const units = ['B', 'KiB', 'MiB', 'GiB'];
+  let value = bytes;
+  let unit = 0;
+  while (value >= 1024 && unit < units.length - 1) {
+    value /= 1024;
+    unit++;
+  }
+  return unit === 0
+    ? withUnit(formatDecimal(value, 0, locale), 'B', locale)
+    : withUnit(formatDecimal(value, 1, locale), units[unit], locale);
+}
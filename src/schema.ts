@@ -0,0 +1,263 @@
+import { StepStatus } from './models';
+
+// A JSON Schema document (draft 2020-12), kept as plain data so it can be printed as is
+export type JsonSchema = Record<string, unknown>;
+
+const DRAFT = 'https://json-schema.org/draft/2020-12/schema';
+
+const STRING = { type: 'string' };
+const INTEGER = { type: 'integer' };
+const TIMESTAMP = { type: 'string', format: 'date-time' };
+const STEP_STATUS = { enum: Object.values(StepStatus) };
+const KEY_VALUE = { type: 'array', prefixItems: [STRING, STRING], minItems: 2, maxItems: 2 };
+const TAGS = { type: 'array', description: 'Key/value tags of the pipeline', items: KEY_VALUE };
+const BENCHMARKS = {
+  type: 'array',
+  items: {
+    type: 'object',
+    required: ['name', 'value', 'unit'],
+    properties: { name: STRING, value: { type: 'number' }, unit: STRING },
+  },
+};
+
+const BUDGET = { type: 'integer', minimum: 1, description: 'Expected duration of the step' };
+const OVER_BUDGET = { type: 'integer', description: 'How long the step ran past its budget' };
+const RETRIES = { type: 'integer', minimum: 1, description: 'Attempts after the first one' };
+
+const STEP = {
+  type: 'object',
+  required: ['number', 'name', 'status', 'additionalInfo'],
+  properties: {
+    number: { type: 'integer', minimum: 1 },
+    name: STRING,
+    status: STEP_STATUS,
+    additionalInfo: { type: 'array', items: KEY_VALUE },
+    startedAt: TIMESTAMP,
+    completedAt: TIMESTAMP,
+    notes: { type: 'array', items: STRING },
+    tables: {
+      type: 'array',
+      items: {
+        type: 'object',
+        required: ['title', 'rows'],
+        properties: {
+          title: STRING,
+          rows: { type: 'array', items: { type: 'array', items: STRING } },
+        },
+      },
+    },
+    benchmarks: BENCHMARKS,
+    phase: STRING,
+    owner: { type: 'string', description: 'GitHub handle, without the leading @' },
+    rollout: {
+      type: 'array',
+      description: 'Rollout percentages reported by a deploy step, oldest first',
+      items: {
+        type: 'object',
+        required: ['percent', 'at'],
+        properties: { percent: { type: 'number', minimum: 0, maximum: 100 }, at: TIMESTAMP },
+      },
+    },
+    budgetMs: BUDGET,
+    retries: RETRIES,
+    logsUrl: { type: 'string', format: 'uri' },
+    failure: {
+      type: 'object',
+      description: 'Error reported by the fail action',
+      required: ['error'],
+      properties: { error: STRING, command: STRING, exitCode: { type: 'integer' } },
+    },
+  },
+};
+
+// The state file shared by the invocations of one pipeline
+const PIPELINE_STATE: JsonSchema = {
+  $schema: DRAFT,
+  title: 'PipelineState',
+  description: 'Pipeline state persisted between invocations (.discord-pipeline-state)',
+  type: 'object',
+  required: [
+    'messageId',
+    'prNumber',
+    'prTitle',
+    'author',
+    'repository',
+    'branch',
+    'steps',
+    'pipelineStartedAt',
+  ],
+  properties: {
+    messageId: { type: 'string', description: 'Empty when the message could not be created' },
+    prNumber: { type: 'integer', minimum: 0 },
+    prTitle: STRING,
+    author: STRING,
+    repository: STRING,
+    branch: STRING,
+    steps: { type: 'array', items: STEP },
+    pipelineStartedAt: TIMESTAMP,
+    tags: TAGS,
+    metadata: {
+      type: 'array',
+      description: 'Key/value details of the pipeline, shown as inline fields',
+      items: KEY_VALUE,
+    },
+    correlationId: STRING,
+    commitSha: STRING,
+    commitMessage: STRING,
+    threadId: STRING,
+    forumThreadId: {
+      type: 'string',
+      description: 'Forum post holding the pipeline message, when posted to a forum channel',
+    },
+    overflowMessageId: STRING,
+    mirrorMessageIds: {
+      type: 'object',
+      description: 'Message ID of the mirrored copy, keyed by channel ID',
+      additionalProperties: STRING,
+    },
+    renderedEmbeds: {
+      type: 'array',
+      description: 'Internal render cache; not part of the contract',
+      items: { type: 'object' },
+    },
+  },
+};
+
+// One completed run of the history file; the file holds an array of them
+const HISTORY: JsonSchema = {
+  $schema: DRAFT,
+  title: 'PipelineHistory',
+  description: 'Completed pipeline runs recorded in the history file',
+  type: 'array',
+  items: {
+    type: 'object',
+    required: [
+      'repository',
+      'prNumber',
+      'prTitle',
+      'author',
+      'branch',
+      'conclusion',
+      'startedAt',
+      'completedAt',
+      'durationMs',
+      'steps',
+    ],
+    properties: {
+      repository: STRING,
+      prNumber: INTEGER,
+      prTitle: STRING,
+      author: STRING,
+      branch: STRING,
+      conclusion: { enum: ['success', 'failed'] },
+      startedAt: TIMESTAMP,
+      completedAt: TIMESTAMP,
+      durationMs: { type: 'integer', minimum: 0 },
+      estimatedCost: { type: 'number' },
+      tags: TAGS,
+      steps: {
+        type: 'array',
+        items: {
+          type: 'object',
+          required: ['name', 'status'],
+          properties: {
+            name: STRING,
+            status: STEP_STATUS,
+            startedAt: TIMESTAMP,
+            completedAt: TIMESTAMP,
+            benchmarks: BENCHMARKS,
+            budgetMs: BUDGET,
+            overBudgetMs: OVER_BUDGET,
+            retries: RETRIES,
+          },
+        },
+      },
+      messageId: STRING,
+      feedback: {
+        type: 'object',
+        required: ['up', 'down', 'collectedAt'],
+        properties: { up: INTEGER, down: INTEGER, collectedAt: TIMESTAMP },
+      },
+      runId: { type: 'integer', description: 'GitHub Actions run, set on imported runs' },
+      correlationId: STRING,
+      rollback: {
+        type: 'object',
+        required: ['at', 'by', 'messageId'],
+        properties: { at: TIMESTAMP, by: STRING, reason: STRING, messageId: STRING },
+      },
+    },
+  },
+};
+
+// Payload published on the NATS subject or Redis channel of the event export
+const EVENT: JsonSchema = {
+  $schema: DRAFT,
+  title: 'PipelineEvent',
+  description: 'Event published at each pipeline lifecycle point by the event export',
+  type: 'object',
+  required: ['type', 'repository', 'prNumber', 'prTitle', 'author', 'branch', 'timestamp'],
+  properties: {
+    type: { enum: ['pipeline.started', 'step.updated', 'pipeline.completed'] },
+    repository: STRING,
+    prNumber: INTEGER,
+    prTitle: STRING,
+    author: STRING,
+    branch: STRING,
+    timestamp: TIMESTAMP,
+    tags: TAGS,
+    step: {
+      type: 'object',
+      description: 'Set on step.updated',
+      required: ['number', 'name', 'status'],
+      properties: {
+        number: INTEGER,
+        name: STRING,
+        status: STEP_STATUS,
+        phase: STRING,
+        overBudgetMs: OVER_BUDGET,
+        retries: RETRIES,
+      },
+    },
+    conclusion: { enum: ['success', 'failed'], description: 'Set on pipeline.completed' },
+    durationMs: { type: 'integer', description: 'Set on pipeline.completed' },
+  },
+};
+
+// Body posted to `alert_webhook_url` when the tracker itself fails
+const ALERT: JsonSchema = {
+  $schema: DRAFT,
+  title: 'TrackerAlert',
+  description: 'Body posted to the alert webhook when a tracker operation fails',
+  type: 'object',
+  required: ['content', 'text'],
+  properties: {
+    content: { type: 'string', description: 'Alert text, read by Discord webhooks' },
+    text: { type: 'string', description: 'Same text, read by Slack-compatible webhooks' },
+  },
+};
+
+// Schemas printed by the `schema` subcommand, by name
+export const SCHEMAS: Record<string, JsonSchema> = {
+  'pipeline-state': PIPELINE_STATE,
+  history: HISTORY,
+  event: EVENT,
+  alert: ALERT,
+};
+
+/// Prints the JSON Schema named by the first positional argument, or every schema keyed by
+/// name when none is given, and returns the process exit code
+export function runSchemaCommand(positional: string[]): number {
+  const [name] = positional;
+  if (!name) {
+    console.log(JSON.stringify(SCHEMAS, null, 2));
+    return 0;
+  }
+
+  const schema = SCHEMAS[name];
+  if (!schema) {
+    console.error(`Error: Usage: schema [${Object.keys(SCHEMAS).join('|')}]`);
+    return 1;
+  }
+  console.log(JSON.stringify(schema, null, 2));
+  return 0;
+}
@@ -0,0 +1,144 @@
+import { TrackerError } from './error';
+import { formatDuration } from './messageBuilder';
+import { StepStatusHelper } from './models';
+import type { InternalPipelineState, Storage } from './pipelineTracker';
+
+// Keys whose values are never printed
+const SECRET_KEY_PATTERN = /token|secret|password|webhook/i;
+
+// Fields that `state set` may overwrite, with how their value is parsed
+const SETTABLE_KEYS: Record<string, 'string' | 'number' | 'date'> = {
+  messageId: 'string',
+  prNumber: 'number',
+  prTitle: 'string',
+  author: 'string',
+  repository: 'string',
+  branch: 'string',
+  pipelineStartedAt: 'date',
+};
+
+/// Returns a deep copy of `value` with secret-looking keys and Discord webhook URLs masked
+export function redactSecrets(value: unknown): unknown {
+  if (Array.isArray(value)) {
+    return value.map(redactSecrets);
+  }
+  if (value instanceof Date) {
+    return value.toISOString();
+  }
+  if (value && typeof value === 'object') {
+    return Object.fromEntries(
+      Object.entries(value).map(([key, val]) => [
+        key,
+        SECRET_KEY_PATTERN.test(key) && val ? '***' : redactSecrets(val),
+      ])
+    );
+  }
+  if (typeof value === 'string' && /discord(app)?\.com\/api\/webhooks\//.test(value)) {
+    return '***';
+  }
+  return value;
+}
+
+/// Formats a timestamp as an ISO string followed by how long ago it was
+export function humanizeTimestamp(timestamp: Date | string, now: number = Date.now()): string {
+  const date = new Date(timestamp);
+  return `${date.toISOString()} (${formatDuration(Math.max(0, now - date.getTime()))} ago)`;
+}
+
+/// Renders the state as human-readable lines for incident debugging
+export function formatStatePretty(state: InternalPipelineState, now: number = Date.now()): string {
+  const redacted = redactSecrets(state) as InternalPipelineState;
+  const lines = [
+    `Message ID:  ${redacted.messageId || '(none)'}`,
+    `PR:          #${redacted.prNumber} ${redacted.prTitle}`,
+    `Author:      ${redacted.author}`,
+    `Repository:  ${redacted.repository} (${redacted.branch})`,
+    `Started:     ${humanizeTimestamp(state.pipelineStartedAt, now)}`,
+  ];
+
+  if (redacted.tags && redacted.tags.length > 0) {
+    lines.push(`Tags:        ${redacted.tags.map(([key, val]) => `${key}=${val}`).join(', ')}`);
+  }
+  if (redacted.metadata && redacted.metadata.length > 0) {
+    lines.push(
+      `Metadata:    ${redacted.metadata.map(([key, val]) => `${key}=${val}`).join(', ')}`
+    );
+  }
+
+  lines.push(`Steps:       ${redacted.steps.length}`);
+  for (const step of state.steps) {
+    const emoji = StepStatusHelper.getEmoji(step.status);
+    let line = `  ${emoji} ${step.number}. ${step.name} - ${step.status}`;
+    if (step.completedAt) {
+      line += `, completed ${humanizeTimestamp(step.completedAt, now)}`;
+    }
+    lines.push(line);
+  }
+
+  return lines.join('\n');
+}
+
+/// Overwrites a single state field, parsing the value according to the field type
+export function setStateField(
+  state: InternalPipelineState,
+  key: string,
+  value: string
+): InternalPipelineState {
+  const kind = SETTABLE_KEYS[key];
+  if (!kind) {
+    throw new TrackerError(
+      `Unknown state key: ${key}. Valid keys: ${Object.keys(SETTABLE_KEYS).join(', ')}`,
+      'INVALID_STATE_KEY'
+    );
+  }
+
+  let parsed: string | number | Date = value;
+  if (kind === 'number') {
+    parsed = Number(value);
+    if (!Number.isInteger(parsed) || parsed < 0) {
+      throw new TrackerError(`${key} must be a non-negative integer`, 'INVALID_STATE_VALUE');
+    }
+  } else if (kind === 'date') {
+    parsed = new Date(value);
+    if (Number.isNaN(parsed.getTime())) {
+      throw new TrackerError(`${key} must be a valid date`, 'INVALID_STATE_VALUE');
+    }
+  }
+
+  return { ...state, [key]: parsed };
+}
+
+/// Runs `state show [--json|--pretty]` or `state set <key> <value>` and returns the exit code
+export async function runStateCommand(
+  storage: Storage,
+  positional: string[],
+  flags: Record<string, string>
+): Promise<number> {
+  const [operation, key, value] = positional;
+  const state = await storage.loadPipelineState();
+  if (!state) {
+    console.error('No pipeline state found');
+    return 1;
+  }
+
+  switch (operation) {
+    case 'show':
+      if (flags.json) {
+        console.log(JSON.stringify(redactSecrets(state), null, 2));
+      } else {
+        console.log(formatStatePretty(state));
+      }
+      return 0;
+    case 'set':
+      if (!key || value === undefined) {
+        console.error('Error: Usage: state set <key> <value>');
+        return 1;
+      }
+      await storage.savePipelineState(setStateField(state, key, value));
+      console.log(`✅ State field ${key} updated`);
+      return 0;
+    default:
+      console.error('Error: Usage: state show [--json|--pretty] | state set <key> <value>');
+      return 1;
+  }
+}
@@ -2,8 +2,10 @@ import * as fs from 'fs/promises';
 import * as path from 'path';
 import * as crypto from 'crypto';
 import { TrackerError } from './error';
+import { ActionsCacheStorage } from './actionsCacheStorage';
+import { GitNotesStorage } from './gitNotesStorage';
 import { StepInfo, PipelineState } from './models';
-import { Storage, InternalPipelineState } from './pipelineTracker';
+import { Storage, InternalPipelineState, TrackerWarning } from './pipelineTracker';
 
 // Legacy format for backward compatibility
 interface LegacyPipelineState {
@@ -39,6 +41,7 @@ export class FileStorage implements Storage {
   private filePath: string;
   private backupPath: string;
   private readonly VERSION = '1.0.0';
+  private warnings: TrackerWarning[] = [];
 
   constructor() {
     // Get current directory and construct file path
@@ -200,6 +203,13 @@ export class FileStorage implements Storage {
     }
   }
 
+  /// Returns the warnings recorded while loading since the last call
+  takeWarnings(): TrackerWarning[] {
+    const warnings = this.warnings;
+    this.warnings = [];
+    return warnings;
+  }
+
   /// Attempts to recover from corrupted state file using backup or validation
   private async recoverCorruptedState(): Promise<InternalPipelineState | null> {
     try {
@@ -210,17 +220,24 @@ export class FileStorage implements Storage {
         if (validation.isValid) {
           // Restore the main file from backup
           await fs.copyFile(this.backupPath, this.filePath);
+          this.warnings.push({
+            code: 'STATE_RECOVERED',
+            message: 'State file was corrupted - restored from its backup',
+          });
           return backupState;
         }
       }
-
-      // If backup is also corrupted or doesn't exist, return null
-      // This will cause the system to start with a fresh state
-      return null;
-    } catch (error: any) {
-      // If recovery fails completely, return null to start fresh
-      return null;
+    } catch {
+      // If recovery fails completely, start fresh like without a backup
     }
+
+    // If backup is also corrupted or doesn't exist, return null
+    // This will cause the system to start with a fresh state
+    this.warnings.push({
+      code: 'STATE_RESET',
+      message: 'State file was corrupted and has no valid backup - starting from a fresh state',
+    });
+    return null;
   }
 
   /// Saves the current pipeline state to a file with backup and validation
@@ -315,6 +332,10 @@ export class FileStorage implements Storage {
         return await this.recoverCorruptedState();
       }
 
+      this.warnings.push({
+        code: 'STATE_MIGRATED',
+        message: 'State file without integrity metadata read - saved in the current format next',
+      });
       return state;
     } catch (error: any) {
       // If file doesn't exist, return null
@@ -365,7 +386,38 @@ export class FileStorage implements Storage {
   }
 }
 
+/// Revives the Date fields of a state parsed from JSON, for `Storage` backends that keep the
+/// state as plain JSON
+export function reviveState(raw: any): InternalPipelineState {
+  return {
+    ...raw,
+    pipelineStartedAt: new Date(raw.pipelineStartedAt),
+    steps: (raw.steps || []).map((step: any) => ({
+      ...step,
+      startedAt: step.startedAt ? new Date(step.startedAt) : undefined,
+      completedAt: step.completedAt ? new Date(step.completedAt) : undefined,
+    })),
+  };
+}
+
+// Where the action persists the pipeline state between steps
+export type StorageBackend = 'file' | 'git-notes' | 'actions-cache';
+
+/// Creates the storage for the configured backend. Git notes are read from `commit` when
+/// given, instead of the workflow commit.
+export function createStorage(backend: StorageBackend, commit?: string): Storage {
+  switch (backend) {
+    case 'git-notes':
+      return new GitNotesStorage({ commit });
+    case 'actions-cache':
+      return new ActionsCacheStorage();
+    default:
+      return new FileStorage();
+  }
+}
+
 /// Legacy MessageStorage class for backward compatibility
+/// @deprecated Reads and writes the pre-`Storage` state format; implement `Storage` instead
 export class MessageStorage {
   private filePath: string;
 
@@ -0,0 +1,119 @@
+import { describe, it, expect } from 'bun:test';
+import { ActionsCacheStorage, type CacheClient } from '../actionsCacheStorage';
+import { StepStatus } from '../models';
+import type { InternalPipelineState } from '../pipelineTracker';
+
+// In-memory stand-in for the cache service: immutable entries, restored newest first
+function createFakeCache() {
+  const entries: Array<{ key: string; content: string }> = [];
+  const client: CacheClient = {
+    async restore(prefix) {
+      const matches = entries.filter((entry) => entry.key.startsWith(prefix));
+      return matches[matches.length - 1]?.content;
+    },
+    async save(key, content) {
+      if (entries.some((entry) => entry.key === key)) {
+        throw new Error(`entry ${key} already exists`);
+      }
+      entries.push({ key, content });
+    },
+  };
+  return { client, entries };
+}
+
+function createState(): InternalPipelineState {
+  return {
+    messageId: 'msg-1',
+    prNumber: 42,
+    prTitle: 'Add feature',
+    author: 'octocat',
+    repository: 'owner/repo',
+    branch: 'feature',
+    steps: [
+      {
+        number: 1,
+        name: 'Build',
+        status: StepStatus.Success,
+        additionalInfo: [],
+        startedAt: new Date('2024-01-01T00:00:00Z'),
+        completedAt: new Date('2024-01-01T00:01:00Z'),
+      },
+    ],
+    pipelineStartedAt: new Date('2024-01-01T00:00:00Z'),
+  };
+}
+
+describe('ActionsCacheStorage', () => {
+  it('should share the newest state between jobs of the same run', async () => {
+    const { client, entries } = createFakeCache();
+    const initJob = new ActionsCacheStorage({ runId: '1001', client });
+    const stepJob = new ActionsCacheStorage({ runId: '1001', client });
+
+    expect(await stepJob.loadPipelineState()).toBeNull();
+    await initJob.savePipelineState({ ...createState(), messageId: 'old' });
+    await initJob.savePipelineState(createState());
+
+    const loaded = await stepJob.loadPipelineState();
+    expect(loaded?.messageId).toBe('msg-1');
+    expect(loaded?.pipelineStartedAt).toBeInstanceOf(Date);
+    expect(loaded?.steps[0].completedAt).toEqual(new Date('2024-01-01T00:01:00Z'));
+    expect(entries.every((entry) => entry.key.startsWith('discord-tracker-state-1001-'))).toBe(
+      true
+    );
+  });
+
+  it('should keep the runs apart and treat a cleared state as none', async () => {
+    const { client } = createFakeCache();
+    const storage = new ActionsCacheStorage({ runId: '1001', client });
+    await storage.savePipelineState(createState());
+
+    expect(await new ActionsCacheStorage({ runId: '1002', client }).loadPipelineState()).toBeNull();
+    await storage.clearPipelineState();
+    expect(await storage.loadPipelineState()).toBeNull();
+  });
+
+  it('should report cache failures as state errors', async () => {
+    const client: CacheClient = {
+      restore: async () => {
+        throw new Error('cache service unavailable');
+      },
+      save: async () => {
+        throw new Error('cache service unavailable');
+      },
+    };
+    const storage = new ActionsCacheStorage({ runId: '1001', client });
+
+    await expect(storage.loadPipelineState()).rejects.toThrow('cache service unavailable');
+    await expect(storage.savePipelineState(createState())).rejects.toThrow(
+      'cache service unavailable'
+    );
+  });
+
+  it('should report a corrupted entry and non-Error failures with their codes', async () => {
+    const { client } = createFakeCache();
+    await client.save('discord-tracker-state-1001-1', '{not json');
+    const storage = new ActionsCacheStorage({ runId: '1001', client });
+
+    await expect(storage.loadPipelineState()).rejects.toMatchObject({ code: 'JSON_ERROR' });
+
+    client.restore = () => Promise.reject('connection reset');
+    await expect(storage.loadPipelineState()).rejects.toMatchObject({
+      code: 'STATE_LOAD_ERROR',
+      message: 'Failed to load pipeline state: connection reset',
+    });
+  });
+
+  it('should fail to save without the runtime token of a job', async () => {
+    const runtimeToken = process.env.ACTIONS_RUNTIME_TOKEN;
+    delete process.env.ACTIONS_RUNTIME_TOKEN;
+    try {
+      await expect(
+        new ActionsCacheStorage({ runId: '1001' }).savePipelineState(createState())
+      ).rejects.toMatchObject({ code: 'STATE_SAVE_ERROR' });
+    } finally {
+      if (runtimeToken !== undefined) {
+        process.env.ACTIONS_RUNTIME_TOKEN = runtimeToken;
+      }
+    }
+  });
+});
@@ -0,0 +1,57 @@
+import { describe, it, expect, beforeEach, afterEach } from 'bun:test';
+import * as http from 'http';
+import type { AddressInfo } from 'net';
+import { sendTrackerAlert } from '../alerting';
+import { silenceConsole } from './helpers';
+
+describe('Tracker alerting', () => {
+  let server: http.Server;
+  let webhookUrl: string;
+  let receivedBodies: any[];
+
+  silenceConsole('warn');
+
+  beforeEach(async () => {
+    receivedBodies = [];
+
+    server = http.createServer((req, res) => {
+      let body = '';
+      req.on('data', (chunk) => {
+        body += chunk;
+      });
+      req.on('end', () => {
+        receivedBodies.push(JSON.parse(body));
+        res.writeHead(204);
+        res.end();
+      });
+    });
+    await new Promise<void>((resolve) => server.listen(0, '127.0.0.1', resolve));
+    webhookUrl = `http://127.0.0.1:${(server.address() as AddressInfo).port}/alert`;
+  });
+
+  afterEach(async () => {
+    await new Promise<void>((resolve) => server.close(() => resolve()));
+  });
+
+  it('should post a minimal failure notice to the webhook', async () => {
+    await sendTrackerAlert(webhookUrl, 'initPipeline', new Error('Missing Access'));
+
+    expect(receivedBodies).toHaveLength(1);
+    expect(receivedBodies[0].content).toBe(
+      'tracker is broken: initPipeline failed - Missing Access'
+    );
+    expect(receivedBodies[0].text).toBe(receivedBodies[0].content);
+  });
+
+  it('should do nothing when no webhook is configured', async () => {
+    await sendTrackerAlert(undefined, 'initPipeline', new Error('Missing Access'));
+
+    expect(receivedBodies).toHaveLength(0);
+  });
+
+  it('should never throw when the webhook is unreachable', async () => {
+    await expect(
+      sendTrackerAlert('http://127.0.0.1:1/unreachable', 'updateStep', 'boom')
+    ).resolves.toBeUndefined();
+  });
+});
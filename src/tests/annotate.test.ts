@@ -0,0 +1,61 @@
+import { describe, it, expect, beforeEach } from 'bun:test';
+import { InMemoryStorage, PipelineTracker } from '../pipelineTracker';
+import { TrackerError } from '../error';
+import { StepStatus, type DiscordMessage } from '../models';
+import { silenceConsole } from './helpers';
+
+class MockDiscordApi {
+  public updatedMessages: DiscordMessage[] = [];
+
+  async sendMessage(): Promise<string> {
+    return 'mock-message-id';
+  }
+
+  async updateMessage(_messageId: string, message: DiscordMessage): Promise<void> {
+    this.updatedMessages.push(message);
+  }
+}
+
+describe('Step annotations', () => {
+  let storage: InMemoryStorage;
+  let tracker: PipelineTracker;
+  let mockApi: MockDiscordApi;
+
+  silenceConsole();
+
+  beforeEach(async () => {
+    storage = new InMemoryStorage();
+    tracker = new PipelineTracker('test-token', '123456789', storage);
+    mockApi = new MockDiscordApi();
+    (tracker as any).api = mockApi;
+
+    await tracker.initPipeline('42', 'Test PR', 'testuser', 'test/repo', 'main');
+    await tracker.updateStep(1, 2, 'Build', 'success', []);
+  });
+
+  it('should append a note without changing the step status or timings', async () => {
+    const before = await storage.loadPipelineState();
+    const completedAt = before!.steps[0].completedAt;
+
+    await tracker.annotateStep(1, 'retried manually');
+
+    const state = await storage.loadPipelineState();
+    expect(state!.steps[0].status).toBe(StepStatus.Success);
+    expect(state!.steps[0].completedAt).toEqual(completedAt);
+    expect(state!.steps[0].notes).toEqual(['retried manually']);
+  });
+
+  it('should render notes in italics under the step', async () => {
+    await tracker.annotateStep(1, 'known flaky: #issue-42');
+
+    const lastUpdate = mockApi.updatedMessages[mockApi.updatedMessages.length - 1];
+    const stepField = lastUpdate.embeds![0].fields!.find((field) => field.name === 'Step 1');
+    expect(stepField?.value).toContain('\n└ _known flaky: #issue-42_');
+  });
+
+  it('should reject notes for unknown steps', async () => {
+    await expect(tracker.annotateStep(5, 'missing')).rejects.toThrow(
+      TrackerError.invalidStepNumber(5)
+    );
+  });
+});
@@ -0,0 +1,59 @@
+import { describe, it, expect, beforeEach, afterEach } from 'bun:test';
+import * as fs from 'fs/promises';
+import * as os from 'os';
+import * as path from 'path';
+import { readLogAttachment } from '../attachments';
+import { buildMultipartBody } from '../discordApi';
+import { TrackerError } from '../error';
+
+describe('Log attachments', () => {
+  let tempDir: string;
+
+  beforeEach(async () => {
+    tempDir = await fs.mkdtemp(path.join(os.tmpdir(), 'attachments-test-'));
+  });
+
+  afterEach(async () => {
+    await fs.rm(tempDir, { recursive: true, force: true });
+  });
+
+  it('should read the log file as a text upload', async () => {
+    const logPath = path.join(tempDir, 'build.log');
+    await fs.writeFile(logPath, 'error: compilation failed\n');
+
+    const upload = await readLogAttachment(logPath);
+
+    expect(upload.filename).toBe('build.log');
+    expect(upload.contentType).toBe('text/plain');
+    expect(Buffer.from(upload.content).toString()).toBe('error: compilation failed\n');
+  });
+
+  it('should keep only the tail of large logs', async () => {
+    const logPath = path.join(tempDir, 'big.log');
+    await fs.writeFile(logPath, 'aaaaabbbbb');
+
+    const upload = await readLogAttachment(logPath, 5);
+
+    expect(Buffer.from(upload.content).toString()).toBe('[… truncated 5 bytes]\nbbbbb');
+  });
+
+  it('should fail for missing files', async () => {
+    await expect(readLogAttachment(path.join(tempDir, 'missing.log'))).rejects.toThrow(
+      TrackerError
+    );
+  });
+
+  it('should build a multipart body with the payload and files', async () => {
+    const form = buildMultipartBody({ content: 'failed' }, [
+      { filename: 'build.log', content: Buffer.from('log'), contentType: 'text/plain' },
+    ]);
+
+    expect(JSON.parse(form.get('payload_json') as string)).toEqual({
+      content: 'failed',
+      attachments: [{ id: 0, filename: 'build.log' }],
+    });
+    const file = form.get('files[0]') as File;
+    expect(file.name).toBe('build.log');
+    expect(await file.text()).toBe('log');
+  });
+});
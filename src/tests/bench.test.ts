@@ -0,0 +1,83 @@
+import { describe, it, expect } from 'bun:test';
+import { TrackerError } from '../error';
+import type { PipelineRunRecord } from '../history';
+import { StepStatus } from '../models';
+import {
+  buildBenchmarkTable,
+  compareBenchmarks,
+  findBaseline,
+  parseBenchmarkResults,
+} from '../reports/bench';
+
+function createRecord(benchmarks: Array<{ name: string; value: number; unit: string }>) {
+  return {
+    repository: 'owner/repo',
+    prNumber: 1,
+    prTitle: 'PR',
+    author: 'dev',
+    branch: 'main',
+    conclusion: 'success',
+    startedAt: '2024-01-01T10:00:00Z',
+    completedAt: '2024-01-01T10:05:00Z',
+    durationMs: 300000,
+    steps: [{ name: 'Bench', status: StepStatus.Success, benchmarks }],
+  } as PipelineRunRecord;
+}
+
+describe('Benchmark reports', () => {
+  it('should parse custom array and object formats', () => {
+    expect(parseBenchmarkResults('[{"name":"parse","value":12.5,"unit":"ms"}]')).toEqual([
+      { name: 'parse', value: 12.5, unit: 'ms' },
+    ]);
+    expect(parseBenchmarkResults('{"parse": 3}')).toEqual([{ name: 'parse', value: 3, unit: '' }]);
+  });
+
+  it('should parse criterion JSON lines', () => {
+    const content = [
+      '{"reason":"group-complete","group_name":"parse"}',
+      '{"reason":"benchmark-complete","id":"parse/small","typical":{"estimate":120.5,"unit":"ns"}}',
+    ].join('\n');
+
+    expect(parseBenchmarkResults(content)).toEqual([
+      { name: 'parse/small', value: 120.5, unit: 'ns' },
+    ]);
+  });
+
+  it('should reject unsupported formats', () => {
+    expect(() => parseBenchmarkResults('{"parse": "fast"}')).toThrow(TrackerError);
+    expect(() => parseBenchmarkResults('not json')).toThrow(TrackerError);
+  });
+
+  it('should use the most recent baseline for the step', () => {
+    const records = [
+      createRecord([{ name: 'parse', value: 10, unit: 'ms' }]),
+      createRecord([{ name: 'parse', value: 11, unit: 'ms' }]),
+    ];
+
+    expect(findBaseline(records, 'Bench')).toEqual([{ name: 'parse', value: 11, unit: 'ms' }]);
+    expect(findBaseline(records, 'Other')).toEqual([]);
+  });
+
+  it('should flag regressions above the threshold', () => {
+    const comparisons = compareBenchmarks(
+      [
+        { name: 'parse', value: 12, unit: 'ms' },
+        { name: 'render', value: 10.5, unit: 'ms' },
+        { name: 'new', value: 1, unit: 'ms' },
+      ],
+      [
+        { name: 'parse', value: 10, unit: 'ms' },
+        { name: 'render', value: 10, unit: 'ms' },
+      ],
+      10
+    );
+
+    expect(comparisons.map((comparison) => comparison.regression)).toEqual([true, false, false]);
+    expect(buildBenchmarkTable(comparisons).rows).toEqual([
+      ['benchmark', 'baseline', 'current', 'change'],
+      ['⚠️ parse', '10 ms', '12 ms', '+20.0%'],
+      ['render', '10 ms', '10.5 ms', '+5.0%'],
+      ['new', '-', '1 ms', 'new'],
+    ]);
+  });
+});
@@ -0,0 +1,94 @@
+import { describe, it, expect, beforeEach, afterEach } from 'bun:test';
+import * as fs from 'fs/promises';
+import * as os from 'os';
+import * as path from 'path';
+import { BoardStore, type BoardEntry } from '../board';
+import { buildBoardEmbed, DEFAULT_COLOR_SCHEME } from '../messageBuilder';
+import { InMemoryStorage, PipelineTracker } from '../pipelineTracker';
+
+function createEntry(prNumber: number, updatedAt: string, statusLine = '🔄 Running'): BoardEntry {
+  return {
+    repository: 'owner/repo',
+    prNumber,
+    prTitle: `PR ${prNumber}`,
+    statusLine,
+    finished: false,
+    updatedAt,
+  };
+}
+
+describe('Pipeline board', () => {
+  let tempDir: string;
+  let board: BoardStore;
+
+  beforeEach(async () => {
+    tempDir = await fs.mkdtemp(path.join(os.tmpdir(), 'board-test-'));
+    board = new BoardStore(path.join(tempDir, 'board.json'));
+  });
+
+  afterEach(async () => {
+    await fs.rm(tempDir, { recursive: true, force: true });
+  });
+
+  it('should keep one entry per pipeline, most recent first', async () => {
+    const now = new Date('2024-01-02T12:00:00Z');
+    await board.upsert(createEntry(1, '2024-01-02T10:00:00Z'), now);
+    await board.upsert(createEntry(2, '2024-01-02T11:00:00Z'), now);
+    await board.upsert(createEntry(1, '2024-01-02T11:30:00Z', '✅ Completed'), now);
+
+    const entries = await board.load();
+
+    expect(entries.map((entry) => entry.prNumber)).toEqual([1, 2]);
+    expect(entries[0].statusLine).toBe('✅ Completed');
+  });
+
+  it('should drop entries older than a day', async () => {
+    await board.upsert(createEntry(1, '2024-01-01T10:00:00Z'), new Date('2024-01-01T10:00:00Z'));
+    await board.upsert(createEntry(2, '2024-01-02T11:00:00Z'), new Date('2024-01-02T11:00:00Z'));
+
+    expect((await board.load()).map((entry) => entry.prNumber)).toEqual([2]);
+  });
+
+  it('should render one line per pipeline and truncate long boards', () => {
+    const embed = buildBoardEmbed([createEntry(1, '2024-01-02T10:00:00Z', '❌ Failed')]);
+    expect(embed.description).toBe('**owner/repo#1** PR 1 - ❌ Failed');
+    expect(embed.color).toBe(0xff0000);
+
+    const colors = { ...DEFAULT_COLOR_SCHEME, failure: 0xaa0000 };
+    const branded = buildBoardEmbed([createEntry(1, '2024-01-02T10:00:00Z', '❌ Failed')], {
+      colors,
+    });
+    expect(branded.color).toBe(0xaa0000);
+
+    const many = Array.from({ length: 200 }, (_, i) =>
+      createEntry(i, '2024-01-02T10:00:00Z', '🔄 Running - 1/2 steps completed')
+    );
+    const truncated = buildBoardEmbed(many);
+    expect(truncated.description?.length).toBeLessThanOrEqual(4096);
+    expect(truncated.description).toMatch(/… and \d+ more$/);
+  });
+
+  it('should record tracker updates locally and flush them in one edit', async () => {
+    const tracker = new PipelineTracker('test-token', 'test-channel', new InMemoryStorage(), {
+      board,
+    });
+    const calls: string[] = [];
+    (tracker as any).api = {
+      sendMessage: async () => {
+        calls.push('send');
+        return 'msg-1';
+      },
+      updateMessage: async (messageId: string) => {
+        calls.push(`update:${messageId}`);
+      },
+    };
+
+    await tracker.initPipeline('7', 'Board PR', 'dev', 'owner/repo', 'main');
+    await tracker.updateStep(1, 2, 'Build', 'success', []);
+    expect((await board.load())[0].statusLine).toBe('🔄 Running - 1/1 steps completed');
+
+    calls.length = 0;
+    expect(await tracker.flushBoard('board-1')).toBe('board-1');
+    expect(calls).toEqual(['update:board-1']);
+  });
+});
@@ -0,0 +1,48 @@
+import { describe, it, expect, beforeEach } from 'bun:test';
+import { InMemoryDiscordApi } from '../inMemoryDiscordApi';
+import { InMemoryStorage, PipelineTracker } from '../pipelineTracker';
+import { silenceConsole } from './helpers';
+
+describe('Pipeline cancellation', () => {
+  let api: InMemoryDiscordApi;
+  let storage: InMemoryStorage;
+
+  silenceConsole();
+
+  beforeEach(() => {
+    api = new InMemoryDiscordApi();
+    storage = new InMemoryStorage();
+  });
+
+  function createTracker(): PipelineTracker {
+    return new PipelineTracker('test-token', api.channelId, storage, { api });
+  }
+
+  it('should cancel the unfinished steps and complete the pipeline', async () => {
+    const tracker = createTracker();
+    await tracker.initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+    await tracker.updateStep(1, 2, 'Build', 'success', []);
+    await tracker.updateStep(2, 2, 'Deploy', 'running', []);
+
+    // A later job, e.g. a cleanup workflow, reads the pipeline back from the state
+    const cleanup = createTracker();
+    expect(await cleanup.cancelPipeline('Owner/Repo')).toBe(true);
+
+    expect(cleanup.getSteps().map((step) => step.status)).toEqual(['success', 'cancelled']);
+    const [embed] = api.messages.get(tracker.getMessageId() as string)?.message.embeds || [];
+    expect(embed.title).toBe('🚫 Pipeline Cancelled - PR #42');
+    expect(await storage.loadPipelineState()).toBeNull();
+  });
+
+  it('should leave the pipeline of another repository alone', async () => {
+    await createTracker().initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+
+    expect(await createTracker().cancelPipeline('owner/other')).toBe(false);
+    expect(await storage.loadPipelineState()).not.toBeNull();
+  });
+
+  it('should do nothing without a stored pipeline', async () => {
+    expect(await createTracker().cancelPipeline()).toBe(false);
+    expect(api.channelMessages()).toHaveLength(0);
+  });
+});
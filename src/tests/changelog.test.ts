@@ -0,0 +1,38 @@
+import { describe, it, expect, beforeEach } from 'bun:test';
+import { InMemoryDiscordApi } from '../inMemoryDiscordApi';
+import { InMemoryStorage, PipelineTracker, type TrackerOptions } from '../pipelineTracker';
+import { silenceConsole } from './helpers';
+
+describe('Changelog on completion', () => {
+  let api: InMemoryDiscordApi;
+  let storage: InMemoryStorage;
+
+  silenceConsole();
+
+  beforeEach(() => {
+    api = new InMemoryDiscordApi();
+    storage = new InMemoryStorage();
+  });
+
+  async function complete(options: Partial<TrackerOptions>): Promise<string[]> {
+    const tracker = new PipelineTracker('test-token', api.channelId, storage, { api, ...options });
+    await tracker.initPipeline('42', 'Release 2.4', 'dev', 'owner/repo', 'main');
+    await tracker.updateStep(1, 1, 'Deploy', 'success', []);
+    await tracker.completePipeline();
+    const embeds = api.messages.get(tracker.getMessageId() as string)?.message.embeds || [];
+    return embeds.map((embed) => embed.title || '');
+  }
+
+  it('should append the changes embed to the completion message', async () => {
+    const titles = await complete({
+      changelog: [{ sha: 'abc1234', message: 'Fix login' }],
+      githubServerUrl: 'https://ghe.example.com',
+    });
+
+    expect(titles).toEqual(['🎉 Pipeline Completed - PR #42', '📦 Changes (1 commit)']);
+  });
+
+  it('should leave the completion message alone without commits', async () => {
+    expect(await complete({ changelog: [] })).toEqual(['🎉 Pipeline Completed - PR #42']);
+  });
+});
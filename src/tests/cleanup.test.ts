@@ -0,0 +1,93 @@
+import { describe, it, expect } from 'bun:test';
+import { PipelineTracker } from '../pipelineTracker';
+import type { DiscordMessageData } from '../models';
+import { silenceConsole } from './helpers';
+
+const NOW = new Date('2025-06-30T12:00:00Z');
+
+class MockDiscordApi {
+  public deleted: string[] = [];
+  public listCalls: Array<string | undefined> = [];
+  public pages: DiscordMessageData[][];
+
+  constructor(pages: DiscordMessageData[][]) {
+    this.pages = pages;
+  }
+
+  async getCurrentUser() {
+    return { id: 'bot-id', bot: true };
+  }
+
+  async listMessages(before?: string): Promise<DiscordMessageData[]> {
+    this.listCalls.push(before);
+    return this.pages[this.listCalls.length - 1] || [];
+  }
+
+  async deleteMessage(messageId: string): Promise<void> {
+    this.deleted.push(messageId);
+  }
+}
+
+function message(id: string, daysAgo: number, title: string, authorId = 'bot-id') {
+  return {
+    id,
+    content: '',
+    timestamp: new Date(NOW.getTime() - daysAgo * 24 * 60 * 60 * 1000).toISOString(),
+    embeds: [{ title }],
+    author: { id: authorId },
+  };
+}
+
+describe('Message cleanup', () => {
+  silenceConsole();
+
+  function createTracker(pages: DiscordMessageData[][]) {
+    const tracker = new PipelineTracker('test-token', '123456789');
+    const mockApi = new MockDiscordApi(pages);
+    (tracker as any).api = mockApi;
+    return { tracker, mockApi };
+  }
+
+  it('deletes only old pipeline messages authored by the bot', async () => {
+    const { tracker, mockApi } = createTracker([
+      [
+        message('recent', 2, '✅ Pipeline Completed - PR #7'),
+        message('old', 40, '💥 Pipeline Failed - PR #3'),
+        message('old-overflow', 40, '💥 Pipeline Failed - PR #3 (continued)'),
+        message('digest', 40, '📰 Pipeline Digest - last 7 days'),
+        message('foreign', 40, '🚀 Pipeline Started - PR #1', 'someone-else'),
+      ],
+    ]);
+
+    const deleted = await tracker.cleanupMessages(30, 10, NOW);
+
+    expect(deleted).toBe(2);
+    expect(mockApi.deleted).toEqual(['old', 'old-overflow']);
+  });
+
+  it('pages through the channel until a partial page is returned', async () => {
+    const fullPage = Array.from({ length: 100 }, (_, i) =>
+      message(`m${i}`, 1, '🔄 Pipeline Update - PR #1')
+    );
+    const { tracker, mockApi } = createTracker([
+      fullPage,
+      [message('old', 60, '🚀 Pipeline Started - PR #2')],
+    ]);
+
+    const deleted = await tracker.cleanupMessages(30, 10, NOW);
+
+    expect(mockApi.listCalls).toEqual([undefined, 'm99']);
+    expect(deleted).toBe(1);
+  });
+
+  it('stops after the page limit', async () => {
+    const fullPage = Array.from({ length: 100 }, (_, i) =>
+      message(`m${i}`, 1, '🔄 Pipeline Update - PR #1')
+    );
+    const { tracker, mockApi } = createTracker([fullPage, fullPage, fullPage]);
+
+    await tracker.cleanupMessages(30, 2, NOW);
+
+    expect(mockApi.listCalls.length).toBe(2);
+  });
+});
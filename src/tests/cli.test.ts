@@ -0,0 +1,44 @@
+import { describe, it, expect } from 'bun:test';
+import { getFlag, parseArgs, requireFlag } from '../cli';
+import { TrackerError } from '../error';
+
+describe('CLI argument parsing', () => {
+  it('should parse flags and the command after --', () => {
+    const parsed = parseArgs([
+      '--step-number',
+      '2',
+      '--step-name=Unit Tests',
+      '--',
+      'npm',
+      'test',
+      '--coverage',
+    ]);
+
+    expect(parsed.flags).toEqual({ 'step-number': '2', 'step-name': 'Unit Tests' });
+    expect(parsed.command).toEqual(['npm', 'test', '--coverage']);
+  });
+
+  it('should treat flags without a value as booleans', () => {
+    const parsed = parseArgs(['show', '--json', '--pretty']);
+
+    expect(parsed.positional).toEqual(['show']);
+    expect(parsed.flags).toEqual({ json: 'true', pretty: 'true' });
+  });
+
+  it('should fall back to environment variables', () => {
+    process.env.DISCORD_TRACKER_TEST_FLAG = 'from-env';
+
+    expect(getFlag({}, 'test-flag', 'DISCORD_TRACKER_TEST_FLAG')).toBe('from-env');
+    expect(getFlag({ 'test-flag': 'from-flag' }, 'test-flag', 'DISCORD_TRACKER_TEST_FLAG')).toBe(
+      'from-flag'
+    );
+
+    delete process.env.DISCORD_TRACKER_TEST_FLAG;
+  });
+
+  it('should throw for missing required flags', () => {
+    expect(() => requireFlag({}, 'step-name')).toThrow(
+      TrackerError.missingRequiredInput('step-name')
+    );
+  });
+});
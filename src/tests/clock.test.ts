@@ -0,0 +1,61 @@
+import { describe, it, expect } from 'bun:test';
+import { frozenClock, seededClock, seededRandom, systemClock } from '../clock';
+import { runDemo } from '../demo';
+import { InMemoryDiscordApi } from '../inMemoryDiscordApi';
+import { buildBoardEmbed, buildDigestEmbed, buildInitEmbed } from '../messageBuilder';
+import { InMemoryStorage, PipelineTracker } from '../pipelineTracker';
+import { silenceConsole } from './helpers';
+
+describe('Render clock', () => {
+  silenceConsole();
+
+  it('should repeat the same draws for the same seed', () => {
+    const first = seededRandom(42);
+    const second = seededRandom(42);
+    const draws = Array.from({ length: 5 }, () => first());
+
+    expect(Array.from({ length: 5 }, () => second())).toEqual(draws);
+    expect(draws.every((value) => value >= 0 && value < 1)).toBe(true);
+    expect(seededRandom(43)()).not.toBe(draws[0]);
+  });
+
+  it('should freeze timestamps and show footer times in UTC', () => {
+    const clock = frozenClock(new Date('2024-05-01T12:00:00Z'));
+    const embed = buildInitEmbed('1', 'Title', 'dev', 'owner/repo', 'main', { clock });
+
+    expect(embed.timestamp).toBe('2024-05-01T12:00:00.000Z');
+    expect(embed.footer?.text).toBe('Pipeline started at 5/1/2024, 12:00:00 PM');
+    expect(seededClock(1714564800).now().toISOString()).toBe('2024-05-01T12:00:00.000Z');
+    expect(systemClock.frozen).toBe(false);
+  });
+
+  it('should stamp the digest and the board with the render clock', () => {
+    const clock = frozenClock(new Date('2024-05-01T12:00:00Z'));
+    const digest = buildDigestEmbed([], 7, [], { clock });
+    const board = buildBoardEmbed([], { clock });
+
+    expect(digest.timestamp).toBe('2024-05-01T12:00:00.000Z');
+    expect(digest.footer?.text).toBe('Digest generated at 5/1/2024, 12:00:00 PM');
+    expect(board.timestamp).toBe('2024-05-01T12:00:00.000Z');
+    expect(board.footer?.text).toBe('0 running, 0 finished - updated at 5/1/2024, 12:00:00 PM');
+  });
+
+  it('should render byte-identical embeds for the same seed', async () => {
+    async function render(seed: number): Promise<string> {
+      const api = new InMemoryDiscordApi();
+      const tracker = new PipelineTracker('test-token', api.channelId, new InMemoryStorage(), {
+        api,
+        render: { clock: seededClock(seed) },
+      });
+      await runDemo(tracker, {
+        stepDelayMs: 1000,
+        failureRate: 0.3,
+        random: seededRandom(seed),
+        sleep: async () => {},
+      });
+      return JSON.stringify(api.channelMessages().map((stored) => stored.message));
+    }
+
+    expect(await render(1700000000)).toBe(await render(1700000000));
+  });
+});
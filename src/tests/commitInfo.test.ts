@@ -0,0 +1,56 @@
+import { describe, it, expect, beforeEach } from 'bun:test';
+import { InMemoryDiscordApi } from '../inMemoryDiscordApi';
+import { InMemoryStorage, PipelineTracker } from '../pipelineTracker';
+import { silenceConsole } from './helpers';
+
+describe('Commit info', () => {
+  const sha = '0123456789abcdef0123456789abcdef01234567';
+  let api: InMemoryDiscordApi;
+  let storage: InMemoryStorage;
+
+  silenceConsole();
+
+  beforeEach(() => {
+    api = new InMemoryDiscordApi();
+    storage = new InMemoryStorage();
+  });
+
+  function createTracker(): PipelineTracker {
+    return new PipelineTracker('test-token', api.channelId, storage, {
+      api,
+      githubServerUrl: 'https://ghe.example.com',
+    });
+  }
+
+  function commitField(messageId: string) {
+    const [embed] = api.messages.get(messageId)?.message.embeds || [];
+    return embed.fields?.find((field) => field.name === '🔖 Commit');
+  }
+
+  it('should show the commit on the init and completion embeds', async () => {
+    const tracker = createTracker();
+    await tracker.initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main', {
+      commitSha: sha,
+      commitMessage: 'Fix login redirect',
+    });
+    const messageId = tracker.getMessageId() as string;
+    const url = `https://ghe.example.com/owner/repo/commit/${sha}`;
+    const value = `[\`0123456\`](${url}) Fix login redirect`;
+    expect(commitField(messageId)?.value).toBe(value);
+
+    // A later invocation reads the commit back from the state
+    const next = createTracker();
+    await next.updateStep(1, 1, 'Build', 'success', []);
+    expect((await storage.loadPipelineState())?.commitSha).toBe(sha);
+    await next.completePipeline();
+
+    expect(commitField(messageId)?.value).toBe(value);
+  });
+
+  it('should leave the embeds unchanged without a commit', async () => {
+    const tracker = createTracker();
+    await tracker.initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+
+    expect(commitField(tracker.getMessageId() as string)).toBeUndefined();
+  });
+});
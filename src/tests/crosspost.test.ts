@@ -0,0 +1,79 @@
+import { describe, it, expect, beforeEach } from 'bun:test';
+import { InMemoryStorage, PipelineTracker } from '../pipelineTracker';
+
+describe('Crossposting completions', () => {
+  let crossposted: string[];
+  let mockApi: any;
+
+  beforeEach(() => {
+    crossposted = [];
+    mockApi = {
+      sendMessage: async () => 'msg-1',
+      updateMessage: async () => {},
+      crosspostMessage: async (messageId: string) => {
+        crossposted.push(messageId);
+      },
+    };
+  });
+
+  async function runPipeline(branch: string): Promise<void> {
+    const tracker = new PipelineTracker('test-token', 'test-channel', new InMemoryStorage(), {
+      crosspostBranches: ['main', 'release/*'],
+    });
+    (tracker as any).api = mockApi;
+    await tracker.initPipeline('42', 'Test PR', 'dev', 'owner/repo', branch);
+    await tracker.updateStep(1, 1, 'Build', 'success', []);
+    await tracker.completePipeline();
+  }
+
+  it('should crosspost the completion message of a release branch', async () => {
+    await runPipeline('release/2.0');
+
+    expect(crossposted).toEqual(['msg-1']);
+  });
+
+  it('should not crosspost other branches', async () => {
+    await runPipeline('feature/login');
+
+    expect(crossposted).toEqual([]);
+  });
+
+  it('should complete even when crossposting fails', async () => {
+    mockApi.crosspostMessage = async () => {
+      throw new Error('Cannot crosspost a message in a non-announcement channel');
+    };
+
+    await expect(runPipeline('main')).resolves.toBeUndefined();
+  });
+
+  it('should skip crossposting from a text channel with a warning', async () => {
+    mockApi.isWebhookMode = () => false;
+    mockApi.getChannel = async () => ({ id: 'test-channel', type: 0 });
+    const tracker = new PipelineTracker('test-token', 'test-channel', new InMemoryStorage(), {
+      crosspostBranches: ['main'],
+    });
+    (tracker as any).api = mockApi;
+
+    await tracker.initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+    await tracker.completePipeline();
+
+    expect(crossposted).toEqual([]);
+    expect(tracker.getWarnings()).toEqual([
+      {
+        code: 'UNSUPPORTED_CHANNEL_FEATURE',
+        message: 'Crossposting skipped - not supported in a text channel (channel test-channel)',
+        channelId: 'test-channel',
+        channelType: 'text channel',
+      },
+    ]);
+  });
+
+  it('should still crosspost from an announcement channel', async () => {
+    mockApi.isWebhookMode = () => false;
+    mockApi.getChannel = async () => ({ id: 'test-channel', type: 5 });
+
+    await runPipeline('main');
+
+    expect(crossposted).toEqual(['msg-1']);
+  });
+});
@@ -0,0 +1,39 @@
+import { describe, it, expect } from 'bun:test';
+import { InMemoryDiscordApi } from '../inMemoryDiscordApi';
+import { type InternalPipelineState, PipelineTracker, type Storage } from '../pipelineTracker';
+import { silenceConsole } from './helpers';
+
+describe('Custom storage backends', () => {
+  silenceConsole();
+
+  it('should run a pipeline on a backend implementing only save/load/clear', async () => {
+    // Serializes like a key/value store would, so dates come back as strings
+    const store = new Map<string, string>();
+    const storage: Storage = {
+      savePipelineState: async (state: InternalPipelineState) => {
+        store.set('state', JSON.stringify(state));
+      },
+      loadPipelineState: async () => {
+        const json = store.get('state');
+        return json ? JSON.parse(json) : null;
+      },
+      clearPipelineState: async () => {
+        store.delete('state');
+      },
+    };
+    const api = new InMemoryDiscordApi();
+    const createTracker = () => new PipelineTracker('test-token', api.channelId, storage, { api });
+
+    await createTracker().initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+    await createTracker().updateStep(1, 2, 'Build', 'running', []);
+    await createTracker().updateStep(1, 2, 'Build', 'success', []);
+    const last = createTracker();
+    await last.updateStep(2, 2, 'Deploy', 'success', []);
+    await last.completePipeline();
+
+    expect(last.getSteps().map((step) => step.status)).toEqual(['success', 'success']);
+    const [embed] = api.messages.get(last.getMessageId() as string)?.message.embeds || [];
+    expect(embed.title).toBe('🎉 Pipeline Completed - PR #42');
+    expect(store.has('state')).toBe(false);
+  });
+});
@@ -0,0 +1,92 @@
+import { describe, it, expect, beforeEach } from 'bun:test';
+import { InMemoryDiscordApi } from '../inMemoryDiscordApi';
+import {
+  decodeHiddenText,
+  encodeHiddenText,
+  formatMachineTag,
+  isPipelineMessage,
+  parseMachineTag,
+  readMachineTag,
+} from '../messageBuilder';
+import { InMemoryStorage, PipelineTracker } from '../pipelineTracker';
+import { silenceConsole } from './helpers';
+
+describe('Machine tag', () => {
+  const tag = { repository: 'owner/repo', prNumber: '42', runId: '1001' };
+
+  it('should format and parse v1 tags', () => {
+    expect(formatMachineTag(tag)).toBe('dtrk:v1:owner/repo:42:1001');
+    expect(parseMachineTag('dtrk:v1:owner/repo:42:1001')).toEqual(tag);
+    expect(parseMachineTag('dtrk:v2:owner/repo:42:1001')).toBeUndefined();
+  });
+
+  it('should hide text in zero-width characters', () => {
+    const hidden = encodeHiddenText('dtrk:v1:ö');
+
+    expect(hidden).toMatch(/^[\u2063\u200b\u200c]+$/);
+    expect(decodeHiddenText(`Last updated at 10:00${hidden}`)).toBe('dtrk:v1:ö');
+    expect(decodeHiddenText('Last updated at 10:00')).toBeUndefined();
+  });
+});
+
+describe('Init deduplication', () => {
+  let api: InMemoryDiscordApi;
+
+  silenceConsole();
+
+  beforeEach(() => {
+    api = new InMemoryDiscordApi();
+  });
+
+  function createTracker(runId: string, storage = new InMemoryStorage()): PipelineTracker {
+    return new PipelineTracker('test-token', api.channelId, storage, {
+      api,
+      runId,
+      dedupeSearchLimit: 50,
+    });
+  }
+
+  async function init(tracker: PipelineTracker): Promise<void> {
+    await tracker.initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+  }
+
+  it('should tag the pipeline embeds with the run', async () => {
+    await init(createTracker('1001'));
+
+    const [stored] = api.channelMessages();
+    const message = await api.getMessage(stored.id);
+    expect(readMachineTag(message)).toEqual({
+      repository: 'owner/repo',
+      prNumber: '42',
+      runId: '1001',
+    });
+    const untitled = { ...message, embeds: [{ footer: message.embeds?.[0].footer }] };
+    expect(isPipelineMessage(untitled)).toBe(true);
+  });
+
+  it('should adopt the message of the same run when no state is stored', async () => {
+    await init(createTracker('1001'));
+    const [original] = api.channelMessages();
+
+    const storage = new InMemoryStorage();
+    await init(createTracker('1001', storage));
+
+    expect(api.channelMessages()).toHaveLength(1);
+    expect((await storage.loadPipelineState())?.messageId).toBe(original.id);
+  });
+
+  it('should post a new message for another run', async () => {
+    await init(createTracker('1001'));
+    await init(createTracker('1002'));
+
+    expect(api.channelMessages()).toHaveLength(2);
+  });
+
+  it('should not search when state is stored', async () => {
+    const storage = new InMemoryStorage();
+    await init(createTracker('1001', storage));
+    await init(createTracker('1001', storage));
+
+    expect(api.channelMessages()).toHaveLength(2);
+  });
+});
@@ -0,0 +1,77 @@
+import { describe, it, expect } from 'bun:test';
+import { DEMO_STEPS, planDemoSteps, runDemo } from '../demo';
+import { InMemoryDiscordApi } from '../inMemoryDiscordApi';
+import { InMemoryStorage, PipelineTracker } from '../pipelineTracker';
+import { silenceConsole } from './helpers';
+
+// Replays the given values, then repeats the last one
+function sequence(values: number[]): () => number {
+  let index = 0;
+  return () => values[Math.min(index++, values.length - 1)];
+}
+
+describe('Demo mode', () => {
+  silenceConsole();
+
+  it('should plan every step as succeeded when nothing fails', () => {
+    const steps = planDemoSteps({ stepDelayMs: 1000, failureRate: 0, random: () => 0.5 });
+
+    expect(steps.map((step) => step.name)).toEqual(DEMO_STEPS);
+    expect(steps.every((step) => step.status === 'success')).toBe(true);
+    expect(steps.every((step) => step.durationMs === 1000)).toBe(true);
+  });
+
+  it('should skip the steps after a failure', () => {
+    // Duration then outcome draws: the second step fails
+    const steps = planDemoSteps({
+      stepDelayMs: 1000,
+      failureRate: 0.2,
+      random: sequence([0.5, 0.9, 0.5, 0.1, 0.5, 0.9]),
+    });
+
+    expect(steps.map((step) => step.status)).toEqual([
+      'success',
+      'failed',
+      'skipped',
+      'skipped',
+      'skipped',
+    ]);
+    expect(steps[2].durationMs).toBe(0);
+  });
+
+  it('should mark steps just above the failure rate as warnings', () => {
+    const steps = planDemoSteps({ stepDelayMs: 0, failureRate: 0.2, random: () => 0.25 });
+
+    expect(steps.every((step) => step.status === 'warning')).toBe(true);
+  });
+
+  it('should drive the whole pipeline through the tracker', async () => {
+    const api = new InMemoryDiscordApi();
+    const tracker = new PipelineTracker('test-token', api.channelId, new InMemoryStorage(), {
+      api,
+    });
+    const slept: number[] = [];
+    let updates = 0;
+
+    const steps = await runDemo(tracker, {
+      stepDelayMs: 1000,
+      failureRate: 0,
+      random: () => 0.5,
+      sleep: async (ms) => {
+        slept.push(ms);
+      },
+      onUpdate: () => {
+        updates++;
+      },
+    });
+
+    const [stored] = api.channelMessages();
+    expect(api.channelMessages()).toHaveLength(1);
+    expect(stored.message.embeds[0].title).toContain('Pipeline Completed - PR #1');
+    expect(JSON.stringify(stored.message)).toContain('Demo: simulated pipeline');
+    expect(slept).toEqual(steps.map((step) => step.durationMs));
+    // Init, running and final status of each step, completion
+    expect(updates).toBe(2 + steps.length * 2);
+    expect(stored.edits).toBeGreaterThan(0);
+  });
+});
@@ -0,0 +1,100 @@
+import { describe, it, expect, beforeEach } from 'bun:test';
+import { diffEmbeds } from '../messageBuilder';
+import { InMemoryStorage, PipelineTracker } from '../pipelineTracker';
+import { silenceConsole } from './helpers';
+
+describe('Diff-aware updates', () => {
+  it('should ignore footers and timestamps', () => {
+    const previous = [
+      { title: 'A', footer: { text: 'at 10:00' }, timestamp: '2025-01-01T10:00:00Z' },
+    ];
+    const next = [{ title: 'A', footer: { text: 'at 10:05' }, timestamp: '2025-01-01T10:05:00Z' }];
+
+    expect(diffEmbeds(previous, next)).toEqual([]);
+  });
+
+  it('should ignore the elapsed time', () => {
+    const status = { name: '📋 Status', value: '🔄 Running' };
+    const previous = [{ title: 'A', fields: [status, { name: '⏱️ Elapsed', value: '1m 5s' }] }];
+    const next = [{ title: 'A', fields: [status, { name: '⏱️ Elapsed', value: '2m 40s' }] }];
+
+    expect(diffEmbeds(previous, next)).toEqual([]);
+  });
+
+  it('should name the changed parts', () => {
+    const previous = [{ title: 'A', fields: [{ name: 'Step 1', value: '🔄 Running' }] }];
+    const next = [
+      { title: 'B', fields: [{ name: 'Step 1', value: '✅ Success' }] },
+      { title: 'Details' },
+    ];
+
+    expect(diffEmbeds(previous, next)).toEqual([
+      'embed 1 title',
+      'embed 1 field "Step 1"',
+      'embed 2 added',
+    ]);
+  });
+
+  describe('tracker', () => {
+    silenceConsole('log', 'debug', 'warn', 'error');
+
+    let storage: InMemoryStorage;
+    let updates: any[];
+    let mockApi: any;
+
+    beforeEach(() => {
+      storage = new InMemoryStorage();
+      updates = [];
+      mockApi = {
+        sendMessage: async () => 'msg-1',
+        updateMessage: async (messageId: string, message: any) => {
+          updates.push({ messageId, message });
+        },
+      };
+    });
+
+    // A fresh tracker per call, like separate workflow steps sharing the state
+    function createTracker(): PipelineTracker {
+      const tracker = new PipelineTracker('test-token', 'test-channel', storage);
+      (tracker as any).api = mockApi;
+      return tracker;
+    }
+
+    it('should skip the edit when a step is re-reported unchanged', async () => {
+      await createTracker().initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+
+      await createTracker().updateStep(1, 2, 'Build', 'running', []);
+      await createTracker().updateStep(1, 2, 'Build', 'running', []);
+      expect(updates).toHaveLength(1);
+
+      await createTracker().updateStep(1, 2, 'Build', 'success', []);
+      expect(updates).toHaveLength(2);
+      expect((await storage.loadPipelineState())?.renderedEmbeds?.[0].footer).toBeUndefined();
+    });
+
+    it('should replace the stalled embed on the next update', async () => {
+      await createTracker().initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+      await createTracker().updateStep(1, 2, 'Build', 'running', []);
+
+      await createTracker().markStalled(30, '');
+      await createTracker().updateStep(1, 2, 'Build', 'running', []);
+
+      expect(updates).toHaveLength(3);
+      expect(updates[2].message.embeds[0].title).toBe('🔄 Pipeline Update - PR #42');
+    });
+
+    it('should retry an edit that failed instead of skipping it as unchanged', async () => {
+      await createTracker().initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+      const updateMessage = mockApi.updateMessage;
+      mockApi.updateMessage = async () => {
+        throw new Error('Discord unavailable');
+      };
+      await createTracker().updateStep(1, 2, 'Build', 'running', []);
+      expect((await storage.loadPipelineState())?.renderedEmbeds).toBeUndefined();
+
+      mockApi.updateMessage = updateMessage;
+      await createTracker().updateStep(1, 2, 'Build', 'running', []);
+      expect(updates).toHaveLength(1);
+    });
+  });
+});
@@ -0,0 +1,83 @@
+import { describe, it, expect, beforeEach } from 'bun:test';
+import { buildFailureDirectMessage } from '../messageBuilder';
+import { InMemoryStorage, PipelineTracker } from '../pipelineTracker';
+
+const AUTHOR_ID = '123456789012345678';
+
+describe('Failure direct messages', () => {
+  let directMessages: Array<{ userId: string; message: any }>;
+  let mockApi: any;
+
+  beforeEach(() => {
+    directMessages = [];
+    mockApi = {
+      sendMessage: async () => 'msg-1',
+      updateMessage: async () => {},
+      getMessageUrl: async (id: string) => `https://discord.com/channels/1/2/${id}`,
+      sendDirectMessage: async (userId: string, message: any) => {
+        directMessages.push({ userId, message });
+        return 'dm-1';
+      },
+    };
+  });
+
+  function createTracker(dmAuthor: boolean, ownerMap: Record<string, string>): PipelineTracker {
+    const tracker = new PipelineTracker('test-token', 'test-channel', new InMemoryStorage(), {
+      dmAuthor,
+      ownerMap,
+    });
+    (tracker as any).api = mockApi;
+    return tracker;
+  }
+
+  it('should build the DM with a link to the pipeline message', () => {
+    const message = buildFailureDirectMessage(
+      '42',
+      'owner/repo',
+      { title: 'Pipeline' },
+      'https://discord.com/channels/1/2/3'
+    );
+
+    expect(message.content).toBe(
+      '❌ The pipeline of your PR #42 in owner/repo failed.\nhttps://discord.com/channels/1/2/3'
+    );
+    expect(message.embeds).toEqual([{ title: 'Pipeline' }]);
+  });
+
+  it('should DM the mapped author once, on the first failure', async () => {
+    const tracker = createTracker(true, { octocat: AUTHOR_ID });
+    await tracker.initPipeline('42', 'Test PR', 'OctoCat', 'owner/repo', 'main');
+
+    await tracker.updateStep(1, 2, 'Build', 'failed', []);
+    await tracker.updateStep(2, 2, 'Test', 'failed', []);
+
+    expect(directMessages).toHaveLength(1);
+    expect(directMessages[0].userId).toBe(AUTHOR_ID);
+    expect(directMessages[0].message.content).toContain('https://discord.com/channels/1/2/msg-1');
+    expect(directMessages[0].message.embeds[0].title).toContain('PR #42');
+  });
+
+  it('should skip the DM when disabled or the author is not mapped', async () => {
+    const disabled = createTracker(false, { octocat: AUTHOR_ID });
+    await disabled.initPipeline('42', 'Test PR', 'octocat', 'owner/repo', 'main');
+    await disabled.updateStep(1, 1, 'Build', 'failed', []);
+
+    const unmapped = createTracker(true, { someone: AUTHOR_ID });
+    await unmapped.initPipeline('43', 'Test PR', 'octocat', 'owner/repo', 'main');
+    await unmapped.updateStep(1, 1, 'Build', 'failed', []);
+
+    expect(directMessages).toHaveLength(0);
+  });
+
+  it('should not fail the step update when the DM cannot be delivered', async () => {
+    mockApi.sendDirectMessage = async () => {
+      throw new Error('Cannot send messages to this user');
+    };
+    const tracker = createTracker(true, { octocat: AUTHOR_ID });
+    await tracker.initPipeline('42', 'Test PR', 'octocat', 'owner/repo', 'main');
+
+    await tracker.updateStep(1, 1, 'Build', 'failed', []);
+
+    expect(tracker.getSteps()[0].status).toBe('failed');
+  });
+});
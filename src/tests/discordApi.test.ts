@@ -1,5 +1,5 @@
 import { describe, it, expect } from 'vitest';
-import { DiscordApi } from '../discordApi';
+import { buildMessageUrl, DiscordApi, isWebhookUrl } from '../discordApi';
 import { TrackerError } from '../error';
 
 describe('DiscordApi', () => {
@@ -58,3 +58,63 @@ describe('DiscordApi', () => {
     expect(api).toBeInstanceOf(DiscordApi);
   });
 });
+
+describe('buildMessageUrl', () => {
+  it('should build a guild message URL', () => {
+    expect(buildMessageUrl('111', '222', '333')).toBe('https://discord.com/channels/111/222/333');
+  });
+
+  it('should fall back to @me for channels without a guild', () => {
+    expect(buildMessageUrl(undefined, '222', '333')).toBe(
+      'https://discord.com/channels/@me/222/333'
+    );
+  });
+});
+
+describe('Webhook transport', () => {
+  const webhookUrl = 'https://discord.com/api/webhooks/123456/abc-DEF_token';
+
+  it('should recognize webhook URLs', () => {
+    expect(isWebhookUrl(webhookUrl)).toBe(true);
+    expect(isWebhookUrl('https://discordapp.com/api/v10/webhooks/1/token/')).toBe(true);
+    expect(isWebhookUrl('1234567890.abcdefghijklmnopqrstuvwxyz.abcdef')).toBe(false);
+    expect(isWebhookUrl('https://example.com/api/webhooks/1/token')).toBe(false);
+  });
+
+  it('should not require a channel ID in webhook mode', () => {
+    const api = new DiscordApi(webhookUrl, '');
+
+    expect(api.isWebhookMode()).toBe(true);
+    expect(() => new DiscordApi('1234567890.abcdefghijklmnopqrstuvwxyz.abcdef', '')).toThrow(
+      TrackerError
+    );
+  });
+
+  it('should execute and edit messages through the webhook endpoints', async () => {
+    const api = new DiscordApi(webhookUrl, '');
+    const requests: Array<{ method: string; url: string; params?: unknown }> = [];
+    (api as any).client = {
+      post: async (url: string, _body: unknown, config?: { params?: unknown }) => {
+        requests.push({ method: 'post', url, params: config?.params });
+        return { data: { id: 'msg-1', channel_id: '42' } };
+      },
+      patch: async (url: string) => {
+        requests.push({ method: 'patch', url });
+      },
+      get: async (url: string) => {
+        requests.push({ method: 'get', url });
+        return { data: { channel_id: '42', guild_id: '7' } };
+      },
+    };
+
+    expect(await api.sendMessage({ content: 'hi' })).toBe('msg-1');
+    await api.updateMessage('msg-1', { content: 'edited' });
+    expect(await api.getMessageUrl('msg-1')).toBe('https://discord.com/channels/7/42/msg-1');
+
+    expect(requests).toEqual([
+      { method: 'post', url: '', params: { wait: true } },
+      { method: 'patch', url: '/messages/msg-1' },
+      { method: 'get', url: '' },
+    ]);
+  });
+});
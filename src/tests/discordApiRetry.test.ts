@@ -1,5 +1,13 @@
 import { describe, it, expect } from 'bun:test';
-import { DiscordApi } from '../discordApi';
+import * as http from 'http';
+import type { AddressInfo } from 'net';
+import {
+  classifyDiscordError,
+  DiscordApi,
+  formatLatencyHistogram,
+  latencyBucket,
+  payloadBytes,
+} from '../discordApi';
 
 describe('Discord API Retry Logic Tests', () => {
   const validBotToken = '1234567890.abcdefghijklmnopqrstuvwxyz.abcdef';
@@ -220,4 +228,270 @@ describe('Discord API Retry Logic Tests', () => {
       expect(typeof api.checkApiHealth).toBe('function');
     });
   });
-});
\ No newline at end of file
+
+  describe('Rate Limit Handling', () => {
+    function rateLimitError(retryAfter?: number, header?: string) {
+      return Object.assign(new Error('Request failed with status code 429'), {
+        response: {
+          status: 429,
+          data: retryAfter !== undefined ? { message: 'Rate limited', retry_after: retryAfter } : {},
+          headers: header ? { 'retry-after': header } : {},
+        },
+      });
+    }
+
+    function createApi(failures: unknown[], config = {}) {
+      const api = new DiscordApi(validBotToken, validChannelId, { maxRetries: 0, ...config });
+      const delays: number[] = [];
+      (api as any).sleep = async (ms: number) => {
+        delays.push(ms);
+      };
+      (api as any).client = {
+        post: async () => {
+          const failure = failures.shift();
+          if (failure) {
+            throw failure;
+          }
+          return { data: { id: 'msg-1' } };
+        },
+      };
+      return { api, delays };
+    }
+
+    it('should wait for retry_after and retry without using up retries', async () => {
+      const { api, delays } = createApi([rateLimitError(1.5), rateLimitError(0.25)]);
+
+      await expect(api.sendMessage({ content: 'hi' })).resolves.toBe('msg-1');
+      expect(delays).toEqual([1500, 250]);
+    });
+
+    it('should fall back to the Retry-After header', async () => {
+      const { api, delays } = createApi([rateLimitError(undefined, '2')]);
+
+      await expect(api.sendMessage({ content: 'hi' })).resolves.toBe('msg-1');
+      expect(delays).toEqual([2000]);
+    });
+
+    it('should give up when the rate limit wait budget is exceeded', async () => {
+      const { api, delays } = createApi([rateLimitError(3), rateLimitError(3)], {
+        maxRateLimitWait: 5000,
+      });
+
+      await expect(api.sendMessage({ content: 'hi' })).rejects.toThrow('rate limited');
+      expect(delays).toEqual([3000]);
+    });
+  });
+
+  describe('Server Error Handling', () => {
+    function serverError(status: number) {
+      return Object.assign(new Error(`Request failed with status code ${status}`), {
+        response: { status, data: { message: 'Server error' }, headers: {} },
+      });
+    }
+
+    function createApi(failures: unknown[], config = {}) {
+      const api = new DiscordApi(validBotToken, validChannelId, {
+        baseDelay: 100,
+        maxDelay: 1000,
+        ...config,
+      });
+      const delays: number[] = [];
+      (api as any).sleep = async (ms: number) => {
+        delays.push(ms);
+      };
+      (api as any).client = {
+        patch: async () => {
+          const failure = failures.shift();
+          if (failure) {
+            throw failure;
+          }
+        },
+      };
+      return { api, delays };
+    }
+
+    it('should retry 5xx responses with exponential backoff and jitter', async () => {
+      const { api, delays } = createApi([serverError(502), serverError(503), serverError(500)]);
+
+      await expect(api.updateMessage('msg-1', { content: 'hi' })).resolves.toBeUndefined();
+      expect(delays).toHaveLength(3);
+      [100, 200, 400].forEach((base, i) => {
+        expect(delays[i]).toBeGreaterThanOrEqual(base);
+        expect(delays[i]).toBeLessThanOrEqual(base * 1.1);
+      });
+    });
+
+    it('should fail after the configured number of retries', async () => {
+      const { api, delays } = createApi([serverError(504), serverError(504)], { maxRetries: 1 });
+
+      await expect(api.updateMessage('msg-1', { content: 'hi' })).rejects.toThrow(
+        'failed after 2 attempts'
+      );
+      expect(delays).toHaveLength(1);
+    });
+
+    it('should explain messages rejected by a channel without messages', async () => {
+      const { api } = createApi([
+        Object.assign(new Error('Request failed with status code 400'), {
+          response: {
+            status: 400,
+            data: { code: 50008, message: 'Cannot send messages in a non-text channel' },
+            headers: {},
+          },
+        }),
+      ]);
+
+      const error = await api.updateMessage('msg-1', { content: 'hi' }).catch((e) => e);
+      expect(error.code).toBe('UNSUPPORTED_CHANNEL_TYPE');
+      expect(error.message).toContain('Use a text, announcement or forum channel, or a thread');
+    });
+
+    it('should not retry client errors', async () => {
+      const { api, delays } = createApi([serverError(400)]);
+
+      await expect(api.updateMessage('msg-1', { content: 'hi' })).rejects.toThrow();
+      expect(delays).toHaveLength(0);
+    });
+  });
+
+  describe('Error Classification', () => {
+    it('should map HTTP statuses to typed errors', () => {
+      expect(classifyDiscordError(401, undefined, 'x').code).toBe('UNAUTHORIZED');
+      expect(classifyDiscordError(403, undefined, 'x').code).toBe('FORBIDDEN');
+      expect(classifyDiscordError(404, undefined, 'x').code).toBe('MESSAGE_NOT_FOUND');
+      expect(classifyDiscordError(429, undefined, 'x').code).toBe('RATE_LIMITED');
+      expect(classifyDiscordError(500, undefined, 'x').code).toBe('UNKNOWN_DISCORD_ERROR');
+    });
+
+    it('should prefer Discord JSON error codes', () => {
+      expect(classifyDiscordError(404, 10003, 'x').code).toBe('INVALID_CHANNEL_ID');
+      expect(classifyDiscordError(400, 10008, 'x').code).toBe('MESSAGE_NOT_FOUND');
+      expect(classifyDiscordError(400, 50013, 'x').code).toBe('FORBIDDEN');
+    });
+
+    it('should throw the typed error from API calls', async () => {
+      const api = new DiscordApi(validBotToken, validChannelId);
+      (api as any).client = {
+        patch: async () => {
+          throw Object.assign(new Error('Request failed with status code 404'), {
+            response: { status: 404, data: { code: 10008, message: 'Unknown Message' }, headers: {} },
+          });
+        },
+      };
+
+      await expect(api.updateMessage('msg-1', { content: 'hi' })).rejects.toMatchObject({
+        code: 'MESSAGE_NOT_FOUND',
+      });
+    });
+  });
+
+  describe('Telemetry', () => {
+    it('should count calls, retries and rate limit waits', async () => {
+      const api = new DiscordApi(validBotToken, validChannelId, { baseDelay: 1 });
+      (api as any).sleep = async () => {};
+      const failures: unknown[] = [
+        Object.assign(new Error('rate limited'), {
+          response: { status: 429, data: { message: 'Rate limited', retry_after: 0.5 }, headers: {} },
+        }),
+        Object.assign(new Error('bad gateway'), {
+          response: { status: 502, data: { message: 'Bad Gateway' }, headers: {} },
+        }),
+      ];
+      (api as any).client = {
+        post: async () => {
+          const failure = failures.shift();
+          if (failure) {
+            throw failure;
+          }
+          return { data: { id: 'msg-1' } };
+        },
+      };
+
+      await api.sendMessage({ content: 'hi' });
+
+      const telemetry = api.getTelemetry();
+      expect(telemetry.httpCalls).toBe(3);
+      expect(telemetry.retries).toBe(1);
+      expect(telemetry.rateLimitHits).toBe(1);
+      expect(telemetry.rateLimitWaitMs).toBe(500);
+      expect(telemetry.totalLatencyMs).toBeGreaterThanOrEqual(0);
+    });
+
+    it('should bucket latencies by their upper bound', () => {
+      expect(latencyBucket(0)).toBe('100');
+      expect(latencyBucket(100)).toBe('100');
+      expect(latencyBucket(101)).toBe('250');
+      expect(latencyBucket(4999)).toBe('5000');
+      expect(latencyBucket(5001)).toBe('+Inf');
+    });
+
+    it('should measure JSON and multipart payloads', () => {
+      expect(payloadBytes(undefined)).toBe(0);
+      expect(payloadBytes('{"content":"é"}')).toBe(16);
+      expect(payloadBytes({ content: 'hi' })).toBe(16);
+
+      const form = new FormData();
+      form.append('payload_json', '{}');
+      form.append('files[0]', new Blob(['x'.repeat(100)]), 'log.txt');
+      expect(payloadBytes(form)).toBe(102);
+    });
+
+    it('should draw one bar per bucket', () => {
+      const lines = formatLatencyHistogram({ '100': 4, '+Inf': 2 }).split('\n');
+
+      expect(lines).toHaveLength(7);
+      expect(lines[0]).toBe(`  ≤ 100ms | ${'█'.repeat(20)} 4`);
+      expect(lines[1]).toBe('  ≤ 250ms |  0');
+      expect(lines[6]).toBe(` > 5000ms | ${'█'.repeat(10)} 2`);
+    });
+
+    it('should record the size, latency and status of every request', async () => {
+      const statuses = [502, 200];
+      const server = http.createServer((req, res) => {
+        req.resume();
+        req.on('end', () => {
+          const status = statuses.shift() || 200;
+          res.writeHead(status, { 'Content-Type': 'application/json' });
+          res.end(JSON.stringify(status === 200 ? { id: 'msg-1' } : { message: 'Bad Gateway' }));
+        });
+      });
+      await new Promise<void>((resolve) => server.listen(0, '127.0.0.1', resolve));
+
+      try {
+        const api = new DiscordApi(validBotToken, validChannelId, { baseDelay: 1 });
+        (api as any).sleep = async () => {};
+        (api as any).client.defaults.baseURL =
+          `http://127.0.0.1:${(server.address() as AddressInfo).port}`;
+
+        await api.sendMessage({ content: 'hi' });
+
+        const telemetry = api.getTelemetry();
+        expect(telemetry.statusCodes).toEqual({ '200': 1, '502': 1 });
+        expect(telemetry.payloadBytes).toBe(2 * JSON.stringify({ content: 'hi' }).length);
+        expect(Object.values(telemetry.latencyHistogram).reduce((a, b) => a + b, 0)).toBe(2);
+      } finally {
+        await new Promise<void>((resolve) => server.close(() => resolve()));
+      }
+    });
+  });
+
+  describe('Embed limits', () => {
+    it('should truncate oversized embeds instead of sending a message Discord rejects', async () => {
+      const api = new DiscordApi(validBotToken, validChannelId);
+      let sent: any;
+      (api as any).client = {
+        patch: async (_path: string, body: unknown) => {
+          sent = body;
+        },
+      };
+
+      await api.updateMessage('msg-1', {
+        content: '',
+        embeds: [{ title: 'x'.repeat(1000), fields: [{ name: 'Error', value: 'y'.repeat(5000) }] }],
+      });
+
+      expect(sent.embeds[0].title).toHaveLength(256);
+      expect(sent.embeds[0].fields[0].value).toHaveLength(1024);
+    });
+  });
+});
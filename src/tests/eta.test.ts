@@ -0,0 +1,102 @@
+import { describe, it, expect, beforeEach, afterEach } from 'bun:test';
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import { frozenClock } from '../clock';
+import {
+  estimateRemainingMs,
+  estimateStepDurations,
+  HistoryStore,
+  type PipelineRunRecord,
+} from '../history';
+import { InMemoryDiscordApi } from '../inMemoryDiscordApi';
+import { StepInfoManager, StepStatus } from '../models';
+import { InMemoryStorage, PipelineTracker } from '../pipelineTracker';
+import { silenceConsole } from './helpers';
+
+// A run of `owner/repo` whose steps took the given number of minutes, in order
+function makeRun(minutes: Record<string, number>, repository = 'owner/repo'): PipelineRunRecord {
+  let start = Date.parse('2024-03-01T10:00:00Z');
+  const steps = Object.entries(minutes).map(([name, duration]) => {
+    const startedAt = new Date(start).toISOString();
+    start += duration * 60000;
+    const completedAt = new Date(start).toISOString();
+    return { name, status: StepStatus.Success, startedAt, completedAt };
+  });
+  return {
+    repository,
+    prNumber: 1,
+    prTitle: 'Run',
+    author: 'dev',
+    branch: 'main',
+    conclusion: 'success',
+    startedAt: '2024-03-01T10:00:00Z',
+    completedAt: new Date(start).toISOString(),
+    durationMs: start - Date.parse('2024-03-01T10:00:00Z'),
+    steps,
+  };
+}
+
+describe('ETA estimation', () => {
+  let tempDir: string;
+
+  silenceConsole();
+
+  beforeEach(() => {
+    tempDir = fs.mkdtempSync(path.join(os.tmpdir(), 'discord-tracker-eta-'));
+  });
+
+  afterEach(() => {
+    fs.rmSync(tempDir, { recursive: true, force: true });
+  });
+
+  it('should take the median duration per step of the repository', () => {
+    const durations = estimateStepDurations(
+      [
+        makeRun({ Build: 2, Test: 10 }),
+        makeRun({ Build: 4, Test: 20 }),
+        makeRun({ Build: 9 }),
+        makeRun({ Build: 60 }, 'other/repo'),
+      ],
+      'owner/repo'
+    );
+
+    expect(durations.get('Build')).toBe(4 * 60000);
+    expect(durations.get('Test')).toBe(15 * 60000);
+  });
+
+  it('should deduct finished steps and the elapsed time of running ones', () => {
+    const records = [makeRun({ Build: 2, Test: 10, Deploy: 5 })];
+    const build = StepInfoManager.new(1, 'Build', StepStatus.Success, []);
+    build.startedAt = new Date('2024-05-01T12:00:00Z');
+    build.completedAt = new Date('2024-05-01T12:02:00Z');
+    const test = StepInfoManager.new(2, 'Test', StepStatus.Running, []);
+    test.startedAt = new Date('2024-05-01T12:02:00Z');
+    const now = new Date('2024-05-01T12:06:00Z');
+
+    expect(estimateRemainingMs(records, 'owner/repo', [build, test], now)).toBe(11 * 60000);
+    expect(estimateRemainingMs(records, 'other/repo', [build, test], now)).toBeUndefined();
+  });
+
+  it('should show the estimate on step updates when enabled', async () => {
+    const history = new HistoryStore(path.join(tempDir, 'history.json'));
+    await history.append(makeRun({ Build: 2, Test: 10 }));
+    const api = new InMemoryDiscordApi();
+    const tracker = new PipelineTracker('test-token', api.channelId, new InMemoryStorage(), {
+      api,
+      history,
+      eta: true,
+      render: { clock: frozenClock(new Date('2024-05-01T12:00:00Z')) },
+    });
+
+    await tracker.initPipeline('42', 'Feature', 'dev', 'owner/repo', 'main');
+    await tracker.updateStep(1, 2, 'Build', 'running', []);
+
+    const [embed] = api.channelMessages()[0].message.embeds;
+    const names = embed.fields?.map((field) => field.name) || [];
+    expect(names.indexOf('⏳ ETA')).toBe(names.indexOf('📋 Status') + 1);
+    expect(embed.fields?.find((field) => field.name === '⏳ ETA')?.value).toBe(
+      '~12m 0s remaining'
+    );
+  });
+});
@@ -0,0 +1,148 @@
+import { describe, it, expect, beforeEach } from 'bun:test';
+import { buildFailureMentionMessage } from '../messageBuilder';
+import { InMemoryStorage, PipelineTracker } from '../pipelineTracker';
+import { TrackerError } from '../error';
+import { CodeownersResolver } from '../github';
+import { parseDiscordIdList, parseOwnerMap } from '../validation';
+
+const USER_ID = '123456789012345678';
+const ROLE_ID = '234567890123456789';
+
+describe('Failure mentions', () => {
+  let storage: InMemoryStorage;
+  let sent: any[];
+  let mockApi: any;
+
+  beforeEach(() => {
+    storage = new InMemoryStorage();
+    sent = [];
+    mockApi = {
+      sendMessage: async (message: any) => {
+        sent.push(message);
+        return 'msg-1';
+      },
+      updateMessage: async () => {},
+    };
+  });
+
+  function createTracker(
+    users: string[],
+    roles: string[],
+    ownerMap: Record<string, string> = {}
+  ): PipelineTracker {
+    const tracker = new PipelineTracker('test-token', 'test-channel', storage, {
+      failureMentions: { users, roles },
+      ownerMap,
+    });
+    (tracker as any).api = mockApi;
+    return tracker;
+  }
+
+  it('should restrict allowed_mentions to the configured IDs', () => {
+    const message = buildFailureMentionMessage(
+      { users: [USER_ID], roles: [ROLE_ID] },
+      'Deploy @everyone',
+      'msg-1'
+    );
+
+    expect(message.content).toBe(
+      `<@&${ROLE_ID}> <@${USER_ID}> ❌ Step **Deploy @everyone** failed.`
+    );
+    expect(message.allowed_mentions).toEqual({ parse: [], users: [USER_ID], roles: [ROLE_ID] });
+    expect(message.message_reference).toEqual({ message_id: 'msg-1' });
+  });
+
+  it('should ping only once when a step turns failed', async () => {
+    const tracker = createTracker([], [ROLE_ID]);
+    await tracker.initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+
+    await tracker.updateStep(1, 2, 'Build', 'running', []);
+    await tracker.updateStep(1, 2, 'Build', 'failed', [['error', 'exit 1']]);
+    await tracker.updateStep(1, 2, 'Build', 'failed', [['error', 'exit 2']]);
+
+    // The init message plus a single ping
+    expect(sent).toHaveLength(2);
+    expect(sent[1].content).toContain(`<@&${ROLE_ID}>`);
+  });
+
+  it('should not ping for successful steps or without mentions', async () => {
+    const tracker = createTracker([], []);
+    await tracker.initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+
+    await tracker.updateStep(1, 2, 'Build', 'success', []);
+    await tracker.updateStep(2, 2, 'Test', 'failed', []);
+
+    expect(sent).toHaveLength(1);
+  });
+
+  it('should parse and validate Discord ID lists', () => {
+    expect(parseDiscordIdList('', 'mention_users')).toEqual([]);
+    expect(parseDiscordIdList(` ${USER_ID}, ${ROLE_ID} `, 'mention_roles')).toEqual([
+      USER_ID,
+      ROLE_ID,
+    ]);
+    expect(() => parseDiscordIdList('@devops', 'mention_roles')).toThrow(TrackerError);
+  });
+});
+
+describe('Step owners', () => {
+  let sent: any[];
+  let updates: any[];
+  let tracker: PipelineTracker;
+
+  beforeEach(async () => {
+    sent = [];
+    updates = [];
+    tracker = new PipelineTracker('test-token', 'test-channel', new InMemoryStorage(), {
+      ownerMap: parseOwnerMap(`@OctoCat=${USER_ID}`),
+    });
+    (tracker as any).api = {
+      sendMessage: async (message: any) => {
+        sent.push(message);
+        return 'msg-1';
+      },
+      updateMessage: async (_messageId: string, message: any) => {
+        updates.push(message);
+      },
+    };
+    await tracker.initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+  });
+
+  it('should show the owner next to the step', async () => {
+    await tracker.updateStep(1, 1, 'Deploy', 'running', [], { owner: '@octocat' });
+
+    const field = updates[0].embeds[0].fields.find((f: any) => f.name === 'Step 1');
+    expect(field.value).toContain('👤 @octocat');
+  });
+
+  it("should ping only the failed step's owner", async () => {
+    await tracker.updateStep(1, 2, 'Build', 'failed', [], { owner: 'hubot' });
+    await tracker.updateStep(2, 2, 'Deploy', 'failed', [], { owner: 'octocat' });
+
+    // The init message plus the ping for the mapped owner of Deploy
+    expect(sent).toHaveLength(2);
+    expect(sent[1].content).toBe(`<@${USER_ID}> ❌ Step **Deploy** failed.`);
+    expect(sent[1].allowed_mentions).toEqual({ parse: [], users: [USER_ID], roles: [] });
+  });
+
+  it('should ping the mapped code owners of the failing paths', async () => {
+    (tracker as any).options.codeowners = new CodeownersResolver(
+      async () => '*.ts @OctoCat\n/docs/ @my-org/docs'
+    );
+
+    await tracker.updateStep(1, 1, 'Lint', 'failed', [], {
+      failingPaths: ['src/index.ts', 'docs/setup.md'],
+    });
+
+    expect(sent).toHaveLength(2);
+    expect(sent[1].content).toBe(
+      `<@${USER_ID}> ❌ Step **Lint** failed.\n` +
+        '📂 Code owners of the failing paths: @OctoCat, @my-org/docs'
+    );
+    expect(sent[1].allowed_mentions.users).toEqual([USER_ID]);
+  });
+
+  it('should reject owner map entries that are not Discord IDs', () => {
+    expect(() => parseOwnerMap('octocat=octocat')).toThrow(TrackerError);
+  });
+});
@@ -0,0 +1,101 @@
+import { describe, it, expect, beforeEach, afterEach } from 'bun:test';
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import { HistoryStore } from '../history';
+import { countFeedback } from '../messageBuilder';
+import { InMemoryStorage, PipelineTracker } from '../pipelineTracker';
+import { silenceConsole } from './helpers';
+
+describe('Release feedback poll', () => {
+  let tempDir: string;
+  let history: HistoryStore;
+
+  silenceConsole('log', 'warn');
+
+  beforeEach(() => {
+    tempDir = fs.mkdtempSync(path.join(os.tmpdir(), 'discord-tracker-feedback-'));
+    history = new HistoryStore(path.join(tempDir, 'history.json'));
+  });
+
+  afterEach(() => {
+    fs.rmSync(tempDir, { recursive: true, force: true });
+  });
+
+  function createTracker(reactions: string[], feedbackPoll = true): PipelineTracker {
+    const tracker = new PipelineTracker('test-token', 'test-channel', new InMemoryStorage(), {
+      history,
+      feedbackPoll,
+    });
+    (tracker as any).api = {
+      sendMessage: async () => 'msg-1',
+      updateMessage: async () => {},
+      addReaction: async (_messageId: string, emoji: string) => {
+        reactions.push(emoji);
+      },
+      getMessage: async (id: string) => ({
+        id,
+        reactions: [
+          { count: 4, me: true, emoji: { id: null, name: '👍' } },
+          { count: 2, me: true, emoji: { id: null, name: '👎' } },
+          { count: 3, me: false, emoji: { id: null, name: '🎉' } },
+        ],
+      }),
+    };
+    return tracker;
+  }
+
+  it('should count votes without the bot reactions', () => {
+    expect(
+      countFeedback([
+        { count: 3, me: true, emoji: { id: null, name: '👍' } },
+        { count: 1, me: false, emoji: { id: null, name: '👎' } },
+      ])
+    ).toEqual({ up: 2, down: 1 });
+    expect(countFeedback([])).toEqual({ up: 0, down: 0 });
+  });
+
+  it('should add the poll reactions on completion only when enabled', async () => {
+    const enabled: string[] = [];
+    const tracker = createTracker(enabled);
+    await tracker.initPipeline('42', 'Deploy', 'dev', 'owner/repo', 'main');
+    await tracker.updateStep(1, 1, 'Deploy', 'success', []);
+    await tracker.completePipeline();
+    expect(enabled).toEqual(['👍', '👎']);
+
+    const disabled: string[] = [];
+    const other = createTracker(disabled, false);
+    await other.initPipeline('43', 'Deploy', 'dev', 'owner/repo', 'main');
+    await other.completePipeline();
+    expect(disabled).toEqual([]);
+  });
+
+  it('should record the collected feedback on the run of the message', async () => {
+    const tracker = createTracker([]);
+    await tracker.initPipeline('42', 'Deploy', 'dev', 'owner/repo', 'main');
+    await tracker.updateStep(1, 1, 'Deploy', 'success', []);
+    await tracker.completePipeline();
+
+    const feedback = await tracker.collectFeedback('msg-1');
+
+    expect({ up: feedback.up, down: feedback.down }).toEqual({ up: 3, down: 1 });
+    const [run] = await history.load();
+    expect(run.messageId).toBe('msg-1');
+    expect(run.feedback).toEqual(feedback);
+  });
+
+  it('should leave the history untouched for an unknown message', async () => {
+    expect(await history.recordFeedback('missing', { up: 1, down: 0, collectedAt: '' })).toBe(
+      false
+    );
+    expect(fs.existsSync(path.join(tempDir, 'history.json'))).toBe(false);
+  });
+
+  it('should require a history file', async () => {
+    const tracker = new PipelineTracker('test-token', 'test-channel', new InMemoryStorage());
+
+    await expect(tracker.collectFeedback('msg-1')).rejects.toThrow(
+      'Collecting feedback requires a history file to be configured'
+    );
+  });
+});
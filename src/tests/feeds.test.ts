@@ -0,0 +1,113 @@
+import { describe, it, expect, beforeEach, afterEach } from 'bun:test';
+import * as fs from 'fs/promises';
+import * as os from 'os';
+import * as path from 'path';
+import { buildFeed, buildFeedEntry, escapeXml, type FeedEntry, FeedStore } from '../feeds';
+import { InMemoryStorage, PipelineTracker } from '../pipelineTracker';
+
+function createEntry(prNumber: number, overrides: Partial<FeedEntry> = {}): FeedEntry {
+  return {
+    repository: 'owner/repo',
+    prNumber,
+    prTitle: `PR ${prNumber}`,
+    author: 'octocat',
+    branch: 'main',
+    conclusion: 'success',
+    completedAt: `2026-01-0${prNumber}T10:00:00.000Z`,
+    summary: ['✅ Build'],
+    ...overrides,
+  };
+}
+
+/// Checks that every opened element is closed in order (the feed has no comments or CDATA)
+function isWellFormed(xml: string): boolean {
+  const stack: string[] = [];
+  for (const match of xml.matchAll(/<(\/?)([a-zA-Z:]+)[^>]*?(\/?)>/g)) {
+    const [, closing, name, selfClosing] = match;
+    if (selfClosing) {
+      continue;
+    }
+    if (closing) {
+      if (stack.pop() !== name) {
+        return false;
+      }
+    } else {
+      stack.push(name);
+    }
+  }
+  return stack.length === 0;
+}
+
+describe('Atom feed', () => {
+  let tempDir: string;
+
+  beforeEach(async () => {
+    tempDir = await fs.mkdtemp(path.join(os.tmpdir(), 'feed-test-'));
+  });
+
+  afterEach(async () => {
+    await fs.rm(tempDir, { recursive: true, force: true });
+  });
+
+  it('should escape XML special characters', () => {
+    expect(escapeXml(`<a href="x">Tom & Jerry's</a>`)).toBe(
+      '&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&apos;s&lt;/a&gt;'
+    );
+  });
+
+  it('should render a valid Atom document with the required elements', () => {
+    const entry = buildFeedEntry(
+      createEntry(1, { prTitle: 'Fix <script> & more', url: 'https://discord.com/channels/1/2/3' })
+    );
+    const xml = buildFeed([entry], '2026-01-01T10:00:00.000Z', { link: 'https://example.com/' });
+
+    expect(xml.startsWith('<?xml version="1.0" encoding="utf-8"?>')).toBe(true);
+    expect(xml).toContain('<feed xmlns="http://www.w3.org/2005/Atom">');
+    for (const element of ['id', 'title', 'updated']) {
+      expect(xml.match(new RegExp(`<${element}>`, 'g'))?.length).toBe(2);
+    }
+    expect(xml).toContain('<author><name>octocat</name></author>');
+    expect(xml).toContain('Fix &lt;script&gt; &amp; more');
+    expect(xml).toContain('<link rel="alternate" href="https://discord.com/channels/1/2/3"/>');
+    expect(isWellFormed(xml)).toBe(true);
+  });
+
+  it('should prepend entries and keep the newest ones', async () => {
+    const feedPath = path.join(tempDir, 'pipelines.atom');
+    const store = new FeedStore(feedPath, { maxEntries: 2 });
+
+    await store.append(createEntry(1));
+    await store.append(createEntry(2, { conclusion: 'failed' }));
+    await store.append(createEntry(3));
+
+    const entries = await store.loadEntries();
+    expect(entries).toHaveLength(2);
+    expect(entries[0]).toContain('PR #3');
+    expect(entries[1]).toContain('❌ Failed: owner/repo PR #2');
+
+    const xml = await fs.readFile(feedPath, 'utf-8');
+    expect(xml).toContain('<updated>2026-01-03T10:00:00.000Z</updated>');
+    expect(isWellFormed(xml)).toBe(true);
+  });
+
+  it('should add an entry when the pipeline completes', async () => {
+    const feed = new FeedStore(path.join(tempDir, 'pipelines.atom'));
+    const tracker = new PipelineTracker('test-token', 'test-channel', new InMemoryStorage(), {
+      feed,
+    });
+    (tracker as any).api = {
+      sendMessage: async () => 'msg-1',
+      updateMessage: async () => {},
+      getMessageUrl: async (messageId: string) => `https://discord.com/channels/1/2/${messageId}`,
+    };
+
+    await tracker.initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+    await tracker.updateStep(1, 1, 'Build', 'failed', []);
+    await tracker.completePipeline();
+
+    const [entry] = await feed.loadEntries();
+    expect(entry).toContain('❌ Failed: owner/repo PR #42 - Test PR');
+    expect(entry).toContain('href="https://discord.com/channels/1/2/msg-1"');
+    expect(entry).toContain('❌ Build');
+  });
+});
@@ -0,0 +1,114 @@
+import { describe, it, expect, beforeEach } from 'bun:test';
+import { InMemoryStorage, PipelineTracker } from '../pipelineTracker';
+
+describe('Forum channels', () => {
+  let storage: InMemoryStorage;
+  let calls: Array<{ channel: string; method: string; args: any[] }>;
+  let channelType: number;
+
+  // Mock client of one channel; `forChannel` hands out the client of the post's thread
+  function createMockApi(channel: string): any {
+    const record =
+      (method: string, result?: unknown) =>
+      async (...args: any[]) => {
+        calls.push({ channel, method, args });
+        return result;
+      };
+    return {
+      isWebhookMode: () => false,
+      getChannel: record('getChannel', { id: 'forum-channel', type: channelType, guild_id: 'g-1' }),
+      sendMessage: record('sendMessage', 'msg-1'),
+      createForumPost: record('createForumPost', 'post-1'),
+      updateMessage: record('updateMessage'),
+      forChannel: (channelId: string) => {
+        calls.push({ channel, method: 'forChannel', args: [channelId] });
+        return createMockApi(channelId);
+      },
+    };
+  }
+
+  beforeEach(() => {
+    storage = new InMemoryStorage();
+    calls = [];
+    channelType = 15;
+  });
+
+  function createTracker(): PipelineTracker {
+    const tracker = new PipelineTracker('test-token', 'forum-channel', storage);
+    (tracker as any).api = createMockApi('forum-channel');
+    return tracker;
+  }
+
+  it('should open a forum post holding the pipeline message', async () => {
+    const tracker = createTracker();
+    await tracker.initPipeline('42', 'Add forum support', 'dev', 'owner/repo', 'main');
+
+    expect(tracker.getMessageId()).toBe('post-1');
+    expect(calls.find((call) => call.method === 'createForumPost')?.args[0]).toBe(
+      'PR #42: Add forum support'
+    );
+    expect(calls.some((call) => call.method === 'sendMessage')).toBe(false);
+    expect((await storage.loadPipelineState())?.forumThreadId).toBe('post-1');
+  });
+
+  it('should edit the starter message inside the post from later invocations', async () => {
+    await createTracker().initPipeline('42', 'Add forum support', 'dev', 'owner/repo', 'main');
+    calls = [];
+
+    const next = createTracker();
+    await next.updateStep(1, 2, 'Build', 'running', []);
+    await next.updateStep(1, 2, 'Build', 'success', []);
+
+    // The state is loaded on every update, but the thread client is derived once
+    expect(calls.filter((call) => call.method === 'forChannel')).toHaveLength(1);
+    const edits = calls.filter((call) => call.method === 'updateMessage');
+    expect(edits).toHaveLength(2);
+    expect(edits.every((call) => call.channel === 'post-1' && call.args[0] === 'post-1')).toBe(
+      true
+    );
+  });
+
+  it('should post to text channels as before', async () => {
+    channelType = 0;
+    const tracker = createTracker();
+    await tracker.initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+
+    expect(tracker.getMessageId()).toBe('msg-1');
+    expect(calls.some((call) => call.method === 'createForumPost')).toBe(false);
+    expect((await storage.loadPipelineState())?.forumThreadId).toBeUndefined();
+  });
+
+  it('should link the message inside the post without another channel lookup', async () => {
+    const tracker = createTracker();
+    await tracker.initPipeline('42', 'Add forum support', 'dev', 'owner/repo', 'main');
+
+    expect(await tracker.getMessageUrl()).toBe('https://discord.com/channels/g-1/post-1/post-1');
+    expect(calls.filter((call) => call.method === 'getChannel')).toHaveLength(1);
+  });
+
+  it('should name the type of a channel that cannot hold the pipeline message', async () => {
+    channelType = 2;
+    const tracker = createTracker();
+    await tracker.initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+
+    expect(tracker.getMessageId()).toBeUndefined();
+    expect(calls.map((call) => call.method)).toEqual(['getChannel']);
+    expect(tracker.getDeliveryStatus().error).toBe(
+      'initPipeline: Channel forum-channel (type 2) cannot hold the pipeline message: ' +
+        'it is a voice channel. Use a text, announcement or forum channel, or a thread'
+    );
+  });
+
+  it('should continue without a message when the post cannot be created', async () => {
+    const tracker = createTracker();
+    (tracker as any).api.createForumPost = async () => {
+      throw new Error('Missing Permissions');
+    };
+
+    await tracker.initPipeline('42', 'Add forum support', 'dev', 'owner/repo', 'main');
+
+    expect(tracker.getMessageId()).toBeUndefined();
+    expect(tracker.getDeliveryStatus().error).toBe('initPipeline: Missing Permissions');
+    expect((await storage.loadPipelineState())?.forumThreadId).toBeUndefined();
+  });
+});
@@ -0,0 +1,150 @@
+import { describe, it, expect } from 'bun:test';
+import { DEFAULT_NOTES_REF, GitNotesStorage, type GitRunner } from '../gitNotesStorage';
+import { StepStatus } from '../models';
+import type { InternalPipelineState } from '../pipelineTracker';
+
+// In-memory stand-in for a local repository and its remote, tracking notes per commit
+function createFakeGit(options: { rejectPushes?: number } = {}) {
+  const local = new Map<string, string>();
+  const remote = new Map<string, string>();
+  const calls: string[][] = [];
+  let rejectPushes = options.rejectPushes || 0;
+
+  const git: GitRunner = async (args, input) => {
+    calls.push(args);
+    const command = args.filter((arg) => arg !== '-c' && !arg.startsWith('user.'));
+    const commit = command[command.length - 1];
+    switch (command[0]) {
+      case 'fetch':
+        if (remote.size === 0) {
+          throw new Error("couldn't find remote ref");
+        }
+        local.clear();
+        for (const [key, value] of remote) {
+          local.set(key, value);
+        }
+        return '';
+      case 'push':
+        if (rejectPushes > 0) {
+          rejectPushes--;
+          throw new Error('rejected (fetch first)');
+        }
+        remote.clear();
+        for (const [key, value] of local) {
+          remote.set(key, value);
+        }
+        return '';
+      case 'notes':
+        if (command[3] === 'add') {
+          local.set(commit, input || '');
+          return '';
+        }
+        if (command[3] === 'remove') {
+          local.delete(commit);
+          return '';
+        }
+        if (!local.has(commit)) {
+          throw new Error(`no note found for object ${commit}`);
+        }
+        return local.get(commit) as string;
+      default:
+        throw new Error(`unexpected git command ${args.join(' ')}`);
+    }
+  };
+
+  return { git, local, remote, calls };
+}
+
+function createState(): InternalPipelineState {
+  return {
+    messageId: 'msg-1',
+    prNumber: 42,
+    prTitle: 'Add feature',
+    author: 'octocat',
+    repository: 'owner/repo',
+    branch: 'feature',
+    steps: [
+      {
+        number: 1,
+        name: 'Build',
+        status: StepStatus.Success,
+        additionalInfo: [],
+        startedAt: new Date('2026-01-01T10:00:00Z'),
+        completedAt: new Date('2026-01-01T10:01:00Z'),
+      },
+    ],
+    pipelineStartedAt: new Date('2026-01-01T09:59:00Z'),
+  };
+}
+
+describe('GitNotesStorage', () => {
+  it('should return null when no note exists', async () => {
+    const { git } = createFakeGit();
+    const storage = new GitNotesStorage({ commit: 'abc123', git });
+
+    expect(await storage.loadPipelineState()).toBeNull();
+  });
+
+  it('should save the state as a note and read it back on another runner', async () => {
+    const first = createFakeGit();
+    await new GitNotesStorage({ commit: 'abc123', git: first.git }).savePipelineState(
+      createState()
+    );
+
+    const second = createFakeGit();
+    for (const [key, value] of first.remote) {
+      second.remote.set(key, value);
+    }
+    const storage = new GitNotesStorage({ commit: 'abc123', git: second.git });
+    const loaded = await storage.loadPipelineState();
+
+    expect(loaded?.messageId).toBe('msg-1');
+    expect(loaded?.pipelineStartedAt).toBeInstanceOf(Date);
+    expect(loaded?.steps[0].completedAt?.toISOString()).toBe('2026-01-01T10:01:00.000Z');
+  });
+
+  it('should use the tracker notes ref and a fallback identity', async () => {
+    const { git, calls } = createFakeGit();
+    await new GitNotesStorage({ commit: 'abc123', git }).savePipelineState(createState());
+
+    const add = calls.find((args) => args.includes('add')) as string[];
+    expect(add).toContain(DEFAULT_NOTES_REF);
+    expect(add).toContain('user.name=github-actions[bot]');
+    expect(calls.some((args) => args[0] === 'push')).toBe(true);
+  });
+
+  it('should retry a push rejected by a concurrent update', async () => {
+    const { git, remote } = createFakeGit({ rejectPushes: 2 });
+    await new GitNotesStorage({ commit: 'abc123', git }).savePipelineState(createState());
+
+    expect(remote.has('abc123')).toBe(true);
+  });
+
+  it('should fail after repeated push rejections', async () => {
+    const { git } = createFakeGit({ rejectPushes: 5 });
+    const storage = new GitNotesStorage({ commit: 'abc123', git });
+
+    await expect(storage.savePipelineState(createState())).rejects.toThrow(
+      'Failed to save pipeline state'
+    );
+  });
+
+  it('should keep notes local without a remote', async () => {
+    const { git, calls } = createFakeGit();
+    const storage = new GitNotesStorage({ commit: 'abc123', remote: '', git });
+    await storage.savePipelineState(createState());
+
+    expect(calls.some((args) => args[0] === 'push' || args[0] === 'fetch')).toBe(false);
+    expect((await storage.loadPipelineState())?.messageId).toBe('msg-1');
+  });
+
+  it('should remove the note when cleared', async () => {
+    const { git, remote } = createFakeGit();
+    const storage = new GitNotesStorage({ commit: 'abc123', git });
+    await storage.savePipelineState(createState());
+    await storage.clearPipelineState();
+
+    expect(remote.has('abc123')).toBe(false);
+    expect(await storage.loadPipelineState()).toBeNull();
+  });
+});
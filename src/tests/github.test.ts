@@ -0,0 +1,190 @@
+import { describe, it, expect } from 'bun:test';
+import {
+  buildDeliveryDescription,
+  buildPrCommentBody,
+  CodeownersResolver,
+  computeQueueMs,
+  derivePipelineLinks,
+  findCodeowners,
+  matchesBranch,
+  parseCancellationEvent,
+  parseEventCommits,
+  parseCodeowners,
+  STICKY_COMMENT_MARKER,
+  splitRepository,
+} from '../github';
+
+describe('GitHub integration helpers', () => {
+  it('should include the sticky marker, status and Discord link in the comment body', () => {
+    const body = buildPrCommentBody(
+      'https://discord.com/channels/1/2/3',
+      '🔄 Running - 1/3 steps completed'
+    );
+
+    expect(body.startsWith(STICKY_COMMENT_MARKER)).toBe(true);
+    expect(body).toContain('🔄 Running - 1/3 steps completed');
+    expect(body).toContain('(https://discord.com/channels/1/2/3)');
+  });
+
+  it('should split owner/repo strings', () => {
+    expect(splitRepository('flazouh/discord-tracker')).toEqual({
+      owner: 'flazouh',
+      repo: 'discord-tracker',
+    });
+    expect(() => splitRepository('not-a-repo')).toThrow('Invalid repository format');
+  });
+});
+
+describe('Delivery status description', () => {
+  it('should describe delivered notifications', () => {
+    expect(buildDeliveryDescription(true)).toBe('notified ✅');
+  });
+
+  it('should include the failure reason', () => {
+    expect(buildDeliveryDescription(false, 'updateStep: Missing Access')).toBe(
+      'notification failed ❌ updateStep: Missing Access'
+    );
+  });
+
+  it('should respect the 140 character commit status limit', () => {
+    const description = buildDeliveryDescription(false, 'x'.repeat(500));
+    expect(description.length).toBe(140);
+    expect(description.endsWith('…')).toBe(true);
+  });
+});
+
+describe('matchesBranch', () => {
+  it('should match branches exactly or by prefix', () => {
+    expect(matchesBranch('main', ['main'])).toBe(true);
+    expect(matchesBranch('release/1.2', ['main', 'release/*'])).toBe(true);
+    expect(matchesBranch('feature/release', ['main', 'release/*'])).toBe(false);
+    expect(matchesBranch('main', [])).toBe(false);
+  });
+});
+
+describe('CODEOWNERS lookup', () => {
+  const rules = parseCodeowners(
+    [
+      '# Default owners',
+      '*       @my-org/core',
+      '*.ts    @ts-dev',
+      '/docs/  @my-org/docs  # documentation',
+      'apps/*  @apps-dev',
+      '**/migrations  @db-dev',
+    ].join('\n')
+  );
+
+  it('should parse patterns and owners, dropping comments', () => {
+    expect(rules).toHaveLength(5);
+    expect(rules[2]).toEqual({ pattern: '/docs/', owners: ['@my-org/docs'] });
+  });
+
+  it('should let the last matching rule win', () => {
+    expect(findCodeowners(rules, 'src/index.ts')).toEqual(['@ts-dev']);
+    expect(findCodeowners(rules, 'docs/guide/setup.ts')).toEqual(['@my-org/docs']);
+    expect(findCodeowners(rules, 'README.md')).toEqual(['@my-org/core']);
+  });
+
+  it('should follow gitignore matching rules', () => {
+    expect(findCodeowners(rules, 'apps/web.json')).toEqual(['@apps-dev']);
+    expect(findCodeowners(rules, 'apps/web/package.json')).toEqual(['@my-org/core']);
+    expect(findCodeowners(rules, 'services/api/migrations/001.sql')).toEqual(['@db-dev']);
+    expect(findCodeowners(rules, 'src/docs/notes.md')).toEqual(['@my-org/core']);
+  });
+
+  it('should read CODEOWNERS once and list each owner once', async () => {
+    let reads = 0;
+    const resolver = new CodeownersResolver(async () => {
+      reads++;
+      return '*.ts @ts-dev\n/docs/ @my-org/docs';
+    });
+
+    expect(await resolver.ownersOf(['src/a.ts', 'docs/b.md', 'src/c.ts'])).toEqual([
+      '@ts-dev',
+      '@my-org/docs',
+    ]);
+    expect(await resolver.ownersOf(['src/a.ts'])).toEqual(['@ts-dev']);
+    expect(reads).toBe(1);
+  });
+
+  it('should find no owners without a CODEOWNERS file', async () => {
+    const resolver = new CodeownersResolver(async () => undefined);
+    expect(await resolver.ownersOf(['src/a.ts'])).toEqual([]);
+  });
+});
+
+describe('Run timing', () => {
+  it('should measure the queue time up to the first job start', () => {
+    expect(
+      computeQueueMs('2024-01-01T10:00:00Z', [
+        '2024-01-01T10:02:00Z',
+        null,
+        '2024-01-01T10:00:45Z',
+      ])
+    ).toBe(45000);
+    expect(computeQueueMs('2024-01-01T10:00:00Z', [null])).toBe(0);
+  });
+});
+
+describe('Pipeline links', () => {
+  it('should derive the run URL from the workflow environment', () => {
+    const env = {
+      GITHUB_SERVER_URL: 'https://github.com',
+      GITHUB_REPOSITORY: 'owner/repo',
+      GITHUB_RUN_ID: '123',
+    };
+
+    expect(derivePipelineLinks(env, 'https://github.com/owner/repo/pull/42')).toEqual({
+      runUrl: 'https://github.com/owner/repo/actions/runs/123',
+      prUrl: 'https://github.com/owner/repo/pull/42',
+    });
+    expect(derivePipelineLinks({}, undefined)).toEqual({ runUrl: undefined, prUrl: undefined });
+  });
+});
+
+describe('Cancellation events', () => {
+  const repository = { full_name: 'owner/repo' };
+
+  it('should read the cancelled run of a workflow_run or check_suite event', () => {
+    const workflowRun = { conclusion: 'cancelled', head_sha: 'abc123' };
+    expect(
+      parseCancellationEvent('workflow_run', {
+        action: 'completed',
+        workflow_run: workflowRun,
+        repository,
+      })
+    ).toEqual({ repository: 'owner/repo', headSha: 'abc123' });
+    expect(
+      parseCancellationEvent('check_suite', {
+        action: 'completed',
+        check_suite: workflowRun,
+        repository,
+      })
+    ).toEqual({ repository: 'owner/repo', headSha: 'abc123' });
+  });
+
+  it('should ignore other conclusions and events', () => {
+    const success = { conclusion: 'success', head_sha: 'abc123' };
+    expect(
+      parseCancellationEvent('workflow_run', { action: 'completed', workflow_run: success })
+    ).toBeUndefined();
+    expect(parseCancellationEvent('push', { action: 'completed' })).toBeUndefined();
+  });
+});
+
+describe('Event commits', () => {
+  it('should read the pushed commits of a push event', () => {
+    const payload = {
+      commits: [
+        { id: 'abc1234', message: 'Fix login' },
+        { id: 'def5678', message: 'Bump deps' },
+      ],
+    };
+
+    expect(parseEventCommits(payload)).toEqual([
+      { sha: 'abc1234', message: 'Fix login' },
+      { sha: 'def5678', message: 'Bump deps' },
+    ]);
+    expect(parseEventCommits({ pull_request: {} })).toEqual([]);
+  });
+});
@@ -0,0 +1,24 @@
+import { afterEach, beforeEach } from 'bun:test';
+
+// Console methods the tracker reports progress and warnings through
+type ConsoleMethod = 'log' | 'info' | 'warn' | 'error' | 'debug';
+
+/// Silences the given console methods (`console.log` by default) around every test of the
+/// enclosing `describe` block, restoring them afterwards
+export function silenceConsole(...methods: ConsoleMethod[]): void {
+  const silenced: ConsoleMethod[] = methods.length > 0 ? methods : ['log'];
+  const originals = new Map<ConsoleMethod, (...args: unknown[]) => void>();
+
+  beforeEach(() => {
+    for (const method of silenced) {
+      originals.set(method, console[method]);
+      console[method] = () => {};
+    }
+  });
+
+  afterEach(() => {
+    for (const [method, original] of originals) {
+      console[method] = original;
+    }
+  });
+}
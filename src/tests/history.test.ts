@@ -0,0 +1,187 @@
+import { describe, it, expect, beforeEach, afterEach } from 'bun:test';
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import {
+  evaluateSlo,
+  filterRecords,
+  HistoryStore,
+  type PipelineRunRecord,
+  summarizeByDay,
+} from '../history';
+import { withSloBanner } from '../messageBuilder';
+import { StepStatus } from '../models';
+import { InMemoryStorage, PipelineTracker } from '../pipelineTracker';
+
+function makeRecord(overrides: Partial<PipelineRunRecord> = {}): PipelineRunRecord {
+  return {
+    repository: 'test/repo',
+    prNumber: 42,
+    prTitle: 'Test PR',
+    author: 'testuser',
+    branch: 'main',
+    conclusion: 'success',
+    startedAt: '2024-03-01T10:00:00.000Z',
+    completedAt: '2024-03-01T10:05:00.000Z',
+    durationMs: 300000,
+    steps: [{ name: 'Build', status: StepStatus.Success }],
+    ...overrides,
+  };
+}
+
+describe('HistoryStore', () => {
+  let tempDir: string;
+  let store: HistoryStore;
+
+  beforeEach(() => {
+    tempDir = fs.mkdtempSync(path.join(os.tmpdir(), 'discord-tracker-history-'));
+    store = new HistoryStore(path.join(tempDir, 'history.json'));
+  });
+
+  afterEach(() => {
+    fs.rmSync(tempDir, { recursive: true, force: true });
+  });
+
+  it('should return an empty history when no file exists', async () => {
+    expect(await store.load()).toEqual([]);
+  });
+
+  it('should append and load run records', async () => {
+    await store.append(makeRecord());
+    await store.append(makeRecord({ prNumber: 43, conclusion: 'failed' }));
+
+    const records = await store.load();
+    expect(records).toHaveLength(2);
+    expect(records[1].prNumber).toBe(43);
+    expect(records[1].conclusion).toBe('failed');
+  });
+
+  it('should only load runs within the requested window', async () => {
+    await store.append(makeRecord({ completedAt: '2024-03-01T10:05:00.000Z' }));
+    await store.append(makeRecord({ completedAt: '2024-03-09T10:05:00.000Z' }));
+
+    const records = await store.loadSince(7, new Date('2024-03-10T00:00:00.000Z'));
+    expect(records).toHaveLength(1);
+    expect(records[0].completedAt).toBe('2024-03-09T10:05:00.000Z');
+  });
+});
+
+describe('summarizeByDay', () => {
+  it('should aggregate runs, failures, durations and cost per day', () => {
+    const summaries = summarizeByDay([
+      makeRecord({ estimatedCost: 0.04 }),
+      makeRecord({ conclusion: 'failed', durationMs: 60000, estimatedCost: 0.008 }),
+      makeRecord({ completedAt: '2024-03-02T08:00:00.000Z', estimatedCost: 0.04 }),
+    ]);
+
+    expect(summaries).toHaveLength(2);
+    expect(summaries[0].date).toBe('2024-03-02');
+    expect(summaries[1]).toEqual({
+      date: '2024-03-01',
+      runs: 2,
+      failures: 1,
+      totalDurationMs: 360000,
+      totalCost: 0.048,
+    });
+  });
+});
+
+describe('filterRecords', () => {
+  const records = [
+    makeRecord({ prNumber: 1, tags: [['tier', 'critical']], conclusion: 'failed' }),
+    makeRecord({ prNumber: 2, tags: [['tier', 'critical']] }),
+    makeRecord({ prNumber: 3, tags: [['tier', 'low']], conclusion: 'failed' }),
+    makeRecord({ prNumber: 4 }),
+  ];
+
+  it('should return every record without a filter', () => {
+    expect(filterRecords(records, [])).toHaveLength(4);
+  });
+
+  it('should match tags and conclusion together', () => {
+    const filtered = filterRecords(records, [
+      ['tier', 'critical'],
+      ['conclusion', 'failed'],
+    ]);
+
+    expect(filtered.map((record) => record.prNumber)).toEqual([1]);
+  });
+});
+
+describe('SLO tracking', () => {
+  it('should compute the success rate per repository', () => {
+    const statuses = evaluateSlo(
+      [
+        makeRecord(),
+        makeRecord({ conclusion: 'failed' }),
+        makeRecord({ repository: 'other/repo' }),
+      ],
+      95
+    );
+
+    expect(statuses).toEqual([
+      {
+        repository: 'other/repo',
+        runs: 1,
+        successes: 1,
+        successRate: 100,
+        targetPercent: 95,
+        breached: false,
+      },
+      {
+        repository: 'test/repo',
+        runs: 2,
+        successes: 1,
+        successRate: 50,
+        targetPercent: 95,
+        breached: true,
+      },
+    ]);
+  });
+
+  it('should only add a banner for breached repositories', () => {
+    const statuses = evaluateSlo([makeRecord(), makeRecord({ conclusion: 'failed' })], 95);
+
+    expect(withSloBanner({ description: '**PR**' }, statuses, 7).description).toBe(
+      '**PR**\n\n🚨 **SLO breach** - test/repo: 50.0% success over 7 days (2 runs), target 95%'
+    );
+    expect(withSloBanner({ description: '**PR**' }, evaluateSlo([makeRecord()], 95), 7)).toEqual({
+      description: '**PR**',
+    });
+  });
+
+  it('should escape the repository in the banner', () => {
+    const record = makeRecord({ repository: 'team/*api*', conclusion: 'failed' });
+    const banner = withSloBanner({}, evaluateSlo([record], 95), 7).description;
+
+    expect(banner).toContain('🚨 **SLO breach** - team/\\*api\\*: 0.0%');
+  });
+
+  it('should show the banner on completion when the run breaches the SLO', async () => {
+    const tempDir = fs.mkdtempSync(path.join(os.tmpdir(), 'discord-tracker-slo-'));
+    const history = new HistoryStore(path.join(tempDir, 'history.json'));
+    await history.append(
+      makeRecord({ repository: 'owner/repo', completedAt: new Date().toISOString() })
+    );
+
+    const tracker = new PipelineTracker('test-token', 'test-channel', new InMemoryStorage(), {
+      history,
+      slo: { targetPercent: 90, windowDays: 7 },
+    });
+    const updates: any[] = [];
+    (tracker as any).api = {
+      sendMessage: async () => 'msg-1',
+      updateMessage: async (_id: string, message: any) => {
+        updates.push(message);
+      },
+    };
+
+    await tracker.initPipeline('1', 'Test PR', 'dev', 'owner/repo', 'main');
+    await tracker.updateStep(1, 1, 'Build', 'failed', []);
+    await tracker.completePipeline();
+
+    const completion = updates[updates.length - 1].embeds[0];
+    expect(completion.description).toContain('🚨 **SLO breach** - owner/repo: 50.0%');
+    fs.rmSync(tempDir, { recursive: true, force: true });
+  });
+});
@@ -0,0 +1,118 @@
+import { describe, it, expect, beforeEach, afterEach } from 'bun:test';
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import { HistoryStore } from '../history';
+import {
+  importWorkflowHistory,
+  toRunRecord,
+  type WorkflowJob,
+  type WorkflowRun,
+  type WorkflowRunSource,
+} from '../historyImport';
+import { StepStatus } from '../models';
+
+function makeRun(overrides: Partial<WorkflowRun> = {}): WorkflowRun {
+  return {
+    id: 1001,
+    name: 'CI',
+    display_title: 'Add caching',
+    head_branch: 'feature/cache',
+    status: 'completed',
+    conclusion: 'success',
+    run_started_at: '2024-03-01T10:00:00Z',
+    created_at: '2024-03-01T09:59:00Z',
+    updated_at: '2024-03-01T10:05:00Z',
+    actor: { login: 'dev' },
+    pull_requests: [{ number: 42 }],
+    ...overrides,
+  };
+}
+
+const JOBS: WorkflowJob[] = [
+  {
+    name: 'build',
+    conclusion: 'success',
+    started_at: '2024-03-01T10:00:10Z',
+    completed_at: '2024-03-01T10:02:00Z',
+  },
+  { name: 'deploy', conclusion: 'skipped', started_at: '2024-03-01T10:02:00Z', completed_at: null },
+];
+
+describe('History import', () => {
+  let tempDir: string;
+  let history: HistoryStore;
+
+  beforeEach(() => {
+    tempDir = fs.mkdtempSync(path.join(os.tmpdir(), 'discord-tracker-import-'));
+    history = new HistoryStore(path.join(tempDir, 'history.json'));
+  });
+
+  afterEach(() => {
+    fs.rmSync(tempDir, { recursive: true, force: true });
+  });
+
+  it('should convert a run and its jobs into a history record', () => {
+    const record = toRunRecord('owner/repo', makeRun(), JOBS);
+
+    expect(record).toEqual({
+      repository: 'owner/repo',
+      prNumber: 42,
+      prTitle: 'Add caching',
+      author: 'dev',
+      branch: 'feature/cache',
+      conclusion: 'success',
+      startedAt: '2024-03-01T10:00:00Z',
+      completedAt: '2024-03-01T10:05:00Z',
+      durationMs: 300000,
+      steps: [
+        {
+          name: 'build',
+          status: StepStatus.Success,
+          startedAt: '2024-03-01T10:00:10Z',
+          completedAt: '2024-03-01T10:02:00Z',
+        },
+        {
+          name: 'deploy',
+          status: StepStatus.Skipped,
+          startedAt: '2024-03-01T10:02:00Z',
+          completedAt: undefined,
+        },
+      ],
+      runId: 1001,
+    });
+  });
+
+  it('should map failures and leave out cancelled runs', () => {
+    expect(toRunRecord('owner/repo', makeRun({ conclusion: 'timed_out' }), [])?.conclusion).toBe(
+      'failed'
+    );
+    expect(toRunRecord('owner/repo', makeRun({ conclusion: 'cancelled' }), [])).toBeUndefined();
+    expect(toRunRecord('owner/repo', makeRun({ status: 'in_progress' }), [])).toBeUndefined();
+    expect(toRunRecord('owner/repo', makeRun({ pull_requests: [] }), [])?.prNumber).toBe(0);
+  });
+
+  it('should backfill in completion order and skip runs imported before', async () => {
+    const source: WorkflowRunSource = {
+      listRuns: async (limit) =>
+        [
+          makeRun({ id: 3, updated_at: '2024-03-03T10:00:00Z' }),
+          makeRun({ id: 2, conclusion: 'cancelled' }),
+          makeRun({ id: 1, updated_at: '2024-03-01T10:05:00Z' }),
+        ].slice(0, limit),
+      listJobs: async () => JOBS,
+    };
+
+    expect(await importWorkflowHistory(source, history, 'owner/repo', 10)).toEqual({
+      imported: 2,
+      skipped: 1,
+    });
+    expect((await history.load()).map((record) => record.runId)).toEqual([1, 3]);
+
+    expect(await importWorkflowHistory(source, history, 'owner/repo', 10)).toEqual({
+      imported: 0,
+      skipped: 3,
+    });
+    expect(await history.load()).toHaveLength(2);
+  });
+});
@@ -0,0 +1,83 @@
+import { describe, it, expect, beforeEach } from 'bun:test';
+import type { PipelineEvent } from '../events';
+import { HookRunner, type HookName, runHookCommand } from '../hooks';
+import { InMemoryDiscordApi } from '../inMemoryDiscordApi';
+import { InMemoryStorage, PipelineTracker } from '../pipelineTracker';
+import { silenceConsole } from './helpers';
+
+describe('Hook commands', () => {
+  silenceConsole('log', 'warn');
+
+  describe('runHookCommand', () => {
+    it('should pass the input on stdin', async () => {
+      await runHookCommand('test "$(cat)" = \'{"a":1}\'', '{"a":1}', 5000);
+    });
+
+    it('should reject with the exit code and stderr', async () => {
+      await expect(runHookCommand('echo boom >&2; exit 3', '', 5000)).rejects.toThrow(
+        'exited with code 3: boom'
+      );
+    });
+
+    it('should kill a command that runs out of time', async () => {
+      await expect(runHookCommand('sleep 5', '', 50)).rejects.toThrow('timed out after 50ms');
+    });
+  });
+
+  describe('tracker integration', () => {
+    let api: InMemoryDiscordApi;
+    let calls: Array<{ command: string; event: PipelineEvent }>;
+
+    beforeEach(() => {
+      api = new InMemoryDiscordApi();
+      calls = [];
+    });
+
+    function createTracker(commands: Partial<Record<HookName, string>>): PipelineTracker {
+      const hooks = new HookRunner(commands, 1000, async (command, input) => {
+        calls.push({ command, event: JSON.parse(input) });
+        if (command === 'broken') {
+          throw new Error('exited with code 1');
+        }
+      });
+      return new PipelineTracker('test-token', api.channelId, new InMemoryStorage(), {
+        api,
+        hooks,
+      });
+    }
+
+    it('should run each hook with its event, and on_fail once per failure', async () => {
+      const tracker = createTracker({
+        on_init: 'init',
+        on_step: 'step',
+        on_complete: 'complete',
+        on_fail: 'fail',
+      });
+      await tracker.initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+      await tracker.updateStep(1, 1, 'Deploy', 'failed', []);
+      await tracker.updateStep(1, 1, 'Deploy', 'failed', [['error', 'timeout']]);
+      await tracker.completePipeline();
+
+      expect(calls.map((call) => call.command)).toEqual([
+        'init',
+        'step',
+        'fail',
+        'step',
+        'complete',
+      ]);
+      expect(calls[2].event.type).toBe('step.updated');
+      expect(calls[2].event.step).toMatchObject({ name: 'Deploy', status: 'failed' });
+      expect(calls[4].event.conclusion).toBe('failed');
+    });
+
+    it('should report a failing hook as a warning and keep going', async () => {
+      const tracker = createTracker({ on_init: 'broken' });
+      await tracker.initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+
+      expect(tracker.getMessageId()).toBeTruthy();
+      expect(tracker.getWarnings()).toEqual([
+        { code: 'HOOK_FAILED', message: 'The on_init hook failed: exited with code 1' },
+      ]);
+    });
+  });
+});
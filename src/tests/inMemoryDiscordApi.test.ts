@@ -0,0 +1,70 @@
+import { describe, it, expect, beforeEach } from 'bun:test';
+import { InMemoryDiscordApi } from '../inMemoryDiscordApi';
+import { InMemoryStorage, PipelineTracker, type TrackerOptions } from '../pipelineTracker';
+
+describe('In-memory Discord API', () => {
+  let api: InMemoryDiscordApi;
+  let storage: InMemoryStorage;
+
+  beforeEach(() => {
+    api = new InMemoryDiscordApi({ guild_id: '200000000000000000' });
+    storage = new InMemoryStorage();
+  });
+
+  function createTracker(options: TrackerOptions = {}): PipelineTracker {
+    return new PipelineTracker('test-token', 'test-channel', storage, { ...options, api });
+  }
+
+  it('should let the tracker run a pipeline without the network', async () => {
+    const tracker = createTracker({ pin: true, reactions: true });
+    await tracker.initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+    await tracker.updateStep(1, 1, 'Build', 'success', []);
+    await tracker.completePipeline();
+
+    const [pipeline] = api.channelMessages();
+    expect(api.channelMessages()).toHaveLength(1);
+    expect(pipeline.message.embeds?.[0].title).toContain('Pipeline Completed');
+    expect(pipeline.edits).toBe(2);
+    expect(pipeline.pinned).toBe(false);
+    expect([...pipeline.reactions]).toEqual(['✅']);
+  });
+
+  it('should hold thread replies apart from the channel messages', async () => {
+    const tracker = createTracker({ threads: true });
+    await tracker.initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+    await tracker.updateStep(1, 2, 'Build', 'success', []);
+
+    expect(api.channelMessages()).toHaveLength(1);
+    const [threadId] = api.threads.keys();
+    const replies = [...api.messages.values()].filter((stored) => stored.threadId === threadId);
+    expect(replies.map((stored) => stored.message.content)).toEqual([
+      '✅ **Step 1: Build** - Success',
+    ]);
+  });
+
+  it('should list channel messages newest first, paging with before', async () => {
+    const ids: string[] = [];
+    for (let i = 0; i < 3; i++) {
+      ids.push(await api.sendMessage({ content: `message ${i}` }));
+    }
+
+    expect((await api.listMessages()).map((message) => message.id)).toEqual([...ids].reverse());
+    expect((await api.listMessages(ids[2], 1)).map((message) => message.content)).toEqual([
+      'message 1',
+    ]);
+  });
+
+  it('should reject unknown messages like Discord', async () => {
+    await expect(api.updateMessage('404', { content: '' })).rejects.toThrow(
+      'Discord API Error: 404'
+    );
+  });
+
+  it('should pass the verification of its channel', async () => {
+    expect(await createTracker().verifyChannel()).toEqual({
+      channelId: '100000000000000000',
+      channelType: 'text channel',
+      name: undefined,
+    });
+  });
+});
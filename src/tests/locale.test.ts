@@ -0,0 +1,48 @@
+import { describe, it, expect } from 'bun:test';
+import { formatDecimal, formatMinutesSeconds, formatPercent } from '../locale';
+import { buildCompletionEmbed, formatCost, formatDuration } from '../messageBuilder';
+import { StepInfoManager, StepStatus } from '../models';
+import { formatBytes } from '../resources';
+
+describe('Locale-aware formatting', () => {
+  it('should keep the locale-independent output without a locale', () => {
+    expect(formatDecimal(1234.5, 1)).toBe('1234.5');
+    expect(formatPercent(12.5, 1)).toBe('12.5%');
+    expect(formatDuration(185000)).toBe('3m 5s');
+    expect(formatCost(0.05)).toBe('$0.050');
+    expect(formatBytes(1536 * 1024)).toBe('1.5 MiB');
+  });
+
+  it('should use the decimal comma and grouping of the locale', () => {
+    expect(formatDecimal(1234.5, 1, 'de-DE')).toBe('1.234,5');
+    expect(formatCost(1.5, 'de-DE')).toBe('$1,50');
+    expect(formatBytes(1536 * 1024, 'de-DE')).toBe('1,5\u202fMiB');
+  });
+
+  it('should separate the percent sign as the locale does', () => {
+    expect(formatPercent(12.5, 1, 'en-US')).toBe('12.5%');
+    // CLDR versions differ on which no-break space precedes the sign
+    expect(formatPercent(12.5, 1, 'fr-FR')).toMatch(/^12,5\s%$/);
+  });
+
+  it('should use the narrow unit names of the locale for durations', () => {
+    expect(formatMinutesSeconds(3, 5, 'en-US')).toBe('3m 5s');
+    expect(formatDuration(185000, 'fr-FR')).toBe('3min 5s');
+  });
+
+  it('should format the completion embed for the configured locale', () => {
+    const steps = [
+      StepInfoManager.new(1, 'Build', StepStatus.Success, []),
+      StepInfoManager.new(2, 'Test', StepStatus.Running, []),
+      StepInfoManager.new(3, 'Deploy', StepStatus.Pending, []),
+    ];
+    const embed = buildCompletionEmbed('1', 'Test PR', steps, 3, new Date(), {
+      percentDecimals: 1,
+      progressDisplay: 'percent',
+      locale: 'de-DE',
+    });
+
+    const completion = embed.fields?.find((f) => f.name === '📈 Completion');
+    expect(completion?.value).toMatch(/^33,3\s%$/);
+  });
+});
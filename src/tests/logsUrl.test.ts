@@ -0,0 +1,46 @@
+import { describe, it, expect, beforeEach } from 'bun:test';
+import { InMemoryDiscordApi } from '../inMemoryDiscordApi';
+import { InMemoryStorage, PipelineTracker } from '../pipelineTracker';
+import { silenceConsole } from './helpers';
+
+describe('Step logs links', () => {
+  const logsUrl = 'https://github.com/owner/repo/actions/runs/1/job/2';
+  let api: InMemoryDiscordApi;
+
+  silenceConsole();
+
+  beforeEach(() => {
+    api = new InMemoryDiscordApi();
+  });
+
+  function stepField(tracker: PipelineTracker): string | undefined {
+    const [embed] = api.messages.get(tracker.getMessageId() as string)?.message.embeds || [];
+    return embed.fields?.find((field) => field.name === 'Step 1')?.value;
+  }
+
+  it('should take the link out of the additional info and keep it on the step', async () => {
+    const tracker = new PipelineTracker('test-token', api.channelId, new InMemoryStorage(), {
+      api,
+    });
+    await tracker.initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+    await tracker.updateStep(1, 1, 'Tests', 'running', [
+      ['logs_url', logsUrl],
+      ['shard', '2'],
+    ]);
+    expect(tracker.getSteps()[0].additionalInfo).toEqual([['shard', '2']]);
+
+    await tracker.updateStep(1, 1, 'Tests', 'failed', []);
+    expect(stepField(tracker)).toContain(`[📜 Logs](${logsUrl})`);
+  });
+
+  it('should leave a value that is not a URL as a plain pair', async () => {
+    const tracker = new PipelineTracker('test-token', api.channelId, new InMemoryStorage(), {
+      api,
+    });
+    await tracker.initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+    await tracker.updateStep(1, 1, 'Tests', 'running', [['logs_url', 'see CI']]);
+
+    expect(tracker.getSteps()[0].logsUrl).toBeUndefined();
+    expect(stepField(tracker)).toContain('**logs\\_url:** see CI');
+  });
+});
@@ -1,5 +1,42 @@
 import { describe, it, expect } from 'vitest';
-import { buildInitEmbed, buildStepUpdateEmbed, buildCompletionEmbed } from '../messageBuilder';
+import {
+  buildAvatarUrl,
+  buildBoardEmbed,
+  buildChangelogEmbed,
+  buildCommitUrl,
+  buildContentFallback,
+  buildInitEmbed,
+  buildLinkButtons,
+  buildMetadataFields,
+  buildStepDetailEmbeds,
+  buildStepUpdateEmbed,
+  buildCompletionEmbed,
+  buildStatusLine,
+  buildTagsField,
+  embedLength,
+  enforceEmbedLimits,
+  escapeMarkdown,
+  escapeUserText,
+  estimateRunCost,
+  fitChecklist,
+  formatCodeBlock,
+  formatPercentage,
+  formatPhaseRollup,
+  formatProgressBar,
+  formatScanBadge,
+  formatTable,
+  neutralizeMentions,
+  sanitizeEmbed,
+  splitMessageEmbeds,
+  splitScanReport,
+  stripVolatileFields,
+  truncateText,
+  withCommitField,
+  withDecorations,
+  withPipelineLinks,
+  withRunTimingFields,
+} from '../messageBuilder';
+import { frozenClock } from '../clock';
 import { StepInfo, StepStatus, StepInfoManager } from '../models';
 
 describe('MessageBuilder', () => {
@@ -178,3 +215,1022 @@ describe('MessageBuilder', () => {
     expect(embed.fields!.length).toBeGreaterThan(0);
   });
 });
+
+describe('Progress display configuration', () => {
+  const steps: StepInfo[] = [
+    { number: 1, name: 'Build', status: StepStatus.Success, additionalInfo: [] },
+    { number: 2, name: 'Test', status: StepStatus.Running, additionalInfo: [] },
+    { number: 3, name: 'Deploy', status: StepStatus.Pending, additionalInfo: [] },
+  ];
+
+  it('should round percentages instead of truncating', () => {
+    expect(formatPercentage(2, 3, 0)).toBe('67');
+    expect(formatPercentage(1, 3, 1)).toBe('33.3');
+    expect(formatPercentage(2, 3, 2)).toBe('66.67');
+    expect(formatPercentage(0, 0, 1)).toBe('0.0');
+  });
+
+  it('should show both steps and percentage by default', () => {
+    const embed = buildStepUpdateEmbed('123', 'Test PR', steps, 2, 3);
+    const progressField = embed.fields!.find((field) => field.name === '📊 Progress');
+
+    expect(progressField?.value).toBe('1/3 steps completed (33%)');
+  });
+
+  it('should support steps-only and percent-only display', () => {
+    const stepsOnly = buildStepUpdateEmbed('123', 'Test PR', steps, 2, 3, {
+      progressDisplay: 'steps',
+    });
+    const percentOnly = buildStepUpdateEmbed('123', 'Test PR', steps, 2, 3, {
+      progressDisplay: 'percent',
+      percentDecimals: 1,
+    });
+
+    expect(stepsOnly.fields!.find((f) => f.name === '📊 Progress')?.value).toBe(
+      '1/3 steps completed'
+    );
+    expect(percentOnly.fields!.find((f) => f.name === '📊 Progress')?.value).toBe('33.3%');
+  });
+
+  it('should apply the display mode to the completion embed', () => {
+    const embed = buildCompletionEmbed('123', 'Test PR', steps, 3, new Date(), {
+      progressDisplay: 'percent',
+    });
+
+    expect(embed.fields!.find((f) => f.name === '📈 Completion')?.value).toBe('33%');
+  });
+
+  it('should draw a progress bar with the percentage', () => {
+    const embed = buildStepUpdateEmbed('123', 'Test PR', steps, 2, 3, { progressDisplay: 'bar' });
+
+    expect(embed.fields!.find((f) => f.name === '📊 Progress')?.value).toBe('▰▱▱ 33%');
+  });
+
+  it('should use one segment per step up to ten segments', () => {
+    expect(formatProgressBar(3, 7)).toBe('▰▰▰▱▱▱▱');
+    expect(formatProgressBar(7, 7)).toBe('▰▰▰▰▰▰▰');
+    expect(formatProgressBar(15, 30)).toBe('▰▰▰▰▰▱▱▱▱▱');
+    expect(formatProgressBar(0, 0)).toBe('▱');
+  });
+});
+
+describe('Run cost estimation', () => {
+  const steps: StepInfo[] = [
+    { number: 1, name: 'Build', status: StepStatus.Success, additionalInfo: [] },
+  ];
+
+  it('should bill every started minute', () => {
+    expect(estimateRunCost(61000, 0.008)).toBeCloseTo(0.016);
+    expect(estimateRunCost(0, 0.008)).toBeCloseTo(0.008);
+  });
+
+  it('should only show the cost field when a cost per minute is configured', () => {
+    const startTime = new Date(Date.now() - 90000);
+    const withoutCost = buildCompletionEmbed('123', 'Test PR', steps, 1, startTime);
+    const withCost = buildCompletionEmbed('123', 'Test PR', steps, 1, startTime, {
+      costPerMinute: 0.008,
+    });
+
+    expect(withoutCost.fields!.some((f) => f.name === '💰 Estimated Cost')).toBe(false);
+    expect(withCost.fields!.find((f) => f.name === '💰 Estimated Cost')?.value).toBe('$0.016');
+  });
+});
+
+describe('Pipeline tags', () => {
+  it('should render tags as a compact field', () => {
+    expect(
+      buildTagsField([
+        ['team', 'payments'],
+        ['tier', 'critical'],
+      ])
+    ).toEqual({
+      name: '🏷️ Tags',
+      value: '`team=payments` `tier=critical`',
+      inline: false,
+    });
+  });
+
+  it('should omit the field when there are no tags', () => {
+    expect(buildTagsField([])).toBeUndefined();
+  });
+});
+
+describe('Status line', () => {
+  it('should describe running, completed and failed pipelines', () => {
+    const running: StepInfo[] = [
+      { number: 1, name: 'Build', status: StepStatus.Success, additionalInfo: [] },
+      { number: 2, name: 'Test', status: StepStatus.Running, additionalInfo: [] },
+    ];
+    const failed: StepInfo[] = [
+      { number: 1, name: 'Build', status: StepStatus.Failed, additionalInfo: [] },
+    ];
+
+    expect(buildStatusLine(running, false)).toBe('🔄 Running - 1/2 steps completed');
+    expect(buildStatusLine(running.slice(0, 1), true)).toBe('✅ Completed - 1/1 steps completed');
+    expect(buildStatusLine(failed, true)).toBe('❌ Failed - 1/1 steps completed');
+  });
+});
+
+describe('Embed sanitization', () => {
+  it('should replace empty field names and values with a placeholder', () => {
+    const embed = sanitizeEmbed({
+      title: 'Test',
+      fields: [
+        { name: '', value: 'value', inline: false },
+        { name: 'Empty', value: '  ', inline: false },
+      ],
+    });
+
+    expect(embed.fields).toEqual([
+      { name: '—', value: 'value', inline: false },
+      { name: 'Empty', value: '—', inline: false },
+    ]);
+  });
+
+  it('should rename duplicate field names', () => {
+    const embed = sanitizeEmbed({
+      fields: [
+        { name: 'Step 1', value: 'a', inline: false },
+        { name: 'Step 1', value: 'b', inline: false },
+      ],
+    });
+
+    expect(embed.fields?.map((field) => field.name)).toEqual(['Step 1', 'Step 1 (2)']);
+  });
+
+  it('should render empty additional info values as a placeholder', () => {
+    const steps: StepInfo[] = [
+      { number: 1, name: 'Build', status: StepStatus.Success, additionalInfo: [['version', '']] },
+    ];
+
+    const embed = buildStepUpdateEmbed('1', 'Test', steps, 1, 1);
+    const stepField = embed.fields?.find((field) => field.name === 'Step 1');
+
+    expect(stepField?.value).toContain('**version:** —');
+  });
+});
+
+describe('Step tables', () => {
+  it('should align columns under a header separator', () => {
+    expect(
+      formatTable([
+        ['file', 'size'],
+        ['main.js', '120 kB'],
+      ])
+    ).toBe('```\nfile    | size\n--------|-------\nmain.js | 120 kB\n```');
+  });
+
+  it('should truncate long cells and extra rows', () => {
+    const rows = [['name'], ...Array.from({ length: 12 }, (_, i) => [`row-${i}`])];
+    rows[1][0] = 'a-very-long-benchmark-name';
+
+    const table = formatTable(rows);
+
+    expect(table).toContain('a-very-long-benchma…');
+    expect(table).toContain('row-9');
+    expect(table).not.toContain('row-10');
+    expect(table).toContain('… 2 more rows');
+  });
+
+  it('should render tables under the step', () => {
+    const steps: StepInfo[] = [
+      {
+        number: 1,
+        name: 'Bundle',
+        status: StepStatus.Success,
+        additionalInfo: [],
+        tables: [{ title: 'Sizes', rows: [['file'], ['main.js']] }],
+      },
+    ];
+
+    const embed = buildStepUpdateEmbed('1', 'Test', steps, 1, 1);
+    const stepField = embed.fields?.find((field) => field.name === 'Step 1');
+
+    expect(stepField?.value).toContain('**Sizes**\n```\nfile');
+  });
+});
+
+describe('Step list density', () => {
+  const steps: StepInfo[] = [
+    {
+      number: 1,
+      name: 'Build',
+      status: StepStatus.Success,
+      additionalInfo: [['artifact', 'app.zip']],
+      startedAt: new Date('2024-01-01T10:00:00Z'),
+      completedAt: new Date('2024-01-01T10:01:05Z'),
+    },
+    { number: 2, name: 'Test', status: StepStatus.Running, additionalInfo: [] },
+  ];
+
+  it('should show step durations and additional info by default', () => {
+    const embed = buildStepUpdateEmbed('1', 'Test', steps, 2, 2);
+    const stepField = embed.fields?.find((field) => field.name === 'Step 1');
+
+    expect(stepField?.value).toBe('✅ **Build** - Success (1m 5s)\n└ **artifact:** app.zip');
+  });
+
+  it('should hide durations, info and numbers when configured', () => {
+    const embed = buildStepUpdateEmbed('1', 'Test', steps, 2, 2, {
+      showStepDurations: false,
+      showAdditionalInfo: false,
+      showStepNumbers: false,
+    });
+    const stepField = embed.fields?.find((field) => field.name === 'Build');
+
+    expect(stepField?.value).toBe('✅ **Build** - Success');
+  });
+
+  it('should render the steps as a single line', () => {
+    const embed = buildStepUpdateEmbed('1', 'Test', steps, 2, 2, { stepListStyle: 'inline' });
+
+    expect(embed.fields?.find((field) => field.name === 'Step 1')).toBeUndefined();
+    expect(embed.fields?.find((field) => field.name === '📝 Steps')?.value).toBe(
+      '✅ 1. Build (1m 5s), 🔄 2. Test'
+    );
+  });
+
+  it('should render the steps as checkboxes', () => {
+    const embed = buildStepUpdateEmbed('1', 'Test', steps, 2, 2, {
+      stepListStyle: 'checkboxes',
+      showStepNumbers: false,
+    });
+
+    expect(embed.fields?.find((field) => field.name === '📝 Steps')?.value).toBe(
+      '- [x] Build (1m 5s)\n- [ ] Test'
+    );
+  });
+
+  it('should use the configured status emoji', () => {
+    const statusEmoji = { [StepStatus.Success]: '<:ci_pass:123456789012345678>' };
+    const inline = buildStepUpdateEmbed('1', 'Test', steps, 2, 2, {
+      stepListStyle: 'inline',
+      statusEmoji,
+    });
+    const completion = buildCompletionEmbed('1', 'Test', steps, 2, new Date(), { statusEmoji });
+
+    expect(inline.fields?.find((field) => field.name === '📝 Steps')?.value).toBe(
+      '<:ci_pass:123456789012345678> 1. Build (1m 5s), 🔄 2. Test'
+    );
+    const summary = completion.fields?.find((field) => field.name === '📝 Steps Summary');
+    expect(summary?.value).toContain('<:ci_pass:123456789012345678> Build');
+  });
+});
+
+describe('Link Buttons', () => {
+  it('should build a row with run and PR link buttons', () => {
+    const rows = buildLinkButtons({
+      runUrl: 'https://github.com/owner/repo/actions/runs/1',
+      prUrl: 'https://github.com/owner/repo/pull/2',
+    });
+
+    expect(rows).toHaveLength(1);
+    expect(rows[0].type).toBe(1);
+    expect(rows[0].components.map((button) => [button.label, button.url, button.style])).toEqual([
+      ['View Run', 'https://github.com/owner/repo/actions/runs/1', 5],
+      ['View PR', 'https://github.com/owner/repo/pull/2', 5],
+    ]);
+  });
+
+  it('should only include the links that are set', () => {
+    const rows = buildLinkButtons({ prUrl: 'https://github.com/owner/repo/pull/2' });
+
+    expect(rows[0].components.map((button) => button.label)).toEqual(['View PR']);
+  });
+
+  it('should build no rows without links', () => {
+    expect(buildLinkButtons({})).toEqual([]);
+  });
+});
+
+describe('Multi-embed layout', () => {
+  function createStep(number: number, status: StepStatus, phase?: string): StepInfo {
+    const step = StepInfoManager.new(number, `Step ${phase || ''}${number}`, status, []);
+    step.phase = phase;
+    return step;
+  }
+
+  it('should keep step fields out of the summary embed', () => {
+    const steps = [createStep(1, StepStatus.Success)];
+    const embed = buildStepUpdateEmbed('1', 'Test', steps, 1, 1, { embedLayout: 'multi' });
+
+    expect(embed.fields?.map((field) => field.name)).toEqual([
+      '📊 Progress',
+      '🎯 Current Step',
+      '📋 Status',
+      '📝 Steps',
+    ]);
+    expect(buildStepDetailEmbeds(steps, { embedLayout: 'multi' })).toHaveLength(1);
+  });
+
+  it('should build one details embed per phase', () => {
+    const steps = [
+      createStep(1, StepStatus.Success, 'build'),
+      createStep(1, StepStatus.Failed, 'deploy'),
+    ];
+    const embeds = buildStepDetailEmbeds(steps, { embedLayout: 'multi' });
+
+    expect(embeds.map((embed) => [embed.title, embed.color])).toEqual([
+      ['🧩 build', 0x00ff00],
+      ['🧩 deploy', 0xff0000],
+    ]);
+  });
+
+  it('should merge phases beyond the embed limit into the last details embed', () => {
+    const steps = Array.from({ length: 12 }, (_, i) => createStep(1, StepStatus.Success, `p${i}`));
+    const embeds = buildStepDetailEmbeds(steps, { embedLayout: 'multi' });
+
+    expect(embeds).toHaveLength(9);
+    expect(embeds[8].fields?.filter((field) => field.name.startsWith('🧩'))).toHaveLength(3);
+  });
+
+  it('should build no details embeds in the single layout', () => {
+    expect(buildStepDetailEmbeds([createStep(1, StepStatus.Success)])).toEqual([]);
+  });
+
+  it('should move embeds that do not fit into the overflow reply', () => {
+    const summary = { title: 'Summary', fields: [{ name: 'a', value: 'b' }] };
+    const details = [1, 2, 3].map((i) => ({ title: `Details ${i}`, description: 'x'.repeat(2500) }));
+    const { main, overflow } = splitMessageEmbeds([summary, ...details]);
+
+    expect(main.map((embed) => embed.title)).toEqual(['Summary', 'Details 1', 'Details 2']);
+    expect(main[0].fields?.[1].name).toBe('📎 More details');
+    expect(overflow.map((embed) => embed.title)).toEqual(['Details 3']);
+  });
+});
+
+describe('Checklist truncation', () => {
+  function stepItem(number: number, status: StepStatus) {
+    const step = StepInfoManager.new(number, `Step ${number}`, status, []);
+    return { text: `${status} ${step.name}`, separator: '\n', step };
+  }
+
+  it('should keep a list that fits unchanged', () => {
+    const items = [stepItem(1, StepStatus.Success), stepItem(2, StepStatus.Failed)];
+
+    expect(fitChecklist(items)).toBe('success Step 1\nfailed Step 2');
+  });
+
+  it('should collapse the longest run of passed steps first', () => {
+    const items = [
+      stepItem(1, StepStatus.Success),
+      stepItem(2, StepStatus.Failed),
+      ...[3, 4, 5, 6].map((n) => stepItem(n, StepStatus.Success)),
+      stepItem(7, StepStatus.Running),
+    ];
+
+    expect(fitChecklist(items, 80)).toBe(
+      'success Step 1\nfailed Step 2\n… 4 steps passed …\nrunning Step 7'
+    );
+  });
+
+  it('should keep failed and running steps visible in long compact lists', () => {
+    const steps = Array.from({ length: 120 }, (_, i) =>
+      StepInfoManager.new(i + 1, `A fairly long step name ${i + 1}`, StepStatus.Success, [])
+    );
+    steps[59].status = StepStatus.Failed;
+    steps[119].status = StepStatus.Running;
+
+    const embed = buildStepUpdateEmbed('1', 'PR', steps, 120, 120, { stepListStyle: 'checkboxes' });
+    const list = embed.fields?.find((field) => field.name === '📝 Steps')?.value || '';
+
+    expect(list.length).toBeLessThanOrEqual(1024);
+    expect(list).toContain('A fairly long step name 60 ❌');
+    expect(list).toContain('A fairly long step name 120');
+    expect(list).toContain('steps passed …');
+  });
+
+  it('should cut off a list that cannot be collapsed further', () => {
+    const items = Array.from({ length: 100 }, (_, i) => stepItem(i, StepStatus.Failed));
+    const value = fitChecklist(items, 100);
+
+    expect(value).toHaveLength(100);
+    expect(value.endsWith('…')).toBe(true);
+  });
+});
+
+describe('Content fallback', () => {
+  const prInfo = {
+    number: '42',
+    title: 'Add caching',
+    author: 'octocat',
+    repository: 'owner/repo',
+    branch: 'feature',
+  };
+
+  it('should summarize a running step with progress and the PR link', () => {
+    const steps = [
+      StepInfoManager.new(1, 'Build', StepStatus.Success, []),
+      StepInfoManager.new(2, 'Test', StepStatus.Running, []),
+    ];
+
+    expect(buildContentFallback(prInfo, steps, 4, 'running')).toBe(
+      '🔄 Test · PR #42 Add caching · 1/4 steps (25%) · <https://github.com/owner/repo/pull/42>'
+    );
+  });
+
+  it('should name the failed step and prefer the configured PR URL', () => {
+    const steps = [
+      StepInfoManager.new(1, 'Build', StepStatus.Failed, []),
+      StepInfoManager.new(2, 'Test', StepStatus.Running, []),
+    ];
+    const content = buildContentFallback(
+      prInfo,
+      steps,
+      2,
+      'running',
+      { progressDisplay: 'steps' },
+      'https://example.com/pr/42'
+    );
+
+    expect(content).toBe(
+      '❌ Build failed · PR #42 Add caching · 1/2 steps · <https://example.com/pr/42>'
+    );
+  });
+
+  it('should report the outcome on completion and omit progress before any step', () => {
+    const steps = [StepInfoManager.new(1, 'Build', StepStatus.Success, [])];
+
+    expect(buildContentFallback(prInfo, steps, 1, 'completed')).toMatch(
+      /^✅ Pipeline succeeded · PR #42 /
+    );
+    expect(buildContentFallback(prInfo, [], 0, 'started')).toBe(
+      '🚀 Pipeline started · PR #42 Add caching · <https://github.com/owner/repo/pull/42>'
+    );
+  });
+
+  it('should cut long PR titles', () => {
+    const content = buildContentFallback({ ...prInfo, title: 'x'.repeat(300) }, [], 0, 'started');
+
+    expect(content).toContain(`${'x'.repeat(99)}…`);
+    expect(content.length).toBeLessThan(200);
+  });
+});
+
+describe('Embed limits', () => {
+  it('should truncate text with an ellipsis only when it is too long', () => {
+    expect(truncateText('short', 10)).toBe('short');
+    expect(truncateText('0123456789', 5)).toBe('0123…');
+    expect(truncateText('anything', 0)).toBe('');
+  });
+
+  it('should leave embeds within the limits unchanged', () => {
+    const embed = buildInitEmbed('1', 'Title', 'octocat', 'owner/repo', 'main');
+
+    expect(enforceEmbedLimits([embed])).toEqual([embed]);
+  });
+
+  it('should truncate each part to its own limit', () => {
+    const [embed] = enforceEmbedLimits([
+      {
+        title: 't'.repeat(300),
+        description: 'd'.repeat(5000),
+        fields: [{ name: 'n'.repeat(300), value: 'v'.repeat(2000), inline: false }],
+        footer: { text: 'f'.repeat(100) },
+      },
+    ]);
+
+    expect(embed.title).toHaveLength(256);
+    expect(embed.title?.endsWith('…')).toBe(true);
+    expect(embed.description).toHaveLength(4096);
+    expect(embed.fields?.[0].name).toHaveLength(256);
+    expect(embed.fields?.[0].value).toHaveLength(1024);
+    expect(embed.footer?.text).toHaveLength(100);
+  });
+
+  it('should keep at most 25 fields', () => {
+    const fields = Array.from({ length: 30 }, (_, i) => ({ name: `F${i}`, value: 'v' }));
+
+    expect(enforceEmbedLimits([{ fields }])[0].fields).toHaveLength(25);
+  });
+
+  it('should share the total budget between embeds, dropping what no longer fits', () => {
+    const big = (title: string) => ({
+      title,
+      description: 'd'.repeat(4000),
+      fields: [{ name: 'Error', value: 'e'.repeat(1000) }],
+      footer: { text: 'footer' },
+    });
+    const embeds = enforceEmbedLimits([big('First'), big('Second')]);
+
+    expect(embeds.reduce((sum, embed) => sum + embedLength(embed), 0)).toBeLessThanOrEqual(6000);
+    expect(embeds[0]).toEqual(big('First'));
+    expect(embeds[1].title).toBe('Second');
+    expect(embeds[1].description?.endsWith('…')).toBe(true);
+    expect(embeds[1].fields).toEqual([]);
+    expect(embeds[1].footer).toBeUndefined();
+  });
+
+  it('should count and truncate the author name', () => {
+    const author = { name: 'a'.repeat(300), icon_url: 'https://github.com/octocat.png' };
+
+    expect(embedLength({ title: 'Title', author: { name: 'octocat' } })).toBe(12);
+    expect(enforceEmbedLimits([{ author }])[0].author).toEqual({
+      ...author,
+      name: `${'a'.repeat(255)}…`,
+    });
+  });
+});
+
+describe('Embed decorations', () => {
+  it('should add the title link, author, thumbnail and image', () => {
+    const embed = withDecorations(buildInitEmbed('1', 'Title', 'octocat', 'owner/repo', 'main'), {
+      url: 'https://github.com/owner/repo/pull/1',
+      author: { name: 'octocat', icon_url: 'https://github.com/octocat.png' },
+      thumbnailUrl: 'https://github.com/owner.png',
+      imageUrl: 'https://example.com/banner.png',
+    });
+
+    expect(embed.url).toBe('https://github.com/owner/repo/pull/1');
+    expect(embed.author).toEqual({ name: 'octocat', icon_url: 'https://github.com/octocat.png' });
+    expect(embed.thumbnail).toEqual({ url: 'https://github.com/owner.png' });
+    expect(embed.image).toEqual({ url: 'https://example.com/banner.png' });
+  });
+
+  it('should skip an author without a name and keep values set by the builder', () => {
+    const embed = withDecorations(
+      { title: 'Title', url: 'https://example.com/own' },
+      { url: 'https://example.com/configured', author: { name: '', icon_url: 'https://x.png' } }
+    );
+
+    expect(embed).toEqual({ title: 'Title', url: 'https://example.com/own' });
+  });
+});
+
+describe('Scan reports', () => {
+  const report = 'https://scanner.example.com/reports/812';
+
+  it('should render the scan report as a link with its badge', () => {
+    const steps: StepInfo[] = [
+      {
+        number: 1,
+        name: 'Scan image',
+        status: StepStatus.Failed,
+        additionalInfo: [
+          ['image', 'app:1.4.2'],
+          ['scan_report', report],
+          ['scan_result', 'vulnerable'],
+        ],
+      },
+    ];
+    const embed = buildStepUpdateEmbed('1', 'Test', steps, 1, 1);
+    const stepField = embed.fields?.find((field) => field.name === 'Step 1');
+
+    expect(stepField?.value).toBe(
+      `❌ **Scan image** - Failed\n└ **image:** app:1.4.2\n└ 🔍 [Scan report](${report}) ❌ Failed`
+    );
+  });
+
+  it('should parse the scan result badge', () => {
+    expect(formatScanBadge('PASSED')).toBe('✅ Passed');
+    expect(formatScanBadge('3 critical')).toBe('⚠️ 3 critical');
+    expect(splitScanReport([['scan_report', 'not a url']]).scanReport).toBeUndefined();
+  });
+});
+
+describe('Pipeline links', () => {
+  const links = {
+    runUrl: 'https://github.com/owner/repo/actions/runs/123',
+    prUrl: 'https://github.com/owner/repo/pull/42',
+  };
+
+  it('should link the title to the run and list the links in the description', () => {
+    const embed = withPipelineLinks({ title: 'Pipeline', description: '**Test PR**' }, links);
+
+    expect(embed.url).toBe(links.runUrl);
+    expect(embed.description).toBe(
+      `**Test PR**\n[🏃 Workflow run](${links.runUrl}) · [🔀 Pull request](${links.prUrl})`
+    );
+  });
+
+  it('should keep an existing title link and leave embeds without links alone', () => {
+    const embed = { title: 'Pipeline', url: 'https://example.com' };
+
+    expect(withPipelineLinks(embed, links).url).toBe('https://example.com');
+    expect(withPipelineLinks(embed, {})).toBe(embed);
+  });
+});
+
+describe('Themes', () => {
+  const steps: StepInfo[] = [
+    {
+      number: 1,
+      name: 'Build',
+      status: StepStatus.Success,
+      additionalInfo: [['artifact', 'app.zip']],
+      startedAt: new Date('2024-01-01T10:00:00Z'),
+      completedAt: new Date('2024-01-01T10:01:05Z'),
+    },
+    { number: 2, name: 'Test', status: StepStatus.Running, additionalInfo: [] },
+  ];
+
+  it('should render a one-line embed without fields in the compact theme', () => {
+    const embed = buildStepUpdateEmbed('1', 'Test PR', steps, 2, 2, { theme: 'compact' });
+
+    expect(embed.title).toBe('🔄 Pipeline Update - PR #1');
+    expect(embed.fields).toBeUndefined();
+    expect(embed.description).toBe('**Test PR** · 🔄 Running 1/2\n✅ Build · 🔄 Test');
+    expect(buildStepDetailEmbeds(steps, { theme: 'compact', embedLayout: 'multi' })).toEqual([]);
+  });
+
+  it('should show every step detail in the detailed theme', () => {
+    const embed = buildStepUpdateEmbed('1', 'Test PR', steps, 2, 2, {
+      theme: 'detailed',
+      stepListStyle: 'inline',
+      showAdditionalInfo: false,
+    });
+    const stepField = embed.fields?.find((field) => field.name === 'Step 1');
+
+    expect(stepField?.value).toBe(
+      '✅ **Build** - Success (1m 5s)\n🕐 <t:1704103200:T> → <t:1704103265:T>\n' +
+        '└ **artifact:** app.zip'
+    );
+  });
+});
+
+describe('Color scheme', () => {
+  const steps: StepInfo[] = [
+    { number: 1, name: 'Build', status: StepStatus.Failed, additionalInfo: [] },
+  ];
+  const colors = {
+    init: 0x111111,
+    inProgress: 0x222222,
+    success: 0x333333,
+    failure: 0x444444,
+    skipped: 0x555555,
+    warning: 0x666666,
+    cancelled: 0x777777,
+  };
+
+  it('should color the pipeline embeds from the configured scheme', () => {
+    expect(buildInitEmbed('1', 'Test', 'dev', 'o/r', 'main', { colors }).color).toBe(0x111111);
+    expect(buildStepUpdateEmbed('1', 'Test', steps, 1, 2, { colors }).color).toBe(0x222222);
+    expect(buildCompletionEmbed('1', 'Test', steps, 1, new Date(), { colors }).color).toBe(
+      0x444444
+    );
+  });
+});
+
+describe('Run timing fields', () => {
+  it('should show the queue and billable time right after the duration', () => {
+    const steps: StepInfo[] = [
+      { number: 1, name: 'Build', status: StepStatus.Success, additionalInfo: [] },
+    ];
+    const embed = withRunTimingFields(
+      buildCompletionEmbed('1', 'Test', steps, 1, new Date()),
+      { queueMs: 95000, billableMs: 600000 }
+    );
+
+    const names = embed.fields?.map((field) => field.name);
+    expect(names?.slice(1, 4)).toEqual(['⏱️ Duration', '🕒 Queue Time', '💳 Billable Time']);
+    expect(embed.fields?.[2].value).toBe('1m 35s');
+    expect(embed.fields?.[3].value).toBe('10m 0s');
+  });
+});
+
+describe('Commit field', () => {
+  const sha = '0123456789abcdef0123456789abcdef01234567';
+
+  it('should link the short SHA after the branch with the message subject', () => {
+    const url = buildCommitUrl('o/r', sha);
+    const embed = withCommitField(
+      buildInitEmbed('1', 'Test', 'dev', 'o/r', 'main'),
+      sha,
+      'Fix login redirect\n\nLong body',
+      url
+    );
+
+    expect(url).toBe(`https://github.com/o/r/commit/${sha}`);
+    expect(embed.fields?.[3]).toEqual({
+      name: '🔖 Commit',
+      value: `[\`0123456\`](${url}) Fix login redirect`,
+      inline: false,
+    });
+  });
+
+  it('should link commits on a GitHub Enterprise Server', () => {
+    expect(buildCommitUrl('o/r', sha, 'https://ghe.example.com/')).toBe(
+      `https://ghe.example.com/o/r/commit/${sha}`
+    );
+  });
+
+  it('should add a description line to compact embeds', () => {
+    const embed = withCommitField(
+      buildInitEmbed('1', 'Test', 'dev', 'o/r', 'main', { theme: 'compact' }),
+      sha
+    );
+
+    expect(embed.fields).toBeUndefined();
+    expect(embed.description?.endsWith('\n🔖 `0123456`')).toBe(true);
+  });
+});
+
+describe('Author avatar', () => {
+  it('should link the GitHub avatar of a login', () => {
+    expect(buildAvatarUrl('octo-cat')).toBe('https://github.com/octo-cat.png');
+    expect(buildAvatarUrl('@octocat', 'https://ghe.example.com/')).toBe(
+      'https://ghe.example.com/octocat.png'
+    );
+  });
+
+  it('should not link authors that are not GitHub logins', () => {
+    expect(buildAvatarUrl('Mona Lisa')).toBeUndefined();
+    expect(buildAvatarUrl('-octocat')).toBeUndefined();
+    expect(buildAvatarUrl('')).toBeUndefined();
+  });
+});
+
+describe('User text escaping', () => {
+  it('should escape markdown but keep URLs working', () => {
+    expect(escapeMarkdown('fix: *bold* _it_ `code` ~~x~~ ||s|| [a](b)')).toBe(
+      'fix: \\*bold\\* \\_it\\_ \\`code\\` \\~\\~x\\~\\~ \\|\\|s\\|\\| \\[a\\](b)'
+    );
+    expect(escapeMarkdown('see https://example.com/a_b*c for details')).toBe(
+      'see https://example.com/a_b*c for details'
+    );
+    expect(escapeMarkdown('# title\n- item\n> quote')).toBe('\\# title\n\\- item\n\\> quote');
+  });
+
+  it('should neutralize mentions', () => {
+    expect(neutralizeMentions('@everyone @here <@123> <@&456>')).toBe(
+      '@\u200beveryone @\u200bhere <@\u200b123> <@\u200b&456>'
+    );
+  });
+
+  it('should escape the PR title, branch, step names and additional info', () => {
+    const steps: StepInfo[] = [
+      {
+        number: 1,
+        name: 'Deploy @everyone',
+        status: StepStatus.Failed,
+        additionalInfo: [['error_code', '`rm -rf` *failed*']],
+      },
+    ];
+
+    const init = buildInitEmbed('1', '**Big** _news_', 'dev', 'o/r', 'feat/x_y');
+    expect(init.description).toBe('**\\*\\*Big\\*\\* \\_news\\_**');
+    expect(init.fields?.[2].value).toBe('feat/x\\_y');
+
+    const update = buildStepUpdateEmbed('1', 'Test', steps, 1, 1);
+    const stepField = update.fields?.find((field) => field.name === 'Step 1');
+    expect(stepField?.value).toContain('**Deploy @\u200beveryone**');
+    expect(stepField?.value).toContain('**error\\_code:** \\`rm -rf\\` \\*failed\\*');
+
+    const content = buildContentFallback(
+      { number: '1', title: '@here ship it', author: 'dev', repository: 'o/r', branch: 'main' },
+      steps,
+      1,
+      'running'
+    );
+    expect(content).toContain('❌ Deploy @\u200beveryone failed');
+    expect(content).toContain('PR #1 @\u200bhere ship it');
+    expect(escapeUserText('plain text')).toBe('plain text');
+  });
+
+  it('should escape metadata keys, tags, board repositories, step names and table titles', () => {
+    expect(buildMetadataFields([['*env*', 'prod']])[0].name).toBe('\\*env\\*');
+    expect(buildTagsField([['team', 'a`b @here']])?.value).toBe('`team=aˋb @\u200bhere`');
+    expect(
+      buildBoardEmbed([
+        {
+          repository: 'owner/__repo__',
+          prNumber: 1,
+          prTitle: 'PR',
+          statusLine: '✅ Completed',
+          finished: true,
+          updatedAt: '2024-01-02T10:00:00Z',
+        },
+      ]).description
+    ).toBe('**owner/\\_\\_repo\\_\\_#1** PR - ✅ Completed');
+
+    const steps: StepInfo[] = [
+      {
+        number: 1,
+        name: '_lint_',
+        status: StepStatus.Success,
+        additionalInfo: [],
+        tables: [{ title: '**Coverage** @everyone', rows: [['file', '%']] }],
+      },
+    ];
+    const embed = buildStepUpdateEmbed('1', 'Test', steps, 1, 1, { showStepNumbers: false });
+    const stepField = embed.fields?.find((field) => field.name === '\\_lint\\_');
+    expect(stepField?.value).toContain('**\\*\\*Coverage\\*\\* @\u200beveryone**');
+  });
+});
+
+describe('Cancelled steps', () => {
+  const steps: StepInfo[] = [
+    { number: 1, name: 'Build', status: StepStatus.Success, additionalInfo: [] },
+    { number: 2, name: 'Deploy', status: StepStatus.Cancelled, additionalInfo: [] },
+  ];
+
+  it('should count cancelled steps as finished and mark the pipeline cancelled', () => {
+    const update = buildStepUpdateEmbed('1', 'Test', steps, 2, 2);
+    expect(update.fields?.find((field) => field.name === '📋 Status')?.value).toBe('🚫 Cancelled');
+    expect(update.color).toBe(0x555555);
+
+    const completion = buildCompletionEmbed('1', 'Test', steps, 2, new Date());
+    expect(completion.title).toBe('🚫 Pipeline Cancelled - PR #1');
+    expect(completion.fields?.[0].value).toBe('🚫 🚫 Cancelled');
+    expect(formatPhaseRollup(steps)).toBe('🚫 Cancelled (2/2)');
+    expect(buildStatusLine(steps, true)).toBe('🚫 Cancelled - 2/2 steps completed');
+  });
+
+  it('should keep a failure ahead of a cancellation', () => {
+    const failed = [{ ...steps[0], status: StepStatus.Failed }, steps[1]];
+
+    expect(buildCompletionEmbed('1', 'Test', failed, 2, new Date()).title).toBe(
+      '💥 Pipeline Failed - PR #1'
+    );
+  });
+});
+
+describe('Step budgets', () => {
+  const step = (budgetMs: number): StepInfo => ({
+    number: 1,
+    name: 'Build',
+    status: StepStatus.Success,
+    additionalInfo: [],
+    startedAt: new Date('2024-01-01T00:00:00Z'),
+    completedAt: new Date('2024-01-01T00:05:42Z'),
+    budgetMs,
+  });
+
+  it('should flag a step that ran past its budget', () => {
+    const embed = buildStepUpdateEmbed('1', 'Test', [step(300000)], 1, 1);
+
+    expect(embed.fields?.find((field) => field.name === 'Step 1')?.value).toBe(
+      '✅ **Build** - Success (5m 42s) · ⏰ over budget by 0m 42s'
+    );
+  });
+
+  it('should keep the flag when durations are hidden', () => {
+    const embed = buildStepUpdateEmbed('1', 'Test', [step(300000)], 1, 1, {
+      showStepDurations: false,
+    });
+
+    expect(embed.fields?.find((field) => field.name === 'Step 1')?.value).toBe(
+      '✅ **Build** - Success · ⏰ over budget by 0m 42s'
+    );
+  });
+
+  it('should leave a step within its budget unchanged', () => {
+    const embed = buildStepUpdateEmbed('1', 'Test', [step(600000)], 1, 1);
+
+    expect(embed.fields?.find((field) => field.name === 'Step 1')?.value).toBe(
+      '✅ **Build** - Success (5m 42s)'
+    );
+  });
+});
+
+describe('Elapsed time', () => {
+  const steps: StepInfo[] = [
+    { number: 1, name: 'Build', status: StepStatus.Running, additionalInfo: [] },
+  ];
+  const clock = frozenClock(new Date('2024-01-01T00:04:32Z'));
+
+  it('should show the time since the pipeline started after the status', () => {
+    const embed = buildStepUpdateEmbed(
+      '1',
+      'Test',
+      steps,
+      1,
+      2,
+      { clock },
+      new Date('2024-01-01T00:00:00Z')
+    );
+
+    const names = embed.fields?.map((field) => field.name);
+    expect(names?.slice(2, 4)).toEqual(['📋 Status', '⏱️ Elapsed']);
+    expect(embed.fields?.[3].value).toBe('4m 32s');
+  });
+
+  it('should leave the elapsed time out of render comparisons', () => {
+    const embed = buildStepUpdateEmbed('1', 'Test', steps, 1, 2, { clock }, new Date(0));
+
+    expect(stripVolatileFields(embed)).toEqual(
+      stripVolatileFields(buildStepUpdateEmbed('1', 'Test', steps, 1, 2, { clock }))
+    );
+  });
+});
+
+describe('Step durations', () => {
+  const at = (seconds: number) => new Date(Date.UTC(2024, 0, 1, 0, 0, seconds));
+  const steps: StepInfo[] = [
+    {
+      number: 1,
+      name: 'Lint',
+      status: StepStatus.Success,
+      additionalInfo: [],
+      startedAt: at(0),
+      completedAt: at(30),
+    },
+    {
+      number: 2,
+      name: 'Tests',
+      status: StepStatus.Success,
+      additionalInfo: [],
+      startedAt: at(30),
+      completedAt: at(120),
+    },
+    { number: 3, name: 'Notify', status: StepStatus.Success, additionalInfo: [] },
+  ];
+
+  it('should list the timed steps in the completion embed, slowest first', () => {
+    const embed = buildCompletionEmbed('1', 'Test', steps, 3, at(0));
+    const field = embed.fields?.find((candidate) => candidate.name === '⏱️ Step Durations');
+
+    expect(field?.value).toBe(
+      [
+        '```',
+        'step  | duration | share',
+        '------|----------|------',
+        'Tests | 1m 30s   | 75%',
+        'Lint  | 0m 30s   | 25%',
+        '```',
+      ].join('\n')
+    );
+  });
+
+  it('should leave the table out when durations are hidden', () => {
+    const embed = buildCompletionEmbed('1', 'Test', steps, 3, at(0), {
+      showStepDurations: false,
+    });
+
+    expect(embed.fields?.some((field) => field.name === '⏱️ Step Durations')).toBe(false);
+  });
+});
+
+describe('Changelog embed', () => {
+  const sha = '0123456789abcdef0123456789abcdef01234567';
+
+  it('should list each commit with its linked short SHA and subject', () => {
+    const embed = buildChangelogEmbed(
+      [
+        { sha, message: 'Fix *login* redirect\n\nLonger body' },
+        { sha: 'fedcba9', message: '' },
+      ],
+      'owner/repo'
+    );
+
+    expect(embed.title).toBe('📦 Changes (2 commits)');
+    expect(embed.description).toBe(
+      [
+        `[\`0123456\`](https://github.com/owner/repo/commit/${sha}) Fix \\*login\\* redirect`,
+        '[`fedcba9`](https://github.com/owner/repo/commit/fedcba9)',
+      ].join('\n')
+    );
+  });
+
+  it('should count the commits that do not fit', () => {
+    const commits = Array.from({ length: 100 }, () => ({ sha, message: 'x'.repeat(100) }));
+    const embed = buildChangelogEmbed(commits, 'owner/repo');
+
+    expect(embed.description!.length).toBeLessThanOrEqual(4096);
+    expect(embed.description).toMatch(/\n… and \d+ more$/);
+  });
+});
+
+describe('Failure fields', () => {
+  const failed: StepInfo = {
+    number: 1,
+    name: 'Build',
+    status: StepStatus.Failed,
+    additionalInfo: [],
+    failure: {
+      error: 'error[E0425]: cannot find value `x`\n --> src/main.rs:2:5',
+      command: 'cargo build',
+      exitCode: 101,
+    },
+  };
+
+  it('should show the error in a code block, then the command and exit code', () => {
+    const embed = buildStepUpdateEmbed('1', 'Test', [failed], 1, 1);
+    const fields = embed.fields?.filter((field) =>
+      ['💥 Error - Build', '⌨️ Command', '🔢 Exit Code'].includes(field.name)
+    );
+
+    expect(fields).toEqual([
+      {
+        name: '💥 Error - Build',
+        value: '```\nerror[E0425]: cannot find value `x`\n --> src/main.rs:2:5\n```',
+        inline: false,
+      },
+      { name: '⌨️ Command', value: '```sh\ncargo build\n```', inline: false },
+      { name: '🔢 Exit Code', value: '`101`', inline: true },
+    ]);
+  });
+
+  it('should keep the error on the completion embed', () => {
+    const embed = buildCompletionEmbed('1', 'Test', [failed], 1, new Date());
+    expect(embed.fields?.some((field) => field.name === '💥 Error - Build')).toBe(true);
+  });
+
+  it('should break fences and fit the field limit', () => {
+    expect(formatCodeBlock('a ``` b')).toBe('```\na `\u200b`\u200b` b\n```');
+
+    const block = formatCodeBlock('x'.repeat(2000));
+    expect(block.length).toBe(1024);
+    expect(block.endsWith('…\n```')).toBe(true);
+  });
+});
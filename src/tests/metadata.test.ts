@@ -0,0 +1,47 @@
+import { describe, it, expect, beforeEach } from 'bun:test';
+import { InMemoryDiscordApi } from '../inMemoryDiscordApi';
+import { InMemoryStorage, PipelineTracker } from '../pipelineTracker';
+import { silenceConsole } from './helpers';
+
+describe('Pipeline metadata', () => {
+  let api: InMemoryDiscordApi;
+  let storage: InMemoryStorage;
+
+  silenceConsole();
+
+  beforeEach(() => {
+    api = new InMemoryDiscordApi();
+    storage = new InMemoryStorage();
+  });
+
+  function createTracker(): PipelineTracker {
+    return new PipelineTracker('test-token', api.channelId, storage, { api });
+  }
+
+  function metadataFields(tracker: PipelineTracker) {
+    const [embed] = api.messages.get(tracker.getMessageId() as string)?.message.embeds || [];
+    return embed.fields?.filter((field) => ['Environment', 'Version'].includes(field.name));
+  }
+
+  it('should show the metadata passed to init on every embed', async () => {
+    const tracker = createTracker();
+    await tracker.initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main', {
+      metadata: [
+        ['Environment', 'staging'],
+        ['Version', '1.4.2'],
+      ],
+    });
+    const expected = [
+      { name: 'Environment', value: 'staging', inline: true },
+      { name: 'Version', value: '1.4.2', inline: true },
+    ];
+    expect(metadataFields(tracker)).toEqual(expected);
+
+    // Later invocations read the metadata back from the state
+    const next = createTracker();
+    await next.updateStep(1, 1, 'Build', 'success', []);
+    expect(metadataFields(next)).toEqual(expected);
+    await next.completePipeline();
+    expect(metadataFields(next)).toEqual(expected);
+  });
+});
@@ -0,0 +1,121 @@
+import { describe, it, expect, beforeEach } from 'bun:test';
+import { InMemoryStorage, PipelineTracker } from '../pipelineTracker';
+
+describe('Mirror channels', () => {
+  let storage: InMemoryStorage;
+  let calls: string[];
+
+  beforeEach(() => {
+    storage = new InMemoryStorage();
+    calls = [];
+  });
+
+  function mockApi(channelId: string, failing = false) {
+    return {
+      isWebhookMode: () => false,
+      sendMessage: async () => {
+        calls.push(`send ${channelId}`);
+        if (failing) {
+          throw new Error('Missing Access');
+        }
+        return `msg-${channelId}`;
+      },
+      updateMessage: async (messageId: string) => {
+        calls.push(`update ${channelId} ${messageId}`);
+      },
+    };
+  }
+
+  function createTracker(failingMirror?: string): PipelineTracker {
+    const tracker = new PipelineTracker('test-token', 'primary', storage, {
+      mirrorChannelIds: ['team', 'deployments'],
+    });
+    (tracker as any).api = mockApi('primary');
+    const mirrors = (tracker as any).mirrorApis as Map<string, unknown>;
+    for (const channelId of mirrors.keys()) {
+      mirrors.set(channelId, mockApi(channelId, channelId === failingMirror));
+    }
+    return tracker;
+  }
+
+  it('should post and update the message in every channel', async () => {
+    const tracker = createTracker();
+    await tracker.initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+    await tracker.updateStep(1, 1, 'Build', 'success', []);
+    await tracker.completePipeline();
+
+    expect(calls).toEqual([
+      'send primary',
+      'send team',
+      'send deployments',
+      'update primary msg-primary',
+      'update team msg-team',
+      'update deployments msg-deployments',
+      'update primary msg-primary',
+      'update team msg-team',
+      'update deployments msg-deployments',
+    ]);
+  });
+
+  it('should persist the mirrored message IDs in the state', async () => {
+    const tracker = createTracker();
+    await tracker.initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+
+    expect((await storage.loadPipelineState())?.mirrorMessageIds).toEqual({
+      team: 'msg-team',
+      deployments: 'msg-deployments',
+    });
+
+    // A later invocation updates the copies from the persisted IDs
+    const next = createTracker();
+    await next.updateStep(1, 1, 'Build', 'running', []);
+    expect(calls.slice(-2)).toEqual(['update team msg-team', 'update deployments msg-deployments']);
+  });
+
+  it('should skip a mirror that cannot be posted to', async () => {
+    const tracker = createTracker('team');
+    await tracker.initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+    await tracker.updateStep(1, 1, 'Build', 'running', []);
+
+    expect(tracker.getMessageId()).toBe('msg-primary');
+    expect(calls.filter((call) => call.startsWith('update'))).toEqual([
+      'update primary msg-primary',
+      'update deployments msg-deployments',
+    ]);
+  });
+
+  it('should derive the mirror clients from the primary client', () => {
+    const derived: string[] = [];
+    const api = {
+      ...mockApi('primary'),
+      forChannel: (channelId: string) => {
+        derived.push(channelId);
+        return mockApi(channelId);
+      },
+    };
+    new PipelineTracker('test-token', 'primary', storage, {
+      api: api as any,
+      mirrorChannelIds: ['team', 'deployments'],
+    });
+
+    expect(derived).toEqual(['team', 'deployments']);
+  });
+
+  it('should count the requests of every channel in the telemetry', () => {
+    const tracker = new PipelineTracker('test-token', 'primary', storage, {
+      mirrorChannelIds: ['team'],
+    });
+    const mirror = (tracker as any).mirrorApis.get('team');
+    mirror.telemetry.httpCalls += 1;
+
+    expect(tracker.getTelemetry().httpCalls).toBe(1);
+  });
+
+  it('should ignore the primary channel in the mirror list', () => {
+    const tracker = new PipelineTracker('test-token', 'primary', storage, {
+      mirrorChannelIds: ['primary', 'team'],
+    });
+
+    expect([...(tracker as any).mirrorApis.keys()]).toEqual(['team']);
+  });
+});
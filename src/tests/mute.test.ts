@@ -0,0 +1,71 @@
+import { describe, it, expect, beforeEach, afterEach } from 'bun:test';
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import { HistoryStore } from '../history';
+import { InMemoryDiscordApi } from '../inMemoryDiscordApi';
+import { MuteStore } from '../mute';
+import { InMemoryStorage, PipelineTracker } from '../pipelineTracker';
+import { silenceConsole } from './helpers';
+
+describe('Muting notifications', () => {
+  let tempDir: string;
+
+  silenceConsole();
+
+  beforeEach(() => {
+    tempDir = fs.mkdtempSync(path.join(os.tmpdir(), 'discord-tracker-mute-'));
+  });
+
+  afterEach(() => {
+    fs.rmSync(tempDir, { recursive: true, force: true });
+  });
+
+  describe('MuteStore', () => {
+    it('should keep the flag until unmuted', async () => {
+      const store = new MuteStore(path.join(tempDir, 'mute'));
+      expect(await store.active()).toBeUndefined();
+
+      await store.mute({ mutedAt: '2024-05-01T12:00:00.000Z', by: 'octocat', reason: 'incident' });
+      expect(await store.active()).toMatchObject({ by: 'octocat', reason: 'incident' });
+
+      await store.unmute();
+      expect(await store.active()).toBeUndefined();
+      // Unmuting twice is harmless
+      await store.unmute();
+    });
+
+    it('should ignore a mute that has expired', async () => {
+      const store = new MuteStore(path.join(tempDir, 'mute'));
+      await store.mute({
+        mutedAt: '2024-05-01T12:00:00.000Z',
+        by: 'octocat',
+        until: '2024-05-01T13:00:00.000Z',
+      });
+
+      expect(await store.active(new Date('2024-05-01T12:59:00Z'))).toBeDefined();
+      expect(await store.active(new Date('2024-05-01T13:00:00Z'))).toBeUndefined();
+    });
+  });
+
+  it('should record the pipeline without posting anything while muted', async () => {
+    const api = new InMemoryDiscordApi();
+    const storage = new InMemoryStorage();
+    const history = new HistoryStore(path.join(tempDir, 'history.json'));
+    const tracker = new PipelineTracker('test-token', api.channelId, storage, {
+      api,
+      history,
+      muted: true,
+    });
+
+    await tracker.initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+    await tracker.updateStep(1, 1, 'Build', 'success', []);
+    expect((await storage.loadPipelineState())?.steps.map((step) => step.name)).toEqual(['Build']);
+    await tracker.completePipeline();
+
+    expect(api.channelMessages()).toEqual([]);
+    expect(await tracker.getMessageUrl()).toBeUndefined();
+    const [run] = await history.load();
+    expect(run.steps[0]).toMatchObject({ name: 'Build', status: 'success' });
+  });
+});
@@ -0,0 +1,104 @@
+import { describe, it, expect, beforeEach, afterEach } from 'bun:test';
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import { formatOutput, writeOutputs } from '../outputs';
+
+/// Reads a GITHUB_OUTPUT file the way the runner does, line by line
+function readOutputs(content: string): Record<string, string> {
+  const outputs: Record<string, string> = {};
+  const lines = content.split(os.EOL);
+  for (let i = 0; i < lines.length; i++) {
+    const heredoc = lines[i].match(/^([^=<]+)<<(.+)$/);
+    if (!heredoc) {
+      const [name, ...value] = lines[i].split('=');
+      if (name) {
+        outputs[name] = value.join('=');
+      }
+      continue;
+    }
+    const value: string[] = [];
+    for (i++; lines[i] !== heredoc[2]; i++) {
+      value.push(lines[i]);
+    }
+    outputs[heredoc[1]] = value.join(os.EOL);
+  }
+  return outputs;
+}
+
+describe('Outputs', () => {
+  let tempDir: string;
+  let outputPath: string;
+
+  beforeEach(() => {
+    tempDir = fs.mkdtempSync(path.join(os.tmpdir(), 'discord-tracker-outputs-'));
+    outputPath = path.join(tempDir, 'github_output');
+  });
+
+  afterEach(() => {
+    fs.rmSync(tempDir, { recursive: true, force: true });
+  });
+
+  it('should format outputs with a heredoc delimiter', () => {
+    const formatted = formatOutput('success', 'true');
+    const lines = formatted.split(os.EOL);
+
+    expect(lines[0]).toMatch(/^success<<ghadelimiter_/);
+    expect(lines[1]).toBe('true');
+    expect(lines[2]).toBe(lines[0].replace('success<<', ''));
+  });
+
+  it('should use a unique delimiter per output', () => {
+    const first = formatOutput('error', 'boom').split(os.EOL)[0];
+    const second = formatOutput('error', 'boom').split(os.EOL)[0];
+
+    expect(first).not.toBe(second);
+  });
+
+  it('should append instead of truncating existing outputs', () => {
+    fs.writeFileSync(outputPath, `previous=value${os.EOL}`);
+
+    writeOutputs(outputPath, { success: 'true' });
+
+    const content = fs.readFileSync(outputPath, 'utf-8');
+    expect(content.startsWith(`previous=value${os.EOL}`)).toBe(true);
+    expect(content).toContain(`${os.EOL}true${os.EOL}`);
+  });
+
+  it('should write every provided output', () => {
+    writeOutputs(outputPath, { error: 'Something failed', success: 'false' });
+
+    const content = fs.readFileSync(outputPath, 'utf-8');
+    expect(content).toContain('error<<');
+    expect(content).toContain('Something failed');
+    expect(content).toContain('success<<');
+  });
+
+  it('should keep multi-line error values intact next to other outputs', () => {
+    const error = `Action failed: Discord API error${os.EOL}  at updateStep${os.EOL}${os.EOL}end`;
+    fs.writeFileSync(outputPath, `previous=a=b${os.EOL}`);
+
+    writeOutputs(outputPath, { error, success: 'false' });
+
+    expect(readOutputs(fs.readFileSync(outputPath, 'utf-8'))).toEqual({
+      previous: 'a=b',
+      error,
+      success: 'false',
+    });
+  });
+
+  it('should keep quotes, heredoc markers and unicode as is', () => {
+    const error = [`Step "Build" failed: it's <<EOF broken`, 'EOF', '❌ échec 失敗 🚀'].join(
+      os.EOL
+    );
+
+    writeOutputs(outputPath, { error, empty: '' });
+
+    expect(readOutputs(fs.readFileSync(outputPath, 'utf-8'))).toEqual({ error, empty: '' });
+  });
+
+  it('should reject output names that would end the entry early', () => {
+    expect(() => formatOutput(`error${os.EOL}success`, 'x')).toThrow('Invalid output name');
+    expect(() => formatOutput('error<<EOF', 'x')).toThrow('Invalid output name');
+  });
+});
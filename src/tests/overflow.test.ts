@@ -0,0 +1,127 @@
+import { describe, it, expect, beforeEach } from 'bun:test';
+import { TrackerError } from '../error';
+import { embedLength, MESSAGE_EMBED_CHAR_LIMIT, splitEmbedOverflow } from '../messageBuilder';
+import type { DiscordEmbed } from '../models';
+import { InMemoryStorage, PipelineTracker } from '../pipelineTracker';
+
+function createEmbed(fieldCount: number, valueLength: number): DiscordEmbed {
+  return {
+    title: 'Pipeline Update',
+    description: 'PR',
+    fields: Array.from({ length: fieldCount }, (_, i) => ({
+      name: `Step ${i + 1}`,
+      value: 'x'.repeat(valueLength),
+    })),
+  };
+}
+
+describe('Message overflow', () => {
+  it('should leave embeds within the limit untouched', () => {
+    const embed = createEmbed(3, 100);
+
+    expect(splitEmbedOverflow(embed)).toEqual({ main: embed });
+  });
+
+  it('should move the trailing fields into an overflow embed', () => {
+    const embed = createEmbed(10, 900);
+    const { main, overflow } = splitEmbedOverflow(embed);
+
+    expect(embedLength(main)).toBeLessThanOrEqual(MESSAGE_EMBED_CHAR_LIMIT);
+    expect(main.fields?.[0].name).toBe('Step 1');
+    expect(main.fields?.[main.fields.length - 1].name).toBe('📎 More details');
+    expect(overflow?.title).toBe('Pipeline Update (continued)');
+    expect(overflow?.fields?.[overflow.fields.length - 1].name).toBe('Step 10');
+    expect((main.fields?.length || 0) - 1 + (overflow?.fields?.length || 0)).toBe(10);
+  });
+
+  it('should count fields that fit in neither message', () => {
+    const { overflow } = splitEmbedOverflow(createEmbed(20, 900));
+
+    expect(embedLength(overflow as DiscordEmbed)).toBeLessThanOrEqual(MESSAGE_EMBED_CHAR_LIMIT);
+    expect(overflow?.description).toMatch(/^… \d+ more field\(s\) omitted$/);
+  });
+
+  describe('tracker', () => {
+    let storage: InMemoryStorage;
+    let calls: Array<{ method: string; args: any[] }>;
+    let mockApi: any;
+
+    beforeEach(() => {
+      storage = new InMemoryStorage();
+      calls = [];
+      mockApi = {
+        sendMessage: async (...args: any[]) => {
+          calls.push({ method: 'sendMessage', args });
+          return calls.length === 1 ? 'msg-1' : 'overflow-1';
+        },
+        updateMessage: async (...args: any[]) => {
+          calls.push({ method: 'updateMessage', args });
+        },
+        deleteMessage: async (...args: any[]) => {
+          calls.push({ method: 'deleteMessage', args });
+        },
+      };
+    });
+
+    function createTracker(): PipelineTracker {
+      const tracker = new PipelineTracker('test-token', 'test-channel', storage);
+      (tracker as any).api = mockApi;
+      return tracker;
+    }
+
+    it('should maintain the overflow reply alongside the pipeline message', async () => {
+      const longInfo: Array<[string, string]> = [['log', 'x'.repeat(900)]];
+      await createTracker().initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+      for (let step = 1; step <= 8; step++) {
+        await createTracker().updateStep(step, 8, `Step ${step}`, 'success', longInfo);
+      }
+
+      const state = await storage.loadPipelineState();
+      expect(state?.overflowMessageId).toBe('overflow-1');
+      const overflowSends = calls.filter(
+        (call) => call.method === 'sendMessage' && call.args[0].message_reference
+      );
+      expect(overflowSends).toHaveLength(1);
+      expect(overflowSends[0].args[0].message_reference).toEqual({ message_id: 'msg-1' });
+
+      // Later updates edit the existing reply instead of posting a new one
+      await createTracker().updateStep(8, 8, 'Step 8', 'success', longInfo);
+      expect(calls[calls.length - 1]).toEqual({
+        method: 'updateMessage',
+        args: ['overflow-1', expect.objectContaining({ content: '' })],
+      });
+    });
+
+    it('should delete the overflow reply once everything fits again', async () => {
+      await createTracker().initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+      const state = await storage.loadPipelineState();
+      await storage.savePipelineState({ ...(state as any), overflowMessageId: 'overflow-1' });
+
+      await createTracker().updateStep(1, 1, 'Build', 'success', []);
+
+      expect(calls.some((call) => call.method === 'deleteMessage')).toBe(true);
+      expect((await storage.loadPipelineState())?.overflowMessageId).toBeUndefined();
+    });
+
+    it('should post the overflow reply again when it was deleted by hand', async () => {
+      const longInfo: Array<[string, string]> = [['log', 'x'.repeat(900)]];
+      await createTracker().initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+      for (let step = 1; step <= 7; step++) {
+        await createTracker().updateStep(step, 8, `Step ${step}`, 'success', longInfo);
+      }
+      const state = await storage.loadPipelineState();
+      await storage.savePipelineState({ ...(state as any), overflowMessageId: 'deleted-reply' });
+      mockApi.updateMessage = async (...args: any[]) => {
+        calls.push({ method: 'updateMessage', args });
+        if (args[0] === 'deleted-reply') {
+          throw TrackerError.messageNotFound('Unknown Message');
+        }
+      };
+
+      await createTracker().updateStep(8, 8, 'Step 8', 'success', longInfo);
+
+      expect(calls[calls.length - 1].method).toBe('sendMessage');
+      expect((await storage.loadPipelineState())?.overflowMessageId).toBe('overflow-1');
+    });
+  });
+});
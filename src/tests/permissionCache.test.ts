@@ -0,0 +1,129 @@
+import { describe, it, expect, beforeEach, afterEach } from 'bun:test';
+import * as fs from 'fs/promises';
+import * as os from 'os';
+import * as path from 'path';
+import { TrackerError } from '../error';
+import { PermissionCache, preflightKey } from '../permissionCache';
+import { InMemoryStorage, PipelineTracker } from '../pipelineTracker';
+import { silenceConsole } from './helpers';
+
+describe('Permission preflight caching', () => {
+  let tempDir: string;
+  let cacheFile: string;
+
+  silenceConsole('log', 'warn');
+
+  beforeEach(async () => {
+    tempDir = await fs.mkdtemp(path.join(os.tmpdir(), 'preflight-test-'));
+    cacheFile = path.join(tempDir, '.discord-permission-cache');
+  });
+
+  afterEach(async () => {
+    await fs.rm(tempDir, { recursive: true, force: true });
+  });
+
+  function createTracker(cache: PermissionCache, channelError?: Error) {
+    const tracker = new PipelineTracker('test-token', 'test-channel', new InMemoryStorage(), {
+      permissionCache: cache,
+    });
+    const calls = { channel: 0 };
+    (tracker as any).api = {
+      isWebhookMode: () => false,
+      getChannel: async () => {
+        calls.channel++;
+        if (channelError) {
+          throw channelError;
+        }
+        return { id: 'test-channel', type: 0 };
+      },
+    };
+    return { tracker, calls };
+  }
+
+  it('should never write the token to the cache file', async () => {
+    const key = preflightKey('secret-token', '123');
+    expect(key).toMatch(/^[0-9a-f]{64}$/);
+    expect(key).not.toBe(preflightKey('secret-token', '456'));
+
+    await new PermissionCache(cacheFile).record(key);
+    expect(await fs.readFile(cacheFile, 'utf-8')).not.toContain('secret-token');
+  });
+
+  it('should report entries as fresh only within the TTL', async () => {
+    const cache = new PermissionCache(cacheFile, 60);
+    const checkedAt = new Date('2026-01-01T10:00:00Z');
+    await cache.record('key', checkedAt);
+
+    expect(await cache.isFresh('key', new Date('2026-01-01T10:00:59Z'))).toBe(true);
+    expect(await cache.isFresh('key', new Date('2026-01-01T10:01:00Z'))).toBe(false);
+    expect(await cache.isFresh('other', checkedAt)).toBe(false);
+  });
+
+  it('should treat a missing or corrupt cache file as empty', async () => {
+    const cache = new PermissionCache(cacheFile);
+    expect(await cache.isFresh('key')).toBe(false);
+
+    await fs.writeFile(cacheFile, 'not json', 'utf-8');
+    expect(await cache.isFresh('key')).toBe(false);
+  });
+
+  it('should drop expired entries when recording', async () => {
+    const cache = new PermissionCache(cacheFile, 60);
+    await cache.record('old', new Date('2026-01-01T10:00:00Z'));
+    await cache.record('new', new Date('2026-01-01T11:00:00Z'));
+
+    expect(Object.keys(JSON.parse(await fs.readFile(cacheFile, 'utf-8')))).toEqual(['new']);
+  });
+
+  it('should skip the check while a successful preflight is cached', async () => {
+    const cache = new PermissionCache(cacheFile);
+    const { tracker, calls } = createTracker(cache);
+
+    await tracker.preflight();
+    await tracker.preflight();
+
+    expect(calls.channel).toBe(1);
+  });
+
+  it('should check again on every invocation with a TTL of 0', async () => {
+    const { tracker, calls } = createTracker(new PermissionCache(cacheFile, 0));
+
+    await tracker.preflight();
+    await tracker.preflight();
+
+    expect(calls.channel).toBe(2);
+  });
+
+  it('should fail with the actionable error and not cache a failed preflight', async () => {
+    const cache = new PermissionCache(cacheFile);
+    const { tracker, calls } = createTracker(cache, TrackerError.forbidden('Missing Access'));
+
+    await expect(tracker.preflight()).rejects.toThrow(
+      'The bot cannot see the channel. Grant it the View Channel permission'
+    );
+    await expect(tracker.preflight()).rejects.toThrow();
+    expect(calls.channel).toBe(2);
+  });
+
+  it('should re-validate after a permission error', async () => {
+    const cache = new PermissionCache(cacheFile);
+    const { tracker, calls } = createTracker(cache);
+    await tracker.preflight();
+
+    await (tracker as any).reportFailure('updateStep', TrackerError.forbidden('Missing Access'));
+    await tracker.preflight();
+
+    expect(calls.channel).toBe(2);
+  });
+
+  it('should keep the cached preflight after unrelated errors', async () => {
+    const cache = new PermissionCache(cacheFile);
+    const { tracker, calls } = createTracker(cache);
+    await tracker.preflight();
+
+    await (tracker as any).reportFailure('updateStep', TrackerError.rateLimited('Slow down'));
+    await tracker.preflight();
+
+    expect(calls.channel).toBe(1);
+  });
+});
@@ -0,0 +1,129 @@
+import { describe, it, expect } from 'bun:test';
+import { buildStepUpdateEmbed, formatPhaseRollup, groupStepsByPhase } from '../messageBuilder';
+import { type StepInfo, StepInfoManager, StepStatus, splitStepGroup } from '../models';
+import { InMemoryStorage, PipelineTracker } from '../pipelineTracker';
+
+describe('Phases', () => {
+  function createStep(number: number, name: string, status: StepStatus, phase?: string): StepInfo {
+    const step = StepInfoManager.new(number, name, status, []);
+    step.phase = phase;
+    return step;
+  }
+
+  const steps = [
+    createStep(1, 'Compile', StepStatus.Success, 'build'),
+    createStep(2, 'Package', StepStatus.Success, 'build'),
+    createStep(1, 'Staging', StepStatus.Failed, 'deploy'),
+    createStep(2, 'Production', StepStatus.Pending, 'deploy'),
+  ];
+
+  it('should group steps by phase in order of appearance', () => {
+    const groups = groupStepsByPhase(steps);
+
+    expect(groups.map((group) => [group.phase, group.steps.length])).toEqual([
+      ['build', 2],
+      ['deploy', 2],
+    ]);
+  });
+
+  it('should roll up the status of each phase', () => {
+    expect(formatPhaseRollup(steps.slice(0, 2))).toBe('✅ Success (2/2)');
+    expect(formatPhaseRollup(steps.slice(2))).toBe('❌ Failed (1/2)');
+    expect(formatPhaseRollup([createStep(1, 'Smoke', StepStatus.Running)])).toBe(
+      '🔄 Running (0/1)'
+    );
+  });
+
+  it('should add a header field before the steps of each phase', () => {
+    const embed = buildStepUpdateEmbed('1', 'Test', steps, 1, 2, { showStepNumbers: false });
+    const names = embed.fields?.slice(4).map((field) => field.name);
+
+    expect(names).toEqual(['🧩 build', 'Compile', 'Package', '🧩 deploy', 'Staging', 'Production']);
+    expect(embed.fields?.find((field) => field.name === '🧩 deploy')?.value).toBe(
+      '❌ Failed (1/2)'
+    );
+  });
+
+  it('should not add phase headers when no step has a phase', () => {
+    const single = [createStep(1, 'Build', StepStatus.Success)];
+    const embed = buildStepUpdateEmbed('1', 'Test', single, 1, 1);
+
+    expect(embed.fields?.some((field) => field.name.startsWith('🧩'))).toBe(false);
+  });
+
+  it('should number steps per phase across invocations', async () => {
+    const storage = new InMemoryStorage();
+    const createTracker = () => {
+      const tracker = new PipelineTracker('test-token', 'test-channel', storage);
+      (tracker as any).api = { sendMessage: async () => 'msg-1', updateMessage: async () => {} };
+      return tracker;
+    };
+
+    await createTracker().initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+    await createTracker().updateStep(1, 1, 'Compile', 'success', [], { phase: 'build' });
+    await createTracker().updateStep(1, 1, 'Staging', 'running', [], { phase: 'deploy' });
+    await createTracker().updateStep(1, 1, 'Staging', 'success', [], { phase: 'deploy' });
+
+    const state = await storage.loadPipelineState();
+    expect(state?.steps.map((step) => [step.phase, step.name, step.status])).toEqual([
+      ['build', 'Compile', 'success'],
+      ['deploy', 'Staging', 'success'],
+    ]);
+  });
+
+  it('should split the group off `group/step name` step names', () => {
+    expect(splitStepGroup('build/compile')).toEqual({ group: 'build', name: 'compile' });
+    expect(splitStepGroup('e2e/Run Cypress / Chrome')).toEqual({
+      group: 'e2e',
+      name: 'Run Cypress / Chrome',
+    });
+    expect(splitStepGroup('Compile')).toEqual({ name: 'Compile' });
+    expect(splitStepGroup('Deploy to s3://bucket')).toEqual({ name: 'Deploy to s3://bucket' });
+    expect(splitStepGroup('build/')).toEqual({ name: 'build/' });
+  });
+
+  function createGroupingTracker(storage: InMemoryStorage, stepGroups: boolean) {
+    const tracker = new PipelineTracker('test-token', 'test-channel', storage, { stepGroups });
+    const updates: any[] = [];
+    (tracker as any).api = {
+      sendMessage: async () => 'msg-1',
+      updateMessage: async (_id: string, message: any) => {
+        updates.push(message);
+      },
+    };
+    return { tracker, updates };
+  }
+
+  it('should nest `group/step name` steps under their group', async () => {
+    const storage = new InMemoryStorage();
+    const { tracker, updates } = createGroupingTracker(storage, true);
+
+    await tracker.initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+    await tracker.updateStep(1, 3, 'build/compile', 'success', []);
+    await tracker.updateStep(2, 3, 'build/lint', 'success', []);
+    await tracker.updateStep(3, 3, 'Deploy', 'running', [], { phase: 'release/eu' });
+
+    const state = await storage.loadPipelineState();
+    expect(state?.steps.map((step) => [step.phase, step.name])).toEqual([
+      ['build', 'compile'],
+      ['build', 'lint'],
+      ['release/eu', 'Deploy'],
+    ]);
+    const names = updates[updates.length - 1].embeds[0].fields.map((field: any) => field.name);
+    expect(names).toContain('🧩 build');
+  });
+
+  it('should keep slashed step names intact without step groups', async () => {
+    const storage = new InMemoryStorage();
+    const { tracker } = createGroupingTracker(storage, false);
+
+    await tracker.initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+    await tracker.updateStep(1, 1, 'build/test', 'running', []);
+    await tracker.updateStep(1, 1, 'build/test', 'success', []);
+
+    const state = await storage.loadPipelineState();
+    expect(state?.steps.map((step) => [step.phase, step.name, step.status])).toEqual([
+      [undefined, 'build/test', 'success'],
+    ]);
+  });
+});
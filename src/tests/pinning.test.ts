@@ -0,0 +1,81 @@
+import { describe, it, expect, beforeEach } from 'bun:test';
+import { DiscordApi } from '../discordApi';
+import { InMemoryStorage, PipelineTracker } from '../pipelineTracker';
+
+describe('Pinning the pipeline message', () => {
+  let calls: string[];
+  let mockApi: any;
+
+  beforeEach(() => {
+    calls = [];
+    mockApi = {
+      sendMessage: async () => 'msg-1',
+      updateMessage: async () => {},
+      pinMessage: async (messageId: string) => {
+        calls.push(`pin ${messageId}`);
+      },
+      unpinMessage: async (messageId: string) => {
+        calls.push(`unpin ${messageId}`);
+      },
+    };
+  });
+
+  function createTracker(pin: boolean): PipelineTracker {
+    const tracker = new PipelineTracker('test-token', 'test-channel', new InMemoryStorage(), {
+      pin,
+    });
+    (tracker as any).api = mockApi;
+    return tracker;
+  }
+
+  it('should pin on init and unpin on completion', async () => {
+    const tracker = createTracker(true);
+    await tracker.initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+    await tracker.updateStep(1, 1, 'Build', 'success', []);
+    await tracker.completePipeline();
+
+    expect(calls).toEqual(['pin msg-1', 'unpin msg-1']);
+  });
+
+  it('should not pin unless enabled', async () => {
+    const tracker = createTracker(false);
+    await tracker.initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+    await tracker.completePipeline();
+
+    expect(calls).toEqual([]);
+  });
+
+  it('should keep tracking when pinning fails', async () => {
+    mockApi.pinMessage = async () => {
+      throw new Error('Missing Permissions');
+    };
+    const tracker = createTracker(true);
+
+    await tracker.initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+    expect(tracker.getMessageId()).toBe('msg-1');
+  });
+
+  it('should use the channel pins endpoint', async () => {
+    const api = new DiscordApi(
+      '1234567890.abcdefghijklmnopqrstuvwxyz.abcdef',
+      '123456789012345678'
+    );
+    const urls: string[] = [];
+    (api as any).client = {
+      put: async (url: string) => {
+        urls.push(`PUT ${url}`);
+      },
+      delete: async (url: string) => {
+        urls.push(`DELETE ${url}`);
+      },
+    };
+
+    await api.pinMessage('msg-1');
+    await api.unpinMessage('msg-1');
+
+    expect(urls).toEqual([
+      'PUT /channels/123456789012345678/pins/msg-1',
+      'DELETE /channels/123456789012345678/pins/msg-1',
+    ]);
+  });
+});
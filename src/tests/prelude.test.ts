@@ -0,0 +1,45 @@
+import { describe, it, expect } from 'bun:test';
+import * as prelude from '../prelude';
+
+describe('Prelude', () => {
+  it('should expose the stable public API', () => {
+    expect(prelude.PRELUDE_VERSION).toBe(1);
+    expect(typeof prelude.PipelineTracker).toBe('function');
+    expect(typeof prelude.TrackerError).toBe('function');
+    expect(typeof prelude.EmbedBuilder).toBe('function');
+    expect(typeof prelude.InMemoryDiscordApi).toBe('function');
+    expect(prelude.StepStatus.Success).toBe('success');
+  });
+
+  it('should build embeds field by field', () => {
+    const embed = new prelude.EmbedBuilder()
+      .title('Deploy')
+      .color(0x00ff00)
+      .field('Environment', 'production', true)
+      .footer('footer text')
+      .timestamp(new Date('2024-01-01T00:00:00Z'))
+      .build();
+
+    expect(embed).toEqual({
+      title: 'Deploy',
+      color: 0x00ff00,
+      fields: [{ name: 'Environment', value: 'production', inline: true }],
+      footer: { text: 'footer text' },
+      timestamp: '2024-01-01T00:00:00.000Z',
+    });
+  });
+
+  it('should set the author, thumbnail and image', () => {
+    const embed = new prelude.EmbedBuilder()
+      .title('Deploy')
+      .url('https://github.com/owner/repo/pull/1')
+      .author('octocat', undefined, 'https://github.com/octocat.png')
+      .thumbnail('https://github.com/owner.png')
+      .image('https://example.com/banner.png')
+      .build();
+
+    expect(embed.author).toEqual({ name: 'octocat', icon_url: 'https://github.com/octocat.png' });
+    expect(embed.thumbnail).toEqual({ url: 'https://github.com/owner.png' });
+    expect(embed.image).toEqual({ url: 'https://example.com/banner.png' });
+  });
+});
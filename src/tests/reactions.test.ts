@@ -0,0 +1,94 @@
+import { describe, it, expect, beforeEach } from 'bun:test';
+import { DiscordApi } from '../discordApi';
+import { statusReaction } from '../messageBuilder';
+import { StepInfoManager, StepStatus } from '../models';
+import { InMemoryStorage, PipelineTracker } from '../pipelineTracker';
+
+describe('Status reactions', () => {
+  let storage: InMemoryStorage;
+  let reactions: string[];
+  let mockApi: any;
+
+  beforeEach(() => {
+    storage = new InMemoryStorage();
+    reactions = [];
+    mockApi = {
+      sendMessage: async () => 'msg-1',
+      updateMessage: async () => {},
+      addReaction: async (_messageId: string, emoji: string) => {
+        reactions.push(`+${emoji}`);
+      },
+      removeReaction: async (_messageId: string, emoji: string) => {
+        reactions.push(`-${emoji}`);
+      },
+    };
+  });
+
+  function createTracker(enabled: boolean): PipelineTracker {
+    const tracker = new PipelineTracker('test-token', 'test-channel', storage, {
+      reactions: enabled,
+    });
+    (tracker as any).api = mockApi;
+    return tracker;
+  }
+
+  it('should pick the reaction from the pipeline status', () => {
+    const running = [StepInfoManager.new(1, 'Build', StepStatus.Success, [])];
+    const failed = [...running, StepInfoManager.new(2, 'Test', StepStatus.Failed, [])];
+
+    expect(statusReaction(running, false)).toBe('🔄');
+    expect(statusReaction(running, true)).toBe('✅');
+    expect(statusReaction(failed, true)).toBe('❌');
+  });
+
+  it('should swap reactions only when the pipeline status changes', async () => {
+    const tracker = createTracker(true);
+    await tracker.initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+    await tracker.updateStep(1, 2, 'Build', 'success', []);
+    await tracker.updateStep(2, 2, 'Test', 'failed', []);
+    await tracker.completePipeline();
+
+    expect(reactions).toEqual(['+🔄', '-🔄', '+❌']);
+  });
+
+  it('should mark a successful pipeline as completed', async () => {
+    const tracker = createTracker(true);
+    await tracker.initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+    await tracker.updateStep(1, 1, 'Build', 'success', []);
+    await tracker.completePipeline();
+
+    expect(reactions).toEqual(['+🔄', '-🔄', '+✅']);
+  });
+
+  it('should not react unless enabled', async () => {
+    const tracker = createTracker(false);
+    await tracker.initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+    await tracker.updateStep(1, 1, 'Build', 'failed', []);
+
+    expect(reactions).toEqual([]);
+  });
+
+  it('should encode the emoji in the reaction endpoint', async () => {
+    const api = new DiscordApi(
+      '1234567890.abcdefghijklmnopqrstuvwxyz.abcdef',
+      '123456789012345678'
+    );
+    const urls: string[] = [];
+    (api as any).client = {
+      put: async (url: string) => {
+        urls.push(`PUT ${url}`);
+      },
+      delete: async (url: string) => {
+        urls.push(`DELETE ${url}`);
+      },
+    };
+
+    await api.addReaction('msg-1', '✅');
+    await api.removeReaction('msg-1', '🔄');
+
+    expect(urls).toEqual([
+      'PUT /channels/123456789012345678/messages/msg-1/reactions/%E2%9C%85/@me',
+      'DELETE /channels/123456789012345678/messages/msg-1/reactions/%F0%9F%94%84/@me',
+    ]);
+  });
+});
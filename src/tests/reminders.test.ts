@@ -0,0 +1,174 @@
+import { describe, it, expect } from 'bun:test';
+import { InMemoryStorage, PipelineTracker } from '../pipelineTracker';
+import {
+  type DiscordMessage,
+  type DiscordMessageData,
+  type DiscordReaction,
+  StepStatus,
+} from '../models';
+import { silenceConsole } from './helpers';
+
+class MockDiscordApi {
+  public sentMessages: DiscordMessage[] = [];
+  public channelMessages: DiscordMessageData[] = [];
+  public message: DiscordMessageData;
+
+  constructor(message: DiscordMessageData) {
+    this.message = message;
+  }
+
+  isWebhookMode(): boolean {
+    return false;
+  }
+
+  async getMessage(): Promise<DiscordMessageData> {
+    return this.message;
+  }
+
+  async getReactions(): Promise<DiscordReaction[]> {
+    return this.message.reactions || [];
+  }
+
+  async listMessages(): Promise<DiscordMessageData[]> {
+    return this.channelMessages;
+  }
+
+  async sendMessage(message: DiscordMessage): Promise<string> {
+    this.sentMessages.push(message);
+    return 'reminder-message-id';
+  }
+}
+
+function failedMessage(minutesAgo: number, overrides: Partial<DiscordMessageData> = {}) {
+  return {
+    id: 'pipeline-message-id',
+    content: '',
+    timestamp: new Date(Date.now() - (minutesAgo + 5) * 60000).toISOString(),
+    edited_timestamp: new Date(Date.now() - minutesAgo * 60000).toISOString(),
+    embeds: [{ title: '💥 Pipeline Failed - PR #42' }],
+    ...overrides,
+  };
+}
+
+describe('Failure reminders', () => {
+  silenceConsole();
+
+  function createTracker(message: DiscordMessageData, storage = new InMemoryStorage()) {
+    const tracker = new PipelineTracker('test-token', '123456789', storage);
+    const mockApi = new MockDiscordApi(message);
+    (tracker as any).api = mockApi;
+    return { tracker, mockApi };
+  }
+
+  it('should ping the escalation target when nobody reacted in time', async () => {
+    const { tracker, mockApi } = createTracker(failedMessage(90));
+
+    const reminded = await tracker.remindUnacknowledged('pipeline-message-id', 60, '<@&555>');
+
+    expect(reminded).toBe(true);
+    expect(mockApi.sentMessages).toHaveLength(1);
+    expect(mockApi.sentMessages[0].content).toContain('<@&555>');
+    expect(mockApi.sentMessages[0].message_reference).toEqual({
+      message_id: 'pipeline-message-id',
+    });
+    expect(mockApi.sentMessages[0].allowed_mentions).toEqual({
+      parse: [],
+      users: [],
+      roles: ['555'],
+    });
+  });
+
+  it('should not remind before the window has elapsed', async () => {
+    const { tracker, mockApi } = createTracker(failedMessage(10));
+
+    expect(await tracker.remindUnacknowledged('pipeline-message-id', 60, '<@&555>')).toBe(false);
+    expect(mockApi.sentMessages).toHaveLength(0);
+  });
+
+  it('should treat reactions from other users as an acknowledgement', async () => {
+    const { tracker, mockApi } = createTracker(
+      failedMessage(90, {
+        reactions: [{ count: 2, me: true, emoji: { id: null, name: '👀' } }],
+      })
+    );
+
+    expect(await tracker.remindUnacknowledged('pipeline-message-id', 60, '<@&555>')).toBe(false);
+    expect(mockApi.sentMessages).toHaveLength(0);
+  });
+
+  it('should ignore the bot\'s own reactions', async () => {
+    const { tracker } = createTracker(
+      failedMessage(90, {
+        reactions: [{ count: 1, me: true, emoji: { id: null, name: '❌' } }],
+      })
+    );
+
+    expect(await tracker.remindUnacknowledged('pipeline-message-id', 60, '<@&555>')).toBe(true);
+  });
+
+  it('should skip messages that do not show a failure', async () => {
+    const { tracker, mockApi } = createTracker(
+      failedMessage(90, { embeds: [{ title: '🎉 Pipeline Completed - PR #42' }] })
+    );
+
+    expect(await tracker.remindUnacknowledged('pipeline-message-id', 60, '<@&555>')).toBe(false);
+    expect(mockApi.sentMessages).toHaveLength(0);
+  });
+
+  it('should remind of a failed step shown in an update embed', async () => {
+    const { tracker, mockApi } = createTracker(
+      failedMessage(90, {
+        embeds: [
+          {
+            title: '🔄 Pipeline Update - PR #42',
+            fields: [{ name: 'Step 1', value: '❌ **Build** - Failed', inline: false }],
+          },
+        ],
+      })
+    );
+
+    expect(await tracker.remindUnacknowledged('pipeline-message-id', 60, '<@&555>')).toBe(true);
+    expect(mockApi.sentMessages).toHaveLength(1);
+  });
+
+  it('should decide from the stored steps when the state belongs to the message', async () => {
+    const storage = new InMemoryStorage();
+    await storage.savePipelineState({
+      messageId: 'pipeline-message-id',
+      prNumber: 42,
+      prTitle: 'Add feature',
+      author: 'octocat',
+      repository: 'owner/repo',
+      branch: 'feature',
+      steps: [{ number: 1, name: 'Build', status: StepStatus.Failed, additionalInfo: [] }],
+      pipelineStartedAt: new Date(),
+    });
+    const { tracker, mockApi } = createTracker(
+      failedMessage(90, { embeds: [{ title: '🔄 Pipeline Update - PR #42' }] }),
+      storage
+    );
+
+    expect(await tracker.remindUnacknowledged(undefined, 60, '<@&555>')).toBe(true);
+    expect(mockApi.sentMessages[0].message_reference).toEqual({
+      message_id: 'pipeline-message-id',
+    });
+  });
+
+  it('should not remind again once a reminder reply was posted', async () => {
+    const { tracker, mockApi } = createTracker(failedMessage(90));
+    mockApi.channelMessages = [
+      {
+        id: 'reminder-message-id',
+        content: '<@&555> ⏰ This pipeline failure has not been acknowledged for 60 minutes.',
+        timestamp: new Date().toISOString(),
+        edited_timestamp: null,
+        embeds: [],
+        author: { id: '900000000000000000', bot: true },
+        message_reference: { message_id: 'pipeline-message-id' },
+      },
+    ];
+
+    expect(await tracker.remindUnacknowledged('pipeline-message-id', 60, '<@&555>')).toBe(false);
+    expect(mockApi.sentMessages).toHaveLength(0);
+  });
+});
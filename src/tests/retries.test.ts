@@ -0,0 +1,49 @@
+import { describe, it, expect, beforeEach } from 'bun:test';
+import { frozenClock } from '../clock';
+import { InMemoryDiscordApi } from '../inMemoryDiscordApi';
+import { InMemoryStorage, PipelineTracker } from '../pipelineTracker';
+import { silenceConsole } from './helpers';
+
+describe('Step retries', () => {
+  let api: InMemoryDiscordApi;
+  let storage: InMemoryStorage;
+
+  silenceConsole();
+
+  beforeEach(() => {
+    api = new InMemoryDiscordApi();
+    storage = new InMemoryStorage();
+  });
+
+  function createTracker(at: string): PipelineTracker {
+    return new PipelineTracker('test-token', api.channelId, storage, {
+      api,
+      render: { clock: frozenClock(new Date(at)) },
+    });
+  }
+
+  function stepField(tracker: PipelineTracker): string | undefined {
+    const [embed] = api.messages.get(tracker.getMessageId() as string)?.message.embeds || [];
+    return embed.fields?.find((field) => field.name === 'Step 1')?.value;
+  }
+
+  it('should count each retry as a new attempt of the step', async () => {
+    const tracker = createTracker('2024-05-01T12:00:00Z');
+    await tracker.initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+    await tracker.updateStep(1, 1, 'Tests', 'running', []);
+    await tracker.updateStep(1, 1, 'Tests', 'failed', []);
+    expect(stepField(tracker)).not.toContain('attempt');
+
+    await tracker.updateStep(1, 1, 'Tests', 'retrying', []);
+    const [step] = tracker.getSteps();
+    expect(step).toMatchObject({ status: 'running', retries: 1, completedAt: undefined });
+    expect(stepField(tracker)).toContain('Running (attempt 2)');
+
+    // Later invocations read the count back from the state
+    const next = createTracker('2024-05-01T12:05:00Z');
+    await next.updateStep(1, 1, 'Tests', 'retrying', []);
+    await next.updateStep(1, 1, 'Tests', 'success', []);
+    expect(next.getSteps()[0].retries).toBe(2);
+    expect(stepField(next)).toContain('Success (attempt 3)');
+  });
+});
@@ -0,0 +1,90 @@
+import { describe, it, expect, beforeEach, afterEach } from 'bun:test';
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import { frozenClock } from '../clock';
+import { HistoryStore } from '../history';
+import { InMemoryDiscordApi } from '../inMemoryDiscordApi';
+import { InMemoryStorage, PipelineTracker } from '../pipelineTracker';
+import { silenceConsole } from './helpers';
+
+describe('Deploy rollback', () => {
+  let tempDir: string;
+  let history: HistoryStore;
+  let api: InMemoryDiscordApi;
+
+  silenceConsole();
+
+  beforeEach(() => {
+    tempDir = fs.mkdtempSync(path.join(os.tmpdir(), 'discord-tracker-rollback-'));
+    history = new HistoryStore(path.join(tempDir, 'history.json'));
+    api = new InMemoryDiscordApi({ guild_id: '200000000000000000' });
+  });
+
+  afterEach(() => {
+    fs.rmSync(tempDir, { recursive: true, force: true });
+  });
+
+  function createTracker(at: string): PipelineTracker {
+    return new PipelineTracker('test-token', api.channelId, new InMemoryStorage(), {
+      api,
+      history,
+      render: { clock: frozenClock(new Date(at)) },
+    });
+  }
+
+  async function deploy(correlationId: string): Promise<string> {
+    const tracker = createTracker('2024-05-01T12:00:00Z');
+    await tracker.initPipeline('42', 'Release 2.4', 'dev', 'owner/repo', 'main', {
+      correlationId,
+    });
+    await tracker.updateStep(1, 1, 'Deploy', 'success', []);
+    await tracker.completePipeline();
+    return tracker.getMessageId() as string;
+  }
+
+  it('should add a banner to the deploy message and reply with a linked rollback', async () => {
+    const deployMessageId = await deploy('v2.4.0');
+
+    const result = await createTracker('2024-05-01T13:00:00Z').rollbackDeploy(
+      'v2.4.0',
+      'oncall',
+      'Error rate spike'
+    );
+
+    expect(result.originalMessageId).toBe(deployMessageId);
+    const [original] = api.messages.get(deployMessageId)?.message.embeds || [];
+    expect(original.description).toContain('↩️ **Rolled back** at <t:1714568400:f> by oncall');
+    expect(original.color).toBe(0xff8800);
+
+    const reply = api.messages.get(result.messageId)?.message;
+    expect(reply?.message_reference).toEqual({ message_id: deployMessageId });
+    const fields = reply?.embeds?.[0].fields || [];
+    const url = await api.getMessageUrl(deployMessageId);
+    expect(fields.map((field) => field.value)).toEqual([
+      'v2.4.0',
+      'oncall',
+      `[Pipeline message](${url})`,
+      'Error rate spike',
+    ]);
+
+    const [run] = await history.load();
+    expect(run.correlationId).toBe('v2.4.0');
+    expect(run.rollback).toEqual({
+      at: '2024-05-01T13:00:00.000Z',
+      by: 'oncall',
+      reason: 'Error rate spike',
+      messageId: result.messageId,
+    });
+  });
+
+  it('should fail for an unknown deploy', async () => {
+    await deploy('v2.4.0');
+
+    const tracker = createTracker('2024-05-01T13:00:00Z');
+
+    await expect(tracker.rollbackDeploy('v9', 'oncall')).rejects.toThrow(
+      'No recorded deploy with correlation ID v9'
+    );
+  });
+});
@@ -0,0 +1,62 @@
+import { describe, it, expect } from 'bun:test';
+import { frozenClock } from '../clock';
+import { InMemoryDiscordApi } from '../inMemoryDiscordApi';
+import { buildRolloutFields } from '../messageBuilder';
+import { type StepInfo, StepInfoManager, StepStatus } from '../models';
+import { InMemoryStorage, PipelineTracker } from '../pipelineTracker';
+import { silenceConsole } from './helpers';
+
+describe('Canary rollouts', () => {
+  silenceConsole();
+
+  function makeStep(): StepInfo {
+    return StepInfoManager.new(1, 'Deploy', StepStatus.Running, []);
+  }
+
+  it('should record increments, ignoring repeats and clamping to 0-100', () => {
+    const step = makeStep();
+    StepInfoManager.recordRollout(step, 10, new Date('2024-05-01T12:00:00Z'));
+    StepInfoManager.recordRollout(step, 10, new Date('2024-05-01T12:05:00Z'));
+    StepInfoManager.recordRollout(step, 150, new Date('2024-05-01T12:10:00Z'));
+
+    expect(step.rollout).toEqual([
+      { percent: 10, at: '2024-05-01T12:00:00.000Z' },
+      { percent: 100, at: '2024-05-01T12:10:00.000Z' },
+    ]);
+    expect(StepInfoManager.isRolledBack(step)).toBe(false);
+  });
+
+  it('should draw the current percentage and list each increment with its time', () => {
+    const step = makeStep();
+    StepInfoManager.recordRollout(step, 10, new Date('2024-05-01T12:00:00Z'));
+    StepInfoManager.recordRollout(step, 50, new Date('2024-05-01T12:20:00Z'));
+
+    expect(buildRolloutFields([step])).toEqual([
+      {
+        name: '🚦 Rollout - Deploy',
+        value: '▰▰▰▰▰▱▱▱▱▱ 50%\n↗️ 10% at <t:1714564800:t>\n↗️ 50% at <t:1714566000:t>',
+        inline: false,
+      },
+    ]);
+    expect(buildRolloutFields([makeStep()])).toEqual([]);
+  });
+
+  it('should render a rollback when the percentage decreases', async () => {
+    const api = new InMemoryDiscordApi();
+    const tracker = new PipelineTracker('test-token', api.channelId, new InMemoryStorage(), {
+      api,
+      render: { clock: frozenClock(new Date('2024-05-01T12:00:00Z')) },
+    });
+    await tracker.initPipeline('42', 'Release', 'dev', 'owner/repo', 'main');
+    for (const percent of [10, 50, 10]) {
+      await tracker.updateStep(1, 1, 'Deploy', 'running', [], { rolloutPercent: percent });
+    }
+
+    const [embed] = api.channelMessages()[0].message.embeds;
+    const rollout = embed.fields?.find((field) => field.name === '🚦 Rollout - Deploy');
+    expect(rollout?.value.split('\n')[0]).toBe('⏪ ▰▱▱▱▱▱▱▱▱▱ 10% - rolled back from 50%');
+    expect(rollout?.value).toContain('↘️ 10% at');
+    expect(embed.fields?.find((field) => field.name === '📋 Status')?.value).toBe('⏪ Rolled back');
+    expect(embed.color).toBe(0xff8800);
+  });
+});
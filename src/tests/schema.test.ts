@@ -0,0 +1,59 @@
+import { describe, it, expect, beforeEach, afterEach } from 'bun:test';
+import { StepStatus } from '../models';
+import { runSchemaCommand, SCHEMAS } from '../schema';
+
+describe('Schema command', () => {
+  let printed: string[];
+  let originalConsoleLog: typeof console.log;
+  let originalConsoleError: typeof console.error;
+
+  beforeEach(() => {
+    printed = [];
+    originalConsoleLog = console.log;
+    originalConsoleError = console.error;
+    console.log = (line: string) => printed.push(line);
+    console.error = (line: string) => printed.push(line);
+  });
+
+  afterEach(() => {
+    console.log = originalConsoleLog;
+    console.error = originalConsoleError;
+  });
+
+  it('should print every schema keyed by name', () => {
+    expect(runSchemaCommand([])).toBe(0);
+
+    expect(Object.keys(JSON.parse(printed[0]))).toEqual([
+      'pipeline-state',
+      'history',
+      'event',
+      'alert',
+    ]);
+  });
+
+  it('should print a single schema', () => {
+    expect(runSchemaCommand(['event'])).toBe(0);
+
+    const schema = JSON.parse(printed[0]);
+    expect(schema.title).toBe('PipelineEvent');
+    expect(schema.properties.step.properties.status.enum).toEqual(Object.values(StepStatus));
+  });
+
+  it('should list the schema names on an unknown name', () => {
+    expect(runSchemaCommand(['metrics'])).toBe(1);
+    expect(printed).toEqual(['Error: Usage: schema [pipeline-state|history|event|alert]']);
+  });
+
+  it('should require the fields every state file has', () => {
+    expect(SCHEMAS['pipeline-state'].required).toEqual([
+      'messageId',
+      'prNumber',
+      'prTitle',
+      'author',
+      'repository',
+      'branch',
+      'steps',
+      'pipelineStartedAt',
+    ]);
+  });
+});
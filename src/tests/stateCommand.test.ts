@@ -0,0 +1,83 @@
+import { describe, it, expect } from 'bun:test';
+import { TrackerError } from '../error';
+import { InMemoryStorage, type InternalPipelineState } from '../pipelineTracker';
+import {
+  formatStatePretty,
+  humanizeTimestamp,
+  redactSecrets,
+  runStateCommand,
+  setStateField,
+} from '../stateCommand';
+import { StepStatus } from '../models';
+
+function createState(): InternalPipelineState {
+  return {
+    messageId: 'msg-1',
+    prNumber: 42,
+    prTitle: 'Test PR',
+    author: 'dev',
+    repository: 'owner/repo',
+    branch: 'main',
+    steps: [{ number: 1, name: 'Build', status: StepStatus.Success, additionalInfo: [] }],
+    pipelineStartedAt: new Date('2024-01-01T10:00:00Z'),
+    tags: [['env', 'staging']],
+  };
+}
+
+describe('State command', () => {
+  it('should redact secret keys and webhook URLs', () => {
+    const redacted = redactSecrets({
+      botToken: 'abc',
+      nested: { alertWebhookUrl: 'https://example.com/hook' },
+      link: 'https://discord.com/api/webhooks/1/secret',
+      plain: 'value',
+    });
+
+    expect(redacted).toEqual({
+      botToken: '***',
+      nested: { alertWebhookUrl: '***' },
+      link: '***',
+      plain: 'value',
+    });
+  });
+
+  it('should humanize timestamps relative to now', () => {
+    const now = new Date('2024-01-01T10:05:30Z').getTime();
+    expect(humanizeTimestamp('2024-01-01T10:00:00Z', now)).toBe(
+      '2024-01-01T10:00:00.000Z (5m 30s ago)'
+    );
+  });
+
+  it('should pretty-print the state', () => {
+    const now = new Date('2024-01-01T10:01:00Z').getTime();
+    const output = formatStatePretty(createState(), now);
+
+    expect(output).toContain('PR:          #42 Test PR');
+    expect(output).toContain('(1m 0s ago)');
+    expect(output).toContain('Tags:        env=staging');
+    expect(output).toContain('✅ 1. Build - success');
+  });
+
+  it('should parse values when setting fields', () => {
+    const state = createState();
+
+    expect(setStateField(state, 'messageId', 'msg-2').messageId).toBe('msg-2');
+    expect(setStateField(state, 'prNumber', '7').prNumber).toBe(7);
+    expect(() => setStateField(state, 'prNumber', 'abc')).toThrow(TrackerError);
+    expect(() => setStateField(state, 'steps', '[]')).toThrow(TrackerError);
+  });
+
+  it('should persist `state set` changes', async () => {
+    const storage = new InMemoryStorage();
+    await storage.savePipelineState(createState());
+
+    const exitCode = await runStateCommand(storage, ['set', 'messageId', 'msg-9'], {});
+
+    expect(exitCode).toBe(0);
+    expect((await storage.loadPipelineState())?.messageId).toBe('msg-9');
+  });
+
+  it('should fail when there is no state', async () => {
+    expect(await runStateCommand(new InMemoryStorage(), ['show'], {})).toBe(1);
+  });
+});
@@ -177,6 +177,7 @@ describe('FileStorage State Validation and Backup', () => {
       expect(recoveredState).not.toBeNull();
       expect(recoveredState?.prNumber).toBe(state.prNumber);
       expect(recoveredState?.prTitle).toBe(state.prTitle);
+      expect(storage.takeWarnings().map((warning) => warning.code)).toEqual(['STATE_RECOVERED']);
     });
 
     it('should detect checksum mismatch and recover', async () => {
@@ -208,6 +209,7 @@ describe('FileStorage State Validation and Backup', () => {
       // Should return null when recovery fails
       const state = await storage.loadPipelineState();
       expect(state).toBeNull();
+      expect(storage.takeWarnings().map((warning) => warning.code)).toEqual(['STATE_RESET']);
     });
 
     it('should handle missing backup gracefully', async () => {
@@ -241,6 +243,8 @@ describe('FileStorage State Validation and Backup', () => {
       expect(loadedState).not.toBeNull();
       expect(loadedState?.messageId).toBe('legacy-message');
       expect(loadedState?.prNumber).toBe(99);
+      expect(storage.takeWarnings().map((warning) => warning.code)).toEqual(['STATE_MIGRATED']);
+      expect(storage.takeWarnings()).toEqual([]);
     });
 
     it('should upgrade legacy format on next save', async () => {
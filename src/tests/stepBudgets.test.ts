@@ -0,0 +1,56 @@
+import { describe, it, expect, beforeEach, afterEach } from 'bun:test';
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import { frozenClock } from '../clock';
+import { HistoryStore } from '../history';
+import { InMemoryDiscordApi } from '../inMemoryDiscordApi';
+import { InMemoryStorage, PipelineTracker, type TrackerOptions } from '../pipelineTracker';
+import { silenceConsole } from './helpers';
+
+describe('Step budgets', () => {
+  let tempDir: string;
+  let history: HistoryStore;
+  let storage: InMemoryStorage;
+  let api: InMemoryDiscordApi;
+
+  silenceConsole();
+
+  beforeEach(() => {
+    tempDir = fs.mkdtempSync(path.join(os.tmpdir(), 'discord-tracker-budgets-'));
+    history = new HistoryStore(path.join(tempDir, 'history.json'));
+    storage = new InMemoryStorage();
+    api = new InMemoryDiscordApi();
+  });
+
+  afterEach(() => {
+    fs.rmSync(tempDir, { recursive: true, force: true });
+  });
+
+  function createTracker(at: string, options: Partial<TrackerOptions> = {}): PipelineTracker {
+    return new PipelineTracker('test-token', api.channelId, storage, {
+      api,
+      history,
+      render: { clock: frozenClock(new Date(at)) },
+      ...options,
+    });
+  }
+
+  it('should keep the budget in the state and record the overrun in the history', async () => {
+    const first = createTracker('2024-05-01T12:00:00Z', { stepBudgets: { Build: 300000 } });
+    await first.initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+    await first.updateStep(1, 1, 'Build', 'running', []);
+
+    // The finishing invocation does not pass the budgets again
+    const last = createTracker('2024-05-01T12:06:00Z');
+    await last.updateStep(1, 1, 'Build', 'success', []);
+    const [embed] = api.messages.get(last.getMessageId() as string)?.message.embeds || [];
+    expect(embed.fields?.find((field) => field.name === 'Step 1')?.value).toContain(
+      '⏰ over budget by 1m 0s'
+    );
+    await last.completePipeline();
+
+    const [run] = await history.load();
+    expect(run.steps[0]).toMatchObject({ name: 'Build', budgetMs: 300000, overBudgetMs: 60000 });
+  });
+});
@@ -0,0 +1,32 @@
+import { describe, it, expect } from 'bun:test';
+import { TrackerError } from '../error';
+import { renderConfigFromInputs, renderThemeSamples, sideBySideDiff } from '../themeDiff';
+
+describe('Theme diff', () => {
+  it('should read theme files like the action inputs', () => {
+    const config = renderConfigFromInputs({ theme: 'compact', color_scheme: 'success=#2ecc71' });
+
+    expect(config.theme).toBe('compact');
+    expect(config.colors?.success).toBe(0x2ecc71);
+    expect(() => renderConfigFromInputs({ theme: 'neon' })).toThrow(TrackerError);
+    expect(() => renderConfigFromInputs({ discord_bot_token: 'x' })).toThrow(TrackerError);
+  });
+
+  it('should render the same samples for the same theme', () => {
+    const samples = renderThemeSamples({});
+
+    expect(Object.keys(samples)).toEqual(['init', 'running', 'failed', 'completed']);
+    expect(renderThemeSamples({})).toEqual(samples);
+    expect(renderThemeSamples({ theme: 'compact' }).running).not.toBe(samples.running);
+  });
+
+  it('should align common lines and mark the changes', () => {
+    expect(sideBySideDiff('a\nb\nc', 'a\nB\nc\nd', 4)).toEqual([
+      'a      a',
+      'b    | B',
+      'c      c',
+      '     > d',
+    ]);
+    expect(sideBySideDiff('a\nb', 'a', 4)).toEqual(['a      a', 'b    <']);
+  });
+});
@@ -0,0 +1,92 @@
+import { describe, it, expect, beforeEach } from 'bun:test';
+import { InMemoryStorage, PipelineTracker } from '../pipelineTracker';
+
+describe('Pipeline threads', () => {
+  let storage: InMemoryStorage;
+  let calls: Array<{ method: string; args: any[] }>;
+  let mockApi: any;
+
+  beforeEach(() => {
+    storage = new InMemoryStorage();
+    calls = [];
+    mockApi = {
+      sendMessage: async (...args: any[]) => {
+        calls.push({ method: 'sendMessage', args });
+        return args[1] ? 'thread-msg' : 'msg-1';
+      },
+      updateMessage: async (...args: any[]) => {
+        calls.push({ method: 'updateMessage', args });
+      },
+      createThread: async (...args: any[]) => {
+        calls.push({ method: 'createThread', args });
+        return 'thread-1';
+      },
+    };
+  });
+
+  function createTracker(threads: boolean): PipelineTracker {
+    const tracker = new PipelineTracker('test-token', 'test-channel', storage, { threads });
+    (tracker as any).api = mockApi;
+    return tracker;
+  }
+
+  it('should create a thread on init and post step details into it', async () => {
+    await createTracker(true).initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+    expect((await storage.loadPipelineState())?.threadId).toBe('thread-1');
+
+    // A later invocation restores the thread from state
+    await createTracker(true).updateStep(1, 2, 'Build', 'success', [['artifact', 'app.zip']]);
+
+    expect(calls.map((call) => call.method)).toEqual([
+      'sendMessage',
+      'createThread',
+      'updateMessage',
+      'sendMessage',
+    ]);
+    expect(calls[1].args).toEqual(['msg-1', 'PR #42: Test PR']);
+    expect(calls[3].args).toEqual([
+      { content: '✅ **Step 1: Build** - Success\n└ **artifact:** app.zip' },
+      'thread-1',
+    ]);
+  });
+
+  it('should keep working when the thread cannot be created', async () => {
+    mockApi.createThread = async () => {
+      throw new Error('Missing Permissions');
+    };
+    const tracker = createTracker(true);
+
+    await tracker.initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+    await tracker.updateStep(1, 2, 'Build', 'success', []);
+
+    expect(calls.map((call) => call.method)).toEqual(['sendMessage', 'updateMessage']);
+  });
+
+  it('should skip the thread with a warning when the channel is a thread', async () => {
+    mockApi.isWebhookMode = () => false;
+    mockApi.getChannel = async () => ({ id: 'test-channel', type: 11 });
+    const tracker = createTracker(true);
+
+    await tracker.initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+
+    expect(calls.map((call) => call.method)).toEqual(['sendMessage']);
+    expect(tracker.getWarnings()).toEqual([
+      {
+        code: 'UNSUPPORTED_CHANNEL_FEATURE',
+        message:
+          'Thread creation skipped - not supported in a public thread (channel test-channel)',
+        channelId: 'test-channel',
+        channelType: 'public thread',
+      },
+    ]);
+  });
+
+  it('should not create threads unless enabled', async () => {
+    const tracker = createTracker(false);
+
+    await tracker.initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+    await tracker.updateStep(1, 2, 'Build', 'success', []);
+
+    expect(calls.map((call) => call.method)).toEqual(['sendMessage', 'updateMessage']);
+  });
+});
@@ -1,5 +1,22 @@
 import { describe, it, expect } from 'vitest';
-import { validateBotToken, validateChannelId, validateStepNumber } from '../validation';
+import {
+  parseChangelog,
+  parseChannelIdList,
+  parseKeyValueList,
+  parseColorScheme,
+  parseStatusEmoji,
+  parseStepBudgets,
+  parseStepListHide,
+  splitAdditionalInfo,
+  validateBotToken,
+  validateBotTokenFormat,
+  validateChannelId,
+  validateLinkUrl,
+  validateLocale,
+  validateMessageEmbeds,
+  validateStepListStyle,
+  validateStepNumber,
+} from '../validation';
 import { TrackerError } from '../error';
 
 describe('Validation Functions', () => {
@@ -21,7 +38,59 @@ describe('Validation Functions', () => {
     });
   });
 
+  describe('validateBotTokenFormat', () => {
+    it('should pass for a three-part dotted token', () => {
+      expect(() =>
+        validateBotTokenFormat('MTIzNDU2Nzg5MDEyMzQ1Njc4.GaBcDe.abcdefghijklmnopqrstuvwxyz_-12')
+      ).not.toThrow();
+      expect(() =>
+        validateBotTokenFormat(' 1234567890.abcdefghijklmnopqrstuvwxyz.abcdef ')
+      ).not.toThrow();
+    });
+
+    it('should reject obviously malformed tokens', () => {
+      expect(() => validateBotTokenFormat('invalid_token')).toThrow(
+        TrackerError.invalidBotToken(
+          'expected three dot-separated segments of letters, digits, "-" and "_"'
+        )
+      );
+      expect(() => validateBotTokenFormat('abc.def.ghi')).toThrow('Bot token is invalid');
+      expect(() => validateBotTokenFormat('abcdefgh.ijk lmn.opqrstuv')).toThrow(
+        'Bot token is invalid'
+      );
+      expect(() => validateBotTokenFormat('')).toThrow(
+        TrackerError.missingRequiredInput('discord_bot_token')
+      );
+    });
+
+    it('should explain common mistakes', () => {
+      expect(() =>
+        validateBotTokenFormat('https://example.com/api/webhooks/123/abc')
+      ).toThrow('got a URL that is not a channel webhook URL');
+      expect(() => validateBotTokenFormat('Bot 1234567890.abcdefghijkl.abcdefgh')).toThrow(
+        'remove the "Bot " prefix'
+      );
+      expect(() => validateBotTokenFormat('mfa.abcdefghijklmnopqrstuvwxyz0123456789')).toThrow(
+        'got a user account token, not a bot token'
+      );
+    });
+  });
+
   // Tests for validateChannelId
+  describe('parseChannelIdList', () => {
+    it('should treat a single channel as the primary without mirrors', () => {
+      expect(parseChannelIdList('123')).toEqual({ primary: '123', mirrors: [] });
+      expect(parseChannelIdList('')).toEqual({ primary: '', mirrors: [] });
+    });
+
+    it('should mirror to the other distinct channels', () => {
+      expect(parseChannelIdList(' 123, 456 ,,456, 123,789 ')).toEqual({
+        primary: '123',
+        mirrors: ['456', '789'],
+      });
+    });
+  });
+
   describe('validateChannelId', () => {
     it('should pass for a valid regular integer channel ID', () => {
       expect(() => validateChannelId('123456789012345678')).not.toThrow();
@@ -75,3 +144,171 @@ describe('Validation Functions', () => {
     });
   });
 });
+
+describe('parseKeyValueList', () => {
+  it('should parse comma-separated key=value pairs', () => {
+    expect(parseKeyValueList('team=payments, tier=critical', 'tags')).toEqual([
+      ['team', 'payments'],
+      ['tier', 'critical'],
+    ]);
+  });
+
+  it('should keep "=" characters inside values', () => {
+    expect(parseKeyValueList('query=a=b', 'tags')).toEqual([['query', 'a=b']]);
+  });
+
+  it('should return an empty list for empty input', () => {
+    expect(parseKeyValueList('', 'tags')).toEqual([]);
+  });
+
+  it('should reject entries without a key or value', () => {
+    expect(() => parseKeyValueList('team', 'tags')).toThrow('Invalid tags entry: team');
+    expect(() => parseKeyValueList('=payments', 'tags')).toThrow('Invalid tags entry');
+  });
+});
+
+describe('parseStepBudgets', () => {
+  it('should parse durations in hours, minutes and seconds', () => {
+    expect(parseStepBudgets('Build=5m, Integration tests=1m30s,Deploy=1h,Lint=45')).toEqual({
+      Build: 300000,
+      'Integration tests': 90000,
+      Deploy: 3600000,
+      Lint: 45000,
+    });
+    expect(parseStepBudgets('')).toEqual({});
+  });
+
+  it('should reject malformed or zero durations', () => {
+    expect(() => parseStepBudgets('Build=5 minutes')).toThrow(
+      'Invalid step_budgets duration for Build'
+    );
+    expect(() => parseStepBudgets('Build=0s')).toThrow(TrackerError);
+  });
+});
+
+describe('parseChangelog', () => {
+  it('should parse one commit per line', () => {
+    expect(parseChangelog('abc1234 Fix login\n\n0123456789abcdef Bump deps \ndef5678')).toEqual([
+      { sha: 'abc1234', message: 'Fix login' },
+      { sha: '0123456789abcdef', message: 'Bump deps' },
+      { sha: 'def5678', message: '' },
+    ]);
+    expect(parseChangelog('')).toEqual([]);
+  });
+
+  it('should reject lines without a commit SHA', () => {
+    expect(() => parseChangelog('Fix login')).toThrow('Invalid changelog line: Fix login');
+  });
+});
+
+describe('splitAdditionalInfo', () => {
+  it('should separate table values from key/value pairs', () => {
+    expect(
+      splitAdditionalInfo({
+        duration: '30s',
+        sizes: [
+          ['file', 'size'],
+          ['main.js', 120],
+        ],
+        files: ['a', 'b'],
+      })
+    ).toEqual({
+      pairs: [
+        ['duration', '30s'],
+        ['files', 'a,b'],
+      ],
+      tables: [
+        {
+          title: 'sizes',
+          rows: [
+            ['file', 'size'],
+            ['main.js', '120'],
+          ],
+        },
+      ],
+    });
+  });
+});
+
+describe('Step list density options', () => {
+  it('should validate the step list style', () => {
+    expect(validateStepListStyle('')).toBe('fields');
+    expect(validateStepListStyle('Checkboxes')).toBe('checkboxes');
+    expect(() => validateStepListStyle('table')).toThrow(TrackerError);
+  });
+
+  it('should parse the hidden details', () => {
+    expect(parseStepListHide('')).toEqual({
+      showStepDurations: true,
+      showAdditionalInfo: true,
+      showStepNumbers: true,
+    });
+    expect(parseStepListHide('durations, numbers')).toEqual({
+      showStepDurations: false,
+      showAdditionalInfo: true,
+      showStepNumbers: false,
+    });
+    expect(() => parseStepListHide('emoji')).toThrow(TrackerError);
+  });
+
+  it('should parse the color overrides on top of the defaults', () => {
+    const colors = parseColorScheme('success=#2ECC71, in-progress=0x5865f2');
+
+    expect(colors.success).toBe(0x2ecc71);
+    expect(colors.inProgress).toBe(0x5865f2);
+    expect(colors.failure).toBe(0xff0000);
+    expect(() => parseColorScheme('done=#2ecc71')).toThrow(TrackerError);
+    expect(() => parseColorScheme('success=green')).toThrow(TrackerError);
+  });
+
+  it('should parse the status emoji overrides', () => {
+    expect(parseStatusEmoji('')).toEqual({});
+    expect(parseStatusEmoji('passed=<:ci_pass:123456789012345678>, running=🏃')).toEqual({
+      success: '<:ci_pass:123456789012345678>',
+      running: '🏃',
+    });
+    expect(() => parseStatusEmoji('done=✅')).toThrow(TrackerError);
+    expect(() => parseStatusEmoji('failed=<:ci_fail>')).toThrow(TrackerError);
+  });
+});
+
+describe('validateLinkUrl', () => {
+  it('should return undefined for an empty URL', () => {
+    expect(validateLinkUrl('', 'run_url')).toBeUndefined();
+  });
+
+  it('should accept and trim http(s) URLs', () => {
+    expect(validateLinkUrl(' https://github.com/owner/repo/pull/1 ', 'pr_url')).toBe(
+      'https://github.com/owner/repo/pull/1'
+    );
+  });
+
+  it('should reject non-http URLs', () => {
+    expect(() => validateLinkUrl('javascript:alert(1)', 'run_url')).toThrow(TrackerError);
+    expect(() => validateLinkUrl('github.com/owner/repo', 'run_url')).toThrow(TrackerError);
+  });
+});
+
+describe('validateLocale', () => {
+  it('should default to locale-independent formatting', () => {
+    expect(validateLocale('')).toBe('');
+    expect(validateLocale(' de-DE ')).toBe('de-DE');
+  });
+
+  it('should reject malformed or unknown tags', () => {
+    expect(() => validateLocale('not a locale')).toThrow(TrackerError);
+    expect(() => validateLocale('xx-YY')).toThrow(TrackerError);
+  });
+});
+
+describe('validateMessageEmbeds', () => {
+  it('should accept up to 10 embeds', () => {
+    const embeds = Array.from({ length: 10 }, () => ({ title: 'x' }));
+    expect(() => validateMessageEmbeds({ content: '', embeds })).not.toThrow();
+  });
+
+  it('should reject more than 10 embeds', () => {
+    const embeds = Array.from({ length: 11 }, () => ({ title: 'x' }));
+    expect(() => validateMessageEmbeds({ content: '', embeds })).toThrow(TrackerError);
+  });
+});
@@ -0,0 +1,187 @@
+import { describe, it, expect } from 'bun:test';
+import { TrackerError } from '../error';
+import type { DiscordChannel, DiscordPermissionOverwrite, DiscordRole } from '../models';
+import { computeChannelPermissions, missingPermissions, PERMISSIONS } from '../permissions';
+import { InMemoryStorage, PipelineTracker } from '../pipelineTracker';
+
+const GUILD = '100';
+const BOT = '200';
+const ROLE = '300';
+
+const ALL_REQUIRED =
+  PERMISSIONS.VIEW_CHANNEL |
+  PERMISSIONS.SEND_MESSAGES |
+  PERMISSIONS.EMBED_LINKS |
+  PERMISSIONS.READ_MESSAGE_HISTORY;
+
+describe('Channel permission computation', () => {
+  const roles: DiscordRole[] = [
+    { id: GUILD, permissions: String(PERMISSIONS.VIEW_CHANNEL) },
+    { id: ROLE, permissions: String(PERMISSIONS.SEND_MESSAGES | PERMISSIONS.EMBED_LINKS) },
+    { id: '999', permissions: String(PERMISSIONS.READ_MESSAGE_HISTORY) },
+  ];
+
+  it('should combine @everyone with the member roles only', () => {
+    const permissions = computeChannelPermissions(GUILD, BOT, [ROLE], roles);
+
+    expect(missingPermissions(permissions, ['VIEW_CHANNEL', 'SEND_MESSAGES'])).toEqual([]);
+    expect(missingPermissions(permissions, ['EMBED_LINKS'])).toEqual([]);
+    expect(missingPermissions(permissions, ['READ_MESSAGE_HISTORY'])).toEqual([
+      'Read Message History',
+    ]);
+  });
+
+  it('should apply @everyone, role and member overwrites in order', () => {
+    const overwrites: DiscordPermissionOverwrite[] = [
+      { id: GUILD, type: 0, allow: '0', deny: String(PERMISSIONS.VIEW_CHANNEL) },
+      { id: ROLE, type: 0, allow: String(PERMISSIONS.VIEW_CHANNEL), deny: '0' },
+      { id: BOT, type: 1, allow: '0', deny: String(PERMISSIONS.EMBED_LINKS) },
+    ];
+    const permissions = computeChannelPermissions(GUILD, BOT, [ROLE], roles, overwrites);
+
+    expect(
+      missingPermissions(permissions, ['VIEW_CHANNEL', 'SEND_MESSAGES', 'EMBED_LINKS'])
+    ).toEqual(['Embed Links']);
+  });
+
+  it('should grant everything to administrators regardless of overwrites', () => {
+    const adminRoles: DiscordRole[] = [
+      { id: ROLE, permissions: String(PERMISSIONS.ADMINISTRATOR) },
+    ];
+    const overwrites: DiscordPermissionOverwrite[] = [
+      { id: BOT, type: 1, allow: '0', deny: String(ALL_REQUIRED) },
+    ];
+    const permissions = computeChannelPermissions(GUILD, BOT, [ROLE], adminRoles, overwrites);
+
+    expect(missingPermissions(permissions, ['VIEW_CHANNEL', 'SEND_MESSAGES'])).toEqual([]);
+  });
+});
+
+describe('Channel verification', () => {
+  function createTracker(
+    channels: Record<string, DiscordChannel>,
+    options: { roles?: DiscordRole[]; channelError?: Error; webhook?: boolean } = {}
+  ) {
+    const tracker = new PipelineTracker('test-token', 'test-channel', new InMemoryStorage());
+    const calls: string[] = [];
+    (tracker as any).api = {
+      isWebhookMode: () => options.webhook === true,
+      getChannel: async (channelId = 'test-channel') => {
+        calls.push(`getChannel ${channelId}`);
+        if (options.channelError) {
+          throw options.channelError;
+        }
+        return channels[channelId];
+      },
+      getCurrentUser: async () => ({ id: BOT }),
+      getGuildMember: async () => ({ roles: [ROLE] }),
+      getGuildRoles: async () =>
+        options.roles || [{ id: GUILD, permissions: String(ALL_REQUIRED) }],
+    };
+    return { tracker, calls };
+  }
+
+  it('should accept a text channel the bot can post embeds in', async () => {
+    const { tracker } = createTracker({
+      'test-channel': { id: 'test-channel', type: 0, guild_id: GUILD, name: 'ci' },
+    });
+
+    expect(await tracker.verifyChannel()).toEqual({
+      channelId: 'test-channel',
+      channelType: 'text channel',
+      name: 'ci',
+    });
+  });
+
+  it('should list the missing permissions', async () => {
+    const { tracker } = createTracker(
+      { 'test-channel': { id: 'test-channel', type: 0, guild_id: GUILD } },
+      { roles: [{ id: GUILD, permissions: String(PERMISSIONS.VIEW_CHANNEL) }] }
+    );
+
+    const error = await tracker.verifyChannel().catch((e) => e);
+    expect(error.code).toBe('FORBIDDEN');
+    expect(error.message).toBe(
+      'Discord API Error: The bot is missing permissions in channel test-channel: ' +
+        'Send Messages, Embed Links, Read Message History'
+    );
+  });
+
+  it('should reject channels that cannot hold messages', async () => {
+    const { tracker } = createTracker({
+      'test-channel': { id: 'test-channel', type: 2, guild_id: GUILD },
+    });
+
+    await expect(tracker.verifyChannel()).rejects.toThrow(
+      'Channel test-channel (type 2) cannot hold the pipeline message'
+    );
+  });
+
+  it('should name the type of the rejected channel', async () => {
+    const { tracker } = createTracker({
+      'test-channel': { id: 'test-channel', type: 4, guild_id: GUILD },
+    });
+
+    const error = await tracker.verifyChannel().catch((e) => e);
+    expect(error.code).toBe('UNSUPPORTED_CHANNEL_TYPE');
+    expect(error.message).toBe(
+      'Channel test-channel (type 4) cannot hold the pipeline message: it is a category. ' +
+        'Use one of the text channels in the category'
+    );
+  });
+
+  it('should accept forum channels', async () => {
+    const { tracker } = createTracker({
+      'test-channel': { id: 'test-channel', type: 15, guild_id: GUILD, name: 'ci' },
+    });
+
+    expect((await tracker.verifyChannel()).channelType).toBe('forum channel');
+  });
+
+  it('should explain an unknown channel', async () => {
+    const { tracker } = createTracker({}, { channelError: TrackerError.messageNotFound('404') });
+
+    await expect(tracker.verifyChannel()).rejects.toThrow(
+      'Check discord_channel_id and that the bot was added to the server'
+    );
+  });
+
+  it('should read thread permissions from the parent channel overwrites', async () => {
+    const { tracker, calls } = createTracker(
+      {
+        'test-channel': { id: 'test-channel', type: 11, guild_id: GUILD, parent_id: 'parent' },
+        parent: {
+          id: 'parent',
+          type: 0,
+          guild_id: GUILD,
+          permission_overwrites: [
+            { id: GUILD, type: 0, allow: String(PERMISSIONS.SEND_MESSAGES_IN_THREADS), deny: '0' },
+          ],
+        },
+      },
+      {
+        roles: [
+          {
+            id: GUILD,
+            permissions: String(
+              PERMISSIONS.VIEW_CHANNEL | PERMISSIONS.EMBED_LINKS | PERMISSIONS.READ_MESSAGE_HISTORY
+            ),
+          },
+        ],
+      }
+    );
+
+    expect((await tracker.verifyChannel()).channelType).toBe('public thread');
+    expect(calls).toEqual(['getChannel test-channel', 'getChannel parent']);
+  });
+
+  it('should only check the channel of a webhook', async () => {
+    const { tracker, calls } = createTracker(
+      { 'test-channel': { id: 'test-channel', type: 0, guild_id: GUILD } },
+      { webhook: true, roles: [] }
+    );
+
+    expect((await tracker.verifyChannel()).channelType).toBe('text channel');
+    expect(calls).toEqual(['getChannel test-channel']);
+  });
+});
@@ -0,0 +1,74 @@
+import { describe, it, expect, beforeEach } from 'bun:test';
+import { InMemoryDiscordApi } from '../inMemoryDiscordApi';
+import {
+  InMemoryStorage,
+  PipelineTracker,
+  type TrackerOptions,
+  type TrackerWarning,
+} from '../pipelineTracker';
+import { silenceConsole } from './helpers';
+
+describe('Tracker warnings', () => {
+  let api: InMemoryDiscordApi;
+
+  silenceConsole('log', 'warn');
+
+  beforeEach(() => {
+    api = new InMemoryDiscordApi();
+  });
+
+  function createTracker(
+    options: Partial<TrackerOptions> = {},
+    storage = new InMemoryStorage()
+  ): PipelineTracker {
+    return new PipelineTracker('test-token', api.channelId, storage, { api, ...options });
+  }
+
+  function codes(tracker: PipelineTracker): string[] {
+    return tracker.getWarnings().map((warning) => warning.code);
+  }
+
+  it('should warn once when a failed step owner is not mapped to a Discord user', async () => {
+    const tracker = createTracker({
+      failureMentions: { users: ['123456789012345678'], roles: [] },
+      ownerMap: {},
+    });
+    await tracker.initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+    await tracker.updateStep(1, 2, 'Deploy', 'failed', [], { owner: 'octocat' });
+
+    expect(tracker.getWarnings()).toEqual([
+      {
+        code: 'MENTION_SKIPPED',
+        message: 'No Discord user mapped to octocat in owner_map - not pinged',
+      },
+    ]);
+  });
+
+  it('should warn when the embed text is truncated', async () => {
+    const tracker = createTracker();
+    await tracker.initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+    await tracker.updateStep(1, 1, 'Build', 'success', [['log', 'x'.repeat(2000)]]);
+
+    expect(codes(tracker)).toEqual(['EMBED_TRUNCATED']);
+  });
+
+  it('should pass on the warnings of the storage', async () => {
+    const migrated: TrackerWarning = { code: 'STATE_MIGRATED', message: 'Legacy state read' };
+    const legacy = Object.assign(new InMemoryStorage(), { takeWarnings: () => [migrated] });
+    await createTracker({}, legacy).initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+
+    const tracker = createTracker({}, legacy);
+    await tracker.loadState();
+
+    expect(tracker.getWarnings()).toEqual([migrated]);
+  });
+
+  it('should report no warnings for a clean run', async () => {
+    const tracker = createTracker();
+    await tracker.initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+    await tracker.updateStep(1, 1, 'Build', 'success', []);
+    await tracker.completePipeline();
+
+    expect(tracker.getWarnings()).toEqual([]);
+  });
+});
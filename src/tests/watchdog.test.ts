@@ -0,0 +1,102 @@
+import { describe, it, expect, beforeEach } from 'bun:test';
+import { InMemoryStorage, PipelineTracker } from '../pipelineTracker';
+import { watchPipeline } from '../watchdog';
+
+describe('Watchdog', () => {
+  let storage: InMemoryStorage;
+  let tracker: PipelineTracker;
+  let mockApi: any;
+
+  beforeEach(async () => {
+    storage = new InMemoryStorage();
+    tracker = new PipelineTracker('test-token', 'test-channel', storage);
+    mockApi = {
+      sent: [] as any[],
+      updated: [] as any[],
+      sendMessage: async (message: any) => {
+        mockApi.sent.push(message);
+        return 'msg-1';
+      },
+      updateMessage: async (messageId: string, message: any) => {
+        mockApi.updated.push({ messageId, message });
+      },
+    };
+    (tracker as any).api = mockApi;
+    await tracker.initPipeline('42', 'Test PR', 'dev', 'owner/repo', 'main');
+    mockApi.sent = [];
+  });
+
+  it('should mark the pipeline as stalled after the silence window', async () => {
+    let clock = 0;
+    const outcome = await watchPipeline(
+      tracker,
+      storage,
+      { maxSilenceMinutes: 5, pollIntervalSeconds: 60, escalationTarget: '<@&99>' },
+      async (ms) => {
+        clock += ms;
+      },
+      () => clock
+    );
+
+    expect(outcome).toBe('stalled');
+    expect(mockApi.updated).toHaveLength(1);
+    const embed = mockApi.updated[0].message.embeds[0];
+    expect(embed.title).toBe('⚠️ Pipeline Stalled - PR #42');
+    expect(embed.fields[0].value).toBe('⚠️ stalled — no updates for 5 minutes');
+    expect(mockApi.sent).toHaveLength(1);
+    expect(mockApi.sent[0].allowed_mentions).toEqual({ parse: [], users: [], roles: ['99'] });
+    expect(mockApi.sent[0].message_reference).toEqual({ message_id: 'msg-1' });
+  });
+
+  it('should not ping without an escalation target', async () => {
+    let clock = 0;
+    await watchPipeline(
+      tracker,
+      storage,
+      { maxSilenceMinutes: 1, pollIntervalSeconds: 30, escalationTarget: '' },
+      async (ms) => {
+        clock += ms;
+      },
+      () => clock
+    );
+
+    expect(mockApi.updated).toHaveLength(1);
+    expect(mockApi.sent).toHaveLength(0);
+  });
+
+  it('should reset the silence window when the state changes', async () => {
+    let clock = 0;
+    let polls = 0;
+    const outcome = await watchPipeline(
+      tracker,
+      storage,
+      { maxSilenceMinutes: 3, pollIntervalSeconds: 60, escalationTarget: '' },
+      async (ms) => {
+        clock += ms;
+        polls++;
+        if (polls === 2) {
+          await tracker.updateStep(1, 2, 'Build', 'running', []);
+        }
+      },
+      () => clock
+    );
+
+    expect(outcome).toBe('stalled');
+    // Stalled three minutes after the update seen on the third poll
+    expect(polls).toBe(5);
+  });
+
+  it('should stop once the pipeline state is cleared', async () => {
+    const outcome = await watchPipeline(
+      tracker,
+      storage,
+      { maxSilenceMinutes: 5, pollIntervalSeconds: 60, escalationTarget: '' },
+      async () => {
+        await storage.clearPipelineState();
+      }
+    );
+
+    expect(outcome).toBe('completed');
+    expect(mockApi.updated).toHaveLength(0);
+  });
+});
@@ -0,0 +1,30 @@
+import { describe, it, expect } from 'bun:test';
+import {
+  escapeCommandData,
+  escapeCommandProperty,
+  formatStepFailureAnnotation,
+  formatWorkflowCommand,
+} from '../workflowCommands';
+
+describe('Workflow commands', () => {
+  it('should escape the message and property values', () => {
+    expect(escapeCommandData('100% done\r\nnext')).toBe('100%25 done%0D%0Anext');
+    expect(escapeCommandProperty('a: b, c')).toBe('a%3A b%2C c');
+  });
+
+  it('should format a command with its properties', () => {
+    expect(formatWorkflowCommand('error', { title: 'T', file: undefined, line: 3 }, 'msg')).toBe(
+      '::error title=T,line=3::msg'
+    );
+    expect(formatWorkflowCommand('warning', {}, 'msg')).toBe('::warning::msg');
+  });
+
+  it('should annotate a failed step with its name and error', () => {
+    expect(formatStepFailureAnnotation('Deploy', 'exit code 1\nstack')).toBe(
+      '::error title=Pipeline step failed::Deploy: exit code 1%0Astack'
+    );
+    expect(formatStepFailureAnnotation('Deploy')).toBe(
+      '::error title=Pipeline step failed::Deploy failed'
+    );
+  });
+});
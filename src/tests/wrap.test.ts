@@ -0,0 +1,155 @@
+import { describe, it, expect } from 'bun:test';
+import * as fs from 'fs';
+import type { StepDetails } from '../models';
+import type { PipelineTracker } from '../pipelineTracker';
+import {
+  formatBytes,
+  measureProcessTree,
+  parseProcStat,
+  type ProcStat,
+  ResourceSampler,
+} from '../resources';
+import { buildWrappedInfo, runCommand, runWrappedStep } from '../wrap';
+
+class RecordingTracker {
+  public updates: Array<{ status: string; info: Array<[string, string]>; phase?: string }> = [];
+
+  async updateStep(
+    _stepNumber: number,
+    _totalSteps: number,
+    _stepName: string,
+    status: string,
+    additionalInfo: Array<[string, string]>,
+    details: StepDetails = {}
+  ): Promise<void> {
+    this.updates.push({ status, info: additionalInfo, phase: details.phase });
+  }
+}
+
+describe('Wrap mode', () => {
+  it('should report the exit code of a command', async () => {
+    const result = await runCommand(['exit 3']);
+
+    expect(result.exitCode).toBe(3);
+    expect(result.durationMs).toBeGreaterThanOrEqual(0);
+  });
+
+  it('should pass the arguments of a command as-is', async () => {
+    const result = await runCommand(['sh', '-c', 'test "$1" = "a b;c"', 'sh', 'a b;c']);
+
+    expect(result.exitCode).toBe(0);
+  });
+
+  it('should fail with 127 when the program does not exist', async () => {
+    const result = await runCommand(['discord-tracker-no-such-program', '--version']);
+
+    expect(result.exitCode).toBe(127);
+  });
+
+  it('should include exit code and duration in the additional info', () => {
+    expect(buildWrappedInfo({ exitCode: 1, durationMs: 65000 })).toEqual([
+      ['exit code', '1'],
+      ['duration', '1m 5s'],
+    ]);
+  });
+
+  it('should report running, then success for a passing command', async () => {
+    const tracker = new RecordingTracker();
+
+    const exitCode = await runWrappedStep(
+      tracker as unknown as PipelineTracker,
+      { number: 1, total: 2, name: 'Build' },
+      ['true']
+    );
+
+    expect(exitCode).toBe(0);
+    expect(tracker.updates.map((update) => update.status)).toEqual(['running', 'success']);
+    expect(tracker.updates[1].info[0]).toEqual(['exit code', '0']);
+  });
+
+  it('should report failure and propagate the exit code', async () => {
+    const tracker = new RecordingTracker();
+
+    const exitCode = await runWrappedStep(
+      tracker as unknown as PipelineTracker,
+      { number: 1, total: 2, name: 'Build' },
+      ['exit 2']
+    );
+
+    expect(exitCode).toBe(2);
+    expect(tracker.updates.map((update) => update.status)).toEqual(['running', 'failed']);
+  });
+
+  it('should report both updates under the given phase', async () => {
+    const tracker = new RecordingTracker();
+
+    await runWrappedStep(
+      tracker as unknown as PipelineTracker,
+      { number: 1, total: 2, name: 'Unit tests', phase: 'test' },
+      ['true']
+    );
+
+    expect(tracker.updates.map((update) => update.phase)).toEqual(['test', 'test']);
+  });
+});
+
+describe('Wrap resource stats', () => {
+  // pid 42, comm "(my (tool))", ppid 7, utime 10, stime 5, cutime 3, cstime 2, rss 256 pages
+  const STAT =
+    '42 ((my (tool))) S 7 42 7 0 -1 4194304 100 0 0 0 10 5 3 2 20 0 1 0 1000 1048576 256 ' +
+    '18446744073709551615 0 0 0 0 0 0 0 0 0 0 0 0 17 0 0 0 0 0 0';
+
+  function stat(pid: number, ppid: number, cpuTicks: number, rssPages: number): ProcStat {
+    return { pid, ppid, cpuTicks, rssPages };
+  }
+
+  it('should parse /proc stat lines whose command contains parentheses', () => {
+    expect(parseProcStat(STAT)).toEqual({ pid: 42, ppid: 7, cpuTicks: 20, rssPages: 256 });
+    expect(parseProcStat('garbage')).toBeUndefined();
+  });
+
+  it('should sum the whole process tree regardless of listing order', () => {
+    const stats = [
+      stat(12, 11, 50, 100),
+      stat(10, 1, 100, 10),
+      stat(11, 10, 50, 20),
+      stat(99, 1, 1, 1),
+    ];
+
+    expect(measureProcessTree(stats, 10)).toEqual({ rssBytes: 130 * 4096, cpuTimeMs: 2000 });
+    expect(measureProcessTree(stats, 500)).toBeUndefined();
+  });
+
+  it('should keep the peak memory across samples', () => {
+    const samples = [[stat(10, 1, 10, 100)], [stat(10, 1, 30, 400)], [stat(10, 1, 50, 200)], []];
+    const sampler = new ResourceSampler(10, () => samples.shift() || []);
+
+    for (let i = 0; i < 4; i++) {
+      sampler.sample();
+    }
+
+    expect(sampler.stop()).toEqual({ peakRssBytes: 400 * 4096, cpuTimeMs: 500 });
+  });
+
+  it('should add peak memory and CPU time to the additional info', () => {
+    const info = buildWrappedInfo({
+      exitCode: 0,
+      durationMs: 1000,
+      resources: { peakRssBytes: 512 * 1024 * 1024, cpuTimeMs: 12340 },
+    });
+
+    expect(info).toContainEqual(['peak memory', '512.0 MiB']);
+    expect(info).toContainEqual(['cpu time', '12.3s']);
+    expect(formatBytes(512)).toBe('512 B');
+  });
+
+  it('should sample a real command on Linux', async () => {
+    if (!fs.existsSync('/proc/self/stat')) {
+      return;
+    }
+
+    const result = await runCommand(['sleep 0.2'], { sampleResources: true });
+
+    expect(result.resources?.peakRssBytes).toBeGreaterThan(0);
+  });
+});
@@ -0,0 +1,195 @@
+import * as fs from 'fs';
+import { requireFlag } from './cli';
+import { frozenClock } from './clock';
+import { TrackerError } from './error';
+import {
+  buildCompletionEmbed,
+  buildInitEmbed,
+  buildStepUpdateEmbed,
+  type RenderConfig,
+} from './messageBuilder';
+import { type StepInfo, StepStatus } from './models';
+import {
+  parseColorScheme,
+  parseStatusEmoji,
+  parseStepListHide,
+  validateEmbedLayout,
+  validateLocale,
+  validatePercentDecimals,
+  validateProgressDisplay,
+  validateStepListStyle,
+  validateTheme,
+} from './validation';
+
+// Action inputs a theme file may set, as the same strings the workflow would pass
+export const THEME_INPUTS = [
+  'theme',
+  'color_scheme',
+  'status_emoji',
+  'progress_display',
+  'percent_decimals',
+  'step_list_style',
+  'step_list_hide',
+  'embed_layout',
+  'locale',
+];
+
+// Time every sample is rendered at, so only the theme changes the output
+const SAMPLE_TIME = new Date('2024-01-01T12:00:00Z');
+
+/// Converts the render inputs of a theme file (e.g. `{"theme": "compact"}`) into render
+/// options, validated like the action inputs
+export function renderConfigFromInputs(inputs: Record<string, string>): Partial<RenderConfig> {
+  for (const name of Object.keys(inputs)) {
+    if (!THEME_INPUTS.includes(name)) {
+      throw new TrackerError(
+        `Unknown theme input: ${name}. Valid inputs: ${THEME_INPUTS.join(', ')}`,
+        'INVALID_THEME_FILE'
+      );
+    }
+  }
+  const input = (name: string) => String(inputs[name] ?? '');
+  return {
+    theme: validateTheme(input('theme')),
+    colors: parseColorScheme(input('color_scheme')),
+    statusEmoji: parseStatusEmoji(input('status_emoji')),
+    progressDisplay: validateProgressDisplay(input('progress_display')),
+    percentDecimals: validatePercentDecimals(input('percent_decimals')),
+    stepListStyle: validateStepListStyle(input('step_list_style')),
+    embedLayout: validateEmbedLayout(input('embed_layout')),
+    locale: validateLocale(input('locale')),
+    ...parseStepListHide(input('step_list_hide')),
+  };
+}
+
+/// Reads a theme file: a JSON object of render inputs
+export function loadThemeFile(filePath: string): Partial<RenderConfig> {
+  const parsed = JSON.parse(fs.readFileSync(filePath, 'utf-8'));
+  if (!parsed || typeof parsed !== 'object' || Array.isArray(parsed)) {
+    throw new TrackerError(`Theme file ${filePath} must hold a JSON object`, 'INVALID_THEME_FILE');
+  }
+  return renderConfigFromInputs(parsed);
+}
+
+/// Renders the representative payloads a theme is reviewed on: the init embed, a running
+/// update, a failed update and the completion embed of the same sample pipeline
+export function renderThemeSamples(config: Partial<RenderConfig>): Record<string, string> {
+  const render = { ...config, clock: frozenClock(SAMPLE_TIME) };
+  const at = (minutes: number) => new Date(SAMPLE_TIME.getTime() - minutes * 60000);
+  const step = (
+    number: number,
+    name: string,
+    status: StepStatus,
+    additionalInfo: Array<[string, string]> = []
+  ): StepInfo => ({
+    number,
+    name,
+    status,
+    additionalInfo,
+    startedAt: at(10 - number * 2),
+    completedAt: status === StepStatus.Running ? undefined : at(9 - number * 2),
+  });
+
+  const running = [
+    step(1, 'Lint', StepStatus.Success),
+    step(2, 'Unit tests', StepStatus.Success, [['coverage', '92%']]),
+    step(3, 'Build', StepStatus.Running),
+  ];
+  const failed = [
+    ...running.slice(0, 2),
+    step(3, 'Build', StepStatus.Failed, [['error', 'exit 1']]),
+  ];
+  const started = at(10);
+
+  const samples = {
+    init: buildInitEmbed('42', 'Add dark mode', 'octocat', 'acme/app', 'main', render),
+    running: buildStepUpdateEmbed('42', 'Add dark mode', running, 3, 4, render),
+    failed: buildStepUpdateEmbed('42', 'Add dark mode', failed, 3, 4, render),
+    completed: buildCompletionEmbed('42', 'Add dark mode', failed, 4, started, render),
+  };
+  return Object.fromEntries(
+    Object.entries(samples).map(([name, embed]) => [name, JSON.stringify(embed, null, 2)])
+  );
+}
+
+/// Fits a line into a column, cutting it with `…` or padding it with spaces
+function fitColumn(line: string, width: number): string {
+  return line.length > width ? `${line.slice(0, width - 1)}…` : line.padEnd(width);
+}
+
+/// Lays two texts out side by side, aligned on their common lines (longest common
+/// subsequence). The gutter marks changed (`|`), removed (`<`) and added (`>`) lines.
+export function sideBySideDiff(left: string, right: string, width = 60): string[] {
+  const a = left.split('\n');
+  const b = right.split('\n');
+
+  // lengths[i][j]: longest common subsequence of a[i..] and b[j..]
+  const lengths = Array.from({ length: a.length + 1 }, () => new Array(b.length + 1).fill(0));
+  for (let i = a.length - 1; i >= 0; i--) {
+    for (let j = b.length - 1; j >= 0; j--) {
+      lengths[i][j] =
+        a[i] === b[j] ? lengths[i + 1][j + 1] + 1 : Math.max(lengths[i + 1][j], lengths[i][j + 1]);
+    }
+  }
+
+  const lines: string[] = [];
+  let removed: string[] = [];
+  let added: string[] = [];
+  // Removed and added lines between two common lines are paired up as changes
+  const flush = () => {
+    for (let k = 0; k < Math.max(removed.length, added.length); k++) {
+      const gutter = k >= added.length ? '<' : k >= removed.length ? '>' : '|';
+      lines.push(`${fitColumn(removed[k] ?? '', width)} ${gutter} ${added[k] ?? ''}`.trimEnd());
+    }
+    removed = [];
+    added = [];
+  };
+
+  let i = 0;
+  let j = 0;
+  while (i < a.length || j < b.length) {
+    if (i < a.length && j < b.length && a[i] === b[j]) {
+      flush();
+      lines.push(`${fitColumn(a[i], width)}   ${b[j]}`.trimEnd());
+      i++;
+      j++;
+    } else if (j >= b.length || (i < a.length && lengths[i + 1][j] >= lengths[i][j + 1])) {
+      removed.push(a[i++]);
+    } else {
+      added.push(b[j++]);
+    }
+  }
+  flush();
+  return lines;
+}
+
+/// Runs `theme diff`: renders the sample payloads with the current theme (`--current FILE`,
+/// the defaults when omitted) and the proposed one (`--proposed FILE`) and prints them side by
+/// side, for review before rollout. Returns the process exit code.
+export function runThemeCommand(positional: string[], flags: Record<string, string>): number {
+  const [operation] = positional;
+  if (operation !== 'diff') {
+    console.error('Error: Usage: theme diff --proposed FILE [--current FILE] [--width N]');
+    return 1;
+  }
+
+  const current = flags.current ? loadThemeFile(flags.current) : {};
+  const proposed = loadThemeFile(requireFlag(flags, 'proposed'));
+  const width = parseInt(flags.width || '60', 10) || 60;
+
+  const before = renderThemeSamples(current);
+  const after = renderThemeSamples(proposed);
+  let changed = 0;
+  for (const name of Object.keys(before)) {
+    const identical = before[name] === after[name];
+    if (!identical) {
+      changed++;
+    }
+    console.log(`=== ${name}${identical ? ' (unchanged)' : ''} ===`);
+    console.log(sideBySideDiff(before[name], after[name], width).join('\n'));
+    console.log('');
+  }
+
+  console.log(`${changed} of ${Object.keys(before).length} payloads differ`);
+  return 0;
+}
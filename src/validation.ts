@@ -1,4 +1,27 @@
 import { TrackerError } from './error';
+import type { StatusPublishMode } from './github';
+import {
+  type ColorScheme,
+  DEFAULT_COLOR_SCHEME,
+  type EmbedLayout,
+  MAX_MESSAGE_EMBEDS,
+  type ProgressDisplay,
+  type RenderConfig,
+  type StepListStyle,
+  type Theme,
+} from './messageBuilder';
+import { DEFAULT_HOOK_TIMEOUT_MS } from './hooks';
+import { isSupportedLocale } from './locale';
+import {
+  type ChangelogCommit,
+  type DiscordMessage,
+  type StatusEmojiMap,
+  StepStatus,
+  StepStatusHelper,
+  type StepTable,
+} from './models';
+import { DEFAULT_PREFLIGHT_TTL_SECONDS } from './permissionCache';
+import type { StorageBackend } from './storage';
 
 /**
  * Validates a Discord bot token
@@ -15,6 +38,37 @@ export function validateBotToken(token: string): void {
   }
 }
 
+// Bot tokens are three dot-separated base64url segments: the encoded bot user ID, a
+// timestamp and an HMAC
+const BOT_TOKEN_PATTERN = /^[A-Za-z0-9_-]{6,}\.[A-Za-z0-9_-]{6,}\.[A-Za-z0-9_-]{6,}$/;
+
+/**
+ * Checks that a bot token has the structure of a Discord bot token, catching typos such as a
+ * pasted URL or user token before they fail later as a confusing 401. Webhook URLs are accepted
+ * by the tracker and must be recognized by the caller before this check.
+ * @param token - The bot token to validate
+ * @throws TrackerError if the token is obviously malformed
+ */
+export function validateBotTokenFormat(token: string): void {
+  validateBotToken(token);
+  const trimmed = token.trim();
+
+  if (/^https?:\/\//i.test(trimmed)) {
+    throw TrackerError.invalidBotToken('got a URL that is not a channel webhook URL');
+  }
+  if (/^Bot\s/i.test(trimmed)) {
+    throw TrackerError.invalidBotToken('remove the "Bot " prefix');
+  }
+  if (trimmed.startsWith('mfa.')) {
+    throw TrackerError.invalidBotToken('got a user account token, not a bot token');
+  }
+  if (!BOT_TOKEN_PATTERN.test(trimmed)) {
+    throw TrackerError.invalidBotToken(
+      'expected three dot-separated segments of letters, digits, "-" and "_"'
+    );
+  }
+}
+
 /**
  * Validates a Discord channel ID
  * @param channelId - The channel ID to validate
@@ -96,7 +150,23 @@ export function validateRepository(repository: string): void {
  * @throws TrackerError if the action is invalid
  */
 export function validateAction(action: string): void {
-  const validActions = ['init', 'step', 'complete', 'fail'];
+  const validActions = [
+    'init',
+    'step',
+    'complete',
+    'fail',
+    'digest',
+    'remind',
+    'annotate',
+    'board',
+    'cleanup',
+    'verify',
+    'collect-feedback',
+    'rollback',
+    'cancel',
+    'mute',
+    'unmute',
+  ];
 
   if (!action || typeof action !== 'string') {
     throw TrackerError.missingRequiredInput('action');
@@ -128,3 +198,717 @@ export function validateAdditionalInfo(additionalInfo: string): Array<[string, s
     return [];
   }
 }
+
+/**
+ * Splits parsed additional info into key/value pairs and tables (values that are arrays of rows)
+ * @param parsed - The parsed additional info object
+ * @returns Key/value pairs and tables, in input order
+ */
+export function splitAdditionalInfo(parsed: Record<string, unknown>): {
+  pairs: Array<[string, string]>;
+  tables: StepTable[];
+} {
+  const pairs: Array<[string, string]> = [];
+  const tables: StepTable[] = [];
+
+  for (const [key, value] of Object.entries(parsed)) {
+    if (Array.isArray(value) && value.length > 0 && value.every(Array.isArray)) {
+      tables.push({
+        title: key,
+        rows: value.map((row: unknown[]) => row.map((cell) => String(cell))),
+      });
+    } else {
+      pairs.push([key, String(value)]);
+    }
+  }
+
+  return { pairs, tables };
+}
+
+/**
+ * Validates the progress display mode
+ * @param display - The display mode ("steps", "percent", "both" or "bar")
+ * @returns The validated display mode, defaulting to "both" when empty
+ * @throws TrackerError if the display mode is unknown
+ */
+export function validateProgressDisplay(display: string): ProgressDisplay {
+  if (!display || display.trim().length === 0) {
+    return 'both';
+  }
+
+  const validDisplays = ['steps', 'percent', 'both', 'bar'];
+  const normalized = display.trim().toLowerCase();
+  if (!validDisplays.includes(normalized)) {
+    throw new TrackerError(
+      `Invalid progress display: ${display}. Expected: steps, percent, both or bar`,
+      'INVALID_PROGRESS_DISPLAY'
+    );
+  }
+  return normalized as ProgressDisplay;
+}
+
+/**
+ * Validates how many recent channel messages are searched for the run's message on init
+ * @param limit - The number of messages as provided by the action input
+ * @returns The validated limit, or 0 (search disabled) when empty
+ * @throws TrackerError if the value is not an integer between 0 and 100
+ */
+export function validateDedupeSearchLimit(limit: string): number {
+  if (!limit || limit.trim().length === 0) {
+    return 0;
+  }
+
+  const num = Number(limit);
+  if (!Number.isInteger(num) || num < 0 || num > 100) {
+    throw new TrackerError(
+      'Invalid dedupe search limit. Expected an integer between 0 and 100',
+      'INVALID_DEDUPE_SEARCH_LIMIT'
+    );
+  }
+  return num;
+}
+
+/**
+ * Validates the number of decimal places used for percentages
+ * @param decimals - The decimal places as provided by the action input
+ * @returns The validated number of decimal places, defaulting to 0 when empty
+ * @throws TrackerError if the value is not an integer between 0 and 4
+ */
+export function validatePercentDecimals(decimals: string): number {
+  if (!decimals || decimals.trim().length === 0) {
+    return 0;
+  }
+
+  const num = Number(decimals);
+  if (!Number.isInteger(num) || num < 0 || num > 4) {
+    throw new TrackerError(
+      'Invalid percent decimals. Expected an integer between 0 and 4',
+      'INVALID_PERCENT_DECIMALS'
+    );
+  }
+  return num;
+}
+
+/**
+ * Validates the rollout percentage reported by a deploy step
+ * @param percent - The percentage (0-100), as provided by the action input
+ * @returns The percentage, or undefined when the step reports no rollout
+ * @throws TrackerError if the value is not a number between 0 and 100
+ */
+export function validateRolloutPercent(percent: string): number | undefined {
+  if (!percent || percent.trim().length === 0) {
+    return undefined;
+  }
+
+  const num = Number(percent.trim().replace(/%$/, ''));
+  if (!Number.isFinite(num) || num < 0 || num > 100) {
+    throw new TrackerError(
+      'Invalid rollout percent. Expected a number between 0 and 100',
+      'INVALID_ROLLOUT_PERCENT'
+    );
+  }
+  return num;
+}
+
+/**
+ * Validates the render seed that freezes rendered timestamps
+ * @param seed - Unix time in seconds, as provided by the action input
+ * @returns The seed, or undefined (live clock) when empty
+ * @throws TrackerError if the seed is not a non-negative integer
+ */
+export function validateRenderSeed(seed: string): number | undefined {
+  if (!seed || seed.trim().length === 0) {
+    return undefined;
+  }
+
+  const num = Number(seed.trim());
+  if (!Number.isSafeInteger(num) || num < 0) {
+    throw new TrackerError(
+      'Invalid render seed. Expected a non-negative integer (Unix time in seconds)',
+      'INVALID_RENDER_SEED'
+    );
+  }
+  return num;
+}
+
+/**
+ * Validates the locale used to format percentages, costs and durations
+ * @param locale - A BCP 47 locale tag such as "de-DE", as provided by the action input
+ * @returns The trimmed tag, or an empty string (locale-independent formatting) when empty
+ * @throws TrackerError if the tag is malformed or the runtime has no data for it
+ */
+export function validateLocale(locale: string): string {
+  if (!locale || locale.trim().length === 0) {
+    return '';
+  }
+
+  const trimmed = locale.trim();
+  if (!isSupportedLocale(trimmed)) {
+    throw new TrackerError(
+      `Invalid locale: ${locale}. Expected a BCP 47 tag such as "en-US" or "de-DE"`,
+      'INVALID_LOCALE'
+    );
+  }
+  return trimmed;
+}
+
+/**
+ * Validates the step list style
+ * @param style - The style ("fields", "inline" or "checkboxes")
+ * @returns The validated style, defaulting to "fields" when empty
+ * @throws TrackerError if the style is unknown
+ */
+export function validateStepListStyle(style: string): StepListStyle {
+  if (!style || style.trim().length === 0) {
+    return 'fields';
+  }
+
+  const validStyles = ['fields', 'inline', 'checkboxes'];
+  const normalized = style.trim().toLowerCase();
+  if (!validStyles.includes(normalized)) {
+    throw new TrackerError(
+      `Invalid step list style: ${style}. Valid styles: ${validStyles.join(', ')}`,
+      'INVALID_STEP_LIST_STYLE'
+    );
+  }
+  return normalized as StepListStyle;
+}
+
+/**
+ * Validates the embed layout
+ * @param layout - "single" (one embed) or "multi" (summary embed plus per-phase detail embeds)
+ * @returns The validated layout, defaulting to "single" when empty
+ * @throws TrackerError if the layout is unknown
+ */
+export function validateEmbedLayout(layout: string): EmbedLayout {
+  if (!layout || layout.trim().length === 0) {
+    return 'single';
+  }
+
+  const normalized = layout.trim().toLowerCase();
+  if (normalized !== 'single' && normalized !== 'multi') {
+    throw new TrackerError(
+      `Invalid embed layout: ${layout}. Expected: single or multi`,
+      'INVALID_EMBED_LAYOUT'
+    );
+  }
+  return normalized;
+}
+
+/**
+ * Validates the display theme
+ * @param theme - "standard", "compact" (one-line embed) or "detailed" (every step detail)
+ * @returns The validated theme, defaulting to "standard" when empty
+ * @throws TrackerError if the theme is unknown
+ */
+export function validateTheme(theme: string): Theme {
+  if (!theme || theme.trim().length === 0) {
+    return 'standard';
+  }
+
+  const normalized = theme.trim().toLowerCase();
+  if (normalized !== 'standard' && normalized !== 'compact' && normalized !== 'detailed') {
+    throw new TrackerError(
+      `Invalid theme: ${theme}. Expected: standard, compact or detailed`,
+      'INVALID_THEME'
+    );
+  }
+  return normalized;
+}
+
+/**
+ * Validates a message against Discord's embed count limit before it is sent
+ * @param message - The message to send
+ * @throws TrackerError if the message has more than 10 embeds
+ */
+export function validateMessageEmbeds(message: DiscordMessage): void {
+  const count = message.embeds?.length || 0;
+  if (count > MAX_MESSAGE_EMBEDS) {
+    throw new TrackerError(
+      `Message has ${count} embeds; Discord allows at most ${MAX_MESSAGE_EMBEDS}`,
+      'TOO_MANY_EMBEDS'
+    );
+  }
+}
+
+/**
+ * Parses the comma-separated list of step list details to hide
+ * @param hide - e.g. "durations,info,numbers"
+ * @returns The corresponding render toggles
+ * @throws TrackerError if an entry is unknown
+ */
+export function parseStepListHide(
+  hide: string
+): Pick<RenderConfig, 'showStepDurations' | 'showAdditionalInfo' | 'showStepNumbers'> {
+  const entries = hide
+    .split(',')
+    .map((entry) => entry.trim().toLowerCase())
+    .filter((entry) => entry.length > 0);
+
+  const validEntries = ['durations', 'info', 'numbers'];
+  for (const entry of entries) {
+    if (!validEntries.includes(entry)) {
+      throw new TrackerError(
+        `Invalid step_list_hide entry: ${entry}. Valid entries: ${validEntries.join(', ')}`,
+        'INVALID_STEP_LIST_HIDE'
+      );
+    }
+  }
+
+  return {
+    showStepDurations: !entries.includes('durations'),
+    showAdditionalInfo: !entries.includes('info'),
+    showStepNumbers: !entries.includes('numbers'),
+  };
+}
+
+// Keys of the color_scheme input, by ColorScheme field
+const COLOR_SCHEME_KEYS: Record<string, keyof ColorScheme> = {
+  init: 'init',
+  in_progress: 'inProgress',
+  success: 'success',
+  failure: 'failure',
+  skipped: 'skipped',
+  warning: 'warning',
+  cancelled: 'cancelled',
+};
+
+/**
+ * Parses the embed color overrides on top of the default colors
+ * @param input - e.g. "success=#2ecc71,failure=0xe74c3c"
+ * @returns The complete color scheme
+ * @throws TrackerError if a key is unknown or a color is not a 6-digit hex color
+ */
+export function parseColorScheme(input: string): ColorScheme {
+  const colors = { ...DEFAULT_COLOR_SCHEME };
+  for (const [key, value] of parseKeyValueList(input, 'color_scheme')) {
+    const field = COLOR_SCHEME_KEYS[key.toLowerCase().replace(/-/g, '_')];
+    if (!field) {
+      const validKeys = Object.keys(COLOR_SCHEME_KEYS).join(', ');
+      throw new TrackerError(
+        `Invalid color_scheme key: ${key}. Valid keys: ${validKeys}`,
+        'INVALID_COLOR_SCHEME'
+      );
+    }
+    const hex = value.match(/^(?:#|0x)([0-9a-f]{6})$/i);
+    if (!hex) {
+      throw new TrackerError(
+        `Invalid color_scheme color: ${value}. Expected: #RRGGBB or 0xRRGGBB`,
+        'INVALID_COLOR_SCHEME'
+      );
+    }
+    colors[field] = parseInt(hex[1], 16);
+  }
+  return colors;
+}
+
+// A custom server emoji as written in a message, e.g. <:ci_pass:123456789012345678>
+const CUSTOM_EMOJI_PATTERN = /^<a?:\w{2,32}:\d{17,20}>$/;
+
+/**
+ * Parses the per-status emoji overrides
+ * @param input - e.g. "success=<:ci_pass:123456789012345678>,running=🏃"
+ * @returns The emoji keyed by status, or an empty map when the input is empty
+ * @throws TrackerError if a status is unknown or a custom emoji is malformed
+ */
+export function parseStatusEmoji(input: string): StatusEmojiMap {
+  const statusEmoji: StatusEmojiMap = {};
+  for (const [key, emoji] of parseKeyValueList(input, 'status_emoji')) {
+    const status = StepStatusHelper.fromStr(key);
+    const validStatuses: string[] = Object.values(StepStatus);
+    if (!validStatuses.includes(status)) {
+      throw new TrackerError(
+        `Invalid status_emoji status: ${key}. Valid statuses: ${validStatuses.join(', ')}`,
+        'INVALID_STATUS_EMOJI'
+      );
+    }
+    if (emoji.startsWith('<') && !CUSTOM_EMOJI_PATTERN.test(emoji)) {
+      throw new TrackerError(
+        `Invalid status_emoji emoji: ${emoji}. Expected: <:name:id> or <a:name:id>`,
+        'INVALID_STATUS_EMOJI'
+      );
+    }
+    statusEmoji[status as StepStatus] = emoji;
+  }
+  return statusEmoji;
+}
+
+/**
+ * Validates the runner cost per minute
+ * @param cost - The cost per minute as provided by the action input
+ * @returns The validated cost, defaulting to 0 (disabled) when empty
+ * @throws TrackerError if the value is not a non-negative number
+ */
+export function validateCostPerMinute(cost: string): number {
+  if (!cost || cost.trim().length === 0) {
+    return 0;
+  }
+
+  const num = Number(cost);
+  if (Number.isNaN(num) || num < 0) {
+    throw new TrackerError('Invalid runner cost per minute', 'INVALID_COST_PER_MINUTE');
+  }
+  return num;
+}
+
+/**
+ * Validates the number of retries for transient Discord API failures
+ * @param retries - The retry count as provided by the action input
+ * @returns The validated retry count, defaulting to 3 when empty
+ * @throws TrackerError if the value is not an integer between 0 and 10
+ */
+export function validateRetryAttempts(retries: string): number {
+  if (!retries || retries.trim().length === 0) {
+    return 3;
+  }
+
+  const num = Number(retries);
+  if (!Number.isInteger(num) || num < 0 || num > 10) {
+    throw new TrackerError(
+      'Invalid retry attempts. Must be an integer between 0 and 10',
+      'INVALID_RETRY_ATTEMPTS'
+    );
+  }
+  return num;
+}
+
+/**
+ * Validates the maximum time spent waiting on Discord rate limits
+ * @param seconds - The wait budget in seconds as provided by the action input
+ * @returns The wait budget in milliseconds, defaulting to 60 seconds when empty
+ * @throws TrackerError if the value is not a non-negative number
+ */
+export function validateRateLimitMaxWait(seconds: string): number {
+  if (!seconds || seconds.trim().length === 0) {
+    return 60000;
+  }
+
+  const num = Number(seconds);
+  if (Number.isNaN(num) || num < 0) {
+    throw new TrackerError('Invalid rate limit max wait', 'INVALID_RATE_LIMIT_MAX_WAIT');
+  }
+  return num * 1000;
+}
+
+/**
+ * Validates how long a hook command may run before it is killed
+ * @param seconds - The timeout in seconds as provided by the action input
+ * @returns The timeout in milliseconds, defaulting to 30 seconds when empty
+ * @throws TrackerError if the value is not a positive number
+ */
+export function validateHookTimeout(seconds: string): number {
+  if (!seconds || seconds.trim().length === 0) {
+    return DEFAULT_HOOK_TIMEOUT_MS;
+  }
+
+  const num = Number(seconds);
+  if (Number.isNaN(num) || num <= 0) {
+    throw new TrackerError(
+      `Invalid hook timeout: ${seconds}. Expected a positive number of seconds`,
+      'INVALID_HOOK_TIMEOUT'
+    );
+  }
+  return num * 1000;
+}
+
+/**
+ * Validates how long a successful channel preflight is reused
+ * @param seconds - The TTL in seconds as provided by the action input
+ * @returns The TTL in seconds, defaulting to 600 when empty; 0 checks on every invocation
+ * @throws TrackerError if the value is not a non-negative number
+ */
+export function validatePreflightTtl(seconds: string): number {
+  if (!seconds || seconds.trim().length === 0) {
+    return DEFAULT_PREFLIGHT_TTL_SECONDS;
+  }
+
+  const num = Number(seconds);
+  if (Number.isNaN(num) || num < 0) {
+    throw new TrackerError(
+      `Invalid preflight cache TTL: ${seconds}. Expected a non-negative number of seconds`,
+      'INVALID_PREFLIGHT_TTL'
+    );
+  }
+  return num;
+}
+
+/**
+ * Validates the exit code of the failing command reported by the `fail` action
+ * @param exitCode - The exit code as provided by the action input
+ * @returns The exit code, or undefined when empty
+ * @throws TrackerError if the value is not an integer
+ */
+export function validateExitCode(exitCode: string): number | undefined {
+  if (!exitCode || exitCode.trim().length === 0) {
+    return undefined;
+  }
+
+  const num = Number(exitCode);
+  if (!Number.isInteger(num)) {
+    throw new TrackerError(
+      `Invalid exit code: ${exitCode}. Expected an integer`,
+      'INVALID_EXIT_CODE'
+    );
+  }
+  return num;
+}
+
+/**
+ * Validates how long the `mute` action silences notifications
+ * @param minutes - The duration in minutes as provided by the action input
+ * @returns The duration in minutes, or undefined (muted until `unmute`) when empty
+ * @throws TrackerError if the value is not a positive number
+ */
+export function validateMuteMinutes(minutes: string): number | undefined {
+  if (!minutes || minutes.trim().length === 0) {
+    return undefined;
+  }
+
+  const num = Number(minutes);
+  if (Number.isNaN(num) || num <= 0) {
+    throw new TrackerError(
+      `Invalid mute duration: ${minutes}. Expected a positive number of minutes`,
+      'INVALID_MUTE_DURATION'
+    );
+  }
+  return num;
+}
+
+/**
+ * Validates the SLO success rate target
+ * @param target - The target in percent as provided by the action input
+ * @returns The validated target, or undefined (SLO tracking disabled) when empty
+ * @throws TrackerError if the value is not a number between 0 and 100
+ */
+export function validateSloTarget(target: string): number | undefined {
+  if (!target || target.trim().length === 0) {
+    return undefined;
+  }
+
+  const num = Number(target);
+  if (Number.isNaN(num) || num < 0 || num > 100) {
+    throw new TrackerError('Invalid SLO target. Must be between 0 and 100', 'INVALID_SLO_TARGET');
+  }
+  return num;
+}
+
+/**
+ * Validates an optional link URL (used for the "View Run"/"View PR" buttons)
+ * @param url - The URL as provided by the action input
+ * @param inputName - The action input name, used in error messages
+ * @returns The trimmed URL, or undefined when empty
+ * @throws TrackerError if the value is not an http(s) URL
+ */
+export function validateLinkUrl(url: string, inputName: string): string | undefined {
+  if (!url || url.trim().length === 0) {
+    return undefined;
+  }
+
+  const trimmed = url.trim();
+  if (!/^https?:\/\/\S+$/.test(trimmed)) {
+    throw new TrackerError(`Invalid ${inputName}: ${url}. Expected an http(s) URL`, 'INVALID_URL');
+  }
+  return trimmed;
+}
+
+/**
+ * Splits the channel input into the primary channel and the mirror channels
+ * @param input - One channel ID, or a comma-separated list whose first entry is the primary
+ * @returns The primary channel ID (empty when none is given) and the other, distinct IDs
+ */
+export function parseChannelIdList(input: string): { primary: string; mirrors: string[] } {
+  const ids = (input || '')
+    .split(',')
+    .map((entry) => entry.trim())
+    .filter((entry) => entry.length > 0);
+  const [primary = '', ...rest] = ids;
+  return { primary, mirrors: [...new Set(rest)].filter((id) => id !== primary) };
+}
+
+/**
+ * Parses a comma-separated list of Discord user or role IDs
+ * @param input - The raw list, e.g. "123456789012345678,234567890123456789"
+ * @param inputName - The action input name, used in error messages
+ * @returns The IDs, or an empty array when the input is empty
+ * @throws TrackerError if an entry is not a numeric Discord ID
+ */
+export function parseDiscordIdList(input: string, inputName: string): string[] {
+  if (!input || input.trim().length === 0) {
+    return [];
+  }
+
+  return input
+    .split(',')
+    .map((entry) => entry.trim())
+    .filter((entry) => entry.length > 0)
+    .map((entry) => {
+      if (!/^\d{17,20}$/.test(entry)) {
+        throw new TrackerError(
+          `Invalid ${inputName} entry: ${entry}. Expected a numeric Discord ID`,
+          'INVALID_DISCORD_ID'
+        );
+      }
+      return entry;
+    });
+}
+
+/**
+ * Parses the mapping of GitHub handles to Discord user IDs used to ping step owners
+ * @param input - e.g. "octocat=123456789012345678,@hubot=234567890123456789"
+ * @returns The Discord user ID of each lowercase handle (without a leading @)
+ * @throws TrackerError if an entry is malformed or its value is not a Discord ID
+ */
+export function parseOwnerMap(input: string): Record<string, string> {
+  const ownerMap: Record<string, string> = {};
+  for (const [handle, discordId] of parseKeyValueList(input, 'owner_map')) {
+    const [id] = parseDiscordIdList(discordId, 'owner_map');
+    ownerMap[handle.replace(/^@/, '').toLowerCase()] = id;
+  }
+  return ownerMap;
+}
+
+// Units of a step budget, in milliseconds
+const BUDGET_UNITS: Record<string, number> = { h: 3600000, m: 60000, s: 1000 };
+
+/**
+ * Parses the expected duration of steps, flagged as over budget when they take longer
+ * @param input - e.g. "Build=5m,Tests=1m30s,Lint=45" (a bare number is in seconds)
+ * @returns The budget in milliseconds of each step name
+ * @throws TrackerError if an entry is malformed or its duration is not positive
+ */
+export function parseStepBudgets(input: string): Record<string, number> {
+  const budgets: Record<string, number> = {};
+  for (const [name, duration] of parseKeyValueList(input, 'step_budgets')) {
+    const normalized = duration.toLowerCase().replace(/\s+/g, '');
+    const parts: string[][] = /^\d+$/.test(normalized)
+      ? [[normalized, normalized, 's']]
+      : Array.from(normalized.matchAll(/(\d+)([hms])/g));
+    const budgetMs = parts.reduce(
+      (sum, [, value, unit]) => sum + Number(value) * BUDGET_UNITS[unit],
+      0
+    );
+    if (parts.map(([part]) => part).join('') !== normalized || budgetMs <= 0) {
+      throw new TrackerError(
+        `Invalid step_budgets duration for ${name}: ${duration}. Expected e.g. 90s, 5m or 1h30m`,
+        'INVALID_STEP_BUDGETS'
+      );
+    }
+    budgets[name] = budgetMs;
+  }
+  return budgets;
+}
+
+/**
+ * Parses the commits listed in the changelog embed of the completion message
+ * @param input - One commit per line, e.g. the output of `git log --format='%H %s' v1.2.0..`
+ * @returns The commits in the given order, or an empty list when the input is empty
+ * @throws TrackerError if a line does not start with a commit SHA
+ */
+export function parseChangelog(input: string): ChangelogCommit[] {
+  return input
+    .split('\n')
+    .map((line) => line.trim())
+    .filter((line) => line.length > 0)
+    .map((line) => {
+      const match = /^([0-9a-f]{7,40})(?:\s+(.*))?$/i.exec(line);
+      if (!match) {
+        throw new TrackerError(
+          `Invalid changelog line: ${line}. Expected: <commit sha> <message>`,
+          'INVALID_CHANGELOG'
+        );
+      }
+      return { sha: match[1], message: match[2] || '' };
+    });
+}
+
+/**
+ * Validates the benchmark regression threshold
+ * @param threshold - The threshold in percent as provided by the action input
+ * @returns The validated threshold, defaulting to 10 when empty
+ * @throws TrackerError if the value is not a non-negative number
+ */
+export function validateBenchThreshold(threshold: string): number {
+  if (!threshold || threshold.trim().length === 0) {
+    return 10;
+  }
+
+  const num = Number(threshold);
+  if (Number.isNaN(num) || num < 0) {
+    throw new TrackerError('Invalid benchmark regression threshold', 'INVALID_BENCH_THRESHOLD');
+  }
+  return num;
+}
+
+/**
+ * Parses a comma-separated list of `key=value` pairs (used for tags and digest filters)
+ * @param input - The raw list, e.g. "team=payments,tier=critical"
+ * @param inputName - The action input name, used in error messages
+ * @returns The parsed pairs, or an empty array when the input is empty
+ * @throws TrackerError if an entry is not of the form key=value
+ */
+export function parseKeyValueList(input: string, inputName: string): Array<[string, string]> {
+  if (!input || input.trim().length === 0) {
+    return [];
+  }
+
+  return input
+    .split(',')
+    .map((entry) => entry.trim())
+    .filter((entry) => entry.length > 0)
+    .map((entry) => {
+      const separator = entry.indexOf('=');
+      const key = separator > 0 ? entry.slice(0, separator).trim() : '';
+      const value = separator > 0 ? entry.slice(separator + 1).trim() : '';
+      if (!key || !value) {
+        throw new TrackerError(
+          `Invalid ${inputName} entry: ${entry}. Expected: key=value`,
+          'INVALID_KEY_VALUE_LIST'
+        );
+      }
+      return [key, value] as [string, string];
+    });
+}
+
+/**
+ * Validates the state storage backend
+ * @param backend - "file" (working directory) or "git-notes" (note on the workflow commit)
+ * @returns The validated backend, defaulting to "file" when empty
+ * @throws TrackerError if the backend is unknown
+ */
+export function validateStorageBackend(backend: string): StorageBackend {
+  if (!backend || backend.trim().length === 0) {
+    return 'file';
+  }
+
+  const validBackends = ['file', 'git-notes', 'actions-cache'];
+  const normalized = backend.trim().toLowerCase();
+  if (!validBackends.includes(normalized)) {
+    throw new TrackerError(
+      `Invalid storage backend: ${backend}. Expected: file, git-notes or actions-cache`,
+      'INVALID_STORAGE_BACKEND'
+    );
+  }
+  return normalized as StorageBackend;
+}
+
+/**
+ * Validates how notification delivery is published back to GitHub
+ * @param mode - "none", "status" (commit status) or "check" (check run)
+ * @returns The validated mode, defaulting to "none" when empty
+ * @throws TrackerError if the mode is unknown
+ */
+export function validateStatusPublishMode(mode: string): StatusPublishMode {
+  if (!mode || mode.trim().length === 0) {
+    return 'none';
+  }
+
+  const validModes = ['none', 'status', 'check'];
+  const normalized = mode.trim().toLowerCase();
+  if (!validModes.includes(normalized)) {
+    throw new TrackerError(
+      `Invalid publish status mode: ${mode}. Expected: none, status or check`,
+      'INVALID_PUBLISH_STATUS'
+    );
+  }
+  return normalized as StatusPublishMode;
+}
@@ -0,0 +1,51 @@
+import type { InternalPipelineState, PipelineTracker, Storage } from './pipelineTracker';
+
+// Settings for the long-running watchdog mode
+export interface WatchdogOptions {
+  maxSilenceMinutes: number;
+  pollIntervalSeconds: number;
+  escalationTarget: string;
+}
+
+// Why the watchdog stopped
+export type WatchdogOutcome = 'completed' | 'stalled';
+
+/// Serializes a state so consecutive polls can be compared for changes
+export function stateFingerprint(state: InternalPipelineState): string {
+  return JSON.stringify(state);
+}
+
+/// Polls the pipeline state until it is cleared (pipeline completed) or stays unchanged for
+/// `maxSilenceMinutes`, in which case the message is marked as stalled
+export async function watchPipeline(
+  tracker: PipelineTracker,
+  storage: Storage,
+  options: WatchdogOptions,
+  sleep: (ms: number) => Promise<void> = (ms) => new Promise((resolve) => setTimeout(resolve, ms)),
+  now: () => number = Date.now
+): Promise<WatchdogOutcome> {
+  let lastFingerprint: string | undefined;
+  let lastChangeAt = now();
+
+  while (true) {
+    const state = await storage.loadPipelineState();
+    if (!state) {
+      console.log('✅ Pipeline state cleared - watchdog stopping');
+      return 'completed';
+    }
+
+    const fingerprint = stateFingerprint(state);
+    if (fingerprint !== lastFingerprint) {
+      lastFingerprint = fingerprint;
+      lastChangeAt = now();
+    }
+
+    const silentMinutes = Math.floor((now() - lastChangeAt) / 60000);
+    if (silentMinutes >= options.maxSilenceMinutes) {
+      await tracker.markStalled(silentMinutes, options.escalationTarget);
+      return 'stalled';
+    }
+
+    await sleep(options.pollIntervalSeconds * 1000);
+  }
+}
@@ -0,0 +1,43 @@
+import * as os from 'os';
+
+// Properties of a workflow command, e.g. the title of an annotation
+export type CommandProperties = Record<string, string | number | undefined>;
+
+// Title of the annotation added for a failed step
+export const STEP_FAILED_TITLE = 'Pipeline step failed';
+
+/// Escapes the message of a workflow command, where `%`, CR and LF would end or corrupt it
+export function escapeCommandData(value: string): string {
+  return value.replace(/%/g, '%25').replace(/\r/g, '%0D').replace(/\n/g, '%0A');
+}
+
+/// Escapes a property value, which additionally cannot hold the `:` and `,` separators
+export function escapeCommandProperty(value: string): string {
+  return escapeCommandData(value).replace(/:/g, '%3A').replace(/,/g, '%2C');
+}
+
+/// Formats a workflow command (`::command key=value,...::message`); undefined properties are
+/// left out
+export function formatWorkflowCommand(
+  command: string,
+  properties: CommandProperties,
+  message: string
+): string {
+  const props = Object.entries(properties)
+    .filter((entry): entry is [string, string | number] => entry[1] !== undefined)
+    .map(([key, value]) => `${key}=${escapeCommandProperty(String(value))}`)
+    .join(',');
+  return `::${command}${props ? ` ${props}` : ''}::${escapeCommandData(message)}`;
+}
+
+/// Builds the `::error` command of a failed step, so the failure also shows in the GitHub
+/// Checks annotations
+export function formatStepFailureAnnotation(stepName: string, error?: string): string {
+  const message = error ? `${stepName}: ${error}` : `${stepName} failed`;
+  return formatWorkflowCommand('error', { title: STEP_FAILED_TITLE }, message);
+}
+
+/// Writes the annotation of a failed step to stdout, where the runner reads workflow commands
+export function annotateStepFailure(stepName: string, error?: string): void {
+  process.stdout.write(`${formatStepFailureAnnotation(stepName, error)}${os.EOL}`);
+}
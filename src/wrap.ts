@@ -0,0 +1,97 @@
+import { spawn } from 'child_process';
+import { formatDuration } from './messageBuilder';
+import type { PipelineTracker } from './pipelineTracker';
+import { formatBytes, ResourceSampler, type ResourceStats } from './resources';
+
+// Step reported by the wrap subcommand
+export interface WrappedStep {
+  number: number;
+  total: number;
+  name: string;
+  // Phase (stage) the step is grouped under, e.g. build, test or deploy
+  phase?: string;
+}
+
+// Outcome of a wrapped command
+export interface WrappedResult {
+  exitCode: number;
+  signal?: string;
+  durationMs: number;
+  // Only collected with resource sampling enabled, on Linux
+  resources?: ResourceStats;
+}
+
+// Options of a wrapped command
+export interface WrapOptions {
+  // Sample peak memory and CPU time of the command's process tree
+  sampleResources?: boolean;
+}
+
+/// Runs a command, inheriting stdio, and resolves with its exit code and duration. A single
+/// argument is run as a shell command line (e.g. `exit 3`); several are the program and its
+/// arguments, passed as-is without a shell so their quoting is kept.
+export function runCommand(command: string[], options: WrapOptions = {}): Promise<WrappedResult> {
+  const startedAt = Date.now();
+
+  return new Promise((resolve) => {
+    const child =
+      command.length === 1
+        ? spawn(command[0], { shell: true, stdio: 'inherit' })
+        : spawn(command[0], command.slice(1), { stdio: 'inherit' });
+    const sampler =
+      options.sampleResources && child.pid ? new ResourceSampler(child.pid) : undefined;
+    sampler?.start();
+
+    child.on('error', (error) => {
+      sampler?.stop();
+      console.error(`❌ Failed to start wrapped command: ${error.message}`);
+      resolve({ exitCode: 127, durationMs: Date.now() - startedAt });
+    });
+
+    child.on('exit', (code, signal) => {
+      resolve({
+        exitCode: code ?? 1,
+        signal: signal || undefined,
+        durationMs: Date.now() - startedAt,
+        resources: sampler?.stop(),
+      });
+    });
+  });
+}
+
+/// Builds the additional info reported for a finished wrapped command
+export function buildWrappedInfo(result: WrappedResult): Array<[string, string]> {
+  const info: Array<[string, string]> = [
+    ['exit code', String(result.exitCode)],
+    ['duration', formatDuration(result.durationMs)],
+  ];
+  if (result.signal) {
+    info.push(['signal', result.signal]);
+  }
+  if (result.resources) {
+    info.push(['peak memory', formatBytes(result.resources.peakRssBytes)]);
+    info.push(['cpu time', `${(result.resources.cpuTimeMs / 1000).toFixed(1)}s`]);
+  }
+  return info;
+}
+
+/// Reports the step as running, runs the command, then reports success or failure.
+/// Returns the command's exit code so the caller can propagate it.
+export async function runWrappedStep(
+  tracker: PipelineTracker,
+  step: WrappedStep,
+  command: string[],
+  options: WrapOptions = {}
+): Promise<number> {
+  const details = { phase: step.phase };
+  await tracker.updateStep(step.number, step.total, step.name, 'running', [], details);
+
+  console.info(`▶️  Running wrapped command: ${command.join(' ')}`);
+  const result = await runCommand(command, options);
+
+  const status = result.exitCode === 0 ? 'success' : 'failed';
+  const info = buildWrappedInfo(result);
+  await tracker.updateStep(step.number, step.total, step.name, status, info, details);
+
+  return result.exitCode;
+}